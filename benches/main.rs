@@ -1,6 +1,7 @@
 use criterion::criterion_main;
 
 pub mod main {
+    pub mod icons;
     pub mod load_candidates;
     pub mod previewer;
     pub mod render;
@@ -12,6 +13,7 @@ pub use main::*;
 
 criterion_main!(
     ui::benches,
+    icons::benches,
     load_candidates::benches,
     previewer::benches,
     render::benches,
@@ -0,0 +1,37 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group};
+use television::channels::{
+    icons::{EntryIcons, IconMode},
+    prototypes::Delimiter,
+};
+
+/// Looking up the icons of a page of results, the way `Entry::from_match`
+/// does every frame, once the icons were looked up.
+pub fn decorate_entries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("entry_icons");
+
+    let entries: Vec<String> = (0..50)
+        .map(|i| format!("television/channels/entry_{i}.rs"))
+        .collect();
+
+    for mode in [IconMode::None, IconMode::Path, IconMode::Auto] {
+        let mut icons = EntryIcons::new(mode, Delimiter::default());
+        for entry in &entries {
+            icons.icon(entry);
+        }
+        group.bench_with_input(
+            BenchmarkId::new("decorate", format!("{mode:?}")),
+            &entries,
+            |b, entries| {
+                b.iter(|| {
+                    for entry in entries {
+                        black_box(icons.icon(black_box(entry)));
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, decorate_entries);
@@ -1,5 +1,6 @@
 use criterion::criterion_group;
 use criterion::{BenchmarkId, Criterion, Throughput, black_box};
+use television::channels::channel::SourceControl;
 use television::channels::entry_processor::{
     AnsiProcessor, DisplayProcessor, PlainProcessor,
 };
@@ -40,10 +41,11 @@ pub fn load_candidates_by_size(c: &mut Criterion) {
 
                     television::channels::channel::load_candidates(
                         black_box(source_spec.command),
-                        black_box(source_spec.entry_delimiter),
+                        black_box(source_spec.record_separator),
                         black_box(0),
                         black_box(PlainProcessor),
                         injector,
+                        SourceControl::default(),
                     )
                     .await;
 
@@ -81,10 +83,11 @@ pub fn load_candidates_with_ansi(c: &mut Criterion) {
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
-                black_box(source_spec.entry_delimiter),
+                black_box(source_spec.record_separator),
                 black_box(0),
                 black_box(PlainProcessor),
                 injector,
+                SourceControl::default(),
             )
             .await;
 
@@ -110,10 +113,11 @@ pub fn load_candidates_with_ansi(c: &mut Criterion) {
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
-                black_box(source_spec.entry_delimiter),
+                black_box(source_spec.record_separator),
                 black_box(0),
                 black_box(AnsiProcessor),
                 injector,
+                SourceControl::default(),
             )
             .await;
 
@@ -147,10 +151,11 @@ pub fn load_candidates_with_display_template(c: &mut Criterion) {
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
-                black_box(source_spec.entry_delimiter),
+                black_box(source_spec.record_separator),
                 black_box(0),
                 black_box(PlainProcessor),
                 injector,
+                SourceControl::default(),
             )
             .await;
 
@@ -175,12 +180,13 @@ pub fn load_candidates_with_display_template(c: &mut Criterion) {
 
             television::channels::channel::load_candidates(
                 black_box(source_spec.command),
-                black_box(source_spec.entry_delimiter),
+                black_box(source_spec.record_separator),
                 black_box(0),
                 black_box(DisplayProcessor {
                     template: source_spec.display.unwrap(),
                 }),
                 injector,
+                SourceControl::default(),
             )
             .await;
 
@@ -5,7 +5,9 @@ use television::{
         entry::Entry,
         prototypes::{CommandSpec, Template},
     },
-    previewer::try_preview,
+    previewer::{
+        directory::DirectoryPreview, limits::OutputLimits, try_preview,
+    },
 };
 use tokio::sync::mpsc;
 
@@ -28,9 +30,21 @@ fn bench_preview(
             let command = black_box(make_command(command));
             let (tx, mut rx) = mpsc::unbounded_channel();
 
-            try_preview(command, 0, None, None, None, entry, tx, None)
-                .await
-                .unwrap();
+            try_preview(
+                command,
+                0,
+                None,
+                None,
+                None,
+                DirectoryPreview::default(),
+                OutputLimits::default(),
+                entry,
+                80,
+                tx,
+                None,
+            )
+            .await
+            .unwrap();
 
             let _ = rx.recv().await;
         });
@@ -0,0 +1,34 @@
+//! Pick files of the current directory with tv's picker, previewing their
+//! size, from a program of your own.
+//!
+//! ```sh
+//! cargo run --features embed --example embedded
+//! ```
+use std::fs;
+
+use television::EmbeddedPicker;
+
+#[tokio::main(flavor = "multi_thread")]
+async fn main() -> anyhow::Result<()> {
+    let files = fs::read_dir(".")?
+        .filter_map(Result::ok)
+        .map(|entry| entry.file_name().to_string_lossy().into_owned());
+
+    let selection = EmbeddedPicker::new(files)
+        .prompt("file> ")
+        .header("Pick some files (tab to select several)")
+        .preview(|entry| match fs::metadata(&entry.raw) {
+            Ok(metadata) => format!("{} bytes", metadata.len()),
+            Err(e) => e.to_string(),
+        })
+        .run()
+        .await?;
+
+    if selection.is_empty() {
+        eprintln!("Nothing picked");
+    }
+    for file in selection.entries {
+        println!("{}", file.raw);
+    }
+    Ok(())
+}
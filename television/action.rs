@@ -10,6 +10,9 @@ pub enum Action {
     /// Add a character to the input buffer.
     #[serde(skip)]
     AddInputChar(char),
+    /// Add pasted text to the input buffer.
+    #[serde(skip)]
+    AddInputString(String),
     /// Delete the character before the cursor from the input buffer.
     DeletePrevChar,
     /// Delete the previous word from the input buffer.
@@ -18,10 +21,22 @@ pub enum Action {
     DeleteNextChar,
     /// Delete the current line from the input buffer.
     DeleteLine,
+    /// Delete the next word from the input buffer.
+    DeleteNextWord,
+    /// Delete everything before the cursor from the input buffer.
+    DeleteToInputStart,
+    /// Delete everything after the cursor from the input buffer.
+    DeleteToInputEnd,
+    /// Swap the characters around the cursor.
+    TransposeChars,
     /// Move the cursor to the character before the current cursor position.
     GoToPrevChar,
     /// Move the cursor to the character after the current cursor position.
     GoToNextChar,
+    /// Move the cursor to the start of the previous word.
+    GoToPrevWord,
+    /// Move the cursor to the start of the next word.
+    GoToNextWord,
     /// Move the cursor to the start of the input buffer.
     GoToInputStart,
     /// Move the cursor to the end of the input buffer.
@@ -41,6 +56,11 @@ pub enum Action {
     ToggleSelectionDown,
     /// Add entry under cursor to the list of selected entries and move the cursor up.
     ToggleSelectionUp,
+    /// Mark or unmark the entry under cursor, marks are kept across queries
+    /// for the whole session.
+    ToggleMark,
+    /// Only show the marked entries, or all of them again.
+    FilterMarked,
     /// Confirm current selection (multi select or entry under cursor).
     ConfirmSelection,
     /// Select the entry currently under the cursor and exit the application.
@@ -55,8 +75,16 @@ pub enum Action {
     SelectNextPage,
     /// Select the previous page of entries in the currently focused list.
     SelectPrevPage,
+    /// Select the entry with the given 1-based index in the results list.
+    SelectIndex(usize),
     /// Copy the currently selected entry to the clipboard.
     CopyEntryToClipboard,
+    /// Copy the selected entries formatted with the channel's `copy`
+    /// template (or as they are) to the clipboard.
+    CopyFormatted,
+    /// Copy the preview of the selected entry, without its styling, to the
+    /// clipboard.
+    CopyPreviewToClipboard,
     // preview actions
     /// Scroll the preview up by one line.
     ScrollPreviewUp,
@@ -66,32 +94,87 @@ pub enum Action {
     ScrollPreviewHalfPageUp,
     /// Scroll the preview down by half a page.
     ScrollPreviewHalfPageDown,
-    /// Open the currently selected entry in the default application.
+    /// Scroll the preview up by a full page.
+    ScrollPreviewPageUp,
+    /// Scroll the preview down by a full page.
+    ScrollPreviewPageDown,
+    /// Scroll the preview back to its first line.
+    GoToPreviewTop,
+    /// Scroll the preview so that its last line is at the bottom.
+    GoToPreviewBottom,
+    /// Give the keyboard focus to the preview pane to scroll and search it,
+    /// or give it back to the input bar.
+    FocusPreview,
+    /// A key pressed while the preview pane is focused.
     #[serde(skip)]
+    PreviewPanelKey(Key),
+    /// Open the currently selected entries in `$EDITOR` (or the channel's
+    /// `open` action if it defines one).
     OpenEntry,
     // application actions
     /// Tick the application state.
     #[serde(skip)]
     Tick,
-    /// Suspend the application.
-    #[serde(skip)]
+    /// Suspend the application (as with `ctrl-z` in a shell).
     Suspend,
     /// Resume the application.
     #[serde(skip)]
     Resume,
+    /// The terminal lost the focus: render less often.
+    #[serde(skip)]
+    FocusLost,
+    /// The terminal got the focus back: render at full rate again.
+    #[serde(skip)]
+    FocusGained,
     /// Quit the application.
     Quit,
+    /// Quit without selecting anything, whatever is shown.
+    #[serde(skip)]
+    Abort,
     /// Toggle a UI feature.
     ToggleRemoteControl,
     ToggleHelp,
+    /// Copy the selected entry into the input to edit it and output the
+    /// edited text, or cancel the edit.
+    EditEntryInline,
+    /// Open the file the channel highlighted in the remote control was
+    /// loaded from in `$EDITOR`.
+    EditChannelConfig,
+    /// Open the full-screen help overlay, or close it.
+    ToggleHelpOverlay,
+    /// Scroll the help panel up by one line.
+    ScrollHelpUp,
+    /// Scroll the help panel down by one line.
+    ScrollHelpDown,
     ToggleStatusBar,
     TogglePreview,
+    /// Open and focus the log panel, or close it if it is focused.
+    ToggleLogs,
+    /// Scroll the log panel up by half a page.
+    ScrollLogUp,
+    /// Scroll the log panel down by half a page.
+    ScrollLogDown,
+    /// Scroll the log panel to the oldest records.
+    GoToLogTop,
+    /// Scroll the log panel to the newest records and follow them.
+    GoToLogBottom,
+    /// A key pressed while the log panel is focused.
+    #[serde(skip)]
+    LogPanelKey(Key),
+    /// Show the full selected entry in a popup, or close it.
+    ShowEntryDetail,
     /// Switch between the portrait and landscape modes.
     #[serde(rename = "toggle_layout")]
     ToggleOrientation,
+    /// Answer a pending confirmation prompt.
+    #[serde(skip)]
+    AnswerConfirmation(bool),
     /// Signal an error with the given message.
     #[serde(skip)]
     Error(String),
+    /// Briefly show the given message to the user.
+    #[serde(skip)]
+    Notify(String),
     /// No operation.
     NoOp,
     // Channel actions
@@ -99,11 +182,22 @@ pub enum Action {
     CycleSources,
     /// Cycle between different preview commands.
     CyclePreviews,
+    /// Forget cached previews and regenerate the current one.
+    ClearPreviewCache,
+    /// Cycle between sorting the results by the channel's sort fields and
+    /// ranking them by match score.
+    CycleSort,
     /// Reload the current source command.
     ReloadSource,
     /// Switch to the specified channel directly via shortcut.
     #[serde(skip)]
     SwitchToChannel(String),
+    /// Go back to the channel that was open before the last channel switch,
+    /// with its query and selections.
+    #[serde(alias = "go_back")]
+    ChannelBack,
+    /// Go forward to the channel left with `ChannelBack`.
+    ChannelForward,
     /// Timer action for watch mode to trigger periodic reloads.
     #[serde(skip)]
     WatchTimer,
@@ -118,6 +212,14 @@ pub enum Action {
     /// Handle mouse click event at specific coordinates
     #[serde(skip)]
     MouseClickAt(u16, u16),
+    /// Run a command template bound in `[keybindings.execute]` against the
+    /// selected entries in the background, showing its output in the preview.
+    #[serde(skip)]
+    Execute(String),
+    /// Like `Execute`, but the output is discarded unless the command fails
+    /// (`[keybindings.execute_silent]`).
+    #[serde(skip)]
+    ExecuteSilent(String),
     /// Execute an external action
     #[serde(untagged)]
     ExternalAction(String),
@@ -126,6 +228,78 @@ pub enum Action {
 /// Prefix used to identify custom external actions defined by the user in a channel's prototype.
 pub const CUSTOM_ACTION_PREFIX: &str = "actions:";
 
+/// The names of the built-in actions as written in the configuration, used
+/// to suggest the intended action when an unknown one is bound.
+pub const ACTION_NAMES: &[&str] = &[
+    "delete_prev_char",
+    "delete_prev_word",
+    "delete_next_char",
+    "delete_line",
+    "delete_next_word",
+    "delete_to_input_start",
+    "delete_to_input_end",
+    "transpose_chars",
+    "go_to_prev_char",
+    "go_to_next_char",
+    "go_to_prev_word",
+    "go_to_next_word",
+    "go_to_input_start",
+    "go_to_input_end",
+    "toggle_selection_down",
+    "toggle_selection_up",
+    "toggle_mark",
+    "filter_marked",
+    "confirm_selection",
+    "select_and_exit",
+    "expect",
+    "select_next_entry",
+    "select_prev_entry",
+    "select_next_page",
+    "select_prev_page",
+    "select_index",
+    "copy_entry_to_clipboard",
+    "copy_formatted",
+    "copy_preview_to_clipboard",
+    "scroll_preview_up",
+    "scroll_preview_down",
+    "scroll_preview_half_page_up",
+    "scroll_preview_half_page_down",
+    "scroll_preview_page_up",
+    "scroll_preview_page_down",
+    "go_to_preview_top",
+    "go_to_preview_bottom",
+    "focus_preview",
+    "open_entry",
+    "suspend",
+    "quit",
+    "toggle_remote_control",
+    "toggle_help",
+    "edit_entry_inline",
+    "edit_channel_config",
+    "toggle_help_overlay",
+    "scroll_help_up",
+    "scroll_help_down",
+    "toggle_status_bar",
+    "toggle_preview",
+    "toggle_logs",
+    "scroll_log_up",
+    "scroll_log_down",
+    "go_to_log_top",
+    "go_to_log_bottom",
+    "show_entry_detail",
+    "toggle_layout",
+    "no_op",
+    "cycle_sources",
+    "cycle_previews",
+    "clear_preview_cache",
+    "cycle_sort",
+    "reload_source",
+    "channel_back",
+    "channel_forward",
+    "select_prev_history",
+    "select_next_history",
+];
+
 /// Container for one or more actions that can be executed together.
 ///
 /// This enum enables binding single keys to multiple actions, allowing for
@@ -312,12 +486,19 @@ impl Action {
         match self {
             // Input actions
             Action::AddInputChar(_) => "Add character",
+            Action::AddInputString(_) => "Add text",
             Action::DeletePrevChar => "Delete previous char",
             Action::DeletePrevWord => "Delete previous word",
             Action::DeleteNextChar => "Delete next char",
             Action::DeleteLine => "Delete line",
+            Action::DeleteNextWord => "Delete next word",
+            Action::DeleteToInputStart => "Delete to start",
+            Action::DeleteToInputEnd => "Delete to end",
+            Action::TransposeChars => "Transpose chars",
             Action::GoToPrevChar => "Move cursor left",
             Action::GoToNextChar => "Move cursor right",
+            Action::GoToPrevWord => "Move to previous word",
+            Action::GoToNextWord => "Move to next word",
             Action::GoToInputStart => "Move to start",
             Action::GoToInputEnd => "Move to end",
 
@@ -329,6 +510,8 @@ impl Action {
             // Selection actions
             Action::ToggleSelectionDown => "Toggle selection down",
             Action::ToggleSelectionUp => "Toggle selection up",
+            Action::ToggleMark => "Toggle mark",
+            Action::FilterMarked => "Filter marked entries",
             Action::ConfirmSelection => "Select entry",
             Action::SelectAndExit => "Select and exit",
             Action::Expect(_) => "Expect key",
@@ -338,7 +521,10 @@ impl Action {
             Action::SelectPrevEntry => "Navigate up",
             Action::SelectNextPage => "Page down",
             Action::SelectPrevPage => "Page up",
+            Action::SelectIndex(_) => "Select index",
             Action::CopyEntryToClipboard => "Copy to clipboard",
+            Action::CopyFormatted => "Copy formatted",
+            Action::CopyPreviewToClipboard => "Copy preview",
 
             // Preview actions
             Action::ScrollPreviewUp => "Preview scroll up",
@@ -347,30 +533,57 @@ impl Action {
             Action::ScrollPreviewHalfPageDown => {
                 "Preview scroll half page down"
             }
+            Action::ScrollPreviewPageUp => "Preview scroll page up",
+            Action::ScrollPreviewPageDown => "Preview scroll page down",
+            Action::GoToPreviewTop => "Preview go to top",
+            Action::GoToPreviewBottom => "Preview go to bottom",
+            Action::FocusPreview => "Focus preview",
+            Action::PreviewPanelKey(_) => "Preview panel key",
             Action::OpenEntry => "Open entry",
 
             // Application actions
             Action::Tick => "Tick",
             Action::Suspend => "Suspend",
             Action::Resume => "Resume",
+            Action::FocusLost => "Focus lost",
+            Action::FocusGained => "Focus gained",
             Action::Quit => "Quit",
+            Action::Abort => "Abort",
 
             // Toggle actions
             Action::ToggleRemoteControl => "Toggle remote control",
             Action::ToggleHelp => "Toggle help",
+            Action::EditEntryInline => "Edit entry inline",
+            Action::EditChannelConfig => "Edit channel config",
+            Action::ToggleHelpOverlay => "Toggle help overlay",
+            Action::ScrollHelpUp => "Help scroll up",
+            Action::ScrollHelpDown => "Help scroll down",
             Action::ToggleStatusBar => "Toggle status bar",
             Action::TogglePreview => "Toggle preview",
+            Action::ToggleLogs => "Toggle logs",
+            Action::ShowEntryDetail => "Show entry detail",
+            Action::ScrollLogUp => "Logs scroll up",
+            Action::ScrollLogDown => "Logs scroll down",
+            Action::GoToLogTop => "Logs top",
+            Action::GoToLogBottom => "Logs bottom",
+            Action::LogPanelKey(_) => "Log panel key",
             Action::ToggleOrientation => "Toggle layout",
 
             // Error and no-op
+            Action::AnswerConfirmation(_) => "Answer confirmation",
             Action::Error(_) => "Error",
+            Action::Notify(_) => "Notify",
             Action::NoOp => "No operation",
 
             // Channel actions
             Action::CycleSources => "Cycle sources",
+            Action::CycleSort => "Cycle sort",
             Action::CyclePreviews => "Cycle previews",
+            Action::ClearPreviewCache => "Clear preview cache",
             Action::ReloadSource => "Reload source",
             Action::SwitchToChannel(_) => "Switch to channel",
+            Action::ChannelBack => "Previous channel",
+            Action::ChannelForward => "Next channel",
             Action::WatchTimer => "Watch timer",
 
             // History actions
@@ -382,7 +595,9 @@ impl Action {
             Action::MouseClickAt(_, _) => "Mouse click",
 
             // External actions
-            Action::ExternalAction(a) => a,
+            Action::Execute(command)
+            | Action::ExecuteSilent(command)
+            | Action::ExternalAction(command) => command,
         }
     }
 }
@@ -415,6 +630,26 @@ mod tests {
         assert_eq!(multiple_from_vec.as_slice(), actions_vec.as_slice());
     }
 
+    #[test]
+    fn test_action_names_are_built_in() {
+        #[derive(Deserialize)]
+        struct Binding {
+            action: Action,
+        }
+        for name in ACTION_NAMES {
+            // these need an argument
+            if ["expect", "select_index"].contains(name) {
+                continue;
+            }
+            let binding: Binding =
+                toml::from_str(&format!("action = \"{name}\"")).unwrap();
+            assert!(
+                !matches!(binding.action, Action::ExternalAction(_)),
+                "`{name}` isn't a built-in action"
+            );
+        }
+    }
+
     #[test]
     fn test_actions_as_slice() {
         let single: Actions = Actions::single(Action::DeleteLine);
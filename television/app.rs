@@ -1,25 +1,37 @@
-use std::{thread::sleep, time::Duration};
+use std::{io::Write, time::Duration};
 
 use crate::{
     action::{Action, CUSTOM_ACTION_PREFIX},
     cable::Cable,
     channels::{
         entry::Entry,
-        prototypes::{ActionSpec, ExecutionMode},
+        prototypes::{ActionSpec, ExecutionMode, OutputMode, Template},
     },
-    config::layers::ConfigLayers,
+    config::{layers::ConfigLayers, ui::EscBehavior},
     event::{ControlEvent, Event, EventLoop, Key},
     history::History,
     mouse::get_action_for_mouse_event,
     render::{RenderingTask, UiState, render},
+    screen::confirm::Confirmation,
     television::{Mode, Television},
     tui::{IoStream, Tui, TuiMode},
-    utils::command::execute_action,
+    utils::{
+        command::{
+            CommandOutput, capture_action, editor_command, execute_action,
+            shell_command,
+        },
+        hashmaps::FxIndexSet,
+    },
 };
 use anyhow::Result;
-use rustc_hash::FxHashSet;
-use tokio::sync::mpsc;
-use tracing::{debug, error, trace};
+use ratatui::{Terminal, backend::CrosstermBackend};
+use rustc_hash::FxHashMap;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, error, trace, warn};
+
+/// Name of the channel action used by [`Action::OpenEntry`] instead of
+/// `$EDITOR` when defined.
+const OPEN_ACTION_NAME: &str = "open";
 
 /// The main application struct that holds the state of the application.
 pub struct App {
@@ -55,24 +67,63 @@ pub struct App {
     watch_timer_task: Option<tokio::task::JoinHandle<()>>,
     /// Global history for selected entries
     history: History,
+    /// Outputs of external actions to print once the application exits.
+    command_outputs: Vec<CommandOutput>,
+    /// External actions currently running in the background.
+    running_actions: Vec<
+        tokio::task::JoinHandle<(
+            ActionSpec,
+            FxIndexSet<Entry>,
+            Result<CommandOutput>,
+        )>,
+    >,
 }
 
 /// The outcome of an action.
 #[derive(Debug, PartialEq)]
 pub enum ActionOutcome {
-    Entries(FxHashSet<Entry>),
-    EntriesWithExpect(FxHashSet<Entry>, Key),
+    Entries(FxIndexSet<Entry>),
+    EntriesWithExpect(FxIndexSet<Entry>, Key),
     Input(String),
+    /// Exited without selecting anything.
     None,
-    ExternalAction(ActionSpec, FxHashSet<Entry>),
+    /// Exited because there was nothing to select.
+    NoMatch,
+    ExternalAction(ActionSpec, FxIndexSet<Entry>),
+}
+
+/// How the application was exited, which tells the process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Something was selected.
+    Selected,
+    /// There was nothing to select.
+    NoMatch,
+    /// The user quit without selecting anything.
+    Aborted,
+}
+
+impl ExitStatus {
+    /// The process exit code, following the convention of fuzzy finders:
+    /// 130 (as when interrupted by `SIGINT`) when aborted.
+    pub fn code(self) -> i32 {
+        match self {
+            ExitStatus::Selected => 0,
+            ExitStatus::NoMatch => 1,
+            ExitStatus::Aborted => 130,
+        }
+    }
 }
 
 /// The result of the application.
 #[derive(Debug)]
 pub struct AppOutput {
-    pub selected_entries: Option<FxHashSet<Entry>>,
+    pub selected_entries: Option<FxIndexSet<Entry>>,
     pub expect_key: Option<Key>,
-    pub external_action: Option<(ActionSpec, FxHashSet<Entry>)>,
+    pub external_action: Option<(ActionSpec, FxIndexSet<Entry>)>,
+    /// Captured outputs of actions run with `output = "print"`.
+    pub command_outputs: Vec<CommandOutput>,
+    pub exit_status: ExitStatus,
 }
 
 impl AppOutput {
@@ -82,28 +133,45 @@ impl AppOutput {
                 selected_entries: Some(entries),
                 expect_key: None,
                 external_action: None,
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::Selected,
             },
             ActionOutcome::EntriesWithExpect(entries, expect_key) => Self {
                 selected_entries: Some(entries),
                 expect_key: Some(expect_key),
                 external_action: None,
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::Selected,
             },
             ActionOutcome::Input(input) => Self {
-                selected_entries: Some(FxHashSet::from_iter([Entry::new(
+                selected_entries: Some(FxIndexSet::from_iter([Entry::new(
                     input,
                 )])),
                 expect_key: None,
                 external_action: None,
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::Selected,
             },
             ActionOutcome::None => Self {
                 selected_entries: None,
                 expect_key: None,
                 external_action: None,
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::Aborted,
+            },
+            ActionOutcome::NoMatch => Self {
+                selected_entries: None,
+                expect_key: None,
+                external_action: None,
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::NoMatch,
             },
             ActionOutcome::ExternalAction(action_spec, entries) => Self {
                 selected_entries: None,
                 expect_key: None,
                 external_action: Some((action_spec, entries)),
+                command_outputs: Vec::new(),
+                exit_status: ExitStatus::Selected,
             },
         }
     }
@@ -147,6 +215,8 @@ impl App {
             render_task: None,
             watch_timer_task: None,
             history,
+            command_outputs: Vec::new(),
+            running_actions: Vec::new(),
         };
 
         // populate input_map by going through all cable channels and adding their shortcuts if remote
@@ -253,13 +323,7 @@ impl App {
         is_output_tty: bool,
         headless: bool,
     ) -> Result<AppOutput> {
-        // Rendering loop
         if !headless {
-            debug!("Starting rendering loop");
-            let (render_tx, render_rx) = mpsc::unbounded_channel();
-            self.render_tx = render_tx.clone();
-            let ui_state_tx = self.ui_state_tx.clone();
-            let action_tx_r = self.action_tx.clone();
             let tui_mode = Self::determine_tui_mode(
                 self.television.merged_config.height,
                 self.television.merged_config.width,
@@ -272,19 +336,77 @@ impl App {
                 debug!("Rendering to stderr");
                 IoStream::BufferedStderr.to_stream()
             };
-            let mut tui = Tui::new(stream, &tui_mode)
+            let tui = Tui::new(stream, &tui_mode)
                 .expect("Failed to create TUI instance");
-            debug!("Entering tui");
-            tui.enter().expect("Failed to enter TUI mode");
-
-            self.render_task = Some(tokio::spawn(async move {
-                render(render_rx, action_tx_r, ui_state_tx, tui).await
-            }));
-            self.action_tx
-                .send(Action::Render)
-                .expect("Unable to send init render action.");
+            self.start_rendering(tui, drop);
         }
+        self.run_loop(headless).await
+    }
 
+    /// Run the application main loop, drawing on a terminal owned by the
+    /// caller.
+    ///
+    /// This is meant for applications embedding tv: the terminal is used
+    /// over its whole area, stays on whichever screen it is on, and is handed
+    /// back, cleared, along with the output once the application exits.
+    ///
+    /// # Errors
+    /// If an error occurs during the execution of the application.
+    pub async fn run_with_terminal<W>(
+        &mut self,
+        terminal: Terminal<CrosstermBackend<W>>,
+    ) -> Result<(AppOutput, Terminal<CrosstermBackend<W>>)>
+    where
+        W: Write + Send + 'static,
+    {
+        let tui = Tui::with_terminal(terminal)?;
+        let (tui_tx, tui_rx) = oneshot::channel();
+        self.start_rendering(tui, move |tui| {
+            let _ = tui_tx.send(tui);
+        });
+        let output = self.run_loop(false).await?;
+        let tui = tui_rx.await?;
+        Ok((output, tui.into_terminal()?))
+    }
+
+    /// Enter `tui` and spawn the rendering loop drawing on it, which hands
+    /// it to `on_exit` once done.
+    fn start_rendering<W>(
+        &mut self,
+        mut tui: Tui<W>,
+        on_exit: impl FnOnce(Tui<W>) + Send + 'static,
+    ) where
+        W: Write + Send + 'static,
+    {
+        debug!("Starting rendering loop");
+        let (render_tx, render_rx) = mpsc::unbounded_channel();
+        self.render_tx = render_tx;
+        let ui_state_tx = self.ui_state_tx.clone();
+        let action_tx_r = self.action_tx.clone();
+        let unfocused_frame_rate =
+            self.television.merged_config.unfocused_frame_rate;
+        debug!("Entering tui");
+        tui.enter().expect("Failed to enter TUI mode");
+
+        self.render_task = Some(tokio::spawn(async move {
+            let tui = render(
+                render_rx,
+                action_tx_r,
+                ui_state_tx,
+                tui,
+                unfocused_frame_rate,
+            )
+            .await?;
+            on_exit(tui);
+            Ok(())
+        }));
+        self.action_tx
+            .send(Action::Render)
+            .expect("Unable to send init render action.");
+    }
+
+    /// Run the event loop and handle actions until the application exits.
+    async fn run_loop(&mut self, headless: bool) -> Result<AppOutput> {
         // Event loop
         if !headless {
             debug!("Starting backend event loop");
@@ -330,6 +452,10 @@ impl App {
             // It's important that this shouldn't block if no actions are available
             action_outcome = self.handle_actions(&mut action_buf).await?;
 
+            if !self.running_actions.is_empty() {
+                self.collect_finished_actions().await?;
+            }
+
             if self.television.merged_config.select_1
                 && !self.television.channel.running()
                 && self.television.channel.total_count() == 1
@@ -339,16 +465,28 @@ impl App {
                 if let Some(outcome) = self.maybe_select_1() {
                     action_outcome = outcome;
                 }
+            } else if self.television.merged_config.select_first
+                && !self.television.channel.running()
+            {
+                // If `select_first` is true and the channel has finished
+                // loading, take the best match for the initial input if
+                // there is one. This only applies to the initial input.
+                if self.television.channel.result_count() == 0 {
+                    self.television.merged_config.select_first = false;
+                } else if !self.television.results_picker.entries.is_empty() {
+                    self.television.merged_config.select_first = false;
+                    action_outcome = self.maybe_take_1();
+                }
             } else if self.television.merged_config.take_1
                 && !self.television.channel.running()
             {
                 // If `take_1` is true and the channel has finished loading,
                 // automatically take the first entry regardless of count.
-                // If there are no entries, exit with None.
+                // If there are no entries, exit with no match.
                 action_outcome = self.maybe_take_1();
             } else if self.television.merged_config.take_1_fast {
                 // If `take_1_fast` is true, immediately take the first entry without
-                // waiting for loading to finish. If there are no entries, exit with None.
+                // waiting for loading to finish. If there are no entries, exit with no match.
                 action_outcome = self.maybe_take_1();
             }
 
@@ -358,6 +496,9 @@ impl App {
                     self.event_control_tx.send(ControlEvent::Abort)?;
                 }
 
+                // don't leave the source command running behind us
+                self.television.channel.shutdown();
+
                 // persist search history
                 if let Err(e) = self.history.save_to_file() {
                     error!("Failed to persist history: {}", e);
@@ -368,7 +509,10 @@ impl App {
                     rendering_task.await?.expect("Rendering task failed");
                 }
 
-                return Ok(AppOutput::new(action_outcome));
+                let mut output = AppOutput::new(action_outcome);
+                output.command_outputs =
+                    std::mem::take(&mut self.command_outputs);
+                return Ok(output);
             }
         }
     }
@@ -382,6 +526,18 @@ impl App {
         self.run(false, true).await
     }
 
+    /// Whether the quit keys clear the query rather than quitting, see
+    /// `EscBehavior::ClearThenQuit`.
+    ///
+    /// The remote control is closed by them either way.
+    fn clears_input_before_quitting(&self) -> bool {
+        self.television.merged_config.esc_behavior
+            == EscBehavior::ClearThenQuit
+            && self.television.mode == Mode::Channel
+            && self.television.inline_edit.is_none()
+            && !self.television.results_picker.input.value().is_empty()
+    }
+
     /// Convert an event to an action.
     ///
     /// This function will convert an event to an action based on the current
@@ -395,6 +551,154 @@ impl App {
     /// will be returned for keys/events bound to action sequences.
     fn convert_event_to_actions(&self, event: Event<Key>) -> Vec<Action> {
         let actions = match event {
+            // abort keys win over every other binding and modal state
+            Event::Input(keycode)
+                if self
+                    .television
+                    .merged_config
+                    .abort_keys
+                    .contains(&keycode) =>
+            {
+                vec![Action::Abort]
+            }
+            // a pending confirmation captures all key presses
+            Event::Input(keycode)
+                if self.television.confirmation.is_some() =>
+            {
+                match keycode {
+                    Key::Char('y' | 'Y') | Key::Enter => {
+                        vec![Action::AnswerConfirmation(true)]
+                    }
+                    Key::Char('n' | 'N') | Key::Esc | Key::Ctrl('c') => {
+                        vec![Action::AnswerConfirmation(false)]
+                    }
+                    _ => vec![Action::NoOp],
+                }
+            }
+            // the help overlay scrolls with the navigation keys and swallows
+            // everything else
+            Event::Input(keycode)
+                if self.television.help_overlay.is_some() =>
+            {
+                let actions = self
+                    .television
+                    .merged_config
+                    .input_map
+                    .get_actions_for_key(&keycode, &self.television.mode)
+                    .map(|actions| actions.as_slice().to_vec())
+                    .unwrap_or_default();
+                if actions.contains(&Action::ToggleHelpOverlay)
+                    || matches!(keycode, Key::Esc | Key::Char('?' | 'q'))
+                {
+                    vec![Action::ToggleHelpOverlay]
+                } else if keycode == Key::Ctrl('c') {
+                    vec![Action::Quit]
+                } else {
+                    let navigation: Vec<Action> = actions
+                        .into_iter()
+                        .filter(|action| {
+                            matches!(
+                                action,
+                                Action::SelectNextEntry
+                                    | Action::SelectPrevEntry
+                                    | Action::SelectNextPage
+                                    | Action::SelectPrevPage
+                                    | Action::ScrollPreviewUp
+                                    | Action::ScrollPreviewDown
+                                    | Action::ScrollPreviewHalfPageUp
+                                    | Action::ScrollPreviewHalfPageDown
+                                    | Action::ScrollPreviewPageUp
+                                    | Action::ScrollPreviewPageDown
+                                    | Action::GoToPreviewTop
+                                    | Action::GoToPreviewBottom
+                                    | Action::ScrollHelpUp
+                                    | Action::ScrollHelpDown
+                            )
+                        })
+                        .collect();
+                    if navigation.is_empty() {
+                        vec![Action::NoOp]
+                    } else {
+                        navigation
+                    }
+                }
+            }
+            // the entry detail popup is closed by any key bound to it or esc
+            Event::Input(keycode)
+                if self.television.entry_detail.is_some() =>
+            {
+                match self
+                    .television
+                    .merged_config
+                    .input_map
+                    .get_actions_for_key(&keycode, &self.television.mode)
+                {
+                    Some(actions)
+                        if actions
+                            .as_slice()
+                            .contains(&Action::ShowEntryDetail) =>
+                    {
+                        vec![Action::ShowEntryDetail]
+                    }
+                    _ if matches!(keycode, Key::Esc | Key::Ctrl('c')) => {
+                        vec![Action::ShowEntryDetail]
+                    }
+                    _ => vec![Action::NoOp],
+                }
+            }
+            // a focused log panel captures all keys but its own toggle
+            Event::Input(keycode)
+                if self
+                    .television
+                    .log_panel
+                    .as_ref()
+                    .is_some_and(|logs| logs.focused) =>
+            {
+                match self
+                    .television
+                    .merged_config
+                    .input_map
+                    .get_actions_for_key(&keycode, &self.television.mode)
+                {
+                    Some(actions)
+                        if actions
+                            .as_slice()
+                            .contains(&Action::ToggleLogs) =>
+                    {
+                        vec![Action::ToggleLogs]
+                    }
+                    _ if keycode == Key::Ctrl('c') => vec![Action::Quit],
+                    _ => vec![Action::LogPanelKey(keycode)],
+                }
+            }
+            // so does a focused preview pane
+            Event::Input(keycode)
+                if self.television.preview_focus.is_some() =>
+            {
+                match self
+                    .television
+                    .merged_config
+                    .input_map
+                    .get_actions_for_key(&keycode, &self.television.mode)
+                {
+                    Some(actions)
+                        if actions
+                            .as_slice()
+                            .contains(&Action::FocusPreview) =>
+                    {
+                        vec![Action::FocusPreview]
+                    }
+                    _ if keycode == Key::Ctrl('c') => vec![Action::Quit],
+                    _ => vec![Action::PreviewPanelKey(keycode)],
+                }
+            }
+            // esc cancels an inline edit instead of quitting
+            Event::Input(Key::Esc)
+                if self.television.inline_edit.is_some()
+                    && self.television.mode == Mode::Channel =>
+            {
+                vec![Action::EditEntryInline]
+            }
             Event::Input(keycode) => {
                 // First try to get actions based on keybindings
                 if let Some(actions) = self
@@ -403,17 +707,56 @@ impl App {
                     .input_map
                     .get_actions_for_key(&keycode, &self.television.mode)
                 {
-                    let actions_vec = actions.as_slice().to_vec();
+                    let mut actions_vec = actions.as_slice().to_vec();
                     debug!("Keybinding found: {actions_vec:?}");
+                    if self.clears_input_before_quitting() {
+                        for action in &mut actions_vec {
+                            if *action == Action::Quit {
+                                *action = Action::DeleteLine;
+                            }
+                        }
+                    }
                     actions_vec
                 } else {
                     // fallback to text input events
                     match keycode {
+                        // `?` on an empty input opens the help overlay
+                        Key::Char('?')
+                            if self.television.mode == Mode::Channel
+                                && self
+                                    .television
+                                    .results_picker
+                                    .input
+                                    .value()
+                                    .is_empty() =>
+                        {
+                            vec![Action::ToggleHelpOverlay]
+                        }
                         Key::Char(c) => vec![Action::AddInputChar(c)],
                         _ => vec![Action::NoOp],
                     }
                 }
             }
+            // pasted text only goes to the input bar
+            Event::Paste(_)
+                if self.television.confirmation.is_some()
+                    || self.television.entry_detail.is_some()
+                    || self.television.help_overlay.is_some()
+                    || self
+                        .television
+                        .log_panel
+                        .as_ref()
+                        .is_some_and(|logs| logs.focused)
+                    || self.television.preview_focus.is_some() =>
+            {
+                vec![Action::NoOp]
+            }
+            Event::Paste(text) => paste_actions(
+                &text,
+                self.television
+                    .merged_config
+                    .input_bar_confirm_pasted_newline,
+            ),
             Event::Mouse(me) => {
                 vec![get_action_for_mouse_event(
                     me,
@@ -424,9 +767,12 @@ impl App {
             // terminal events
             Event::Tick => vec![Action::Tick],
             Event::Resize(x, y) => vec![Action::Resize(x, y)],
-            Event::FocusGained => vec![Action::Resume],
-            Event::FocusLost => vec![Action::Suspend],
+            Event::FocusGained => vec![Action::FocusGained],
+            Event::FocusLost => vec![Action::FocusLost],
             Event::Closed => vec![Action::NoOp],
+            Event::Terminate => vec![Action::Quit],
+            // back from being stopped (e.g. `fg` after `ctrl-z`)
+            Event::Continued => vec![Action::Resume, Action::ClearScreen],
         };
 
         // Filter out Tick actions for logging
@@ -468,15 +814,18 @@ impl App {
                     trace!("{action:?}");
                 }
                 match action {
-                    Action::Quit => {
-                        if self.television.mode == Mode::RemoteControl {
-                            self.action_tx
-                                .send(Action::ToggleRemoteControl)?;
-                        } else {
-                            self.stop_watch_timer();
-                            self.should_quit = true;
+                    Action::Quit
+                        if self.television.mode == Mode::RemoteControl =>
+                    {
+                        self.action_tx.send(Action::ToggleRemoteControl)?;
+                    }
+                    Action::Quit | Action::Abort => {
+                        self.stop_watch_timer();
+                        self.should_quit = true;
+                        if !self.render_tx.is_closed() {
                             self.render_tx.send(RenderingTask::Quit)?;
                         }
+                        return Ok(ActionOutcome::None);
                     }
                     Action::Suspend => {
                         self.render_tx.send(RenderingTask::Suspend)?;
@@ -484,11 +833,22 @@ impl App {
                     Action::Resume => {
                         self.render_tx.send(RenderingTask::Resume)?;
                     }
+                    Action::FocusLost | Action::FocusGained => {
+                        self.render_tx.send(RenderingTask::Focus(
+                            action == Action::FocusGained,
+                        ))?;
+                    }
                     Action::SelectAndExit => {
                         self.should_quit = true;
                         if !self.render_tx.is_closed() {
                             self.render_tx.send(RenderingTask::Quit)?;
                         }
+                        // an entry edited inline is output as is
+                        if let Some(edited) =
+                            self.television.inline_edit_output()
+                        {
+                            return Ok(ActionOutcome::Input(edited));
+                        }
                         if let Some(entries) =
                             self.television.get_selected_entries()
                         {
@@ -533,6 +893,10 @@ impl App {
                     Action::ClearScreen => {
                         self.render_tx.send(RenderingTask::ClearScreen)?;
                     }
+                    Action::Error(ref message) => {
+                        warn!("{}", message);
+                        self.render_tx.send(RenderingTask::Bell)?;
+                    }
                     Action::Resize(w, h) => {
                         self.render_tx.send(RenderingTask::Resize(w, h))?;
                     }
@@ -563,6 +927,50 @@ impl App {
                             self.television.set_pattern("");
                         }
                     }
+                    Action::OpenEntry => {
+                        if self.television.mode == Mode::Channel {
+                            if let Some(outcome) =
+                                self.open_selected_entries()?
+                            {
+                                return Ok(outcome);
+                            }
+                        }
+                    }
+                    Action::Execute(ref command)
+                    | Action::ExecuteSilent(ref command) => {
+                        if self.television.mode == Mode::Channel {
+                            let output =
+                                if matches!(action, Action::Execute(_)) {
+                                    OutputMode::Pager
+                                } else {
+                                    OutputMode::Silent
+                                };
+                            self.execute_bound_command(command, output)?;
+                        }
+                    }
+                    Action::EditChannelConfig => {
+                        if self.television.mode == Mode::RemoteControl {
+                            self.edit_channel_config()?;
+                        }
+                    }
+                    Action::AnswerConfirmation(accepted) => {
+                        if let Some(confirmation) =
+                            self.television.confirmation.take()
+                            && accepted
+                            && let Some(action_spec) = self
+                                .television
+                                .merged_config
+                                .channel_actions
+                                .get(&confirmation.action_name)
+                                .cloned()
+                            && let Some(outcome) = self.run_action_spec(
+                                &action_spec,
+                                &confirmation.entries,
+                            )?
+                        {
+                            return Ok(outcome);
+                        }
+                    }
                     Action::ExternalAction(ref action_name) => {
                         debug!("External action triggered: {}", action_name);
 
@@ -578,21 +986,23 @@ impl App {
                                     ))
                                     .cloned()
                             {
-                                match action_spec.mode {
-                                    // suspend the TUI and execute the action
-                                    ExecutionMode::Fork => {
-                                        self.run_external_command_fork(
-                                            &action_spec,
-                                            &selected_entries,
-                                        )?;
-                                    }
-                                    // clean up and exit the TUI and execute the action
-                                    ExecutionMode::Execute => {
-                                        self.run_external_command_execute(
+                                if action_spec.confirm {
+                                    // wait for the user to confirm first
+                                    self.television.confirmation =
+                                        Some(Confirmation::new(
+                                            action_name.trim_start_matches(
+                                                CUSTOM_ACTION_PREFIX,
+                                            ),
                                             &action_spec,
-                                            &selected_entries,
-                                        )?;
-                                    }
+                                            selected_entries,
+                                        )?);
+                                } else if let Some(outcome) = self
+                                    .run_action_spec(
+                                        &action_spec,
+                                        &selected_entries,
+                                    )?
+                                {
+                                    return Ok(outcome);
                                 }
                             }
                         } else {
@@ -631,7 +1041,7 @@ impl App {
     fn run_external_command_fork(
         &self,
         action_spec: &ActionSpec,
-        entries: &FxHashSet<Entry>,
+        entries: &FxIndexSet<Entry>,
     ) -> Result<()> {
         // suspend the event loop
         self.event_control_tx
@@ -658,28 +1068,255 @@ impl App {
         Ok(())
     }
 
-    fn run_external_command_execute(
+    fn run_action_spec(
+        &mut self,
+        action_spec: &ActionSpec,
+        entries: &FxIndexSet<Entry>,
+    ) -> Result<Option<ActionOutcome>> {
+        match action_spec.mode {
+            // suspend the TUI and execute the action
+            ExecutionMode::Fork
+                if action_spec.output == OutputMode::Inherit =>
+            {
+                self.run_external_command_fork(action_spec, entries)?;
+                self.television.remove_acted_upon(action_spec, entries);
+                if action_spec.reload {
+                    self.reload_after_action()?;
+                }
+                Ok(None)
+            }
+            // run in the background and keep the output around
+            ExecutionMode::Fork => {
+                self.spawn_external_command_captured(action_spec, entries);
+                Ok(None)
+            }
+            // exit the TUI, the action is executed once the terminal is
+            // restored
+            ExecutionMode::Execute | ExecutionMode::Become => {
+                self.stop_watch_timer();
+                self.should_quit = true;
+                if !self.render_tx.is_closed() {
+                    self.render_tx.send(RenderingTask::Quit)?;
+                }
+                Ok(Some(ActionOutcome::ExternalAction(
+                    action_spec.clone(),
+                    entries.clone(),
+                )))
+            }
+        }
+    }
+
+    /// Run a forked action in the background, capturing its output.
+    fn spawn_external_command_captured(
         &mut self,
         action_spec: &ActionSpec,
-        entries: &FxHashSet<Entry>,
+        entries: &FxIndexSet<Entry>,
+    ) {
+        let action_spec = action_spec.clone();
+        let entries = entries.clone();
+        self.running_actions
+            .push(tokio::task::spawn_blocking(move || {
+                let output = capture_action(&action_spec, &entries);
+                (action_spec, entries, output)
+            }));
+        self.television.running_actions = self.running_actions.len();
+    }
+
+    /// Run a command bound in `[keybindings.execute]` against the selected
+    /// entries, in the background so that tv stays responsive.
+    fn execute_bound_command(
+        &mut self,
+        command: &str,
+        output: OutputMode,
     ) -> Result<()> {
-        // cleanup
-        self.render_tx.send(RenderingTask::Quit)?;
-        // wait for the rendering task to finish
-        if let Some(rendering_task) = self.render_task.take() {
-            while !rendering_task.is_finished() {
-                sleep(Duration::from_millis(10));
+        let Some(entries) = self.television.get_selected_entries() else {
+            self.action_tx.send(Action::Error(
+                "No entry available to run the command on".to_string(),
+            ))?;
+            return Ok(());
+        };
+        let template =
+            Template::parse(command).map_err(|e| anyhow::anyhow!(e))?;
+        self.spawn_external_command_captured(
+            &ActionSpec::background(template, output),
+            &entries,
+        );
+        Ok(())
+    }
+
+    /// Collect the results of background actions that have completed.
+    async fn collect_finished_actions(&mut self) -> Result<()> {
+        let (finished, running): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.running_actions)
+                .into_iter()
+                .partition(tokio::task::JoinHandle::is_finished);
+        self.running_actions = running;
+        self.television.running_actions = self.running_actions.len();
+        for handle in finished {
+            let (action_spec, entries, output) = handle.await?;
+            self.handle_captured_output(&action_spec, &entries, output)?;
+        }
+        Ok(())
+    }
+
+    fn handle_captured_output(
+        &mut self,
+        action_spec: &ActionSpec,
+        entries: &FxIndexSet<Entry>,
+        output: Result<CommandOutput>,
+    ) -> Result<()> {
+        let output = match output {
+            Ok(output) => output,
+            Err(e) => {
+                self.action_tx.send(Action::Error(format!(
+                    "Failed to execute external action: {e}"
+                )))?;
+                return Ok(());
+            }
+        };
+        if output.success() {
+            self.television.remove_acted_upon(action_spec, entries);
+        } else {
+            self.action_tx.send(Action::Error(output.error_message()))?;
+        }
+        match action_spec.output {
+            OutputMode::Pager => {
+                if !self.television.show_command_output(&output) {
+                    // no preview panel to show it in
+                    self.command_outputs.push(output);
+                }
             }
+            OutputMode::Print => self.command_outputs.push(output),
+            OutputMode::Silent | OutputMode::Inherit => {}
+        }
+        if action_spec.reload {
+            self.reload_after_action()?;
         }
+        Ok(())
+    }
 
-        execute_action(action_spec, entries).map_err(|e| {
-            error!("Failed to execute external action: {}", e);
-            anyhow::anyhow!("Failed to execute external action: {}", e)
-        })?;
+    /// Clear the selection and reload the source after an action has run.
+    fn reload_after_action(&mut self) -> Result<()> {
+        self.television.channel.clear_selection();
+        self.action_tx.send(Action::ReloadSource)?;
+        Ok(())
+    }
+
+    /// Open the selected entries using the channel's `open` action if it
+    /// defines one, or `$EDITOR` otherwise.
+    fn open_selected_entries(&mut self) -> Result<Option<ActionOutcome>> {
+        let Some(entries) = self.television.get_selected_entries() else {
+            self.action_tx.send(Action::Error(
+                "No entry available to open".to_string(),
+            ))?;
+            return Ok(None);
+        };
+
+        if let Some(action_spec) = self
+            .television
+            .merged_config
+            .channel_actions
+            .get(OPEN_ACTION_NAME)
+            .cloned()
+        {
+            return self.run_action_spec(&action_spec, &entries);
+        }
+
+        let mut paths: Vec<&str> =
+            entries.iter().map(|entry| entry.raw.as_str()).collect();
+        paths.sort_unstable();
+        self.open_in_editor(&paths)?;
+        Ok(None)
+    }
 
+    /// Open the file the channel highlighted in the remote control was loaded
+    /// from in `$EDITOR`, and reload the channel once the editor exits.
+    fn edit_channel_config(&mut self) -> Result<()> {
+        let Some(entry) = self.television.get_selected_cable_entry() else {
+            return Ok(());
+        };
+        let Some(path) = self
+            .television
+            .remote_control
+            .as_ref()
+            .and_then(|rc| rc.cable_channels.get(&entry.channel_name))
+            .and_then(|prototype| prototype.source_path.clone())
+        else {
+            self.action_tx.send(Action::Error(format!(
+                "`{}` is a built-in channel and has no file to edit",
+                entry.channel_name
+            )))?;
+            return Ok(());
+        };
+
+        if self.open_in_editor(&[path.to_string_lossy()])?
+            && let Err(e) = self.television.reload_channel_file(&path)
+        {
+            self.action_tx.send(Action::Error(format!(
+                "Failed to reload {}: {e}",
+                path.display()
+            )))?;
+        }
         Ok(())
     }
 
+    /// Suspend the UI and open the given paths in `$EDITOR`, returning
+    /// whether the editor ran successfully.
+    fn open_in_editor<S: AsRef<str>>(&mut self, paths: &[S]) -> Result<bool> {
+        let Some(editor) = std::env::var("EDITOR")
+            .ok()
+            .filter(|editor| !editor.trim().is_empty())
+        else {
+            self.action_tx
+                .send(Action::Error("$EDITOR is not set".to_string()))?;
+            return Ok(false);
+        };
+        let command = editor_command(&editor, paths);
+        debug!("Opening in editor: {}", command);
+
+        // suspend the event loop and hand the terminal over to the editor
+        self.event_control_tx
+            .send(ControlEvent::Pause)
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to suspend event loop: {}", e)
+            })?;
+        let (ack_tx, ack_rx) = std::sync::mpsc::channel();
+        self.render_tx.send(RenderingTask::Release(ack_tx))?;
+        // the terminal must be fully restored before the editor starts
+        if ack_rx.recv_timeout(Duration::from_secs(1)).is_err() {
+            warn!("Timed out waiting for the terminal to be restored");
+        }
+
+        let status =
+            shell_command(&command, false, &FxHashMap::default()).status();
+
+        // take the terminal back and redraw everything
+        self.event_control_tx
+            .send(ControlEvent::Resume)
+            .map_err(|e| {
+                anyhow::anyhow!("Failed to resume event loop: {}", e)
+            })?;
+        self.render_tx.send(RenderingTask::Resume)?;
+        self.render_tx.send(RenderingTask::ClearScreen)?;
+        self.action_tx.send(Action::Render)?;
+
+        match status {
+            Ok(status) if !status.success() => {
+                self.action_tx.send(Action::Error(format!(
+                    "Editor exited with {status}"
+                )))?;
+                Ok(false)
+            }
+            Err(e) => {
+                self.action_tx.send(Action::Error(format!(
+                    "Failed to launch editor: {e}"
+                )))?;
+                Ok(false)
+            }
+            Ok(_) => Ok(true),
+        }
+    }
+
     /// Maybe select the first entry if there is only one entry available.
     fn maybe_select_1(&mut self) -> Option<ActionOutcome> {
         debug!("Automatically selecting the first entry");
@@ -692,7 +1329,7 @@ impl App {
                 let _ = self.render_tx.send(RenderingTask::Quit);
             }
 
-            return Some(ActionOutcome::Entries(FxHashSet::from_iter([
+            return Some(ActionOutcome::Entries(FxIndexSet::from_iter([
                 unique_entry.clone(),
             ])));
         }
@@ -700,7 +1337,7 @@ impl App {
     }
 
     /// Take the first entry from the list regardless of how many entries are available.
-    /// If the list is empty, exit with None.
+    /// If the list is empty, exit with `NoMatch`.
     fn maybe_take_1(&mut self) -> ActionOutcome {
         if let Some(first_entry) =
             self.television.results_picker.entries.first()
@@ -712,16 +1349,18 @@ impl App {
                 let _ = self.render_tx.send(RenderingTask::Quit);
             }
 
-            ActionOutcome::Entries(FxHashSet::from_iter([first_entry.clone()]))
+            ActionOutcome::Entries(FxIndexSet::from_iter(
+                [first_entry.clone()],
+            ))
         } else {
-            debug!("No entries available, exiting with None");
+            debug!("No entries available, exiting with no match");
             self.should_quit = true;
 
             if !self.render_tx.is_closed() {
                 let _ = self.render_tx.send(RenderingTask::Quit);
             }
 
-            ActionOutcome::None
+            ActionOutcome::NoMatch
         }
     }
 
@@ -749,10 +1388,297 @@ impl App {
     }
 }
 
+/// Turn pasted text into a single input action.
+///
+/// The input bar is a single line so line breaks are replaced with spaces
+/// and other control characters dropped. A trailing newline confirms the
+/// selection when `confirm_on_newline` is set.
+fn paste_actions(text: &str, confirm_on_newline: bool) -> Vec<Action> {
+    let trimmed = text.trim_end_matches(['\r', '\n']);
+    let confirm = confirm_on_newline && trimmed.len() < text.len();
+    let text: String = trimmed
+        .replace("\r\n", "\n")
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect();
+
+    let mut actions = Vec::new();
+    if !text.is_empty() {
+        actions.push(Action::AddInputString(text));
+    }
+    if confirm {
+        actions.push(Action::ConfirmSelection);
+    }
+    if actions.is_empty() {
+        actions.push(Action::NoOp);
+    }
+    actions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_paste_actions() {
+        assert_eq!(
+            paste_actions("foo bar", false),
+            vec![Action::AddInputString("foo bar".to_string())]
+        );
+        // line breaks and control characters don't end up in the input
+        assert_eq!(
+            paste_actions("foo\r\nbar\x1b\n", false),
+            vec![Action::AddInputString("foo bar".to_string())]
+        );
+        assert_eq!(
+            paste_actions("foo\n", true),
+            vec![
+                Action::AddInputString("foo".to_string()),
+                Action::ConfirmSelection
+            ]
+        );
+        assert_eq!(paste_actions("\n", false), vec![Action::NoOp]);
+    }
+
+    #[tokio::test]
+    async fn test_key_bound_to_action_sequence() {
+        use crate::{
+            channels::prototypes::ChannelPrototype, cli::PostProcessedCli,
+        };
+
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "sequence"
+
+            [source]
+            command = "echo"
+
+            [keybindings]
+            ctrl-g = ["delete_line", "reload_source"]
+            "#,
+        )
+        .unwrap();
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype,
+            PostProcessedCli::default(),
+        );
+        let app = App::new(layered_config, Cable::from_prototypes(vec![]));
+
+        // the actions are queued in order for a single key press
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Ctrl('g'))),
+            vec![Action::DeleteLine, Action::ReloadSource]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_key_bound_to_select_index() {
+        use crate::{
+            channels::{channel::Source, prototypes::ChannelPrototype},
+            cli::PostProcessedCli,
+            screen::layout::Layout,
+        };
+        use ratatui::layout::Rect;
+        use std::sync::Arc;
+
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "fruits"
+
+            [source]
+            command = "echo"
+
+            [keybindings]
+            alt-2 = { select_index = 2 }
+            "#,
+        )
+        .unwrap();
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            prototype,
+            PostProcessedCli::default(),
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+        let tv = &mut app.television;
+        tv.update_ui_state(UiState::new(Layout {
+            results: Rect::new(0, 0, 20, 10),
+            ..Layout::default()
+        }));
+        tv.channel.set_source(Source::Static(Arc::new(
+            ["apple", "banana", "cherry"].map(String::from).to_vec(),
+        )));
+        tv.channel.load();
+        for _ in 0..100 {
+            tv.update_results_picker_state();
+            if tv.channel.result_count() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let second = tv.channel.results(3, 0)[1].clone();
+
+        let actions =
+            app.convert_event_to_actions(Event::Input(Key::Alt('2')));
+        assert_eq!(actions, vec![Action::SelectIndex(2)]);
+        for action in &actions {
+            app.television.update(action).unwrap();
+        }
+        assert_eq!(app.television.get_selected_entry(), Some(second));
+    }
+
+    #[tokio::test]
+    async fn test_abort_keys_win_over_bindings() {
+        use crate::{
+            channels::prototypes::ChannelPrototype,
+            cli::{GlobalCli, PostProcessedCli},
+        };
+
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "abort"
+
+            [source]
+            command = "echo"
+
+            [keybindings]
+            ctrl-g = "reload_source"
+            "#,
+        )
+        .unwrap();
+        let layered_config = ConfigLayers::new(
+            crate::config::default_config_from_file().unwrap(),
+            prototype,
+            PostProcessedCli {
+                global: GlobalCli {
+                    abort_keys: vec![Key::Ctrl('g')],
+                    ..GlobalCli::default()
+                },
+                ..PostProcessedCli::default()
+            },
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Ctrl('g'))),
+            vec![Action::Abort]
+        );
+        // even when a confirmation captures the other keys
+        app.television.confirmation = Some(Confirmation {
+            action_name: "delete".to_string(),
+            commands: vec!["rm foo".to_string()],
+            entries: FxIndexSet::default(),
+        });
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Ctrl('g'))),
+            vec![Action::Abort]
+        );
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::AnswerConfirmation(false)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_focused_preview_captures_keys() {
+        let layered_config = ConfigLayers::new(
+            crate::config::default_config_from_file().unwrap(),
+            crate::channels::prototypes::ChannelPrototype::stdin(),
+            crate::cli::PostProcessedCli::default(),
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+        app.television.preview_focus = Some(Default::default());
+
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Char('j'))),
+            vec![Action::PreviewPanelKey(Key::Char('j'))]
+        );
+        // esc gives the focus back instead of quitting
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::PreviewPanelKey(Key::Esc)]
+        );
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Alt('p'))),
+            vec![Action::FocusPreview]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_esc_clears_the_input_then_quits() {
+        use crate::utils::input::Input;
+
+        let mut config = crate::config::default_config_from_file().unwrap();
+        config.ui.esc_behavior = EscBehavior::ClearThenQuit;
+        let layered_config = ConfigLayers::new(
+            config,
+            crate::channels::prototypes::ChannelPrototype::stdin(),
+            crate::cli::PostProcessedCli::default(),
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+        app.television.results_picker.input = Input::new("foo".to_string());
+
+        // the remote control is closed first
+        app.television.mode = Mode::RemoteControl;
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::Quit]
+        );
+        // then the query is cleared
+        app.television.mode = Mode::Channel;
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::DeleteLine]
+        );
+        // and only then does tv quit
+        app.television.results_picker.input = Input::new(String::new());
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::Quit]
+        );
+
+        // quitting right away, whatever the query
+        app.television.merged_config.esc_behavior = EscBehavior::Quit;
+        app.television.results_picker.input = Input::new("foo".to_string());
+        assert_eq!(
+            app.convert_event_to_actions(Event::Input(Key::Esc)),
+            vec![Action::Quit]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_bound_commands_run_in_the_background() {
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            crate::channels::prototypes::ChannelPrototype::stdin(),
+            crate::cli::PostProcessedCli::default(),
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+        let entries = FxIndexSet::from_iter([Entry::new("x".to_string())]);
+
+        let start = std::time::Instant::now();
+        app.spawn_external_command_captured(
+            &ActionSpec::background(
+                Template::parse("sleep 1 && echo {}").unwrap(),
+                OutputMode::Pager,
+            ),
+            &entries,
+        );
+
+        // the command doesn't block the event loop
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(app.television.running_actions, 1);
+        let (_, _, output) = app.running_actions.pop().unwrap().await.unwrap();
+        assert_eq!(output.unwrap().stdout, "x\n");
+    }
+
     #[test]
     fn test_determine_tui_mode() {
         // Test inline mode
@@ -797,4 +1723,63 @@ mod tests {
         // Test error case for width without height
         assert!(App::determine_tui_mode(None, Some(80), false).is_err());
     }
+
+    #[test]
+    fn test_external_action_outcome_keeps_action_spec() {
+        use crate::channels::prototypes::ChannelPrototype;
+
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "branches"
+
+            [source]
+            command = "git branch"
+
+            [actions.delete]
+            command = "git branch -D {}"
+            mode = "execute"
+            separator = "\n"
+            "#,
+        )
+        .unwrap();
+        let action_spec = prototype.actions["delete"].clone();
+        let entries = FxIndexSet::from_iter([Entry::new("main".to_string())]);
+
+        let output = AppOutput::new(ActionOutcome::ExternalAction(
+            action_spec.clone(),
+            entries.clone(),
+        ));
+
+        let (spec, selected) = output.external_action.unwrap();
+        assert_eq!(spec.command.get_nth(0).raw(), "git branch -D {}");
+        assert_eq!(spec.separator, "\n");
+        assert_eq!(spec.mode, ExecutionMode::Execute);
+        assert_eq!(selected, entries);
+        assert!(output.selected_entries.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_execute_actions_exit_before_running() {
+        let layered_config = ConfigLayers::new(
+            crate::config::Config::default(),
+            crate::channels::prototypes::ChannelPrototype::stdin(),
+            crate::cli::PostProcessedCli::default(),
+        );
+        let mut app = App::new(layered_config, Cable::from_prototypes(vec![]));
+        let entries = FxIndexSet::from_iter([Entry::new("x".to_string())]);
+        let mut action_spec = ActionSpec::background(
+            Template::parse("touch {}").unwrap(),
+            OutputMode::Inherit,
+        );
+        action_spec.mode = ExecutionMode::Execute;
+
+        // the action is handed over to `main` rather than run in the TUI
+        let outcome = app.run_action_spec(&action_spec, &entries).unwrap();
+        assert_eq!(
+            outcome,
+            Some(ActionOutcome::ExternalAction(action_spec, entries))
+        );
+        assert!(app.should_quit);
+    }
 }
@@ -1,13 +1,16 @@
 use crate::{
-    action::Action, channels::prototypes::ChannelPrototype,
-    config::Keybindings, errors::unknown_channel_exit, event::Key,
+    action::Action,
+    channels::prototypes::ChannelPrototype,
+    config::{Keybindings, describe_toml_error},
+    errors::unknown_channel_exit,
+    event::Key,
 };
 use rustc_hash::FxHashMap;
 use std::{
     ops::Deref,
     path::{Path, PathBuf},
 };
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 use walkdir::WalkDir;
 
 /// A neat `HashMap` of channel prototypes indexed by their name.
@@ -99,7 +102,7 @@ pub const CHANNEL_FILE_FORMAT: &str = "toml";
 /// ```
 pub const CABLE_DIR_NAME: &str = "cable";
 
-fn get_cable_files<P>(cable_dir: P) -> Vec<PathBuf>
+pub(crate) fn get_cable_files<P>(cable_dir: P) -> Vec<PathBuf>
 where
     P: AsRef<Path>,
 {
@@ -114,26 +117,60 @@ where
         .collect::<Vec<_>>()
 }
 
+/// Load a single cable channel prototype from the given file.
+pub fn load_channel_file(path: &Path) -> anyhow::Result<ChannelPrototype> {
+    let content = std::fs::read_to_string(path)?;
+    let mut prototype =
+        toml::from_str::<ChannelPrototype>(&content).map_err(|e| {
+            anyhow::anyhow!(describe_toml_error(path, &content, &e))
+        })?;
+    warn_legacy_keys(&prototype, path);
+    prototype.source_path = Some(path.to_path_buf());
+    Ok(prototype)
+}
+
+/// Warn about the sections of `prototype` left from older versions of the
+/// channel files, which are ignored.
+fn warn_legacy_keys(prototype: &ChannelPrototype, path: &Path) {
+    if prototype
+        .ui
+        .as_ref()
+        .is_some_and(|ui| ui.features.is_some())
+    {
+        warn!(
+            "{}: `[ui.features]` is no longer supported and will be ignored",
+            path.display()
+        );
+    }
+}
+
+/// Parse the given channel files, indexed by file name along with where they
+/// were read from (built-in channels don't have a source path).
 fn load_prototypes(
-    toml_prototypes: FxHashMap<PathBuf, String>,
+    toml_prototypes: FxHashMap<PathBuf, (Option<PathBuf>, String)>,
 ) -> Vec<ChannelPrototype> {
     toml_prototypes
         .into_iter()
-        .filter_map(|(path, content)| {
+        .filter_map(|(path, (source_path, content))| {
             match toml::from_str::<ChannelPrototype>(&content) {
-                Ok(prototype) => {
+                Ok(mut prototype) => {
                     debug!(
                         "Loaded cable channel prototype from {}: {}",
                         path.display(),
                         prototype.metadata.name
                     );
+                    warn_legacy_keys(&prototype, &path);
+                    prototype.source_path = source_path;
                     Some(prototype)
                 }
                 Err(e) => {
                     eprintln!(
-                        "Failed to parse cable channel file {}: {}",
-                        path.display(),
-                        e
+                        "Failed to parse cable channel file {}",
+                        describe_toml_error(
+                            source_path.as_deref().unwrap_or(&path),
+                            &content,
+                            &e
+                        )
                     );
                     None
                 }
@@ -167,18 +204,22 @@ where
     let cable_files = get_cable_files(cable_dir);
     debug!("Found cable channel files: {:?}", cable_files);
 
-    let mut cable_map: FxHashMap<PathBuf, String> = DEFAULT_CABLE_FILES
-        .iter()
-        .map(|(name, content)| (PathBuf::from(*name), (*content).to_string()))
-        .collect();
+    let mut cable_map: FxHashMap<PathBuf, (Option<PathBuf>, String)> =
+        DEFAULT_CABLE_FILES
+            .iter()
+            .map(|(name, content)| {
+                (PathBuf::from(*name), (None, (*content).to_string()))
+            })
+            .collect();
 
     cable_map.extend(
         cable_files
             .into_iter()
             .filter_map(|path| match std::fs::read_to_string(&path) {
-                Ok(content) => {
-                    Some((path.file_name().unwrap().into(), content))
-                }
+                Ok(content) => Some((
+                    path.file_name().unwrap().into(),
+                    (Some(path.clone()), content),
+                )),
                 Err(e) => {
                     error!(
                         "Failed to read cable channel file {}: {}",
@@ -222,6 +263,7 @@ const DEFAULT_CABLE_FILES: &[(&str, &str)] = &[
         "git-repos.toml",
         include_str!("../cable/unix/git-repos.toml"),
     ),
+    ("grep.toml", include_str!("../cable/unix/grep.toml")),
     ("text.toml", include_str!("../cable/unix/text.toml")),
 ];
 
@@ -251,5 +293,46 @@ const DEFAULT_CABLE_FILES: &[(&str, &str)] = &[
         "git-repos.toml",
         include_str!("../cable/windows/git-repos.toml"),
     ),
+    ("grep.toml", include_str!("../cable/windows/grep.toml")),
     ("text.toml", include_str!("../cable/windows/text.toml")),
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_load_cable_records_source_paths() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("custom.toml");
+        std::fs::write(
+            &path,
+            "[metadata]\nname = \"custom\"\n\n[source]\ncommand = \"ls\"\n",
+        )
+        .unwrap();
+
+        let cable = load_cable(dir.path());
+
+        assert_eq!(cable["custom"].source_path.as_deref(), Some(&*path));
+        // built-in channels don't come from a file
+        assert!(cable["files"].source_path.is_none());
+    }
+
+    #[test]
+    fn test_load_channel_file_suggests_fields() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("custom.toml");
+        std::fs::write(
+            &path,
+            "[metadata]\nname = \"custom\"\ndescripton = \"typo\"\n\n[source]\ncommand = \"ls\"\n",
+        )
+        .unwrap();
+
+        let error = load_channel_file(&path).unwrap_err().to_string();
+
+        assert!(error.starts_with(&format!("{}:3:1:", path.display())));
+        assert!(error.contains("unknown field `descripton`"));
+        assert!(error.ends_with("did you mean `description`?"));
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use ignore::{WalkBuilder, WalkState};
+
+/// Source command that lists files with a walker built into tv instead of
+/// running an external command, e.g. `builtin:files --hidden`.
+pub const BUILTIN_FILES: &str = "builtin:files";
+
+/// Source command that searches the contents of files for the query, like
+/// `rg --line-number` would, listing matching lines as `path:line:text`.
+pub const BUILTIN_GREP: &str = "builtin:grep";
+
+/// Files bigger than this aren't searched by the built-in grep source.
+const MAX_GREPPED_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// How the built-in sources walk the current directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileWalkOptions {
+    /// Include hidden files and directories.
+    pub hidden: bool,
+    /// Don't skip files ignored by `.gitignore`, `.ignore` and the like.
+    pub no_ignore: bool,
+}
+
+/// A source implemented by tv itself rather than by an external command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinSource {
+    /// List the files under the current directory.
+    Files(FileWalkOptions),
+    /// Search the files under the current directory for the query.
+    Grep(FileWalkOptions),
+}
+
+impl BuiltinSource {
+    /// The built-in source `command` stands for, if any, the `--hidden` and
+    /// `--no-ignore` flags adding to the `defaults`.
+    pub fn parse(command: &str, defaults: FileWalkOptions) -> Option<Self> {
+        let mut words = command.split_whitespace();
+        let source: fn(FileWalkOptions) -> Self = match words.next()? {
+            BUILTIN_FILES => Self::Files,
+            BUILTIN_GREP => Self::Grep,
+            _ => return None,
+        };
+        let mut options = defaults;
+        for word in words {
+            match word {
+                "--hidden" | "-H" => options.hidden = true,
+                "--no-ignore" | "-I" => options.no_ignore = true,
+                _ => return None,
+            }
+        }
+        Some(source(options))
+    }
+
+    /// Run the source under `root`, calling `push` with each entry until it
+    /// returns `false`, `query` being what the grep source looks for.
+    ///
+    /// Stops after `max_entries` entries, returning whether there were more.
+    pub fn run(
+        self,
+        root: &Path,
+        query: &str,
+        max_entries: Option<u64>,
+        push: impl Fn(String) -> bool + Send + Sync,
+    ) -> bool {
+        match self {
+            Self::Files(options) => {
+                walk(root, options, max_entries, &push, |_, relative| {
+                    vec![relative.to_string()]
+                })
+            }
+            Self::Grep(options) => {
+                // like ripgrep, an empty pattern would match every line
+                if query.is_empty() {
+                    return false;
+                }
+                let matcher = LineMatcher::new(query);
+                walk(root, options, max_entries, &push, |path, relative| {
+                    grep_file(path, relative, &matcher)
+                })
+            }
+        }
+    }
+}
+
+/// Walk `root` with as many threads as there are cores, pushing the entries
+/// `search` finds in each file, which is given both as is and relative to
+/// `root`.
+fn walk(
+    root: &Path,
+    options: FileWalkOptions,
+    max_entries: Option<u64>,
+    push: &(impl Fn(String) -> bool + Send + Sync),
+    search: impl Fn(&Path, &str) -> Vec<String> + Send + Sync,
+) -> bool {
+    let walker = WalkBuilder::new(root)
+        .hidden(!options.hidden)
+        .ignore(!options.no_ignore)
+        .git_ignore(!options.no_ignore)
+        .git_global(!options.no_ignore)
+        .git_exclude(!options.no_ignore)
+        .parents(!options.no_ignore)
+        // don't descend into `.git` when listing hidden files
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build_parallel();
+    let loaded = AtomicU64::new(0);
+    let truncated = AtomicBool::new(false);
+    walker.run(|| {
+        let search = &search;
+        let loaded = &loaded;
+        let truncated = &truncated;
+        Box::new(move |entry| {
+            let Ok(entry) = entry else {
+                return WalkState::Continue;
+            };
+            if !entry.file_type().is_some_and(|t| t.is_file()) {
+                return WalkState::Continue;
+            }
+            let path = entry.path();
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            for found in search(path, &relative.to_string_lossy()) {
+                let count = loaded.fetch_add(1, Ordering::Relaxed);
+                if max_entries.is_some_and(|max| count >= max) {
+                    truncated.store(true, Ordering::Relaxed);
+                    return WalkState::Quit;
+                }
+                if !push(found) {
+                    return WalkState::Quit;
+                }
+            }
+            WalkState::Continue
+        })
+    });
+    truncated.load(Ordering::Relaxed)
+}
+
+/// Matches lines containing the query, ignoring case unless the query has
+/// uppercase characters (like `rg --smart-case`).
+struct LineMatcher {
+    query: String,
+    ignore_case: bool,
+}
+
+impl LineMatcher {
+    fn new(query: &str) -> Self {
+        let ignore_case = !query.chars().any(char::is_uppercase);
+        Self {
+            query: if ignore_case {
+                query.to_lowercase()
+            } else {
+                query.to_string()
+            },
+            ignore_case,
+        }
+    }
+
+    fn is_match(&self, line: &str) -> bool {
+        if self.ignore_case {
+            line.to_lowercase().contains(&self.query)
+        } else {
+            line.contains(&self.query)
+        }
+    }
+}
+
+/// The lines of the file at `path` matching, as `relative:line:text`,
+/// skipping binary and very large files.
+fn grep_file(
+    path: &Path,
+    relative: &str,
+    matcher: &LineMatcher,
+) -> Vec<String> {
+    if !std::fs::metadata(path).is_ok_and(|m| m.len() <= MAX_GREPPED_FILE_SIZE)
+    {
+        return Vec::new();
+    }
+    let Ok(content) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    if content[..content.len().min(8192)].contains(&0) {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&content)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| matcher.is_match(line))
+        .map(|(i, line)| format!("{relative}:{}:{}", i + 1, line.trim_end()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    fn run(root: &Path, source: BuiltinSource, query: &str) -> Vec<String> {
+        let entries = Mutex::new(Vec::new());
+        source.run(root, query, None, |entry| {
+            entries.lock().push(entry);
+            true
+        });
+        let mut entries = entries.into_inner();
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn test_parse() {
+        let defaults = FileWalkOptions::default();
+        assert_eq!(BuiltinSource::parse("fd -t f", defaults), None);
+        assert_eq!(BuiltinSource::parse("builtin:filesystem", defaults), None);
+        assert_eq!(
+            BuiltinSource::parse("builtin:files", defaults),
+            Some(BuiltinSource::Files(defaults))
+        );
+        assert_eq!(
+            BuiltinSource::parse("builtin:grep --hidden", defaults),
+            Some(BuiltinSource::Grep(FileWalkOptions {
+                hidden: true,
+                no_ignore: false
+            }))
+        );
+        let no_ignore = FileWalkOptions {
+            hidden: false,
+            no_ignore: true,
+        };
+        assert_eq!(
+            BuiltinSource::parse("builtin:files", no_ignore),
+            Some(BuiltinSource::Files(no_ignore))
+        );
+        assert_eq!(
+            BuiltinSource::parse("builtin:files --follow", defaults),
+            None
+        );
+    }
+
+    #[test]
+    fn test_files_honor_gitignore_and_hidden_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        // `.gitignore` files only apply inside git repositories
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join(".git/HEAD"), "").unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/main.rs"), "").unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join("target/out"), "").unwrap();
+        std::fs::write(root.join(".env"), "").unwrap();
+
+        let sep = std::path::MAIN_SEPARATOR;
+        let files = |options| run(root, BuiltinSource::Files(options), "");
+        assert_eq!(
+            files(FileWalkOptions::default()),
+            vec![format!("src{sep}main.rs")]
+        );
+        assert_eq!(
+            files(FileWalkOptions {
+                hidden: true,
+                no_ignore: true
+            }),
+            vec![
+                ".env".to_string(),
+                ".gitignore".to_string(),
+                format!("src{sep}main.rs"),
+                format!("target{sep}out"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_files_stop_at_max_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..10 {
+            std::fs::write(dir.path().join(i.to_string()), "").unwrap();
+        }
+        let count = AtomicU64::new(0);
+        let source = BuiltinSource::Files(FileWalkOptions::default());
+        let truncated = source.run(dir.path(), "", Some(4), |_| {
+            count.fetch_add(1, Ordering::Relaxed);
+            true
+        });
+        assert!(truncated);
+        assert_eq!(count.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    fn test_grep_lists_matching_lines_with_smart_case() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("notes.txt"),
+            "TODO: write docs\nnothing here\nmore todo items\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"todo\0").unwrap();
+        let grep = BuiltinSource::Grep(FileWalkOptions::default());
+
+        assert_eq!(
+            run(dir.path(), grep, "todo"),
+            vec![
+                "notes.txt:1:TODO: write docs",
+                "notes.txt:3:more todo items"
+            ]
+        );
+        assert_eq!(
+            run(dir.path(), grep, "TODO"),
+            vec!["notes.txt:1:TODO: write docs"]
+        );
+        assert!(run(dir.path(), grep, "").is_empty());
+    }
+}
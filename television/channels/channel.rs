@@ -1,47 +1,154 @@
 use crate::{
     channels::{
-        entry::Entry,
+        builtin::{BuiltinSource, FileWalkOptions},
+        entry::{Entry, EntryShape},
         entry_processor::{
             AnsiProcessor, DisplayProcessor, EntryProcessor, PlainProcessor,
         },
-        prototypes::{CommandSpec, Template},
+        icons::EntryIcons,
+        prototypes::{CommandSpec, Delimiter, Template},
+    },
+    matcher::{MAX_RESULTS, Matcher, config::Config, injector::Injector},
+    utils::{
+        command::{command_shell, shell_command},
+        hashmaps::FxIndexSet,
     },
-    matcher::{Matcher, config::Config, injector::Injector},
-    utils::command::shell_command,
 };
-use rustc_hash::{FxBuildHasher, FxHashSet};
-use std::collections::HashSet;
+use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
+use std::cmp::Ordering as CmpOrdering;
+use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
-use std::time::Duration;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
-use tracing::debug;
+use tracing::{debug, warn};
 
 const RELOAD_RENDERING_DELAY: Duration = Duration::from_millis(200);
+/// How long the query of a dynamic channel has to stay the same before the
+/// source is run again, so that it isn't run for every keystroke.
+const QUERY_DEBOUNCE_DELAY: Duration = Duration::from_millis(150);
+
+/// Where a channel gets its entries from.
+#[derive(Debug, Clone, Default)]
+pub enum Source {
+    /// The output of the channel's source command, or of the built-in source
+    /// it names.
+    #[default]
+    Command,
+    /// These entries, handed over by the caller. They're pushed to the
+    /// matcher right away, without spawning anything.
+    Static(Arc<Vec<String>>),
+}
+
+/// Tells which entries to take out of the results, see
+/// `Channel::remove_entries`.
+pub type EntryRemoval = Box<dyn Fn(&Entry) -> bool + Send + Sync>;
 
 pub struct Channel<P: EntryProcessor> {
     pub source_command: CommandSpec,
-    pub source_entry_delimiter: Option<char>,
-    pub source_output: Option<Template>,
+    pub source_record_separator: Option<String>,
     pub supports_preview: bool,
     processor: P,
     matcher: Matcher<P::Data>,
-    selected_entries: FxHashSet<Entry>,
+    selected_entries: FxIndexSet<Entry>,
     crawl_handle: Option<tokio::task::JoinHandle<()>>,
     current_source_index: usize,
     /// Indicates if the channel is currently reloading to prevent UI flickering
     /// by delaying the rendering of a new frame.
     pub reloading: Arc<AtomicBool>,
+    /// Maximum number of entries to load from the source.
+    pub max_entries: Option<u64>,
+    /// Set when the source produced more entries than `max_entries`.
+    truncated: Arc<AtomicBool>,
+    /// PID of the running source command (0 when there is none).
+    source_pid: Arc<AtomicU32>,
+    /// Why the last run of the source command failed, if it did.
+    source_error: Arc<Mutex<Option<String>>>,
+    /// How the matched results are currently sorted, if at all.
+    sort: Option<ResultsSort>,
+    /// Only show the matched results among these entries, if set.
+    filter: Option<FxHashSet<Entry>>,
+    /// List the entries most recent first as long as there's no pattern.
+    reverse: bool,
+    /// How the built-in sources walk the current directory.
+    file_walk: FileWalkOptions,
+    /// Re-run the source with the pattern instead of matching its entries
+    /// against it.
+    dynamic: bool,
+    /// The pattern the source of a dynamic channel was last run with.
+    query: String,
+    /// When the query of a dynamic channel last changed, until the source is
+    /// run again with it.
+    query_changed: Option<Instant>,
+    /// Also fuzzy match the entries of a dynamic source against the pattern.
+    fuzzy_filter: bool,
+    /// What the matched entries are built with.
+    shape: EntryShape,
+    /// Snapshot of the matched results filtered and sorted according to
+    /// `filter` and `sort`.
+    sorted: Option<SortedResults>,
+    /// Where the entries come from.
+    source: Source,
+    /// Removals waiting for the source to be done loading.
+    pending_removals: Vec<EntryRemoval>,
+}
+
+/// Sorts the matched results of a channel by one of the entries' fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultsSort {
+    /// Index of the field to sort by.
+    pub field: usize,
+    /// Sort in descending order.
+    pub reverse: bool,
+    /// Splits entries into fields.
+    pub delimiter: Delimiter,
+}
+
+impl ResultsSort {
+    fn compare(&self, a: &str, b: &str) -> CmpOrdering {
+        let field = |s: &'_ str| {
+            self.delimiter
+                .field(s, self.field)
+                .unwrap_or_default()
+                .trim()
+        };
+        let ordering = compare_fields(field(a), field(b));
+        if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+/// Compare two fields numerically when they both parse as numbers, numbers
+/// sorting before anything else.
+fn compare_fields(a: &str, b: &str) -> CmpOrdering {
+    match (a.parse::<f64>(), b.parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => CmpOrdering::Less,
+        (Err(_), Ok(_)) => CmpOrdering::Greater,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+/// The matched results at some point in time, filtered and sorted.
+struct SortedResults {
+    /// Matched and total item counts when the snapshot was taken.
+    counts: (u32, u32),
+    entries: Vec<Entry>,
 }
 
 impl<P: EntryProcessor> Channel<P> {
     pub fn new(
         source_command: CommandSpec,
-        source_entry_delimiter: Option<char>,
+        source_record_separator: Option<String>,
         source_output: Option<Template>,
         supports_preview: bool,
+        max_entries: Option<u64>,
         processor: P,
     ) -> Self {
         let config = Config::default().prefer_prefix(true);
@@ -49,27 +156,89 @@ impl<P: EntryProcessor> Channel<P> {
         let current_source_index = 0;
         Self {
             source_command,
-            source_entry_delimiter,
-            source_output,
+            source_record_separator,
             supports_preview,
             processor,
             matcher,
-            selected_entries: HashSet::with_hasher(FxBuildHasher),
+            selected_entries: FxIndexSet::default(),
             crawl_handle: None,
             current_source_index,
             reloading: Arc::new(AtomicBool::new(false)),
+            max_entries,
+            truncated: Arc::new(AtomicBool::new(false)),
+            source_pid: Arc::new(AtomicU32::new(0)),
+            source_error: Arc::new(Mutex::new(None)),
+            sort: None,
+            filter: None,
+            reverse: false,
+            file_walk: FileWalkOptions::default(),
+            dynamic: false,
+            query: String::new(),
+            query_changed: None,
+            fuzzy_filter: false,
+            shape: EntryShape {
+                output: source_output,
+                ..EntryShape::default()
+            },
+            sorted: None,
+            source: Source::Command,
+            pending_removals: Vec::new(),
         }
     }
 
+    /// A channel listing `entries` rather than the output of a source
+    /// command, all of them already pushed to the matcher.
+    pub fn from_entries(
+        entries: Vec<String>,
+        supports_preview: bool,
+        max_entries: Option<u64>,
+        processor: P,
+    ) -> Self {
+        let mut channel = Self::new(
+            CommandSpec::from_template(Template::Raw(String::new())),
+            None,
+            None,
+            supports_preview,
+            max_entries,
+            processor,
+        );
+        channel.set_source(Source::Static(Arc::new(entries)));
+        channel.load();
+        channel
+    }
+
     pub fn load(&mut self) {
+        // the entries are about to be listed afresh
+        self.pending_removals.clear();
         let injector = self.matcher.injector();
         let processor = self.processor.clone();
+        self.truncated.store(false, Ordering::Relaxed);
+        *self.source_error.lock() = None;
+        if let Source::Static(entries) = &self.source {
+            let max_entries = self.max_entries.map_or(usize::MAX, |max| {
+                usize::try_from(max).unwrap_or(usize::MAX)
+            });
+            self.truncated
+                .store(entries.len() > max_entries, Ordering::Relaxed);
+            for entry in entries.iter().take(max_entries) {
+                processor.push_to_injector(entry.clone(), &injector);
+            }
+            return;
+        }
         let crawl_handle = tokio::spawn(load_candidates(
             self.source_command.clone(),
-            self.source_entry_delimiter,
+            self.source_record_separator.clone(),
             self.current_source_index,
             processor,
             injector,
+            SourceControl {
+                max_entries: self.max_entries,
+                truncated: self.truncated.clone(),
+                pid: self.source_pid.clone(),
+                error: self.source_error.clone(),
+                file_walk: self.file_walk,
+                query: self.dynamic.then(|| self.query.clone()),
+            },
         ));
         self.crawl_handle = Some(crawl_handle);
     }
@@ -82,11 +251,9 @@ impl<P: EntryProcessor> Channel<P> {
         self.reloading
             .store(true, std::sync::atomic::Ordering::Relaxed);
 
-        if let Some(handle) = self.crawl_handle.take()
-            && !handle.is_finished()
-        {
-            handle.abort();
-        }
+        self.kill_source();
+        self.crawl_handle = None;
+        self.sorted = None;
         self.matcher.restart();
         self.load();
         // Spawn a thread that turns off reloading after a short delay
@@ -103,59 +270,291 @@ impl<P: EntryProcessor> Channel<P> {
     }
 
     pub fn find(&mut self, pattern: &str) {
+        if self.dynamic {
+            if pattern != self.query {
+                self.query = pattern.to_string();
+                // the first load picks the query up by itself
+                if self.crawl_handle.is_some() {
+                    self.query_changed = Some(Instant::now());
+                }
+            }
+            if !self.fuzzy_filter {
+                return;
+            }
+        }
         self.matcher.find(pattern);
+        self.sorted = None;
+    }
+
+    /// Where to load the entries from the next time the channel (re)loads.
+    pub fn set_source(&mut self, source: Source) {
+        self.source = source;
+    }
+
+    /// Run the source of a dynamic channel again once its query has settled.
+    fn rerun_source_if_settled(&mut self) {
+        if self
+            .query_changed
+            .is_some_and(|changed| changed.elapsed() >= QUERY_DEBOUNCE_DELAY)
+        {
+            self.query_changed = None;
+            self.kill_source();
+            self.crawl_handle = None;
+            self.sorted = None;
+            self.matcher.restart();
+            self.load();
+        }
+    }
+
+    /// Sort the matched results by one of the entries' fields, or go back to
+    /// ranking them by match score with `None`.
+    pub fn sort_by(&mut self, sort: Option<ResultsSort>) {
+        self.sort = sort;
+        self.sorted = None;
+    }
+
+    /// Only keep the matched results that are part of `filter`, or show them
+    /// all again with `None`.
+    pub fn filter_by(&mut self, filter: Option<FxHashSet<Entry>>) {
+        self.filter = filter;
+        self.sorted = None;
+    }
+
+    /// List the entries in reverse arrival order (newest first) when there's
+    /// no pattern. Results ranked by match score aren't affected.
+    pub fn reverse_order(&mut self, reverse: bool) {
+        self.reverse = reverse;
+        self.sorted = None;
+    }
+
+    /// Set which files the built-in sources go through.
+    pub fn file_walk_options(&mut self, options: FileWalkOptions) {
+        self.file_walk = options;
+    }
+
+    /// Re-run the source, with `{q}` standing for the pattern, every time
+    /// the pattern changes instead of matching the entries against it,
+    /// unless `fuzzy_filter` is set.
+    pub fn dynamic_source(&mut self, dynamic: bool, fuzzy_filter: bool) {
+        self.dynamic = dynamic;
+        self.fuzzy_filter = fuzzy_filter;
+    }
+
+    /// Give the listed entries an icon of their own, see `IconMode`.
+    pub fn entry_icons(&mut self, icons: EntryIcons) {
+        self.shape.icons = icons;
+    }
+
+    /// Have the output of the entries end with the line number `line`
+    /// extracts from them, if any.
+    pub fn output_line_numbers(&mut self, line: Option<Template>) {
+        self.shape.output_line = line;
+    }
+
+    /// Split the entries into fields with `delimiter` as they are matched,
+    /// see `Entry::fields`.
+    pub fn entry_delimiter(&mut self, delimiter: Delimiter) {
+        self.shape.delimiter = Some(delimiter);
+    }
+
+    /// Whether the results are currently listed in reverse arrival order.
+    fn reversed(&self) -> bool {
+        self.reverse
+            && self.sort.is_none()
+            && self.matcher.last_pattern.is_empty()
+    }
+
+    /// The matched results filtered and sorted according to `self.filter` and
+    /// `self.sort`, materialized again whenever the match set changed.
+    fn sorted_results(&mut self) -> Option<&[Entry]> {
+        if self.sort.is_none() && self.filter.is_none() {
+            return None;
+        }
+        let sort = self.sort.clone();
+        self.matcher.update_counts();
+        let counts = (
+            self.matcher.matched_item_count,
+            self.matcher.total_item_count,
+        );
+        if self.sorted.as_ref().is_none_or(|s| s.counts != counts) {
+            let shape = &mut self.shape;
+            // walk the matches lazily rather than materializing them twice
+            let mut entries = self
+                .matcher
+                .results_iter(0)
+                .map(|item| self.processor.make_entry(item, shape))
+                .filter(|entry| {
+                    self.filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.contains(entry))
+                })
+                .collect::<Vec<_>>();
+            // stable, so that ties stay ranked by match score
+            if let Some(sort) = sort {
+                entries.sort_by(|a, b| sort.compare(&a.raw, &b.raw));
+            } else if self.reversed() {
+                entries.reverse();
+            }
+            self.sorted = Some(SortedResults { counts, entries });
+        }
+        self.sorted.as_ref().map(|s| s.entries.as_slice())
     }
 
     pub fn results(&mut self, num_entries: u32, offset: u32) -> Vec<Entry> {
+        self.rerun_source_if_settled();
+        self.apply_pending_removals();
         self.matcher.tick();
 
-        let results = self.matcher.results(num_entries, offset);
+        if let Some(sorted) = self.sorted_results() {
+            return sorted
+                .iter()
+                .skip(offset as usize)
+                .take(num_entries.min(MAX_RESULTS) as usize)
+                .cloned()
+                .collect();
+        }
+
+        let results = if self.reversed() {
+            // walk the window mirrored from the end of the matches
+            self.matcher.update_counts();
+            let count = self.matcher.matched_item_count;
+            let end = count.saturating_sub(offset);
+            let start = end.saturating_sub(num_entries.min(MAX_RESULTS));
+            let mut results = self.matcher.results(end - start, start);
+            results.reverse();
+            results
+        } else {
+            self.matcher.results(num_entries, offset)
+        };
 
         // PERF: this could be preallocated and reused by the caller
         let mut entries = Vec::with_capacity(results.len());
 
         for item in results {
-            entries.push(
-                self.processor.make_entry(item, self.source_output.as_ref()),
-            );
+            entries.push(self.processor.make_entry(item, &mut self.shape));
         }
 
         entries
     }
 
     pub fn get_result(&mut self, index: u32) -> Option<Entry> {
-        self.matcher.get_result(index).map(|item| {
-            self.processor.make_entry(item, self.source_output.as_ref())
-        })
+        if let Some(sorted) = self.sorted_results() {
+            return sorted.get(index as usize).cloned();
+        }
+        let index = if self.reversed() {
+            self.matcher.update_counts();
+            self.matcher.matched_item_count.checked_sub(index + 1)?
+        } else {
+            index
+        };
+        self.matcher
+            .get_result(index)
+            .map(|item| self.processor.make_entry(item, &mut self.shape))
     }
 
-    pub fn selected_entries(&self) -> &FxHashSet<Entry> {
+    pub fn selected_entries(&self) -> &FxIndexSet<Entry> {
         &self.selected_entries
     }
 
     pub fn toggle_selection(&mut self, entry: &Entry) {
-        if self.selected_entries.contains(entry) {
-            self.selected_entries.remove(entry);
-        } else {
+        // keep the other entries in the order they were selected in
+        if !self.selected_entries.shift_remove(entry) {
             self.selected_entries.insert(entry.clone());
         }
     }
 
+    pub fn clear_selection(&mut self) {
+        self.selected_entries.clear();
+    }
+
+    /// Remove the entries for which `remove` returns `true` without waiting
+    /// for the source to be reloaded.
+    ///
+    /// Removing entries rebuilds the matcher's item set, which would lose
+    /// what a source still streaming entries pushes in the meantime, so the
+    /// results are only pruned once it is done.
+    pub fn remove_entries(&mut self, remove: EntryRemoval) {
+        self.selected_entries.retain(|entry| !remove(entry));
+        self.pending_removals.push(remove);
+        self.apply_pending_removals();
+    }
+
+    fn apply_pending_removals(&mut self) {
+        if self.pending_removals.is_empty() || self.loading() {
+            return;
+        }
+        let processor = self.processor.clone();
+        let removals = std::mem::take(&mut self.pending_removals);
+        self.matcher.retain(|item| {
+            let entry =
+                processor.make_entry(item.clone(), &mut EntryShape::default());
+            !removals.iter().any(|remove| remove(&entry))
+        });
+        self.sorted = None;
+    }
+
     pub fn result_count(&self) -> u32 {
-        self.matcher.matched_item_count
+        match (&self.filter, &self.sorted) {
+            #[allow(clippy::cast_possible_truncation)]
+            (Some(_), Some(sorted)) => sorted.entries.len() as u32,
+            _ => self.matcher.matched_item_count,
+        }
     }
 
     pub fn total_count(&self) -> u32 {
         self.matcher.total_item_count
     }
 
+    /// Whether the source is still streaming entries or the matcher is
+    /// still scoring them.
     pub fn running(&self) -> bool {
+        self.matching() || self.loading()
+    }
+
+    /// Whether the matcher is still scoring entries against the pattern.
+    pub fn matching(&self) -> bool {
         self.matcher.status.running
-            || (self.crawl_handle.is_some()
-                && !self.crawl_handle.as_ref().unwrap().is_finished())
     }
 
-    pub fn shutdown(&self) {}
+    /// Whether the source command is still streaming entries, or about to
+    /// be run again with a new query.
+    pub fn loading(&self) -> bool {
+        self.query_changed.is_some()
+            || self
+                .crawl_handle
+                .as_ref()
+                .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Why the source command failed, if it exited unsuccessfully.
+    pub fn source_error(&self) -> Option<String> {
+        self.source_error.lock().clone()
+    }
+
+    /// Whether the source was cut short because of `max_entries`.
+    pub fn truncated(&self) -> bool {
+        self.truncated.load(Ordering::Relaxed)
+    }
+
+    /// Stop loading the source, killing the source command if it is still
+    /// running.
+    pub fn shutdown(&self) {
+        self.kill_source();
+    }
+
+    fn kill_source(&self) {
+        if let Some(handle) = &self.crawl_handle
+            && !handle.is_finished()
+        {
+            // dropping the child kills the shell it was spawned through
+            handle.abort();
+        }
+        let pid = self.source_pid.swap(0, Ordering::Relaxed);
+        if pid != 0 {
+            debug!("Killing source command (pid {})", pid);
+            kill_process_group(pid);
+        }
+    }
 
     pub fn cycle_sources(&mut self) {
         if self.source_command.inner.len() > 1 {
@@ -180,56 +579,184 @@ impl<P: EntryProcessor> Channel<P> {
     }
 }
 
-const DEFAULT_LINE_BUFFER_SIZE: usize = 256;
+// Capacity of the buffered reader wrapping the source's stdout
+const READER_BUFFER_SIZE: usize = 64 * 1024;
 // Batch size for pushing candidates to the injector
 // 10k * 500 bytes (pessimistic avg line size) = ~5 MB
 const BATCH_SIZE: usize = 10_000;
 // Maximum number of concurrent flush tasks to prevent unbounded memory growth
 // 4 * 10_000 * average line size = ~20 MB
 const MAX_CONCURRENT_FLUSHES: usize = 4;
-const DEFAULT_DELIMITER: u8 = b'\n';
+const DEFAULT_RECORD_SEPARATOR: &[u8] = b"\n";
+
+/// State shared between a channel and the task loading its source.
+#[derive(Debug, Clone, Default)]
+pub struct SourceControl {
+    /// Stop loading entries past this many.
+    pub max_entries: Option<u64>,
+    /// Set once the source produced more entries than `max_entries`.
+    pub truncated: Arc<AtomicBool>,
+    /// PID of the source command while it is running (0 otherwise).
+    pub pid: Arc<AtomicU32>,
+    /// Set when the source command exits unsuccessfully.
+    pub error: Arc<Mutex<Option<String>>>,
+    /// Which files the built-in sources go through.
+    pub file_walk: FileWalkOptions,
+    /// The pattern the source of a dynamic channel is run with.
+    pub query: Option<String>,
+}
+
+/// How much of the source's stderr is kept around to report failures.
+const MAX_STDERR_SIZE: usize = 4 * 1024;
+
+/// Read the source's stderr until it is closed, keeping only its tail.
+///
+/// This runs alongside the stdout reader so that a chatty stderr can't fill
+/// up its pipe and stall the source command.
+async fn read_stderr(stderr: tokio::process::ChildStderr) -> String {
+    let mut reader = BufReader::new(stderr).lines();
+    let mut tail = String::new();
+    while let Ok(Some(line)) = reader.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !tail.is_empty() {
+            tail.push('\n');
+        }
+        tail.push_str(line.trim_end());
+        if tail.len() > MAX_STDERR_SIZE {
+            let mut cut = tail.len() - MAX_STDERR_SIZE;
+            while !tail.is_char_boundary(cut) {
+                cut += 1;
+            }
+            tail.drain(..cut);
+        }
+    }
+    tail
+}
+
+/// Kill the process group led by `pid` so that every process of a shell
+/// pipeline (e.g. `sh -c 'foo | bar'`) goes down with it.
+#[cfg(unix)]
+fn kill_process_group(pid: u32) {
+    if let Ok(pid) = libc::pid_t::try_from(pid) {
+        // SAFETY: `killpg` has no memory safety requirements
+        unsafe {
+            libc::killpg(pid, libc::SIGTERM);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pid: u32) {
+    // aborting the loading task already kills the child process
+}
 
 /// Collects entries before pushing them to the injector.
 #[allow(clippy::unused_async)]
 pub async fn load_candidates<P: EntryProcessor>(
     command: CommandSpec,
-    entry_delimiter: Option<char>,
+    record_separator: Option<String>,
     command_index: usize,
     processor: P,
     injector: Injector<P::Data>,
+    control: SourceControl,
 ) {
-    debug!("Loading candidates from command: {:?}", command);
-    let mut std_command = shell_command(
-        command.get_nth(command_index).raw(),
-        command.interactive,
-        &command.env,
-    );
+    let raw_command = command.get_nth(command_index).raw();
+    if let Some(source) = BuiltinSource::parse(raw_command, control.file_walk)
+    {
+        load_builtin(source, processor, injector, control).await;
+        return;
+    }
+    let raw_command = match &control.query {
+        Some(query) => substitute_query(raw_command, query),
+        None => raw_command.to_string(),
+    };
+    debug!("Loading candidates from command: {:?}", raw_command);
+    let mut std_command =
+        shell_command(&raw_command, command.interactive, &command.env);
     std_command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    // run the source in its own process group so that it can be killed
+    // along with all of its children (interactive shells need to stay in
+    // the foreground process group to be able to start)
+    #[cfg(unix)]
+    let own_group = !command.interactive;
+    #[cfg(not(unix))]
+    let own_group = false;
+    #[cfg(unix)]
+    if own_group {
+        std::os::unix::process::CommandExt::process_group(&mut std_command, 0);
+    }
     let mut child = TokioCommand::from(std_command)
+        .kill_on_drop(true)
         .spawn()
         .expect("failed to execute process");
+    let pid = if own_group {
+        child.id().unwrap_or(0)
+    } else {
+        0
+    };
+    control.pid.store(pid, Ordering::Relaxed);
+    let stderr_handle =
+        child.stderr.take().map(|e| tokio::spawn(read_stderr(e)));
 
+    let mut produced_output = false;
     if let Some(out) = child.stdout.take() {
-        let mut produced_output = false;
-        let mut reader = BufReader::new(out);
-        let mut buf = Vec::with_capacity(DEFAULT_LINE_BUFFER_SIZE);
-        let mut batch = Vec::with_capacity(BATCH_SIZE);
+        let mut reader = BufReader::with_capacity(READER_BUFFER_SIZE, out);
+        // records are read straight into a contiguous buffer, which saves an
+        // allocation per record, with `ends` marking where each one stops
+        let mut batch = Vec::with_capacity(READER_BUFFER_SIZE);
+        let mut ends = Vec::with_capacity(BATCH_SIZE);
+        let mut loaded: u64 = 0;
         let mut flush_handles = tokio::task::JoinSet::new();
 
-        let delimiter = entry_delimiter
+        let separator = record_separator
             .as_ref()
-            .map(|d| *d as u8)
-            .unwrap_or(DEFAULT_DELIMITER);
-
-        while {
-            buf.clear();
-            let n = reader.read_until(delimiter, &mut buf).await.unwrap_or(0);
-            n > 0
-        } {
-            batch.push(buf.clone());
-
-            // Flush batch when it reaches the target size
-            if batch.len() >= BATCH_SIZE {
+            .map_or(DEFAULT_RECORD_SEPARATOR, |s| s.as_bytes());
+        let last_byte = separator[separator.len() - 1];
+
+        let mut eof = false;
+        while !eof {
+            let start = batch.len();
+            // separators spanning several bytes may show up on their own
+            // inside a record
+            loop {
+                let n = reader
+                    .read_until(last_byte, &mut batch)
+                    .await
+                    .unwrap_or(0);
+                if n == 0 {
+                    eof = true;
+                    break;
+                }
+                if batch[start..].ends_with(separator) {
+                    batch.truncate(batch.len() - separator.len());
+                    break;
+                }
+            }
+            // skip records that wouldn't end up in the matcher anyway so they
+            // don't count towards the limit
+            if batch[start..].iter().all(u8::is_ascii_whitespace) {
+                batch.truncate(start);
+                continue;
+            }
+            if control.max_entries.is_some_and(|max| loaded >= max) {
+                warn!(
+                    "Source produced more than {} entries, truncating input.",
+                    loaded
+                );
+                control.truncated.store(true, Ordering::Relaxed);
+                batch.truncate(start);
+                break;
+            }
+            ends.push(batch.len());
+            loaded += 1;
+
+            // Flush batch when it reaches the target size, or as soon as the
+            // source has nothing more to say for now so that the entries of
+            // slow producers (e.g. `tail -f` piped into tv) show up right away
+            // rather than once the source is done
+            if ends.len() >= BATCH_SIZE || reader.buffer().is_empty() {
                 if flush_handles.len() >= MAX_CONCURRENT_FLUSHES {
                     // Wait for any task to complete
                     let _ = flush_handles.join_next().await;
@@ -237,69 +764,144 @@ pub async fn load_candidates<P: EntryProcessor>(
 
                 let batch_to_flush = std::mem::replace(
                     &mut batch,
+                    Vec::with_capacity(READER_BUFFER_SIZE),
+                );
+                let ends_to_flush = std::mem::replace(
+                    &mut ends,
                     Vec::with_capacity(BATCH_SIZE),
                 );
                 let inj = injector.clone();
                 let proc = processor.clone();
                 flush_handles.spawn_blocking(move || {
-                    flush_batch(batch_to_flush, &inj, &proc, delimiter);
+                    flush_batch(&batch_to_flush, &ends_to_flush, &inj, &proc);
                 });
-                produced_output = true;
             }
         }
 
         debug!("Finished reading command output.");
 
         // Flush any remaining entries in the batch
-        if !batch.is_empty() {
+        if !ends.is_empty() {
             let inj = injector.clone();
             let proc = processor.clone();
             flush_handles.spawn_blocking(move || {
-                flush_batch(batch, &inj, &proc, delimiter);
+                flush_batch(&batch, &ends, &inj, &proc);
             });
-            produced_output = true;
         }
 
         // Wait for all remaining flush tasks to complete
         while flush_handles.join_next().await.is_some() {}
+        produced_output = loaded > 0;
+    }
+    let status = child.wait().await;
+    let stderr = match stderr_handle {
+        Some(handle) => handle.await.unwrap_or_default(),
+        None => String::new(),
+    };
+    // if the command didn't produce any output, display its stderr instead
+    if !produced_output && !stderr.is_empty() {
+        let tv_message =
+            "Command produced no output on stdout, checking stderr...";
+        processor.push_to_injector(tv_message.to_string(), &injector);
+        for line in stderr.lines() {
+            processor.push_to_injector(line.to_string(), &injector);
+        }
+    }
+    // a truncated source is expected to die of a broken pipe
+    if !control.truncated.load(Ordering::Relaxed) {
+        let failure = match status {
+            Ok(status) if status.success() => None,
+            Ok(status) => Some(status.to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(failure) = failure {
+            let message = if stderr.is_empty() {
+                format!("Source command failed ({failure})")
+            } else {
+                format!("Source command failed ({failure}): {stderr}")
+            };
+            warn!("{}", message);
+            *control.error.lock() = Some(message);
+        }
+    }
+    // a newer load might have taken over in the meantime
+    let _ = control.pid.compare_exchange(
+        pid,
+        0,
+        Ordering::Relaxed,
+        Ordering::Relaxed,
+    );
+}
 
-        // if the command didn't produce any output, check stderr and display that instead
-        if !produced_output {
-            let tv_message =
-                "Command produced no output on stdout, checking stderr...";
-            processor.push_to_injector(tv_message.to_string(), &injector);
-            let stderr = child.stderr.take().unwrap();
-            let mut reader = BufReader::new(stderr).lines();
-            while let Ok(Some(line)) = reader.next_line().await {
-                if line.trim().is_empty() {
-                    continue;
-                }
-                processor.push_to_injector(line, &injector);
+/// Sets its flag when dropped, i.e. when the task owning it is aborted.
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Replace `{q}` in a source command with the shell-quoted `query`.
+fn substitute_query(command: &str, query: &str) -> String {
+    command.replace("{q}", &command_shell().kind.quote(query))
+}
+
+/// Load the entries of one of the built-in sources, walking the current
+/// directory instead of running a source command.
+async fn load_builtin<P: EntryProcessor>(
+    source: BuiltinSource,
+    processor: P,
+    injector: Injector<P::Data>,
+    control: SourceControl,
+) {
+    debug!("Loading candidates from built-in source: {:?}", source);
+    let query = control.query.clone().unwrap_or_default();
+    let stop = StopOnDrop(Arc::new(AtomicBool::new(false)));
+    let stopped = stop.0.clone();
+    let max_entries = control.max_entries;
+    let walk = tokio::task::spawn_blocking(move || {
+        source.run(Path::new("."), &query, max_entries, |entry| {
+            if stopped.load(Ordering::Relaxed) {
+                return false;
             }
+            processor.push_to_injector(entry, &injector);
+            true
+        })
+    });
+    match walk.await {
+        Ok(true) => {
+            warn!(
+                "Source produced more than {:?} entries, truncating input.",
+                max_entries
+            );
+            control.truncated.store(true, Ordering::Relaxed);
+        }
+        Ok(false) => {}
+        Err(e) => {
+            let message = format!("Built-in source failed: {e}");
+            warn!("{}", message);
+            *control.error.lock() = Some(message);
         }
     }
-    let _ = child.wait().await;
+    drop(stop);
 }
 
-/// Flushes a batch of entries to the injector.
+/// Flushes a batch of contiguous records, each ending at the matching
+/// offset of `ends`, to the injector.
 /// This is called from a blocking task spawned in the threadpool.
 fn flush_batch<P: EntryProcessor>(
-    batch: Vec<Vec<u8>>,
+    batch: &[u8],
+    ends: &[usize],
     injector: &Injector<P::Data>,
     processor: &P,
-    delimiter: u8,
 ) {
-    // decode utf8 and filter empty/whitespace-only lines
-    for mut bytes in batch {
-        if bytes.is_empty() || bytes.iter().all(u8::is_ascii_whitespace) {
-            continue;
-        }
-        if bytes.last() == Some(&delimiter) {
-            bytes.pop();
-        }
-        if let Ok(line) = String::from_utf8(bytes) {
-            processor.push_to_injector(line, injector);
+    let mut start = 0;
+    for &end in ends {
+        if let Ok(record) = std::str::from_utf8(&batch[start..end]) {
+            processor.push_to_injector(record.to_owned(), injector);
         }
+        start = end;
     }
 }
 
@@ -381,32 +983,36 @@ impl ChannelKind {
     /// and no display template by using `Matcher<()>` instead of `Matcher<String>`.
     pub fn new(
         source_command: CommandSpec,
-        source_entry_delimiter: Option<char>,
+        source_record_separator: Option<String>,
         source_ansi: bool,
         source_display: Option<Template>,
         source_output: Option<Template>,
         supports_preview: bool,
+        max_entries: Option<u64>,
     ) -> Self {
         match (source_ansi, source_display) {
             (false, None) => ChannelKind::Plain(Channel::new(
                 source_command,
-                source_entry_delimiter,
+                source_record_separator,
                 source_output,
                 supports_preview,
+                max_entries,
                 PlainProcessor,
             )),
             (true, None) => ChannelKind::Ansi(Channel::new(
                 source_command,
-                source_entry_delimiter,
+                source_record_separator,
                 source_output,
                 supports_preview,
+                max_entries,
                 AnsiProcessor,
             )),
             (_, Some(template)) => ChannelKind::Display(Channel::new(
                 source_command,
-                source_entry_delimiter,
+                source_record_separator,
                 source_output,
                 supports_preview,
+                max_entries,
                 DisplayProcessor { template },
             )),
         }
@@ -420,16 +1026,31 @@ impl ChannelKind {
         results(num_entries: u32, offset: u32) -> Vec<Entry>,
         get_result(index: u32) -> Option<Entry>,
         toggle_selection(entry: &Entry) -> (),
+        clear_selection() -> (),
+        remove_entries(remove: EntryRemoval) -> (),
         cycle_sources() -> (),
+        sort_by(sort: Option<ResultsSort>) -> (),
+        filter_by(filter: Option<FxHashSet<Entry>>) -> (),
+        reverse_order(reverse: bool) -> (),
+        file_walk_options(options: FileWalkOptions) -> (),
+        dynamic_source(dynamic: bool, fuzzy_filter: bool) -> (),
+        entry_icons(icons: EntryIcons) -> (),
+        output_line_numbers(line: Option<Template>) -> (),
+        entry_delimiter(delimiter: Delimiter) -> (),
+        set_source(source: Source) -> (),
     );
 
     // Generate all immutable delegation methods
     delegate_to_channel!(ref
         current_command() -> &str,
-        selected_entries() -> &FxHashSet<Entry>,
+        selected_entries() -> &FxIndexSet<Entry>,
         result_count() -> u32,
         total_count() -> u32,
         running() -> bool,
+        matching() -> bool,
+        loading() -> bool,
+        source_error() -> Option<String>,
+        truncated() -> bool,
         shutdown() -> (),
         supports_preview() -> bool,
         reloading() -> bool,
@@ -439,7 +1060,10 @@ impl ChannelKind {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{channels::prototypes::SourceSpec, matcher::config::Config};
+    use crate::{
+        channels::{icons::IconMode, prototypes::SourceSpec},
+        matcher::config::Config,
+    };
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
     async fn test_load_candidates_default_delimiter() {
@@ -456,10 +1080,11 @@ mod tests {
 
         load_candidates(
             source_spec.command,
-            source_spec.entry_delimiter,
+            source_spec.record_separator,
             0,
             PlainProcessor,
             injector,
+            SourceControl::default(),
         )
         .await;
 
@@ -486,10 +1111,11 @@ mod tests {
 
         load_candidates(
             source_spec.command,
-            source_spec.entry_delimiter,
+            source_spec.record_separator,
             0,
             PlainProcessor,
             injector,
+            SourceControl::default(),
         )
         .await;
 
@@ -516,10 +1142,11 @@ mod tests {
 
         load_candidates(
             source_spec.command,
-            source_spec.entry_delimiter,
+            source_spec.record_separator,
             0,
             PlainProcessor,
             injector,
+            SourceControl::default(),
         )
         .await;
 
@@ -532,6 +1159,38 @@ mod tests {
         assert_eq!(results[1].matched_string, "test2\ntest3");
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_multi_line_records() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"command = "printf 'commit 1\n\nfirst\n--\ncommit 2\nsecond-\n--\n'"
+            record_separator = "\n--\n""#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            SourceControl::default(),
+        )
+        .await;
+
+        matcher.find("commit");
+        matcher.tick();
+        let mut results: Vec<String> = matcher
+            .results(10, 0)
+            .into_iter()
+            .map(|r| r.matched_string)
+            .collect();
+        results.sort();
+        assert_eq!(results, vec!["commit 1\n\nfirst", "commit 2\nsecond-"]);
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
     async fn test_load_candidates_large_input() {
         // Test with more entries than the batch size
@@ -547,10 +1206,11 @@ mod tests {
 
         load_candidates(
             source_spec.command,
-            source_spec.entry_delimiter,
+            source_spec.record_separator,
             0,
             PlainProcessor,
             injector,
+            SourceControl::default(),
         )
         .await;
 
@@ -578,10 +1238,11 @@ mod tests {
 
         load_candidates(
             source_spec.command,
-            source_spec.entry_delimiter,
+            source_spec.record_separator,
             0,
             AnsiProcessor,
             injector,
+            SourceControl::default(),
         )
         .await;
 
@@ -594,4 +1255,745 @@ mod tests {
         assert_eq!(results[1].matched_string, "test2");
         assert_eq!(results[2].matched_string, "test3");
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_stops_at_max_entries() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "seq 1 100000"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+        let limit = SourceControl {
+            max_entries: Some(1234),
+            truncated: Arc::default(),
+            ..Default::default()
+        };
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            limit.clone(),
+        )
+        .await;
+
+        matcher.find("");
+        matcher.tick();
+        let results = matcher.results(2000, 0);
+        assert_eq!(matcher.total_item_count, 1234);
+        assert!(limit.truncated.load(Ordering::Relaxed));
+        assert_eq!(results.len(), 1234);
+        assert_eq!(results[1233].matched_string, "1234");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_exactly_max_entries_is_not_truncated() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "seq 1 10"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+        let limit = SourceControl {
+            max_entries: Some(10),
+            truncated: Arc::default(),
+            ..Default::default()
+        };
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            limit.clone(),
+        )
+        .await;
+
+        matcher.find("");
+        matcher.tick();
+        matcher.results(10, 0);
+        assert_eq!(matcher.total_item_count, 10);
+        assert!(!limit.truncated.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_reports_source_failure() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "echo partial; echo 'no such file' >&2; exit 2"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+        let control = SourceControl::default();
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            control.clone(),
+        )
+        .await;
+
+        let error = control.error.lock().clone().unwrap();
+        assert!(error.contains('2'), "{error}");
+        assert!(error.ends_with("no such file"), "{error}");
+        // stderr doesn't end up among the entries
+        matcher.find("");
+        matcher.tick();
+        matcher.results(10, 0);
+        assert_eq!(matcher.total_item_count, 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_successful_source_has_no_error() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "echo entry; echo 'just a warning' >&2"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+        let control = SourceControl::default();
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            control.clone(),
+        )
+        .await;
+
+        assert!(control.error.lock().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_shows_stderr_without_stdout() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "echo 'usage: foo' >&2; echo 'try --help' >&2"
+            "#,
+        )
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            SourceControl::default(),
+        )
+        .await;
+
+        matcher.find("");
+        matcher.tick();
+        let results = matcher.results(10, 0);
+        let lines = results
+            .iter()
+            .map(|item| item.matched_string.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            lines,
+            [
+                "Command produced no output on stdout, checking stderr...",
+                "usage: foo",
+                "try --help"
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_load_candidates_flushes_partial_batch() {
+        let source_spec: SourceSpec = toml::from_str(&format!(
+            r#"
+            command = "seq 1 {}"
+            "#,
+            BATCH_SIZE * 2 + 7
+        ))
+        .unwrap();
+
+        let mut matcher = Matcher::<()>::new(&Config::default());
+        let injector = matcher.injector();
+
+        load_candidates(
+            source_spec.command,
+            source_spec.record_separator,
+            0,
+            PlainProcessor,
+            injector,
+            SourceControl::default(),
+        )
+        .await;
+
+        matcher.find("");
+        matcher.tick();
+        matcher.results(1, 0);
+        assert_eq!(matcher.total_item_count as usize, BATCH_SIZE * 2 + 7);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_slow_source_entries_show_up_before_it_is_done() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "echo first; sleep 1; echo second"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.load();
+        for _ in 0..40 {
+            channel.results(10, 0);
+            if channel.total_count() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.total_count(), 1);
+        assert!(channel.loading());
+
+        // late entries keep coming in until the source closes its output
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.total_count(), 2);
+        assert!(!channel.loading());
+        assert_eq!(channel.source_error(), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_removing_entries_while_loading_keeps_late_entries() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'a\nb\n'; sleep 1; echo c"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.load();
+        for _ in 0..40 {
+            channel.results(10, 0);
+            if channel.total_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(channel.loading());
+        let a = Entry::new("a".to_string());
+        channel.toggle_selection(&a);
+
+        channel.remove_entries(Box::new(|entry: &Entry| entry.raw == "a"));
+        // the selection is pruned right away, the results once the source
+        // is done
+        assert!(channel.selected_entries().is_empty());
+        assert_eq!(channel.total_count(), 2);
+
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let mut names: Vec<_> =
+            channel.results(10, 0).into_iter().map(|e| e.raw).collect();
+        names.sort();
+        assert_eq!(names, ["b", "c"]);
+    }
+
+    #[test]
+    fn test_results_sort_is_numeric_aware() {
+        let sort = ResultsSort {
+            field: 1,
+            reverse: false,
+            delimiter: Delimiter::default(),
+        };
+        assert_eq!(sort.compare("a 9", "b 10"), CmpOrdering::Less);
+        assert_eq!(sort.compare("a 1.5", "b 1.25"), CmpOrdering::Greater);
+        assert_eq!(sort.compare("a 3", "b n/a"), CmpOrdering::Less);
+        assert_eq!(sort.compare("a bar", "b foo"), CmpOrdering::Less);
+        let reversed = ResultsSort {
+            reverse: true,
+            ..sort
+        };
+        assert_eq!(reversed.compare("a 9", "b 10"), CmpOrdering::Greater);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_sorted_results() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'bash 120\nnvim 8\ntv 64\nzsh 8.5\n'"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.load();
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 4 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let names = |entries: Vec<Entry>| {
+            entries.into_iter().map(|e| e.raw).collect::<Vec<_>>()
+        };
+
+        channel.sort_by(Some(ResultsSort {
+            field: 1,
+            reverse: true,
+            delimiter: Delimiter::default(),
+        }));
+        assert_eq!(
+            names(channel.results(10, 0)),
+            vec!["bash 120", "tv 64", "zsh 8.5", "nvim 8"]
+        );
+        assert_eq!(names(channel.results(2, 1)), vec!["tv 64", "zsh 8.5"]);
+        assert_eq!(channel.get_result(3).unwrap().raw, "nvim 8");
+
+        // the sorted snapshot follows the pattern
+        channel.find("sh");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(names(channel.results(10, 0)), vec!["bash 120", "zsh 8.5"]);
+
+        channel.sort_by(None);
+        channel.find("");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 4 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.results(10, 0).len(), 4);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_reversed_results() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'one\ntwo\nthree\ntwenty\n'"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.reverse_order(true);
+        channel.load();
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 4 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let names = |entries: Vec<Entry>| {
+            entries.into_iter().map(|e| e.raw).collect::<Vec<_>>()
+        };
+
+        // newest first without a pattern
+        assert_eq!(
+            names(channel.results(10, 0)),
+            vec!["twenty", "three", "two", "one"]
+        );
+        assert_eq!(names(channel.results(2, 1)), vec!["three", "two"]);
+        assert_eq!(channel.get_result(0).unwrap().raw, "twenty");
+        assert_eq!(channel.get_result(3).unwrap().raw, "one");
+        assert!(channel.get_result(4).is_none());
+
+        // matches are still ranked by score
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'one\ntwo\nthree\ntwenty\n'"
+            "#,
+        )
+        .unwrap();
+        let mut unreversed = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        unreversed.load();
+        for c in [&mut channel, &mut unreversed] {
+            c.find("tw");
+            for _ in 0..100 {
+                c.results(10, 0);
+                if !c.running() && c.result_count() == 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        }
+        assert_eq!(
+            names(channel.results(10, 0)),
+            names(unreversed.results(10, 0))
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_dynamic_source_reruns_with_the_query() {
+        async fn results(
+            channel: &mut Channel<PlainProcessor>,
+        ) -> Vec<String> {
+            for _ in 0..100 {
+                channel.results(10, 0);
+                if !channel.running() && channel.total_count() == 2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            channel.results(10, 0).into_iter().map(|e| e.raw).collect()
+        }
+
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf '%s\n' static {q}"
+            dynamic = true
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.dynamic_source(source_spec.dynamic, false);
+        channel.find("it's");
+        channel.load();
+        assert_eq!(results(&mut channel).await, vec!["static", "it's"]);
+
+        // entries aren't filtered by the query, the source is run again once
+        // the query settles
+        channel.find("two words");
+        assert!(channel.loading());
+        assert_eq!(channel.results(10, 0).len(), 2);
+        assert_eq!(results(&mut channel).await, vec!["static", "two words"]);
+
+        // unless asked to
+        channel.dynamic_source(true, true);
+        channel.find("word");
+        assert_eq!(results(&mut channel).await, vec!["word"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_results_and_get_result_build_the_same_entries() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'https://bash.sh 120\nnvim 8\ntv 64\nhttps://zsh.sh 85\n'"
+            "#,
+        )
+        .unwrap();
+        let output = Template::parse("{split: :0}").unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            Some(output),
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.entry_icons(EntryIcons::new(
+            IconMode::Auto,
+            Delimiter::default(),
+        ));
+        channel.output_line_numbers(Some(
+            Template::parse("{split: :1}").unwrap(),
+        ));
+        channel.entry_delimiter(Delimiter::default());
+        channel.load();
+        channel.find("sh");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let shape = |entry: &Entry| {
+            (
+                entry.raw.clone(),
+                entry.display().to_string(),
+                entry.match_ranges.clone(),
+                entry.ansi,
+                entry.output().unwrap(),
+                entry.icon,
+                entry.line_number(),
+                entry.fields.clone(),
+            )
+        };
+        let assert_same_entries = |channel: &mut Channel<PlainProcessor>| {
+            let listed = channel.results(10, 0);
+            assert_eq!(listed.len(), 2);
+            let mut selected = FxHashSet::default();
+            for (index, entry) in (0..).zip(&listed) {
+                let fetched = channel.get_result(index).unwrap();
+                assert!(fetched.match_ranges.is_some());
+                assert!(fetched.icon.is_some());
+                assert!(fetched.line_number().is_some());
+                assert_eq!(shape(entry), shape(&fetched));
+                selected.insert(entry.clone());
+                assert!(selected.contains(&fetched));
+            }
+        };
+
+        assert_same_entries(&mut channel);
+        // sorted results are served from a snapshot instead of the matcher
+        channel.sort_by(Some(ResultsSort {
+            field: 1,
+            reverse: false,
+            delimiter: Delimiter::default(),
+        }));
+        assert_same_entries(&mut channel);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_items_replace_the_source_command() {
+        let mut channel = Channel::new(
+            CommandSpec::from(Template::parse("false").unwrap()),
+            None,
+            None,
+            false,
+            Some(2),
+            PlainProcessor,
+        );
+        channel.set_source(Source::Static(Arc::new(vec![
+            "bash".to_string(),
+            "nvim".to_string(),
+            "zsh".to_string(),
+        ])));
+        channel.load();
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let mut names = channel
+            .results(10, 0)
+            .into_iter()
+            .map(|e| e.raw)
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["bash", "nvim"]);
+        assert!(channel.truncated());
+        assert_eq!(channel.source_error(), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_from_entries_loads_without_a_source() {
+        let mut channel = Channel::from_entries(
+            vec!["bash".to_string(), "zsh".to_string()],
+            false,
+            None,
+            PlainProcessor,
+        );
+        // nothing was spawned, the source is done already
+        assert!(!channel.loading());
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.total_count(), 2);
+
+        // reloading pushes the same entries again
+        channel.reload();
+        assert!(!channel.loading());
+        channel.find("zs");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let names = channel
+            .results(10, 0)
+            .into_iter()
+            .map(|e| e.raw)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["zsh"]);
+        assert_eq!(channel.total_count(), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_filtered_results() {
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "printf 'bash\nnvim\ntv\nzsh\n'"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.load();
+        let marked = FxHashSet::from_iter([
+            Entry::new("tv".to_string()),
+            Entry::new("bash".to_string()),
+        ]);
+        channel.filter_by(Some(marked));
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.total_count() == 4 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let mut names = channel
+            .results(10, 0)
+            .into_iter()
+            .map(|e| e.raw)
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["bash", "tv"]);
+        assert_eq!(channel.result_count(), 2);
+        assert_eq!(channel.total_count(), 4);
+
+        // the filter applies on top of the query
+        channel.find("t");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.get_result(0).unwrap().raw, "tv");
+
+        channel.filter_by(None);
+        channel.find("");
+        for _ in 0..100 {
+            channel.results(10, 0);
+            if !channel.running() && channel.result_count() == 4 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(channel.results(10, 0).len(), 4);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+    async fn test_shutdown_kills_source_pipeline() {
+        let is_alive = |pid: &str| {
+            std::fs::read_to_string(format!("/proc/{pid}/stat"))
+                .is_ok_and(|stat| !stat.contains(") Z "))
+        };
+        let source_spec: SourceSpec = toml::from_str(
+            r#"
+            command = "sleep 30 & echo $!; wait"
+            "#,
+        )
+        .unwrap();
+        let mut channel = Channel::new(
+            source_spec.command,
+            None,
+            None,
+            false,
+            None,
+            PlainProcessor,
+        );
+        channel.load();
+
+        let mut sleep_pid = None;
+        for _ in 0..100 {
+            if let Some(entry) = channel.results(1, 0).first() {
+                sleep_pid = Some(entry.raw.clone());
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let sleep_pid = sleep_pid.expect("source should print its pid");
+        assert!(is_alive(&sleep_pid));
+        assert!(channel.running());
+        // the source is what keeps the channel busy, not the matcher
+        assert!(channel.loading());
+
+        channel.shutdown();
+
+        let mut alive = true;
+        for _ in 0..100 {
+            alive = is_alive(&sleep_pid);
+            if !alive {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        assert!(!alive, "source pipeline should be killed on shutdown");
+    }
 }
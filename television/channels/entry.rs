@@ -1,8 +1,14 @@
 use crate::{
-    channels::prototypes::Template, event::Key,
+    channels::{
+        icons::EntryIcons,
+        prototypes::{Delimiter, Template},
+    },
+    event::Key,
     screen::result_item::ResultItem,
+    utils::command::{format_entry_with, split_line_number},
 };
 use anyhow::Result;
+use devicons::FileIcon;
 use smallvec::SmallVec;
 use std::hash::{Hash, Hasher};
 
@@ -14,10 +20,58 @@ pub struct Entry {
     pub display: Option<String>,
     /// The output string that will be used when the entry is selected.
     pub output: Option<Template>,
+    /// The line number appended to the output, see `output_line_numbers`.
+    pub line_number: Option<usize>,
     /// The optional ranges for matching characters (based on `self.display`).
     pub match_ranges: Option<SmallVec<[(u32, u32); 8]>>,
     /// Whether the entry contains ANSI escape sequences.
     pub ansi: bool,
+    /// The icon shown in front of the entry, see `IconMode`.
+    pub icon: Option<FileIcon>,
+    /// The entry split into fields with the channel's delimiter.
+    pub fields: Option<EntryFields>,
+}
+
+/// The fields of an entry, split once with the delimiter of its channel so
+/// that formatting its commands doesn't split it again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryFields {
+    delimiter: Delimiter,
+    /// Byte ranges of the fields in the raw entry.
+    ranges: SmallVec<[(usize, usize); 4]>,
+}
+
+impl EntryFields {
+    pub fn new(raw: &str, delimiter: &Delimiter) -> Self {
+        let start = raw.as_ptr() as usize;
+        let ranges = delimiter
+            .split(raw)
+            .into_iter()
+            .map(|field| {
+                let offset = field.as_ptr() as usize - start;
+                (offset, offset + field.len())
+            })
+            .collect();
+        Self {
+            delimiter: delimiter.clone(),
+            ranges,
+        }
+    }
+}
+
+/// What the entries matched by a channel are built with, see
+/// [`Entry::from_match`].
+#[derive(Debug, Clone, Default)]
+pub struct EntryShape {
+    /// Formats the output of the entries.
+    pub output: Option<Template>,
+    /// Extracts the line number appended to the output, see
+    /// `output_line_numbers`.
+    pub output_line: Option<Template>,
+    /// Splits the entries into fields.
+    pub delimiter: Option<Delimiter>,
+    /// Gives the entries an icon of their own.
+    pub icons: EntryIcons,
 }
 
 impl Hash for Entry {
@@ -90,11 +144,42 @@ impl Entry {
             raw,
             display: None,
             output: None,
+            line_number: None,
             match_ranges: None,
             ansi: false,
+            icon: None,
+            fields: None,
         }
     }
 
+    /// Create the entry for an item matched by a channel.
+    ///
+    /// Every entry coming out of the matcher is built here, whether it is
+    /// listed in the results, previewed or output, so that they all carry the
+    /// same display string, match ranges, output templates, line number,
+    /// icon and fields.
+    pub fn from_match(
+        raw: String,
+        display: Option<String>,
+        match_indices: &[u32],
+        ansi: bool,
+        shape: &mut EntryShape,
+    ) -> Self {
+        let mut entry = Self::new(raw).with_match_indices(match_indices);
+        entry.display = display;
+        entry.ansi = ansi;
+        entry.output.clone_from(&shape.output);
+        entry.line_number = shape.output_line.as_ref().and_then(|line| {
+            line.format(&entry.raw).ok()?.trim().parse().ok()
+        });
+        entry.icon = shape.icons.icon(&entry.raw);
+        entry.fields = shape
+            .delimiter
+            .as_ref()
+            .map(|delimiter| EntryFields::new(&entry.raw, delimiter));
+        entry
+    }
+
     pub fn with_display(mut self, display: String) -> Self {
         self.display = Some(display);
         self
@@ -105,6 +190,11 @@ impl Entry {
         self
     }
 
+    pub fn with_icon(mut self, icon: FileIcon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
     pub fn with_match_indices(mut self, indices: &[u32]) -> Self {
         self.match_ranges = Some(into_ranges(indices));
         self
@@ -114,14 +204,52 @@ impl Entry {
         self.display.as_deref().unwrap_or(&self.raw)
     }
 
+    /// What's printed when the entry is selected.
+    ///
+    /// This is formatted from the raw entry, never from what's displayed, and
+    /// ends with the entry's line number unless it already does.
     pub fn output(&self) -> Result<String> {
-        if let Some(output) = &self.output {
-            output.format(&self.raw)
+        let output = if let Some(output) = &self.output {
+            output.format(&self.raw)?
         } else {
-            Ok(self.raw.clone())
+            self.raw.clone()
+        };
+        Ok(match self.line_number() {
+            Some(line) if split_line_number(&output).1 != Some(line) => {
+                format!("{output}:{line}")
+            }
+            _ => output,
+        })
+    }
+
+    /// The line number the entry points to, if it is to be output.
+    pub fn line_number(&self) -> Option<usize> {
+        self.line_number
+    }
+
+    /// The fields of the entry split with `delimiter`, reusing the ones split
+    /// when the entry was matched if the delimiter is the same.
+    pub fn fields(&self, delimiter: &Delimiter) -> Vec<&str> {
+        match &self.fields {
+            Some(fields) if fields.delimiter == *delimiter => fields
+                .ranges
+                .iter()
+                .map(|&(start, end)| &self.raw[start..end])
+                .collect(),
+            _ => delimiter.split(&self.raw),
         }
     }
 
+    /// Format `template` with the entry, its positional placeholders taking
+    /// the fields split with `delimiter`.
+    pub fn format(
+        &self,
+        template: &Template,
+        delimiter: &Delimiter,
+    ) -> Result<String> {
+        format_entry_with(template, &self.raw, || self.fields(delimiter))
+    }
+
     /// Sets whether the entry contains ANSI escape sequences.
     pub fn ansi(mut self, ansi: bool) -> Self {
         self.ansi = ansi;
@@ -129,6 +257,18 @@ impl Entry {
     }
 }
 
+impl From<String> for Entry {
+    fn from(raw: String) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl From<&str> for Entry {
+    fn from(raw: &str) -> Self {
+        Self::new(raw.to_string())
+    }
+}
+
 impl ResultItem for Entry {
     fn raw(&self) -> &str {
         &self.raw
@@ -142,6 +282,10 @@ impl ResultItem for Entry {
         self.output()
     }
 
+    fn icon(&self) -> Option<&FileIcon> {
+        self.icon.as_ref()
+    }
+
     fn match_ranges(&self) -> Option<&[(u32, u32)]> {
         self.match_ranges.as_deref()
     }
@@ -189,9 +333,108 @@ mod tests {
             raw: "test name with spaces".to_string(),
             display: None,
             output: None,
+            line_number: None,
             match_ranges: None,
             ansi: false,
+            icon: None,
+            fields: None,
         };
         assert_eq!(entry.output().unwrap(), "test name with spaces");
     }
+
+    #[test]
+    fn test_from_match() {
+        let output = Template::parse("{split: :0}").unwrap();
+        let entry = Entry::from_match(
+            "\x1b[31mfoo\x1b[0m bar".to_string(),
+            Some("foo bar".to_string()),
+            &[0, 1, 4],
+            true,
+            &mut EntryShape {
+                output: Some(output.clone()),
+                ..EntryShape::default()
+            },
+        );
+        assert_eq!(entry.display(), "foo bar");
+        assert_eq!(entry.match_ranges.as_deref(), Some(&[(0, 2), (4, 5)][..]));
+        assert!(entry.ansi);
+        assert_eq!(entry.output, Some(output));
+        // equality and hashing only look at the raw entry
+        assert_eq!(entry, Entry::new("\x1b[31mfoo\x1b[0m bar".to_string()));
+    }
+
+    #[test]
+    fn test_output_line_numbers() {
+        let path = Template::parse("{split:\\::0}").unwrap();
+        let line = Template::parse("{split:\\::1}").unwrap();
+        let entry = |raw: &str, output: Option<&Template>| {
+            Entry::from_match(
+                raw.to_string(),
+                Some("fn main() {}".to_string()),
+                &[],
+                false,
+                &mut EntryShape {
+                    output: output.cloned(),
+                    output_line: Some(line.clone()),
+                    ..EntryShape::default()
+                },
+            )
+        };
+
+        assert_eq!(
+            entry("src/main.rs:12:fn main() {}", Some(&path))
+                .output()
+                .unwrap(),
+            "src/main.rs:12"
+        );
+        // the line number is already there
+        assert_eq!(
+            entry("src/main.rs:12:fn main() {}", None).output().unwrap(),
+            "src/main.rs:12:fn main() {}"
+        );
+        // the entry doesn't have one
+        assert_eq!(
+            entry("src/main.rs", Some(&path)).output().unwrap(),
+            "src/main.rs"
+        );
+        assert_eq!(
+            entry("src/main.rs:12:fn main() {}", None).line_number(),
+            Some(12)
+        );
+    }
+
+    #[test]
+    fn test_cached_fields() {
+        let colon = Delimiter::Fixed(":".to_string());
+        let entry = Entry::from_match(
+            "src/main.rs:12:fn main() {}".to_string(),
+            None,
+            &[],
+            false,
+            &mut EntryShape {
+                delimiter: Some(colon.clone()),
+                ..EntryShape::default()
+            },
+        );
+        assert_eq!(entry.fields, Some(EntryFields::new(&entry.raw, &colon)));
+        assert_eq!(
+            entry.fields(&colon),
+            vec!["src/main.rs", "12", "fn main() {}"]
+        );
+        // another delimiter splits the entry again
+        assert_eq!(
+            entry.fields(&Delimiter::default()),
+            vec!["src/main.rs:12:fn", "main()", "{}"]
+        );
+        let template = Template::parse("bat {0} -H {1}").unwrap();
+        assert_eq!(
+            entry.format(&template, &colon).unwrap(),
+            "bat src/main.rs -H 12"
+        );
+        // e.g. what `copy_formatted` copies without a `copy` template
+        assert_eq!(
+            entry.format(&Template::Entry, &colon).unwrap(),
+            "src/main.rs:12:fn main() {}"
+        );
+    }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    channels::{entry::Entry, prototypes::Template},
+    channels::{
+        entry::{Entry, EntryShape},
+        prototypes::Template,
+    },
     matcher::{injector::Injector, matched_item::MatchedItem},
 };
 use fast_strip_ansi::strip_ansi_string;
@@ -19,7 +22,7 @@ pub trait EntryProcessor: Send + Sync + Clone + 'static {
     fn make_entry(
         &self,
         item: MatchedItem<Self::Data>,
-        source_output: Option<&Template>,
+        shape: &mut EntryShape,
     ) -> Entry;
 
     fn has_ansi(&self) -> bool;
@@ -44,14 +47,15 @@ impl EntryProcessor for PlainProcessor {
     fn make_entry(
         &self,
         item: MatchedItem<()>,
-        source_output: Option<&Template>,
+        shape: &mut EntryShape,
     ) -> Entry {
-        let mut entry = Entry::new(item.matched_string.clone())
-            .with_match_indices(&item.match_indices);
-        if let Some(output) = source_output {
-            entry = entry.with_output(output.clone());
-        }
-        entry
+        Entry::from_match(
+            item.matched_string,
+            None,
+            &item.match_indices,
+            false,
+            shape,
+        )
     }
 
     fn has_ansi(&self) -> bool {
@@ -81,16 +85,15 @@ impl EntryProcessor for AnsiProcessor {
     fn make_entry(
         &self,
         item: MatchedItem<String>,
-        source_output: Option<&Template>,
+        shape: &mut EntryShape,
     ) -> Entry {
-        let mut entry = Entry::new(item.inner)
-            .with_display(item.matched_string)
-            .with_match_indices(&item.match_indices)
-            .ansi(true);
-        if let Some(output) = source_output {
-            entry = entry.with_output(output.clone());
-        }
-        entry
+        Entry::from_match(
+            item.inner,
+            Some(item.matched_string),
+            &item.match_indices,
+            true,
+            shape,
+        )
     }
 
     fn has_ansi(&self) -> bool {
@@ -111,8 +114,8 @@ impl EntryProcessor for DisplayProcessor {
     type Data = String;
 
     fn push_to_injector(&self, line: String, injector: &Injector<String>) {
-        let template = self.template.clone();
-        injector.push(line, move |original, cols| {
+        let template = &self.template;
+        injector.push(line, |original, cols| {
             cols[0] = template.format(original)
                 .unwrap_or_else(|_| {
                     panic!(
@@ -128,16 +131,15 @@ impl EntryProcessor for DisplayProcessor {
     fn make_entry(
         &self,
         item: MatchedItem<String>,
-        source_output: Option<&Template>,
+        shape: &mut EntryShape,
     ) -> Entry {
-        let mut entry = Entry::new(item.inner)
-            .with_display(item.matched_string)
-            .with_match_indices(&item.match_indices)
-            .ansi(false);
-        if let Some(output) = source_output {
-            entry = entry.with_output(output.clone());
-        }
-        entry
+        Entry::from_match(
+            item.inner,
+            Some(item.matched_string),
+            &item.match_indices,
+            false,
+            shape,
+        )
     }
 
     fn has_ansi(&self) -> bool {
@@ -0,0 +1,229 @@
+//! Icons for the entries of channels whose source doesn't come with any,
+//! guessed from what the entries look like (file paths, URLs, git hashes).
+use std::path::Path;
+
+use devicons::FileIcon;
+use rustc_hash::FxHashMap;
+
+use crate::channels::prototypes::Delimiter;
+
+/// How many entries have their icon remembered before starting over.
+const MAX_CACHED_ENTRIES: usize = 10_000;
+
+/// Icon of the entries that look like a URL.
+pub const URL_ICON: FileIcon = FileIcon {
+    icon: '\u{f0ac}',
+    color: "#4f8cc9",
+};
+
+/// Icon of the entries that start with a git hash.
+pub const GIT_HASH_ICON: FileIcon = FileIcon {
+    icon: '\u{e729}',
+    color: "#f05032",
+};
+
+/// Icon of the entries that are directories.
+const DIRECTORY_ICON_PATH: &str = "/";
+
+/// Which entries get an icon of their own.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Default,
+    serde::Deserialize,
+    serde::Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum IconMode {
+    /// None of them.
+    #[default]
+    None,
+    /// The ones naming an existing file or directory.
+    Path,
+    /// Paths, URLs and lines starting with a git hash.
+    Auto,
+}
+
+impl From<crate::cli::args::IconMode> for IconMode {
+    fn from(value: crate::cli::args::IconMode) -> Self {
+        match value {
+            crate::cli::args::IconMode::None => IconMode::None,
+            crate::cli::args::IconMode::Path => IconMode::Path,
+            crate::cli::args::IconMode::Auto => IconMode::Auto,
+        }
+    }
+}
+
+/// Looks up the icons of the entries as they are listed.
+///
+/// Telling whether an entry is an existing path takes a `stat`, so the icon
+/// of each entry is remembered, as is the icon of each file extension.
+#[derive(Debug, Clone, Default)]
+pub struct EntryIcons {
+    mode: IconMode,
+    /// Splits entries into fields, the first of which may be the path.
+    delimiter: Delimiter,
+    by_entry: FxHashMap<String, Option<FileIcon>>,
+    by_extension: FxHashMap<String, FileIcon>,
+}
+
+impl EntryIcons {
+    pub fn new(mode: IconMode, delimiter: Delimiter) -> Self {
+        Self {
+            mode,
+            delimiter,
+            ..Self::default()
+        }
+    }
+
+    /// The icon of the `raw` entry, if it has one.
+    pub fn icon(&mut self, raw: &str) -> Option<FileIcon> {
+        if self.mode == IconMode::None {
+            return None;
+        }
+        if let Some(icon) = self.by_entry.get(raw) {
+            return *icon;
+        }
+        let icon = self.lookup(raw);
+        if self.by_entry.len() >= MAX_CACHED_ENTRIES {
+            self.by_entry.clear();
+        }
+        self.by_entry.insert(raw.to_string(), icon);
+        icon
+    }
+
+    fn lookup(&mut self, raw: &str) -> Option<FileIcon> {
+        if self.mode == IconMode::Auto {
+            if looks_like_url(raw) {
+                return Some(URL_ICON);
+            }
+            if raw
+                .split_whitespace()
+                .next()
+                .is_some_and(looks_like_git_hash)
+            {
+                return Some(GIT_HASH_ICON);
+            }
+        }
+        let first_field = self
+            .delimiter
+            .field(raw, 0)
+            .map(str::trim)
+            .filter(|field| *field != raw);
+        std::iter::once(raw)
+            .chain(first_field)
+            .find_map(|candidate| self.path_icon(candidate))
+    }
+
+    /// The icon of `candidate` if it names an existing file or directory.
+    fn path_icon(&mut self, candidate: &str) -> Option<FileIcon> {
+        if candidate.is_empty() || candidate.contains('\n') {
+            return None;
+        }
+        let path = Path::new(candidate);
+        let metadata = path.metadata().ok()?;
+        if metadata.is_dir() {
+            return Some(FileIcon::from(DIRECTORY_ICON_PATH));
+        }
+        // files without an extension are told apart by their name
+        let key = path
+            .extension()
+            .or_else(|| path.file_name())?
+            .to_string_lossy()
+            .into_owned();
+        Some(
+            *self
+                .by_extension
+                .entry(key)
+                .or_insert_with(|| FileIcon::from(candidate)),
+        )
+    }
+}
+
+fn looks_like_url(raw: &str) -> bool {
+    ["https://", "http://", "ftp://", "file://"]
+        .iter()
+        .any(|scheme| raw.starts_with(scheme))
+}
+
+/// Whether `word` is an abbreviated or full git object hash.
+fn looks_like_git_hash(word: &str) -> bool {
+    (7..=40).contains(&word.len())
+        && word.chars().all(|c| c.is_ascii_hexdigit())
+        // hex words like "deadbeef" or numbers aren't hashes
+        && word.chars().any(|c| c.is_ascii_digit())
+        && word.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn icon_of(icons: &mut EntryIcons, raw: &str) -> Option<FileIcon> {
+        icons.icon(raw)
+    }
+
+    #[test]
+    fn test_no_icons_by_default() {
+        let mut icons = EntryIcons::default();
+        assert_eq!(icon_of(&mut icons, "Cargo.toml"), None);
+        assert!(icons.by_entry.is_empty());
+    }
+
+    #[test]
+    fn test_path_icons() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("main.rs");
+        std::fs::write(&file, "").unwrap();
+        let file = file.to_string_lossy().to_string();
+        let mut icons = EntryIcons::new(IconMode::Path, Delimiter::default());
+
+        assert_eq!(
+            icon_of(&mut icons, &file),
+            Some(FileIcon::from("main.rs"))
+        );
+        assert_eq!(
+            icon_of(&mut icons, &dir.path().to_string_lossy()),
+            Some(FileIcon::from(DIRECTORY_ICON_PATH))
+        );
+        // the path may be the first field of the entry
+        assert_eq!(
+            icon_of(&mut icons, &format!("{file} 42")),
+            Some(FileIcon::from("main.rs"))
+        );
+        assert_eq!(icon_of(&mut icons, "not/a/file.rs"), None);
+        // only paths get an icon
+        assert_eq!(icon_of(&mut icons, "https://example.com"), None);
+        assert_eq!(icons.by_extension.len(), 1);
+    }
+
+    #[test]
+    fn test_auto_icons() {
+        let mut icons = EntryIcons::new(IconMode::Auto, Delimiter::default());
+        assert_eq!(
+            icon_of(&mut icons, "https://example.com/a"),
+            Some(URL_ICON)
+        );
+        assert_eq!(
+            icon_of(&mut icons, "3f2a9c1 Fix the build"),
+            Some(GIT_HASH_ICON)
+        );
+        assert_eq!(icon_of(&mut icons, "deadbeef"), None);
+        assert_eq!(icon_of(&mut icons, "1234567"), None);
+        assert_eq!(icon_of(&mut icons, "just some text"), None);
+    }
+
+    #[test]
+    fn test_looks_like_git_hash() {
+        assert!(looks_like_git_hash("3f2a9c1"));
+        assert!(looks_like_git_hash(
+            "3f2a9c1b3f2a9c1b3f2a9c1b3f2a9c1b3f2a9c1b"
+        ));
+        assert!(!looks_like_git_hash("3f2a9c"));
+        assert!(!looks_like_git_hash("3f2a9cz"));
+    }
+}
@@ -1,5 +1,7 @@
+pub mod builtin;
 pub mod channel;
 pub mod entry;
 pub mod entry_processor;
+pub mod icons;
 pub mod prototypes;
 pub mod remote_control;
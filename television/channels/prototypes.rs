@@ -2,16 +2,19 @@ use crate::cli::parse_source_entry_delimiter;
 use crate::config::ui::{InputBarConfig, ThemeOverrides};
 use crate::utils::strings::SPACE;
 use crate::{
+    channels::icons::IconMode,
     config::{Keybindings, ui},
     event::Key,
     screen::layout::Orientation,
 };
 use anyhow::Result;
+use lazy_regex::Regex;
 use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use serde_with::{OneOrMany, serde_as};
 use std::fmt::{self, Display, Formatter};
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use string_pipeline::MultiTemplate;
 use which::which;
 
@@ -19,6 +22,9 @@ use which::which;
 pub enum Template {
     StringPipeline(MultiTemplate),
     Raw(String),
+    /// The entry itself, e.g. for a preview showing the entry rather than
+    /// the output of a command.
+    Entry,
 }
 
 impl Template {
@@ -26,6 +32,7 @@ impl Template {
         match self {
             Template::StringPipeline(template) => template.template_string(),
             Template::Raw(raw) => raw,
+            Template::Entry => "{}",
         }
     }
 
@@ -49,6 +56,7 @@ impl Template {
                 })
             }
             Template::Raw(raw) => Ok(raw.replace("{}", input)),
+            Template::Entry => Ok(input.to_string()),
         }
     }
 }
@@ -66,6 +74,7 @@ impl PartialEq for Template {
                 (self, other),
                 (Template::StringPipeline(_), Template::StringPipeline(_))
                     | (Template::Raw(_), Template::Raw(_))
+                    | (Template::Entry, Template::Entry)
             )
     }
 }
@@ -101,6 +110,7 @@ impl<'de> Deserialize<'de> for Template {
 #[derive(
     Debug, Clone, serde::Deserialize, serde::Serialize, PartialEq, Default,
 )]
+#[serde(try_from = "RawCommandSpec", into = "RawCommandSpec")]
 pub struct CommandSpec {
     #[serde(rename = "command")]
     #[serde_as(as = "OneOrMany<_>")]
@@ -109,6 +119,256 @@ pub struct CommandSpec {
     pub interactive: bool,
     #[serde(default)]
     pub env: FxHashMap<String, String>,
+    /// Optional label of each command, shown when cycling through them.
+    #[serde(skip)]
+    pub labels: Vec<Option<String>>,
+    /// Optional condition of each command, which then only applies to the
+    /// entries it matches.
+    #[serde(skip)]
+    pub conditions: Vec<Option<EntryCondition>>,
+    /// Optional delimiter of each command, splitting entries into fields for
+    /// its positional placeholders instead of the channel's.
+    #[serde(skip)]
+    pub delimiters: Vec<Option<Delimiter>>,
+    /// What each command reads on its standard input, if anything, instead
+    /// of having the entry substituted into it.
+    #[serde(skip)]
+    pub stdin: Vec<Option<StdinInput>>,
+}
+
+/// What a command is fed on its standard input.
+///
+/// ```toml
+/// [preview]
+/// command = "jq -C ."
+/// stdin = true    # the whole entry
+/// # stdin = 1     # only its second field
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StdinInput {
+    /// The whole entry.
+    Entry,
+    /// The field of the entry at this index.
+    Field(usize),
+}
+
+impl StdinInput {
+    /// What the command reads for the `raw` entry, split into fields with
+    /// `delimiter`.
+    pub fn for_entry(self, raw: &str, delimiter: &Delimiter) -> String {
+        match self {
+            Self::Entry => raw.to_string(),
+            Self::Field(index) => {
+                delimiter.field(raw, index).unwrap_or_default().to_string()
+            }
+        }
+    }
+}
+
+/// The `stdin` option as written in a channel file: either a boolean or
+/// the index of a field.
+#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+enum RawStdinInput {
+    Enabled(bool),
+    Field(usize),
+}
+
+impl From<RawStdinInput> for Option<StdinInput> {
+    fn from(raw: RawStdinInput) -> Self {
+        match raw {
+            RawStdinInput::Enabled(enabled) => {
+                enabled.then_some(StdinInput::Entry)
+            }
+            RawStdinInput::Field(index) => Some(StdinInput::Field(index)),
+        }
+    }
+}
+
+impl From<StdinInput> for RawStdinInput {
+    fn from(stdin: StdinInput) -> Self {
+        match stdin {
+            StdinInput::Entry => RawStdinInput::Enabled(true),
+            StdinInput::Field(index) => RawStdinInput::Field(index),
+        }
+    }
+}
+
+/// A regex an entry has to match for a command to apply to it.
+#[derive(Debug, Clone)]
+pub struct EntryCondition(Regex);
+
+impl EntryCondition {
+    pub fn new(pattern: &str) -> Result<Self> {
+        Ok(Self(Regex::new(pattern)?))
+    }
+
+    pub fn matches(&self, raw: &str) -> bool {
+        self.0.is_match(raw)
+    }
+}
+
+impl PartialEq for EntryCondition {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
+/// A command as written in a channel file: either a bare template or a
+/// template with a label, a condition on the entries it applies to, its own
+/// delimiter and/or what it reads on its standard input.
+///
+/// ```toml
+/// command = [
+///     { command = "ls {}", when = "/$", label = "dir" },
+///     { command = "bat -H {1} {0}", label = "file", delimiter = ":" },
+///     { command = "jq -C .", when = "^\\{", stdin = true },
+///     "git log {}",
+/// ]
+/// ```
+#[derive(serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+enum CommandEntry {
+    Bare(Template),
+    Labeled {
+        command: Template,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        when: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        delimiter: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stdin: Option<RawStdinInput>,
+    },
+}
+
+#[serde_as]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct RawCommandSpec {
+    #[serde(rename = "command")]
+    #[serde_as(as = "OneOrMany<_>")]
+    inner: Vec<CommandEntry>,
+    #[serde(default)]
+    interactive: bool,
+    #[serde(default)]
+    env: FxHashMap<String, String>,
+    /// Feed every command the entry on its standard input, unless it says
+    /// otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stdin: Option<RawStdinInput>,
+}
+
+/// Commands with a label, condition, delimiter or stdin of their own are
+/// written back in their table form.
+impl From<CommandSpec> for RawCommandSpec {
+    fn from(spec: CommandSpec) -> Self {
+        let inner = spec
+            .inner
+            .into_iter()
+            .enumerate()
+            .map(|(i, command)| {
+                let label = spec.labels.get(i).cloned().flatten();
+                let when = spec
+                    .conditions
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .map(|condition| condition.0.as_str().to_string());
+                let delimiter = spec
+                    .delimiters
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .map(ToString::to_string);
+                let stdin =
+                    spec.stdin.get(i).copied().flatten().map(Into::into);
+                if label.is_none()
+                    && when.is_none()
+                    && delimiter.is_none()
+                    && stdin.is_none()
+                {
+                    CommandEntry::Bare(command)
+                } else {
+                    CommandEntry::Labeled {
+                        command,
+                        label,
+                        when,
+                        delimiter,
+                        stdin,
+                    }
+                }
+            })
+            .collect();
+        Self {
+            inner,
+            interactive: spec.interactive,
+            env: spec.env,
+            stdin: None,
+        }
+    }
+}
+
+impl TryFrom<RawCommandSpec> for CommandSpec {
+    type Error = String;
+
+    fn try_from(raw: RawCommandSpec) -> Result<Self, Self::Error> {
+        let mut inner = Vec::with_capacity(raw.inner.len());
+        let mut labels = Vec::with_capacity(raw.inner.len());
+        let mut conditions = Vec::with_capacity(raw.inner.len());
+        let mut delimiters = Vec::with_capacity(raw.inner.len());
+        let mut stdin = Vec::with_capacity(raw.inner.len());
+        let default_stdin = raw.stdin.and_then(Option::from);
+        for entry in raw.inner {
+            match entry {
+                CommandEntry::Bare(template) => {
+                    inner.push(template);
+                    labels.push(None);
+                    conditions.push(None);
+                    delimiters.push(None);
+                    stdin.push(default_stdin);
+                }
+                CommandEntry::Labeled {
+                    command,
+                    label,
+                    when,
+                    delimiter,
+                    stdin: own_stdin,
+                } => {
+                    let condition = when
+                        .map(|pattern| {
+                            EntryCondition::new(&pattern).map_err(|e| {
+                                format!(
+                                    "invalid `when` regex {pattern:?}: {e}"
+                                )
+                            })
+                        })
+                        .transpose()?;
+                    let delimiter = delimiter
+                        .map(|delimiter| {
+                            Delimiter::parse(&delimiter).map_err(|e| {
+                                format!(
+                                    "invalid `delimiter` {delimiter:?}: {e}"
+                                )
+                            })
+                        })
+                        .transpose()?;
+                    inner.push(command);
+                    labels.push(label);
+                    conditions.push(condition);
+                    delimiters.push(delimiter);
+                    stdin.push(own_stdin.map_or(default_stdin, Option::from));
+                }
+            }
+        }
+        Ok(Self {
+            inner,
+            interactive: raw.interactive,
+            env: raw.env,
+            labels,
+            conditions,
+            delimiters,
+            stdin,
+        })
+    }
 }
 
 impl Display for CommandSpec {
@@ -141,6 +401,10 @@ impl CommandSpec {
             inner,
             interactive,
             env,
+            labels: Vec::new(),
+            conditions: Vec::new(),
+            delimiters: Vec::new(),
+            stdin: Vec::new(),
         }
     }
 
@@ -148,6 +412,82 @@ impl CommandSpec {
         self.inner.len()
     }
 
+    /// The delimiter the nth command splits entries with, if it has its own.
+    pub fn delimiter_nth(&self, index: usize) -> Option<&Delimiter> {
+        if self.inner.is_empty() {
+            return None;
+        }
+        self.delimiters.get(index % self.inner.len())?.as_ref()
+    }
+
+    /// What the nth command reads on its standard input, if anything.
+    pub fn stdin_nth(&self, index: usize) -> Option<StdinInput> {
+        if self.inner.is_empty() {
+            return None;
+        }
+        self.stdin.get(index % self.inner.len()).copied().flatten()
+    }
+
+    /// Have the commands without a delimiter of their own split entries
+    /// with `delimiter`.
+    pub fn with_default_delimiter(mut self, delimiter: &Delimiter) -> Self {
+        self.delimiters.resize(self.inner.len(), None);
+        for own in &mut self.delimiters {
+            own.get_or_insert_with(|| delimiter.clone());
+        }
+        self
+    }
+
+    /// The label of the nth command, wrapping around like [`Self::get_nth`].
+    pub fn label_nth(&self, index: usize) -> Option<&str> {
+        if self.inner.is_empty() {
+            return None;
+        }
+        self.labels.get(index % self.inner.len())?.as_deref()
+    }
+
+    /// A short badge telling which command is in use, e.g. `[1/2 file]`.
+    ///
+    /// Only commands that are labeled or have siblings get one.
+    pub fn badge_nth(&self, index: usize) -> Option<String> {
+        if self.inner.is_empty() {
+            return None;
+        }
+        badge(
+            index % self.inner.len() + 1,
+            self.inner.len(),
+            self.label_nth(index),
+        )
+    }
+
+    /// Whether the nth command applies to the `raw` entry, which is always
+    /// the case for commands without a `when` condition.
+    pub fn applies_nth(&self, index: usize, raw: &str) -> bool {
+        self.conditions
+            .get(index)
+            .and_then(Option::as_ref)
+            .is_none_or(|condition| condition.matches(raw))
+    }
+
+    /// The index of the first command applying to the `raw` entry, starting
+    /// from the `from`th command and wrapping around.
+    pub fn nth_for_entry(&self, from: usize, raw: &str) -> Option<usize> {
+        let count = self.inner.len();
+        (0..count)
+            .map(|i| (from + i) % count)
+            .find(|&i| self.applies_nth(i, raw))
+    }
+
+    /// Like [`Self::badge_nth`], only counting the commands applying to the
+    /// `raw` entry.
+    pub fn badge_for_entry(&self, index: usize, raw: &str) -> Option<String> {
+        let applicable = (0..self.inner.len())
+            .filter(|&i| self.applies_nth(i, raw))
+            .collect::<Vec<_>>();
+        let position = applicable.iter().position(|&i| i == index)?;
+        badge(position + 1, applicable.len(), self.label_nth(index))
+    }
+
     pub fn has_multiple_commands(&self) -> bool {
         self.inner.len() > 1
     }
@@ -163,6 +503,32 @@ impl CommandSpec {
     pub fn from_template(template: Template) -> Self {
         Self::new(vec![template], false, FxHashMap::default())
     }
+
+    /// A preview "command" showing the entry itself, used by sources
+    /// emitting multi-line records that don't have a preview command.
+    pub fn entry_preview() -> Self {
+        Self::from_template(Template::Entry)
+    }
+
+    /// Whether the `index`-th command shows the entry itself rather than
+    /// running anything (see [`Self::entry_preview`]).
+    pub fn is_entry_preview_nth(&self, index: usize) -> bool {
+        matches!(self.get_nth(index), Template::Entry)
+    }
+}
+
+fn badge(
+    position: usize,
+    count: usize,
+    label: Option<&str>,
+) -> Option<String> {
+    if count <= 1 {
+        return label.map(|label| format!("[{label}]"));
+    }
+    Some(match label {
+        Some(label) => format!("[{position}/{count} {label}]"),
+        None => format!("[{position}/{count}]"),
+    })
 }
 
 /// Execution mode for external actions
@@ -176,6 +542,32 @@ pub enum ExecutionMode {
     Fork,
     /// Replace the current process with the command (tv exits, command takes over)
     Execute,
+    /// Like `Execute`, but the command is run directly instead of going
+    /// through the shell so that tv doesn't linger in the process tree
+    Become,
+}
+
+/// What to do with the output of a forked external action
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    serde::Deserialize,
+    serde::Serialize,
+    PartialEq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputMode {
+    /// The command inherits the terminal (e.g. for interactive programs)
+    #[default]
+    Inherit,
+    /// Capture the output and print it once tv exits
+    Print,
+    /// Capture the output and discard it unless the command fails
+    Silent,
+    /// Capture the output and display it in the preview panel
+    Pager,
 }
 
 fn default_separator() -> String {
@@ -196,9 +588,41 @@ pub struct ActionSpec {
     /// Example: `rm file1+SEPARATOR+file2+SEPARATOR+file3`
     #[serde(default = "default_separator")]
     pub separator: String,
+    /// Ask for confirmation before running the command
+    #[serde(default)]
+    pub confirm: bool,
+    /// What to do with the command's output (only applies to `fork`)
+    #[serde(default)]
+    pub output: OutputMode,
+    /// Clear the selection and reload the source once the command completes
+    /// (only applies to `fork`)
+    #[serde(default)]
+    pub reload: bool,
+    /// Entries to remove from the results once the command completes, as
+    /// regular expressions matched against the raw entries (`"self"` stands
+    /// for the entries the action was run on)
+    #[serde(default)]
+    pub remove: Vec<String>,
     // TODO: add `requirements` (see `prototypes::BinaryRequirement`)
 }
 
+impl ActionSpec {
+    /// A command run in the background against the selected entries, e.g.
+    /// as bound in `[keybindings.execute]`.
+    pub fn background(template: Template, output: OutputMode) -> Self {
+        Self {
+            description: None,
+            command: CommandSpec::from(template),
+            mode: ExecutionMode::Fork,
+            separator: default_separator(),
+            confirm: false,
+            output,
+            reload: false,
+            remove: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct ChannelKeyBindings {
     /// Optional channel specific shortcut that, when pressed, switches directly to this channel.
@@ -217,6 +641,7 @@ impl ChannelKeyBindings {
 }
 
 #[derive(Default, Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct HistoryConfig {
     /// Whether to use global history for this channel (overrides global setting)
     #[serde(default)]
@@ -224,6 +649,7 @@ pub struct HistoryConfig {
 }
 
 #[derive(Debug, Clone, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ChannelPrototype {
     pub metadata: Metadata,
     pub source: SourceSpec,
@@ -240,6 +666,9 @@ pub struct ChannelPrototype {
     pub history: HistoryConfig,
     #[serde(default)]
     pub actions: FxHashMap<String, ActionSpec>,
+    /// The file the channel was loaded from, if it isn't built in.
+    #[serde(skip)]
+    pub source_path: Option<PathBuf>,
 }
 
 impl ChannelPrototype {
@@ -249,6 +678,8 @@ impl ChannelPrototype {
                 name: name.to_string(),
                 description: None,
                 requirements: vec![],
+                icon: None,
+                icon_mode: IconMode::default(),
             },
             source: SourceSpec {
                 command: CommandSpec {
@@ -258,11 +689,26 @@ impl ChannelPrototype {
                     ],
                     interactive: false,
                     env: FxHashMap::default(),
+                    labels: Vec::new(),
+                    conditions: Vec::new(),
+                    delimiters: Vec::new(),
+                    stdin: Vec::new(),
                 },
                 entry_delimiter: None,
+                record_separator: None,
                 ansi: false,
+                reverse: false,
+                hidden: false,
+                no_ignore: false,
+                dynamic: false,
+                fuzzy_filter: false,
                 display: None,
                 output: None,
+                output_line_numbers: None,
+                delimiter: None,
+                copy: None,
+                group_by: None,
+                sort_fields: Vec::new(),
             },
             preview: None,
             ui: None,
@@ -270,6 +716,7 @@ impl ChannelPrototype {
             watch: 0.0,
             history: HistoryConfig::default(),
             actions: FxHashMap::default(),
+            source_path: None,
         }
     }
 
@@ -281,6 +728,8 @@ impl ChannelPrototype {
                     "A channel that reads from stdin".to_string(),
                 ),
                 requirements: vec![],
+                icon: None,
+                icon_mode: IconMode::default(),
             },
             source: SourceSpec {
                 command: CommandSpec {
@@ -295,6 +744,7 @@ impl ChannelPrototype {
             watch: 0.0,
             history: HistoryConfig::default(),
             actions: FxHashMap::default(),
+            source_path: None,
         }
     }
 
@@ -311,11 +761,35 @@ impl Display for ChannelPrototype {
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct Metadata {
     pub name: String,
     pub description: Option<String>,
     #[serde(default)]
     pub requirements: Vec<BinaryRequirement>,
+    /// Icon shown next to entries that don't resolve to a file icon of their own.
+    #[serde(default)]
+    pub icon: Option<ChannelIcon>,
+    /// Which entries get a file icon of their own.
+    #[serde(default)]
+    pub icon_mode: IconMode,
+}
+
+/// A (nerd-font) glyph and optional color used to decorate a channel.
+///
+/// ```toml
+/// [metadata]
+/// name = "git-branch"
+/// icon = { glyph = "\ue725", color = "#f05032" }
+/// ```
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelIcon {
+    pub glyph: char,
+    #[serde(default)]
+    pub color: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -351,17 +825,279 @@ impl BinaryRequirement {
 }
 
 #[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(try_from = "RawSourceSpec")]
 pub struct SourceSpec {
     #[serde(flatten)]
     pub command: CommandSpec,
-    #[serde(deserialize_with = "deserialize_entry_delimiter", default)]
     pub entry_delimiter: Option<char>,
-    #[serde(default)]
+    /// Splits the source's output into entries, which may then span several
+    /// lines, given as either `record_separator` or `entry_delimiter`.
+    pub record_separator: Option<String>,
     pub ansi: bool,
-    #[serde(default)]
+    /// List the entries newest first when there's no query.
+    pub reverse: bool,
+    /// Have the built-in files source list hidden files.
+    pub hidden: bool,
+    /// Have the built-in files source list files ignored by `.gitignore`.
+    pub no_ignore: bool,
+    /// Re-run the command with `{q}` replaced by the query every time it
+    /// changes instead of matching the entries against it.
+    pub dynamic: bool,
+    /// Still fuzzy match the entries of a dynamic source against the query.
+    pub fuzzy_filter: bool,
     pub display: Option<Template>,
-    #[serde(default)]
     pub output: Option<Template>,
+    /// Append `:{line}` to the output of entries that have a line number,
+    /// as extracted by the preview's `offset`. On by default when there is
+    /// one.
+    pub output_line_numbers: Option<bool>,
+    /// Splits entries into fields for positional placeholders like `{0}`,
+    /// given as either `delimiter` or `delimiter_regex`.
+    pub delimiter: Option<Delimiter>,
+    /// Template used when copying formatted entries to the clipboard.
+    pub copy: Option<Template>,
+    /// Groups consecutive results sharing this field under a header.
+    pub group_by: Option<usize>,
+    /// Fields the results can be sorted by using `cycle_sort`.
+    pub sort_fields: Vec<SortField>,
+}
+
+/// A source as written in a channel file.
+#[derive(serde::Deserialize)]
+struct RawSourceSpec {
+    #[serde(flatten)]
+    command: CommandSpec,
+    #[serde(deserialize_with = "deserialize_entry_delimiter", default)]
+    entry_delimiter: Option<char>,
+    #[serde(default)]
+    record_separator: Option<String>,
+    #[serde(default)]
+    ansi: bool,
+    #[serde(default)]
+    reverse: bool,
+    #[serde(default)]
+    hidden: bool,
+    #[serde(default)]
+    no_ignore: bool,
+    #[serde(default)]
+    dynamic: bool,
+    #[serde(default)]
+    fuzzy_filter: bool,
+    #[serde(default)]
+    display: Option<Template>,
+    #[serde(default)]
+    output: Option<Template>,
+    #[serde(default)]
+    output_line_numbers: Option<bool>,
+    #[serde(default)]
+    delimiter: Option<String>,
+    #[serde(default)]
+    delimiter_regex: Option<String>,
+    #[serde(default)]
+    copy: Option<Template>,
+    #[serde(default)]
+    group_by: Option<usize>,
+    #[serde(default)]
+    sort_fields: Vec<SortField>,
+}
+
+impl TryFrom<RawSourceSpec> for SourceSpec {
+    type Error = String;
+
+    fn try_from(raw: RawSourceSpec) -> Result<Self, Self::Error> {
+        let delimiter = match (raw.delimiter, raw.delimiter_regex) {
+            (Some(_), Some(_)) => {
+                return Err("`delimiter` and `delimiter_regex` are mutually \
+                     exclusive"
+                    .to_string());
+            }
+            (Some(delimiter), None) => Some(Delimiter::Fixed(delimiter)),
+            (None, Some(pattern)) => Some(
+                Delimiter::regex(&pattern)
+                    .map_err(|e| format!("invalid `delimiter_regex`: {e}"))?,
+            ),
+            (None, None) => None,
+        };
+        let record_separator =
+            match (raw.record_separator, raw.entry_delimiter) {
+                (Some(_), Some(_)) => {
+                    return Err("`record_separator` and `entry_delimiter` \
+                         are mutually exclusive"
+                        .to_string());
+                }
+                (Some(separator), None) => {
+                    Some(parse_record_separator(&separator).map_err(|e| {
+                        format!("invalid `record_separator`: {e}")
+                    })?)
+                }
+                (None, delimiter) => delimiter.map(String::from),
+            };
+        Ok(Self {
+            command: raw.command,
+            entry_delimiter: raw.entry_delimiter,
+            record_separator,
+            ansi: raw.ansi,
+            reverse: raw.reverse,
+            hidden: raw.hidden,
+            no_ignore: raw.no_ignore,
+            dynamic: raw.dynamic,
+            fuzzy_filter: raw.fuzzy_filter,
+            display: raw.display,
+            output: raw.output,
+            output_line_numbers: raw.output_line_numbers,
+            delimiter,
+            copy: raw.copy,
+            group_by: raw.group_by,
+            sort_fields: raw.sort_fields,
+        })
+    }
+}
+
+/// Splits entries into fields, on either a fixed string or a regex.
+///
+/// Splitting on a regex drops empty fields so that columns aligned with a
+/// variable amount of whitespace (e.g. `ps aux`) split as expected.
+#[derive(Debug, Clone)]
+pub enum Delimiter {
+    Fixed(String),
+    Regex(Regex),
+}
+
+/// The prefix marking a delimiter given on the command line as a regex.
+pub const DELIMITER_REGEX_PREFIX: &str = "re:";
+
+impl Delimiter {
+    pub fn regex(pattern: &str) -> Result<Self> {
+        Ok(Self::Regex(Regex::new(pattern)?))
+    }
+
+    /// Parse a delimiter given on the command line, where regexes are
+    /// prefixed with `re:`.
+    pub fn parse(delimiter: &str) -> Result<Self> {
+        match delimiter.strip_prefix(DELIMITER_REGEX_PREFIX) {
+            Some(pattern) => Self::regex(pattern),
+            None => Ok(Self::Fixed(delimiter.to_string())),
+        }
+    }
+
+    /// Split `s` into its fields.
+    ///
+    /// # Example
+    /// ```
+    /// use television::channels::prototypes::Delimiter;
+    ///
+    /// let line = "root         1  0.0  0.1 /sbin/init";
+    /// let delimiter = Delimiter::regex(r"\s+").unwrap();
+    /// assert_eq!(
+    ///     delimiter.split(line),
+    ///     vec!["root", "1", "0.0", "0.1", "/sbin/init"]
+    /// );
+    /// ```
+    pub fn split<'a>(&self, s: &'a str) -> Vec<&'a str> {
+        match self {
+            Self::Fixed(delimiter) => s.split(delimiter.as_str()).collect(),
+            Self::Regex(regex) => {
+                regex.split(s).filter(|field| !field.is_empty()).collect()
+            }
+        }
+    }
+
+    /// The `index`-th field of `s`, if any.
+    pub fn field<'a>(&self, s: &'a str, index: usize) -> Option<&'a str> {
+        match self {
+            Self::Fixed(delimiter) => s.split(delimiter.as_str()).nth(index),
+            Self::Regex(regex) => {
+                regex.split(s).filter(|field| !field.is_empty()).nth(index)
+            }
+        }
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Self::Fixed(DEFAULT_DELIMITER.to_string())
+    }
+}
+
+impl PartialEq for Delimiter {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Fixed(a), Self::Fixed(b)) => a == b,
+            (Self::Regex(a), Self::Regex(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Delimiter {}
+
+/// Fixed delimiters are shown as is and regexes with the `re:` prefix.
+impl Display for Delimiter {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Fixed(delimiter) => write!(f, "{delimiter}"),
+            Self::Regex(regex) => {
+                write!(f, "{DELIMITER_REGEX_PREFIX}{}", regex.as_str())
+            }
+        }
+    }
+}
+
+impl Serialize for Delimiter {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+/// A field the matched results can be sorted by.
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize,
+)]
+#[serde(deny_unknown_fields)]
+pub struct SortField {
+    /// Name shown in the results panel title while sorting.
+    pub name: String,
+    /// Index of the field, using the channel's delimiter.
+    pub field: usize,
+    /// Sort in descending order.
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+/// The field delimiter used when a channel doesn't define one.
+pub const DEFAULT_DELIMITER: &str = SPACE;
+
+/// Unescape a record separator, which unlike entry delimiters may span
+/// several characters (e.g. `\0` or `\n---\n`).
+fn parse_record_separator(separator: &str) -> Result<String> {
+    if separator.is_empty() {
+        return Err(anyhow::anyhow!("record separator cannot be empty"));
+    }
+    let mut parsed = String::with_capacity(separator.len());
+    let mut chars = separator.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            parsed.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => parsed.push('\n'),
+            Some('t') => parsed.push('\t'),
+            Some('r') => parsed.push('\r'),
+            Some('0') => parsed.push('\0'),
+            Some('\\') => parsed.push('\\'),
+            other => {
+                return Err(anyhow::anyhow!(
+                    "invalid escape sequence: '\\{}'",
+                    other.map(String::from).unwrap_or_default()
+                ));
+            }
+        }
+    }
+    Ok(parsed)
 }
 
 /// Just a helper function to adapt cli parsing to serde deserialization.
@@ -388,6 +1124,16 @@ pub struct PreviewSpec {
     pub offset: Option<Template>,
     #[serde(default = "cached_default")]
     pub cached: bool,
+    /// Whether to prepare the previews of the next entries in advance,
+    /// which channels with expensive preview commands may rather not.
+    #[serde(default = "prefetch_default")]
+    pub prefetch: bool,
+    /// Preview output past this many bytes is cut off.
+    #[serde(default = "max_bytes_default")]
+    pub max_bytes: usize,
+    /// Preview lines past this many characters are cut off.
+    #[serde(default = "max_line_length_default")]
+    pub max_line_length: usize,
 }
 
 /// Preview caching is enabled by default.
@@ -400,12 +1146,27 @@ fn cached_default() -> bool {
     true
 }
 
+fn prefetch_default() -> bool {
+    true
+}
+
+fn max_bytes_default() -> usize {
+    crate::previewer::limits::DEFAULT_MAX_BYTES
+}
+
+fn max_line_length_default() -> usize {
+    crate::previewer::limits::DEFAULT_MAX_LINE_LENGTH
+}
+
 impl PreviewSpec {
     pub fn new(command: CommandSpec, offset: Option<Template>) -> Self {
         Self {
             command,
             offset,
             cached: false,
+            prefetch: prefetch_default(),
+            max_bytes: max_bytes_default(),
+            max_line_length: max_line_length_default(),
         }
     }
 
@@ -418,14 +1179,22 @@ impl PreviewSpec {
                 ],
                 interactive: false,
                 env: FxHashMap::default(),
+                labels: Vec::new(),
+                conditions: Vec::new(),
+                delimiters: Vec::new(),
+                stdin: Vec::new(),
             },
             offset: None,
             cached: false,
+            prefetch: prefetch_default(),
+            max_bytes: max_bytes_default(),
+            max_line_length: max_line_length_default(),
         }
     }
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
 pub struct UiSpec {
     #[serde(default)]
     pub ui_scale: Option<u16>,
@@ -449,6 +1218,24 @@ pub struct UiSpec {
     pub help_panel: Option<ui::HelpPanelConfig>,
     #[serde(default)]
     pub remote_control: Option<ui::RemoteControlConfig>,
+    /// `[ui.features]` from older channel files, which is ignored.
+    #[serde(default, skip_serializing)]
+    pub features: Option<LegacyKey>,
+}
+
+/// A key from older versions of the channel files which is accepted but
+/// ignored, so that they still load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LegacyKey;
+
+impl<'de> Deserialize<'de> for LegacyKey {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::de::IgnoredAny::deserialize(deserializer)?;
+        Ok(LegacyKey)
+    }
 }
 
 pub const DEFAULT_PROTOTYPE_NAME: &str = "files";
@@ -466,6 +1253,7 @@ impl From<&crate::config::UiConfig> for UiSpec {
             status_bar: Some(config.status_bar.clone()),
             help_panel: Some(config.help_panel.clone()),
             remote_control: Some(config.remote_control.clone()),
+            features: None,
         }
     }
 }
@@ -490,6 +1278,10 @@ mod tests {
             ],
             interactive: false,
             env: FxHashMap::default(),
+            labels: Vec::new(),
+            conditions: Vec::new(),
+            delimiters: Vec::new(),
+            stdin: Vec::new(),
         };
 
         assert_eq!(command_spec.get_nth(0).raw(), "cmd1");
@@ -711,6 +1503,328 @@ mod tests {
         assert_eq!(prototype.source.output.unwrap().raw(), "{}");
     }
 
+    #[test]
+    fn test_channel_prototype_deserialization_labeled_previews() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        command = "fd -t f"
+
+        [preview]
+        command = [
+            { command = "bat {}", label = "file" },
+            "git log {}",
+        ]
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let preview = prototype.preview.unwrap();
+
+        assert_eq!(
+            preview
+                .command
+                .inner
+                .iter()
+                .map(Template::raw)
+                .collect::<Vec<_>>(),
+            vec!["bat {}", "git log {}"]
+        );
+        assert_eq!(preview.command.label_nth(0), Some("file"));
+        assert_eq!(preview.command.label_nth(1), None);
+        assert_eq!(preview.command.badge_nth(0).unwrap(), "[1/2 file]");
+        assert_eq!(preview.command.badge_nth(1).unwrap(), "[2/2]");
+        assert_eq!(preview.command.badge_nth(2).unwrap(), "[1/2 file]");
+        // bare single commands don't get a badge
+        assert_eq!(prototype.source.command.badge_nth(0), None);
+    }
+
+    #[test]
+    fn test_command_spec_serialization_keeps_labels() {
+        let toml_data = r#"
+        command = [
+            { command = "ls {}", when = "/$", label = "dir" },
+            { command = "bat -H {1} {0}", delimiter = ":" },
+            { command = "jq -C .", stdin = true },
+            "git log {}",
+        ]
+        "#;
+        let command: CommandSpec = from_str(toml_data).unwrap();
+
+        let serialized = toml::to_string(&command).unwrap();
+        assert!(serialized.contains("label = \"dir\""), "{serialized}");
+        assert_eq!(from_str::<CommandSpec>(&serialized).unwrap(), command);
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_conditional_previews() {
+        let toml_data = r#"
+        [metadata]
+        name = "git-status"
+
+        [source]
+        command = "git status --short"
+
+        [preview]
+        command = [
+            { command = "ls {}", when = "/$", label = "dir" },
+            { command = "git diff {}", when = "^ ?M" },
+            "cat {}",
+        ]
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let command = prototype.preview.unwrap().command;
+
+        // first match wins, bare commands being unconditional fallbacks
+        assert_eq!(command.nth_for_entry(0, "?? src/"), Some(0));
+        assert_eq!(command.nth_for_entry(0, " M main.rs"), Some(1));
+        assert_eq!(command.nth_for_entry(0, "A  lib.rs"), Some(2));
+        // cycling only goes through matching commands
+        assert_eq!(command.nth_for_entry(1, "?? src/"), Some(2));
+        assert_eq!(command.nth_for_entry(2, " M main.rs"), Some(2));
+        assert_eq!(command.nth_for_entry(3, " M main.rs"), Some(1));
+
+        assert_eq!(
+            command.badge_for_entry(0, "?? src/").unwrap(),
+            "[1/2 dir]"
+        );
+        assert_eq!(command.badge_for_entry(2, "?? src/").unwrap(), "[2/2]");
+        assert_eq!(command.badge_for_entry(2, "A  lib.rs"), None);
+        assert_eq!(command.badge_for_entry(0, "A  lib.rs"), None);
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_invalid_condition() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        command = "fd -t f"
+
+        [preview]
+        command = [{ command = "bat {}", when = "(unclosed" }]
+        "#;
+
+        let err = from_str::<ChannelPrototype>(toml_data).unwrap_err();
+        assert!(err.to_string().contains("invalid `when` regex"), "{err}");
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_preview_delimiters() {
+        let toml_data = r#"
+        [metadata]
+        name = "text"
+
+        [source]
+        command = "rg . --line-number"
+        delimiter = ":"
+
+        [preview]
+        command = [
+            { command = "bat -H {1} {0}", label = "file" },
+            { command = "echo {0}", label = "line", delimiter = "\t" },
+            { command = "echo {1}", delimiter = 're:\s+' },
+            "echo {}",
+        ]
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let command = prototype.preview.unwrap().command;
+        assert_eq!(command.delimiter_nth(0), None);
+        assert_eq!(
+            command.delimiter_nth(1),
+            Some(&Delimiter::Fixed("\t".to_string()))
+        );
+        assert!(matches!(
+            command.delimiter_nth(2),
+            Some(Delimiter::Regex(_))
+        ));
+
+        // the others split entries the channel's way
+        let delimiter = prototype.source.delimiter.unwrap();
+        let command = command.with_default_delimiter(&delimiter);
+        assert_eq!(command.delimiter_nth(0), Some(&delimiter));
+        assert_eq!(
+            command.delimiter_nth(1),
+            Some(&Delimiter::Fixed("\t".to_string()))
+        );
+        assert_eq!(command.delimiter_nth(3), Some(&delimiter));
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_preview_stdin() {
+        let toml_data = r#"
+        [metadata]
+        name = "json"
+
+        [source]
+        command = "ls"
+
+        [preview]
+        command = [
+            { command = "jq -C .", when = "json$" },
+            { command = "cat", stdin = 1 },
+            { command = "echo {}", stdin = false },
+        ]
+        stdin = true
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let command = prototype.preview.unwrap().command;
+        assert_eq!(command.stdin_nth(0), Some(StdinInput::Entry));
+        assert_eq!(command.stdin_nth(1), Some(StdinInput::Field(1)));
+        assert_eq!(command.stdin_nth(2), None);
+        assert_eq!(command.stdin_nth(3), Some(StdinInput::Entry));
+
+        let delimiter = Delimiter::Fixed(":".to_string());
+        assert_eq!(StdinInput::Entry.for_entry("a:b", &delimiter), "a:b");
+        assert_eq!(StdinInput::Field(1).for_entry("a:b", &delimiter), "b");
+        assert_eq!(StdinInput::Field(2).for_entry("a:b", &delimiter), "");
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_preview_limits() {
+        let toml_data = r#"
+        [metadata]
+        name = "logs"
+
+        [source]
+        command = "ls"
+
+        [preview]
+        command = "cat {}"
+        max_bytes = 65536
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let preview = prototype.preview.unwrap();
+        assert_eq!(preview.max_bytes, 65536);
+        assert_eq!(
+            preview.max_line_length,
+            crate::previewer::limits::DEFAULT_MAX_LINE_LENGTH
+        );
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_invalid_preview_delimiter() {
+        let toml_data = r#"
+        [metadata]
+        name = "files"
+
+        [source]
+        command = "fd -t f"
+
+        [preview]
+        command = [{ command = "bat {}", delimiter = "re:(unclosed" }]
+        "#;
+
+        let err = from_str::<ChannelPrototype>(toml_data).unwrap_err();
+        assert!(err.to_string().contains("invalid `delimiter`"), "{err}");
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_delimiter_regex() {
+        let toml_data = r#"
+        [metadata]
+        name = "procs"
+
+        [source]
+        command = "ps aux"
+        delimiter_regex = '\s+'
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        let delimiter = prototype.source.delimiter.unwrap();
+        assert_eq!(delimiter, Delimiter::regex(r"\s+").unwrap());
+        assert_eq!(
+            delimiter.split("root     1  0.0 /sbin/init"),
+            vec!["root", "1", "0.0", "/sbin/init"]
+        );
+        assert_eq!(delimiter.field("  root     1", 1), Some("1"));
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_invalid_delimiter() {
+        let both = r#"
+        [metadata]
+        name = "procs"
+
+        [source]
+        command = "ps aux"
+        delimiter = " "
+        delimiter_regex = '\s+'
+        "#;
+        let err = from_str::<ChannelPrototype>(both).unwrap_err();
+        assert!(err.to_string().contains("mutually exclusive"), "{err}");
+
+        let invalid = r#"
+        [metadata]
+        name = "procs"
+
+        [source]
+        command = "ps aux"
+        delimiter_regex = "(unclosed"
+        "#;
+        let err = from_str::<ChannelPrototype>(invalid).unwrap_err();
+        assert!(
+            err.to_string().contains("invalid `delimiter_regex`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_record_separator() {
+        let toml_data = r#"
+        [metadata]
+        name = "git-log"
+
+        [source]
+        command = "git log --format='%H %s%n%n%b%x00'"
+        record_separator = "\\0\\n"
+        "#;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        assert_eq!(prototype.source.record_separator.as_deref(), Some("\0\n"));
+
+        let toml_data = r#"
+        [metadata]
+        name = "git-log"
+
+        [source]
+        command = "git log"
+        entry_delimiter = "\\0"
+        "#;
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+        assert_eq!(prototype.source.record_separator.as_deref(), Some("\0"));
+
+        let both = r#"
+        [metadata]
+        name = "git-log"
+
+        [source]
+        command = "git log"
+        entry_delimiter = "\\0"
+        record_separator = "\\0"
+        "#;
+        let err = from_str::<ChannelPrototype>(both).unwrap_err();
+        assert!(err.to_string().contains("mutually exclusive"), "{err}");
+    }
+
+    #[test]
+    fn test_delimiter_parse() {
+        assert_eq!(
+            Delimiter::parse(":").unwrap(),
+            Delimiter::Fixed(":".to_string())
+        );
+        let regex = Delimiter::parse(r"re:\s+").unwrap();
+        assert_eq!(regex, Delimiter::regex(r"\s+").unwrap());
+        assert_eq!(regex.to_string(), r"re:\s+");
+        assert!(Delimiter::parse("re:(").is_err());
+    }
+
     #[test]
     fn test_channel_prototype_deserialization_bare_minimum() {
         let toml_data = r#"
@@ -741,6 +1855,29 @@ mod tests {
         assert!(prototype.preview.is_none());
         assert!(prototype.ui.is_none());
         assert!(prototype.keybindings.is_none());
+        assert!(prototype.metadata.icon.is_none());
+    }
+
+    #[test]
+    fn test_channel_prototype_deserialization_icon() {
+        let toml_data = r##"
+        [metadata]
+        name = "git-branch"
+        icon = { glyph = "\ue725", color = "#f05032" }
+
+        [source]
+        command = "git branch"
+        "##;
+
+        let prototype: ChannelPrototype = from_str(toml_data).unwrap();
+
+        assert_eq!(
+            prototype.metadata.icon,
+            Some(ChannelIcon {
+                glyph: '\u{e725}',
+                color: Some("#f05032".to_string()),
+            })
+        );
     }
 
     #[test]
@@ -2,7 +2,7 @@ use crate::{
     cable::Cable,
     channels::{
         entry::into_ranges,
-        prototypes::{BinaryRequirement, ChannelPrototype},
+        prototypes::{BinaryRequirement, ChannelIcon, ChannelPrototype},
     },
     event::Key,
     matcher::{Matcher, config::Config},
@@ -19,6 +19,9 @@ pub struct CableEntry {
     pub shortcut: Option<Key>,
     pub description: Option<String>,
     pub requirements: Vec<BinaryRequirement>,
+    pub icon: Option<ChannelIcon>,
+    pub source_commands: Vec<String>,
+    pub preview_commands: Vec<String>,
 }
 
 impl CableEntry {
@@ -29,11 +32,20 @@ impl CableEntry {
             shortcut: shortcut.copied(),
             description: None,
             requirements: Vec::new(),
+            icon: None,
+            source_commands: Vec::new(),
+            preview_commands: Vec::new(),
         }
     }
 
     pub fn with_match_indices(mut self, indices: &[u32]) -> Self {
-        self.match_ranges = Some(into_ranges(indices));
+        // the matcher also sees the description, only keep the indices that
+        // fall within the displayed channel name
+        let name_len = u32::try_from(self.channel_name.chars().count())
+            .unwrap_or(u32::MAX);
+        let indices: Vec<u32> =
+            indices.iter().copied().filter(|i| *i < name_len).collect();
+        self.match_ranges = Some(into_ranges(&indices));
         self
     }
 
@@ -49,6 +61,43 @@ impl CableEntry {
         self.requirements = requirements;
         self
     }
+
+    pub fn with_icon(mut self, icon: Option<ChannelIcon>) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    pub fn with_commands(mut self, prototype: &ChannelPrototype) -> Self {
+        self.source_commands = prototype
+            .source
+            .command
+            .inner
+            .iter()
+            .map(|t| t.raw().to_string())
+            .collect();
+        self.preview_commands = prototype
+            .preview
+            .as_ref()
+            .map(|p| {
+                p.command
+                    .inner
+                    .iter()
+                    .map(|t| t.raw().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        self
+    }
+
+    /// The string the remote control matches against (name and description).
+    fn matcher_string(&self) -> String {
+        match &self.description {
+            Some(description) => {
+                format!("{} {}", self.channel_name, description)
+            }
+            None => self.channel_name.clone(),
+        }
+    }
 }
 
 impl ResultItem for CableEntry {
@@ -57,8 +106,17 @@ impl ResultItem for CableEntry {
     }
 
     fn icon(&self) -> Option<&devicons::FileIcon> {
-        // Remote control entries always share the same popcorn icon
-        Some(&CABLE_ICON)
+        // Remote control entries share the same popcorn icon unless the
+        // channel defines its own
+        if self.icon.is_some() {
+            None
+        } else {
+            Some(&CABLE_ICON)
+        }
+    }
+
+    fn channel_icon(&self) -> Option<&ChannelIcon> {
+        self.icon.as_ref()
     }
 
     fn display(&self) -> &str {
@@ -76,6 +134,10 @@ impl ResultItem for CableEntry {
     fn shortcut(&self) -> Option<&Key> {
         self.shortcut.as_ref()
     }
+
+    fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
 }
 
 pub struct RemoteControl {
@@ -105,6 +167,8 @@ impl RemoteControl {
             let cable_entry =
                 CableEntry::new(channel_name.to_string(), channel_shortcut)
                     .with_description(prototype.metadata.description.clone())
+                    .with_icon(prototype.metadata.icon.clone())
+                    .with_commands(prototype)
                     .with_requirements(
                         // check if the prototype has binary requirements
                         // and whether they are met
@@ -120,7 +184,7 @@ impl RemoteControl {
                             .collect(),
                     );
             let () = injector.push(cable_entry, |e, cols| {
-                cols[0] = e.channel_name.clone().into();
+                cols[0] = e.matcher_string().into();
             });
         }
         RemoteControl {
@@ -180,3 +244,29 @@ impl RemoteControl {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remote_control_matches_descriptions() {
+        let mut docker = ChannelPrototype::new("docker", "docker ps");
+        docker.metadata.description =
+            Some("List running containers".to_string());
+        let cable = Cable::from_prototypes(vec![
+            docker,
+            ChannelPrototype::new("files", "fd -t f"),
+        ]);
+        let mut rc = RemoteControl::new(cable, true);
+
+        rc.find("containers");
+        let results = rc.results(10, 0);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].channel_name, "docker");
+        assert_eq!(results[0].source_commands, vec!["docker ps"]);
+        // matches in the description are not highlighted in the name
+        assert_eq!(results[0].match_ranges.as_deref(), Some(&[][..]));
+    }
+}
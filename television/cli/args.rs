@@ -1,5 +1,7 @@
 use clap::{Parser, Subcommand, ValueEnum};
 
+use crate::logging::LogLevel;
+
 /// Television CLI arguments structure.
 ///
 /// When a channel is specified, its defaults are used and flags act as overrides.
@@ -23,7 +25,12 @@ pub struct Cli {
 
     /// The working directory to start the application in.
     ///
-    /// Defaults to the current directory.
+    /// Defaults to the current directory. When given a file, the application
+    /// starts in its directory with the input prefilled with its name.
+    ///
+    /// The channel may also be omitted: `tv <PATH>` starts the default
+    /// channel in a directory (or the files channel for a file) unless a
+    /// channel has the same name.
     #[arg(value_name = "PATH", index = 2, verbatim_doc_comment)]
     pub working_directory: Option<String>,
 
@@ -57,6 +64,29 @@ pub struct Cli {
     )]
     pub ansi: bool,
 
+    /// List the source's entries in reverse order, newest first.
+    ///
+    /// This only applies as long as no query is typed and the results aren't
+    /// sorted with `cycle_sort`: matches are still ranked by score, and
+    /// sorted results follow the sort order. Useful for history-like sources
+    /// that emit their oldest entries first.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub tac: bool,
+
+    /// Give the entries an icon of their own.
+    ///
+    /// `path` gives an icon to the entries naming an existing file or
+    /// directory (or whose first field does), `auto` to URLs and lines
+    /// starting with a git hash too. This overrides the channel's
+    /// `icon_mode`, e.g. for paths piped through stdin.
+    #[arg(long, value_enum, verbatim_doc_comment, help_heading = "Source")]
+    pub icon_mode: Option<IconMode>,
+
     /// Source display template to use for the current channel.
     ///
     /// When a channel is specified: This overrides the display template defined in the channel prototype.
@@ -99,6 +129,20 @@ pub struct Cli {
     )]
     pub source_entry_delimiter: Option<String>,
 
+    /// The delimiter splitting entries into fields for positional
+    /// placeholders like `{0}`, sorting and grouping.
+    ///
+    /// Prefix it with `re:` to split on a regex instead, in which case empty
+    /// fields are dropped (e.g. `--delimiter 're:\s+'` for `ps aux`).
+    /// This overrides the channel's `delimiter` and `delimiter_regex`.
+    #[arg(
+        long,
+        value_name = "STRING",
+        verbatim_doc_comment,
+        help_heading = "Source"
+    )]
+    pub delimiter: Option<String>,
+
     /// Preview command to use for the current channel.
     ///
     /// When a channel is specified: This overrides the preview command defined in the channel prototype.
@@ -250,10 +294,11 @@ pub struct Cli {
     /// This flag works identically in both channel mode and ad-hoc mode.
     ///
     /// This can be used to provide a default value for the prompt upon
-    /// startup.
+    /// startup, the cursor is placed at the end of it.
     #[arg(
         short,
         long,
+        visible_alias = "query",
         value_name = "STRING",
         verbatim_doc_comment,
         help_heading = "Input"
@@ -265,9 +310,9 @@ pub struct Cli {
     /// When a channel is specified: Overrides the input header defined in the channel prototype.
     /// When no channel is specified: Sets the input header for the ad-hoc channel.
     ///
-    /// The given value is parsed as a `MultiTemplate`. It is evaluated against
-    /// the current channel name and the resulting text is shown as the input
-    /// field title. Defaults to the current channel name when omitted.
+    /// The header can refer to `{channel}`, `{cwd}`, `{version}` and `{mode}`,
+    /// and is shortened in the middle when it doesn't fit. An empty header
+    /// hides the title. Defaults to `{channel}` when omitted.
     #[arg(
         long,
         value_name = "STRING",
@@ -493,7 +538,7 @@ pub struct Cli {
     /// When set to a positive number, the application will automatically
     /// reload the source command at the specified interval. This is useful
     /// for monitoring changing data sources. Set to 0 to disable (default).
-    #[arg(long, value_name = "FLOAT", verbatim_doc_comment, value_parser = validate_non_negative_float, conflicts_with_all = ["select_1", "take_1", "take_1_fast"], help_heading = "Behavior")]
+    #[arg(long, value_name = "FLOAT", verbatim_doc_comment, value_parser = validate_non_negative_float, conflicts_with_all = ["select_1", "select_first", "take_1", "take_1_fast"], help_heading = "Behavior")]
     pub watch: Option<f64>,
 
     /// Try to guess the channel from the provided input prompt.
@@ -512,6 +557,37 @@ pub struct Cli {
     )]
     pub autocomplete_prompt: Option<String>,
 
+    /// What to print when used with `--autocomplete-prompt`.
+    ///
+    /// `selection` prints the selected entries only, `full` prints the
+    /// prompt with the selected entries substituted for its `{}`
+    /// placeholder (or appended to it when there is none) so that the
+    /// whole command line can be replaced at once.
+    #[arg(
+        long,
+        value_enum,
+        default_value = "selection",
+        requires = "autocomplete_prompt",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub autocomplete_output: AutocompleteOutput,
+
+    /// The order selected entries are output in.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// `selection` outputs them in the order they were selected in,
+    /// `list` in the order they appear in the results when confirming.
+    #[arg(
+        long,
+        value_enum,
+        default_value = "selection",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub output_order: OutputOrder,
+
     /// Use substring matching instead of fuzzy matching.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -527,6 +603,46 @@ pub struct Cli {
     )]
     pub exact: bool,
 
+    /// Start with the cursor on the first entry equal to the given string
+    /// once the channel has finished loading.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Use `--select-mode contains` to look for the first entry containing
+    /// the string instead. The cursor stays on the first entry when nothing
+    /// matches.
+    #[arg(
+        long,
+        value_name = "STRING",
+        conflicts_with = "select_last",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub select: Option<String>,
+
+    /// How `--select` compares the entries with the given string.
+    #[arg(
+        long,
+        value_enum,
+        default_value = "exact",
+        requires = "select",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub select_mode: SelectMode,
+
+    /// Start with the cursor on the last entry once the channel has finished
+    /// loading.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub select_last: bool,
+
     /// Automatically select and output the first entry if there is only one
     /// entry.
     ///
@@ -547,6 +663,23 @@ pub struct Cli {
     )]
     pub select_1: bool,
 
+    /// Automatically select and output the best match for the initial input
+    /// once the channel has finished loading.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Unlike `take_1`, nothing is output when the input doesn't match
+    /// anything: tv stays open so that the query can be refined. This is
+    /// meant to be used along with `--input`/`--query`.
+    #[arg(
+        long,
+        default_value = "false",
+        group = "selection_mode",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub select_first: bool,
+
     /// Take the first entry from the list after the channel has finished loading.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -579,6 +712,45 @@ pub struct Cli {
     )]
     pub take_1_fast: bool,
 
+    /// Keep the preview command and source entry delimiter overrides when
+    /// switching channels.
+    ///
+    /// By default, `--preview-command`, `--preview-offset` and
+    /// `--source-entry-delimiter` only apply to the channel tv starts with.
+    /// With this flag, they also override the configuration of any channel
+    /// selected later on through the remote control.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub override_sticky: bool,
+
+    /// Start on the channel, query and layout the last session was left
+    /// with.
+    ///
+    /// The session is saved on exit to `session.json` in the data directory
+    /// and only restored when no channel, input or source command is given.
+    /// Also available as `resume = true` in the configuration file.
+    #[arg(
+        long,
+        default_value = "false",
+        verbatim_doc_comment,
+        help_heading = "Behavior"
+    )]
+    pub resume: bool,
+
+    /// Stop loading entries from the source past this many.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Useful to bound memory usage when piping huge inputs into tv. When
+    /// the cap is hit, the result count is displayed with a trailing `+`.
+    /// Overrides `matcher.max_entries` from the configuration file.
+    #[arg(long, value_name = "INT", verbatim_doc_comment, value_parser = validate_positive_int, help_heading = "Behavior")]
+    pub max_entries: Option<u64>,
+
     /// Keybindings to override the default keybindings.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -613,6 +785,23 @@ pub struct Cli {
     )]
     pub expect: Option<String>,
 
+    /// Keys that quit without selecting anything, whatever they are bound to and whatever is
+    /// shown (e.g. a confirmation or the help overlay).
+    ///
+    /// tv exits with status 130 when quit this way or with the usual quit keys, 1 when there
+    /// was nothing to select (e.g. `--take-1` on an empty channel) and 0 otherwise.
+    ///
+    /// Example: `tv --abort-keys='ctrl-c;ctrl-g'`
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    #[arg(
+        long,
+        value_name = "STRING",
+        verbatim_doc_comment,
+        help_heading = "Keybindings"
+    )]
+    pub abort_keys: Option<String>,
+
     /// Provide a custom configuration file to use.
     ///
     /// This flag works identically in both channel mode and ad-hoc mode.
@@ -625,6 +814,52 @@ pub struct Cli {
     #[arg(long, value_name = "PATH", verbatim_doc_comment, value_parser = validate_directory_path, help_heading = "Configuration")]
     pub cable_dir: Option<String>,
 
+    /// The most verbose level written to the log file.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Overrides `log_level` from the configuration file. When neither is
+    /// set, the `RUST_LOG` environment variable is used.
+    #[arg(
+        long,
+        value_name = "LEVEL",
+        value_enum,
+        verbatim_doc_comment,
+        help_heading = "Configuration"
+    )]
+    pub log_level: Option<LogLevel>,
+
+    /// Write logs to this file.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// Overrides `log_file` from the configuration file. Defaults to
+    /// `television.log` in the data directory, rotated daily.
+    #[arg(
+        long,
+        value_name = "PATH",
+        verbatim_doc_comment,
+        help_heading = "Configuration"
+    )]
+    pub log_file: Option<String>,
+
+    /// Keep this file up to date with the selected entry, the number of
+    /// matched and total entries and the query, as a line of JSON.
+    ///
+    /// This flag works identically in both channel mode and ad-hoc mode.
+    ///
+    /// e.g. `{"selected": "Cargo.toml", "matched": 12, "total": 345, "query": "car"}`
+    ///
+    /// A FIFO is written to only while something reads it. Any other file is
+    /// replaced atomically on every change, and removed on exit.
+    #[arg(
+        long,
+        value_name = "PATH",
+        verbatim_doc_comment,
+        help_heading = "Configuration"
+    )]
+    pub info_file: Option<String>,
+
     /// Use global history instead of channel-specific history.
     ///
     /// This flag only works in channel mode.
@@ -649,6 +884,16 @@ pub enum Command {
         #[arg(value_enum)]
         shell: Shell,
     },
+    /// Prints diagnostics worth including in bug reports (version,
+    /// directories, loaded files, terminal capabilities...)
+    Info {
+        /// Print the diagnostics as JSON
+        #[arg(long, default_value = "false")]
+        json: bool,
+    },
+    /// Echoes each key press along with the string to use to bind it in
+    /// the configuration file (exit with ctrl-c)
+    Keys,
     /// Downloads the latest collection of channel prototypes from github
     /// and saves them to the local configuration directory.
     UpdateChannels {
@@ -668,6 +913,34 @@ pub enum Shell {
     Nu,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Default, ValueEnum)]
+pub enum AutocompleteOutput {
+    #[default]
+    Selection,
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputOrder {
+    #[default]
+    Selection,
+    List,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum SelectMode {
+    #[default]
+    Exact,
+    Contains,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum IconMode {
+    None,
+    Path,
+    Auto,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
 pub enum LayoutOrientation {
     Landscape,
@@ -1,14 +1,18 @@
 use crate::{
     action::{Action, Actions},
     cable::Cable,
-    channels::prototypes::{ChannelPrototype, Template},
-    cli::args::{Cli, Command},
+    channels::{
+        icons::IconMode,
+        prototypes::{ChannelPrototype, Delimiter, Template},
+    },
+    cli::args::{AutocompleteOutput, Cli, Command, OutputOrder, SelectMode},
     config::{
         Keybindings, get_config_dir, get_data_dir, merge_keybindings,
         ui::{BorderType, Padding},
     },
     errors::cli_parsing_error_exit,
     event::Key,
+    logging::LogLevel,
     screen::layout::{InputPosition, Orientation},
     utils::paths::expand_tilde,
 };
@@ -68,8 +72,12 @@ pub struct ChannelCli {
     pub source_display: Option<Template>,
     pub source_output: Option<Template>,
     pub source_entry_delimiter: Option<char>,
+    pub delimiter: Option<Delimiter>,
     pub autocomplete_prompt: Option<String>,
+    pub autocomplete_output: AutocompleteOutput,
     pub ansi: bool,
+    pub tac: bool,
+    pub icon_mode: Option<IconMode>,
 
     // Preview configuration
     pub preview_command: Option<Template>,
@@ -115,7 +123,11 @@ pub struct ChannelCli {
 
     // Behavior and matching configuration
     pub exact: bool,
+    pub select: Option<String>,
+    pub select_mode: SelectMode,
+    pub select_last: bool,
     pub select_1: bool,
+    pub select_first: bool,
     pub take_1: bool,
     pub take_1_fast: bool,
     pub keybindings: Option<Keybindings>,
@@ -140,6 +152,14 @@ pub struct GlobalCli {
     pub no_preview: bool,
     pub no_help_panel: bool,
     pub no_status_bar: bool,
+    pub override_sticky: bool,
+    pub resume: bool,
+    pub max_entries: Option<u64>,
+    pub log_level: Option<LogLevel>,
+    pub log_file: Option<PathBuf>,
+    pub info_file: Option<PathBuf>,
+    pub output_order: OutputOrder,
+    pub abort_keys: Vec<Key>,
 }
 
 /// Post-processes the raw CLI arguments into a structured format with validation.
@@ -179,6 +199,15 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
         }
     }
 
+    let abort_keys = cli
+        .abort_keys
+        .as_deref()
+        .map(|keys| {
+            parse_abort_keys(keys)
+                .unwrap_or_else(|e| cli_parsing_error_exit(&e.to_string()))
+        })
+        .unwrap_or_default();
+
     // Parse preview overrides if provided
     let preview_command = cli.preview_command.as_ref().map(|preview_cmd| {
         Template::parse(preview_cmd).unwrap_or_else(|e| {
@@ -235,17 +264,10 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
         );
     }
 
-    // Determine channel and working_directory
-    let (channel, working_directory) = match &cli.channel {
-        Some(c) if Path::new(c).exists() => {
-            // If the channel is a path, use it as the working directory
-            (None, Some(PathBuf::from(c)))
-        }
-        _ => (
-            cli.channel.clone(),
-            cli.working_directory.as_ref().map(PathBuf::from),
-        ),
-    };
+    // Paths given in place of a channel are only resolved once the cable
+    // channels are known (see `main::resolve_path_arguments`)
+    let channel = cli.channel.clone();
+    let working_directory = cli.working_directory.as_ref().map(PathBuf::from);
 
     // Parse source overrides if any source fields are provided
     let source_command = cli.source_command.as_ref().map(|source_cmd| {
@@ -279,6 +301,12 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
                 .unwrap_or_else(|e| cli_parsing_error_exit(&e.to_string()))
         });
 
+    let delimiter = cli.delimiter.as_ref().map(|delimiter| {
+        Delimiter::parse(delimiter).unwrap_or_else(|e| {
+            cli_parsing_error_exit(&format!("Error parsing delimiter: {e}"))
+        })
+    });
+
     // Determine layout
     let layout: Option<Orientation> = cli.layout.map(Orientation::from);
     let input_position = cli.input_position.map(InputPosition::from);
@@ -310,10 +338,14 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
             source_display,
             source_output,
             source_entry_delimiter,
+            delimiter,
 
             // Autocomplete and ANSI configuration
             autocomplete_prompt: cli.autocomplete_prompt,
+            autocomplete_output: cli.autocomplete_output,
             ansi: cli.ansi,
+            tac: cli.tac,
+            icon_mode: cli.icon_mode.map(IconMode::from),
 
             // Preview configuration
             preview_command,
@@ -359,7 +391,11 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
 
             // Behavior and matching configuration
             exact: cli.exact,
+            select: cli.select,
+            select_mode: cli.select_mode,
+            select_last: cli.select_last,
             select_1: cli.select_1,
+            select_first: cli.select_first,
             take_1: cli.take_1,
             take_1_fast: cli.take_1_fast,
             keybindings,
@@ -390,6 +426,22 @@ pub fn post_process(cli: Cli, readable_stdin: bool) -> PostProcessedCli {
             no_preview: cli.no_preview,
             no_help_panel: cli.no_help_panel,
             no_status_bar: cli.no_status_bar,
+
+            // Channel switching
+            override_sticky: cli.override_sticky,
+            resume: cli.resume,
+
+            // Source loading
+            max_entries: cli.max_entries,
+
+            // Logging
+            log_level: cli.log_level,
+            log_file: cli.log_file.map(|p| expand_tilde(&p)),
+            info_file: cli.info_file.map(|p| expand_tilde(&p)),
+
+            // Output
+            output_order: cli.output_order,
+            abort_keys,
         },
     }
 }
@@ -609,6 +661,22 @@ fn parse_expect_bindings(expect: &str) -> Result<Keybindings> {
     Ok(bindings)
 }
 
+/// Parses the `abort` keys from the CLI, in the same format as the `expect` keys:
+/// ```ignore
+/// "ctrl-c;ctrl-g"
+/// ```
+fn parse_abort_keys(keys: &str) -> Result<Vec<Key>> {
+    keys.split(CLI_KEYBINDINGS_DELIMITER)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            Key::from_str(s).map_err(|e| {
+                anyhow!("Invalid key in abort keys: '{}'. Error: {}", s, e)
+            })
+        })
+        .collect()
+}
+
 const VERSION_MESSAGE: &str = env!("CARGO_PKG_VERSION");
 
 pub fn version() -> String {
@@ -680,11 +748,111 @@ mod tests {
 
         let post_processed_cli = post_process(cli, false);
 
+        // paths are resolved against the cable channels later on
+        assert_eq!(post_processed_cli.channel.channel, Some(".".to_string()));
+        assert_eq!(post_processed_cli.global.workdir, None);
+        assert_eq!(post_processed_cli.global.command, None);
+    }
+
+    #[test]
+    fn test_from_cli_log_flags() {
+        let cli = Cli {
+            log_level: Some(LogLevel::Debug),
+            log_file: Some("/tmp/tv/debug.log".to_string()),
+            ..Default::default()
+        };
+
+        let post_processed_cli = post_process(cli, false);
+
+        assert_eq!(post_processed_cli.global.log_level, Some(LogLevel::Debug));
         assert_eq!(
-            post_processed_cli.global.workdir,
-            Some(PathBuf::from("."))
+            post_processed_cli.global.log_file,
+            Some(PathBuf::from("/tmp/tv/debug.log"))
+        );
+    }
+
+    #[test]
+    fn test_query_alias_and_select_first() {
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["tv", "--query", "foo", "--select-first"]);
+        let post_processed_cli = post_process(cli, false);
+
+        assert_eq!(post_processed_cli.channel.input, Some("foo".to_string()));
+        assert!(post_processed_cli.channel.select_first);
+        assert!(!post_processed_cli.channel.select_1);
+    }
+
+    #[test]
+    fn test_output_order() {
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["tv"]);
+        assert_eq!(
+            post_process(cli, false).global.output_order,
+            OutputOrder::Selection
+        );
+        let cli = Cli::parse_from(["tv", "--output-order", "list"]);
+        assert_eq!(
+            post_process(cli, false).global.output_order,
+            OutputOrder::List
+        );
+    }
+
+    #[test]
+    fn test_select() {
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["tv", "--select", "foo"]);
+        let post_processed_cli = post_process(cli, false);
+        assert_eq!(post_processed_cli.channel.select, Some("foo".to_string()));
+        assert_eq!(post_processed_cli.channel.select_mode, SelectMode::Exact);
+
+        let cli = Cli::parse_from([
+            "tv",
+            "--select",
+            "foo",
+            "--select-mode",
+            "contains",
+        ]);
+        assert_eq!(
+            post_process(cli, false).channel.select_mode,
+            SelectMode::Contains
+        );
+
+        assert!(
+            Cli::try_parse_from(["tv", "--select", "foo", "--select-last"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_autocomplete_output() {
+        use clap::Parser;
+
+        let cli = Cli::parse_from(["tv", "--autocomplete-prompt", "git "]);
+        assert_eq!(
+            post_process(cli, false).channel.autocomplete_output,
+            AutocompleteOutput::Selection
+        );
+
+        let cli = Cli::parse_from([
+            "tv",
+            "--autocomplete-prompt",
+            "git ",
+            "--autocomplete-output",
+            "full",
+        ]);
+        assert_eq!(
+            post_process(cli, false).channel.autocomplete_output,
+            AutocompleteOutput::Full
+        );
+
+        // only makes sense along with a prompt
+        assert!(
+            Cli::try_parse_from(["tv", "--autocomplete-output", "full"])
+                .is_err()
         );
-        assert_eq!(post_processed_cli.global.command, None);
     }
 
     #[test]
@@ -810,4 +978,13 @@ mod tests {
 
         assert_eq!(bindings, expected);
     }
+
+    #[test]
+    fn test_parse_abort_keys() {
+        assert_eq!(
+            parse_abort_keys("ctrl-c; ctrl-g;").unwrap(),
+            vec![Key::Ctrl('c'), Key::Ctrl('g')]
+        );
+        assert!(parse_abort_keys("ctrl-c;not-a-key").is_err());
+    }
 }
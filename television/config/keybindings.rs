@@ -4,16 +4,74 @@ use crate::{
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use rustc_hash::FxHashMap;
-use serde::Deserialize;
+use serde::{
+    Deserialize,
+    de::{self, MapAccess, Visitor},
+};
+use std::fmt;
 use std::ops::Deref;
 use std::ops::DerefMut;
 use std::str::FromStr;
 use tracing::debug;
 
 /// A hashmap of keyboard key bindings to actions.
-#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Keybindings(pub FxHashMap<Key, Actions>);
 
+/// The table binding keys to shell commands run in the background, whose
+/// output is shown in the preview panel.
+pub const EXECUTE_TABLE: &str = "execute";
+/// The table binding keys to shell commands run in the background, whose
+/// output is discarded.
+pub const EXECUTE_SILENT_TABLE: &str = "execute_silent";
+
+/// Keybindings are written as `key = action(s)`, along with the
+/// `[keybindings.execute]` and `[keybindings.execute_silent]` tables
+/// written as `key = "command template"`.
+impl<'de> Deserialize<'de> for Keybindings {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct KeybindingsVisitor;
+
+        impl<'de> Visitor<'de> for KeybindingsVisitor {
+            type Value = Keybindings;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a table of keybindings")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Keybindings, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut bindings = FxHashMap::default();
+                while let Some(name) = map.next_key::<String>()? {
+                    let execute: fn(String) -> Action = match name.as_str() {
+                        EXECUTE_TABLE => Action::Execute,
+                        EXECUTE_SILENT_TABLE => Action::ExecuteSilent,
+                        _ => {
+                            let key = Key::from_str(&name)
+                                .map_err(de::Error::custom)?;
+                            bindings.insert(key, map.next_value::<Actions>()?);
+                            continue;
+                        }
+                    };
+                    for (key, command) in
+                        map.next_value::<FxHashMap<Key, String>>()?
+                    {
+                        bindings.insert(key, execute(command).into());
+                    }
+                }
+                Ok(Keybindings(bindings))
+            }
+        }
+
+        deserializer.deserialize_map(KeybindingsVisitor)
+    }
+}
+
 impl Deref for Keybindings {
     type Target = FxHashMap<Key, Actions>;
 
@@ -694,6 +752,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_deserialize_execute_tables() {
+        let keybindings: Keybindings = toml::from_str(
+            r#"
+                ctrl-s = "reload_source"
+
+                [execute]
+                ctrl-y = "echo {} | pbcopy"
+
+                [execute_silent]
+                ctrl-t = "touch {}"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            keybindings,
+            Keybindings::from(vec![
+                (Key::Ctrl('s'), Action::ReloadSource),
+                (
+                    Key::Ctrl('y'),
+                    Action::Execute("echo {} | pbcopy".to_string())
+                ),
+                (
+                    Key::Ctrl('t'),
+                    Action::ExecuteSilent("touch {}".to_string())
+                ),
+            ])
+        );
+        assert!(
+            toml::from_str::<Keybindings>("[execute]\nnot-a-key = \"ls\"")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_nested_action_sequences_are_rejected() {
+        // sequences can't contain sequences, so they can't recurse
+        assert!(
+            toml::from_str::<Keybindings>(
+                r#"ctrl-g = ["delete_line", ["reload_source"]]"#
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_complex_configuration_with_all_features() {
         let keybindings: Keybindings = toml::from_str(
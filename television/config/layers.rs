@@ -1,18 +1,37 @@
 use crate::{
-    action::{Action, CUSTOM_ACTION_PREFIX},
-    channels::prototypes::{
-        ActionSpec, BinaryRequirement, ChannelPrototype, CommandSpec, Template,
+    action::{ACTION_NAMES, Action, CUSTOM_ACTION_PREFIX},
+    channels::{
+        builtin::{BuiltinSource, FileWalkOptions},
+        icons::IconMode,
+        prototypes::{
+            ActionSpec, BinaryRequirement, ChannelIcon, ChannelPrototype,
+            CommandSpec, Delimiter, SortField, Template,
+        },
+    },
+    cli::{
+        ChannelCli, GlobalCli, PostProcessedCli,
+        args::{OutputOrder, SelectMode},
     },
-    cli::{ChannelCli, GlobalCli, PostProcessedCli},
     config::{
         Config, Keybindings, merge_keybindings,
-        ui::{BorderType, Padding, ThemeOverrides},
+        ui::{
+            BorderType, ClipboardMode, EscBehavior, Padding,
+            PreviewTitlePosition, ThemeOverrides,
+        },
     },
+    event::Key,
     keymap::InputMap,
-    screen::layout::{InputPosition, Orientation},
+    previewer::limits::OutputLimits,
+    screen::{
+        colors::ColorMode,
+        glyphs::ascii_locale,
+        layout::{InputPosition, Orientation},
+    },
+    utils::strings::closest_match,
 };
 use rustc_hash::FxHashMap;
 use std::path::PathBuf;
+use tracing::debug;
 
 /// Represents the different layers of configuration that make up the final
 /// merged configuration used by the application.
@@ -44,11 +63,26 @@ impl ConfigLayers {
         }
     }
 
+    /// The channel prototype that is currently loaded.
+    pub fn channel(&self) -> &ChannelPrototype {
+        &self.channel
+    }
+
     /// Update the current channel prototype and reset channel CLI options.
+    ///
+    /// With `--override-sticky`, the preview command and delimiter
+    /// overrides are carried over to the new channel.
     pub fn update_channel(&mut self, channel: ChannelPrototype) {
         self.channel = channel;
         // Reset channel-specific CLI options to defaults
-        self.channel_cli = ChannelCli::default();
+        let previous = std::mem::take(&mut self.channel_cli);
+        if self.global_cli.override_sticky {
+            self.channel_cli.preview_command = previous.preview_command;
+            self.channel_cli.preview_offset = previous.preview_offset;
+            self.channel_cli.source_entry_delimiter =
+                previous.source_entry_delimiter;
+            self.channel_cli.delimiter = previous.delimiter;
+        }
     }
 
     /// Merges the different configuration layers into a single `MergedConfig`.
@@ -59,7 +93,11 @@ impl ConfigLayers {
         let autocomplete_prompt = self.channel_cli.autocomplete_prompt.clone();
         let input = self.channel_cli.input.clone();
         let exact_match = self.channel_cli.exact;
+        let select = self.channel_cli.select.clone();
+        let select_mode = self.channel_cli.select_mode;
+        let select_last = self.channel_cli.select_last;
         let select_1 = self.channel_cli.select_1;
+        let select_first = self.channel_cli.select_first;
         let take_1 = self.channel_cli.take_1;
         let take_1_fast = self.channel_cli.take_1_fast;
         let inline = self.global_cli.inline;
@@ -80,6 +118,11 @@ impl ConfigLayers {
         // channel only fields
         let channel_description = self.channel.metadata.description.clone();
         let channel_requirements = self.channel.metadata.requirements.clone();
+        let channel_icon = self.channel.metadata.icon.clone();
+        let channel_icon_mode = self
+            .channel_cli
+            .icon_mode
+            .unwrap_or(self.channel.metadata.icon_mode);
         let channel_actions = self.channel.actions.clone();
 
         // CLI > base config fields
@@ -111,12 +154,31 @@ impl ConfigLayers {
             } else {
                 self.channel.source.command.clone()
             };
-        let channel_source_entry_delimiter = self
+        let channel_source_record_separator = self
             .channel_cli
             .source_entry_delimiter
-            .or(self.channel.source.entry_delimiter);
+            .map(String::from)
+            .or_else(|| self.channel.source.record_separator.clone());
         let channel_source_ansi =
             self.channel_cli.ansi || self.channel.source.ansi;
+        let channel_source_reverse =
+            self.channel_cli.tac || self.channel.source.reverse;
+        let channel_source_file_walk = FileWalkOptions {
+            hidden: self.channel.source.hidden,
+            no_ignore: self.channel.source.no_ignore,
+        };
+        // the built-in grep source searches for the query
+        let channel_source_dynamic = self.channel.source.dynamic
+            || self.channel.source.command.inner.iter().any(|command| {
+                matches!(
+                    BuiltinSource::parse(
+                        command.raw(),
+                        channel_source_file_walk
+                    ),
+                    Some(BuiltinSource::Grep(_))
+                )
+            });
+        let channel_source_fuzzy_filter = self.channel.source.fuzzy_filter;
         let channel_source_display = self
             .channel_cli
             .source_display
@@ -129,12 +191,34 @@ impl ConfigLayers {
             .as_ref()
             .or(self.channel.source.output.as_ref())
             .cloned();
+        let channel_source_delimiter = self
+            .channel_cli
+            .delimiter
+            .as_ref()
+            .or(self.channel.source.delimiter.as_ref())
+            .cloned()
+            .unwrap_or_default();
+        let channel_source_copy = self.channel.source.copy.clone();
+        let channel_source_group_by = self.channel.source.group_by;
+        let channel_source_sort_fields =
+            self.channel.source.sort_fields.clone();
         let channel_preview_command = self
             .channel_cli
             .preview_command
             .as_ref()
             .map(|t| CommandSpec::from_template(t.clone()))
-            .or(self.channel.preview.as_ref().map(|p| p.command.clone()));
+            .or(self.channel.preview.as_ref().map(|p| p.command.clone()))
+            // records spanning several lines can't be read in the results,
+            // unlike the entries split on a legacy `entry_delimiter`
+            .or_else(|| {
+                (self.channel_cli.source_entry_delimiter.is_none()
+                    && self.channel.source.entry_delimiter.is_none()
+                    && channel_source_record_separator.is_some())
+                .then(CommandSpec::entry_preview)
+            })
+            .map(|command| {
+                command.with_default_delimiter(&channel_source_delimiter)
+            });
         let channel_preview_offset =
             self.channel_cli.preview_offset.clone().or(
                 if let Some(preview) = &self.channel.preview {
@@ -143,8 +227,27 @@ impl ConfigLayers {
                     None
                 },
             );
+        let channel_source_output_line =
+            channel_preview_offset.clone().filter(|_| {
+                self.channel.source.output_line_numbers != Some(false)
+            });
         let channel_preview_cached = self.channel_cli.cache_preview
             || self.channel.preview.as_ref().is_some_and(|p| p.cached);
+        // prefetched previews are only of use once they're cached
+        let channel_preview_prefetch = if channel_preview_cached
+            && self.channel.preview.as_ref().is_none_or(|p| p.prefetch)
+        {
+            self.base_config.ui.preview_prefetch
+        } else {
+            0
+        };
+        let channel_preview_limits = self.channel.preview.as_ref().map_or(
+            OutputLimits::default(),
+            |preview| OutputLimits {
+                max_bytes: preview.max_bytes,
+                max_line_length: preview.max_line_length,
+            },
+        );
 
         // Channel > base config fields
         let remote_show_channel_descriptions = self
@@ -243,13 +346,16 @@ impl ConfigLayers {
                 self.channel.ui.as_ref()?.input_bar.as_ref()?.prompt.clone()
             })
             .or_else(|| self.base_config.ui.input_bar.prompt.clone());
+        let ascii = self.base_config.ui.ascii.unwrap_or_else(ascii_locale);
+        let borderless = self.base_config.ui.borderless;
         let input_bar_border_type = self
             .channel_cli
             .input_border
             .or_else(|| {
                 Some(self.channel.ui.as_ref()?.input_bar.as_ref()?.border_type)
             })
-            .unwrap_or(self.base_config.ui.input_bar.border_type);
+            .unwrap_or(self.base_config.ui.input_bar.border_type)
+            .in_borderless_mode(borderless);
         let input_bar_padding = self
             .channel_cli
             .input_padding
@@ -257,6 +363,15 @@ impl ConfigLayers {
                 Some(self.channel.ui.as_ref()?.input_bar.as_ref()?.padding)
             })
             .unwrap_or(self.base_config.ui.input_bar.padding);
+        let input_bar_confirm_pasted_newline = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| ui.input_bar.as_ref())
+            .map_or(
+                self.base_config.ui.input_bar.confirm_pasted_newline,
+                |ib| ib.confirm_pasted_newline,
+            );
         let status_bar_disabled = self.global_cli.no_status_bar;
         let status_bar_hidden = if status_bar_disabled {
             true // --no-status-bar always wins
@@ -285,7 +400,8 @@ impl ConfigLayers {
                         .border_type,
                 )
             })
-            .unwrap_or(self.base_config.ui.results_panel.border_type);
+            .unwrap_or(self.base_config.ui.results_panel.border_type)
+            .in_borderless_mode(borderless);
         let results_panel_padding = self
             .channel_cli
             .results_padding
@@ -293,6 +409,20 @@ impl ConfigLayers {
                 Some(self.channel.ui.as_ref()?.results_panel.as_ref()?.padding)
             })
             .unwrap_or(self.base_config.ui.results_panel.padding);
+        let results_panel_title = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| ui.results_panel.as_ref()?.title.clone())
+            .or_else(|| self.base_config.ui.results_panel.title.clone());
+        let results_panel_show_indices = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| ui.results_panel.as_ref())
+            .map_or(self.base_config.ui.results_panel.show_indices, |rp| {
+                rp.show_indices
+            });
         let preview_panel_size = self
             .channel_cli
             .preview_size
@@ -331,6 +461,16 @@ impl ConfigLayers {
                     .clone()
             })
             .or_else(|| self.base_config.ui.preview_panel.footer.clone());
+        let preview_panel_directory_command = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| {
+                ui.preview_panel.as_ref()?.directory_command.clone()
+            })
+            .or_else(|| {
+                self.base_config.ui.preview_panel.directory_command.clone()
+            });
         let preview_panel_scrollbar = !self.channel_cli.hide_preview_scrollbar
             && self
                 .channel
@@ -340,6 +480,14 @@ impl ConfigLayers {
                 .map_or(self.base_config.ui.preview_panel.scrollbar, |pp| {
                     pp.scrollbar
                 });
+        let preview_panel_title_position = self
+            .channel
+            .ui
+            .as_ref()
+            .and_then(|ui| ui.preview_panel.as_ref())
+            .map_or(self.base_config.ui.preview_panel.title_position, |pp| {
+                pp.title_position
+            });
         let preview_panel_border_type = self
             .channel_cli
             .preview_border
@@ -353,7 +501,8 @@ impl ConfigLayers {
                         .border_type,
                 )
             })
-            .unwrap_or(self.base_config.ui.preview_panel.border_type);
+            .unwrap_or(self.base_config.ui.preview_panel.border_type)
+            .in_borderless_mode(borderless);
         let preview_panel_padding = self
             .channel_cli
             .preview_padding
@@ -404,6 +553,31 @@ impl ConfigLayers {
         let global_history = self.global_cli.global_history
             || self.channel.history.global_mode.unwrap_or_default()
             || self.base_config.application.global_history;
+        let matcher_debounce_ms = self.base_config.matcher.debounce_ms;
+        let max_entries = self
+            .global_cli
+            .max_entries
+            .or(self.base_config.matcher.max_entries);
+        let preview_debounce_ms = self.base_config.ui.preview_debounce_ms;
+        let clipboard = self.base_config.ui.clipboard;
+        let preview_copy_max_bytes =
+            self.base_config.ui.preview_copy_max_bytes;
+        let unfocused_frame_rate = self.base_config.ui.unfocused_frame_rate;
+        let preview_cache_size = self.base_config.ui.preview_cache_size;
+        let inline_preview = self.base_config.ui.inline_preview;
+        let hide_input_decorations =
+            self.base_config.ui.hide_input_decorations;
+        let esc_behavior = self.base_config.ui.esc_behavior;
+        let color_mode = self.base_config.ui.color_mode.unwrap_or_else(|| {
+            let detected = ColorMode::detect();
+            if detected != ColorMode::Truecolor {
+                debug!(
+                    "The terminal doesn't seem to support truecolor, \
+                     showing themes with {detected} colors instead"
+                );
+            }
+            detected
+        });
 
         // Do we have any channel-specific keybindings?
         let mut channel_keybindings = Keybindings::default();
@@ -425,9 +599,20 @@ impl ConfigLayers {
                                 .trim_start_matches(CUSTOM_ACTION_PREFIX),
                         )
                     {
+                        let name = custom_with_prefix
+                            .trim_start_matches(CUSTOM_ACTION_PREFIX);
+                        let suggestion = closest_match(
+                            name,
+                            channel_actions
+                                .keys()
+                                .map(String::as_str)
+                                .chain(ACTION_NAMES.iter().copied()),
+                        )
+                        .map(|s| format!(" Did you mean '{s}'?"))
+                        .unwrap_or_default();
                         eprintln!(
-                            "Action '{}' referenced in keybinding not found in actions section.",
-                            custom_with_prefix
+                            "Action '{}' referenced in keybinding not found in actions section.{}",
+                            custom_with_prefix, suggestion
                         );
                         std::process::exit(1);
                     }
@@ -445,6 +630,8 @@ impl ConfigLayers {
             data_dir,
             config_file,
             cable_dir,
+            info_file: self.global_cli.info_file.clone(),
+            output_order: self.global_cli.output_order,
             tick_rate,
             default_channel,
             history_size,
@@ -453,16 +640,34 @@ impl ConfigLayers {
             autocomplete_prompt,
             // matcher configuration
             exact_match,
+            matcher_debounce_ms,
+            max_entries,
+            select,
+            select_mode,
+            select_last,
             select_1,
+            select_first,
             take_1,
             take_1_fast,
             input,
 
             // Bindings
             input_map,
+            abort_keys: self.global_cli.abort_keys.clone(),
 
             // UI
             ui_scale,
+            preview_debounce_ms,
+            clipboard,
+            preview_copy_max_bytes,
+            unfocused_frame_rate,
+            preview_cache_size,
+            inline_preview,
+            borderless,
+            hide_input_decorations,
+            esc_behavior,
+            color_mode,
+            ascii,
             layout,
             theme,
             inline,
@@ -474,19 +679,24 @@ impl ConfigLayers {
             input_bar_prompt,
             input_bar_border_type,
             input_bar_padding,
+            input_bar_confirm_pasted_newline,
             // status bar
             status_bar_separator_open,
             status_bar_separator_close,
             status_bar_hidden,
             status_bar_disabled,
             // results panel
+            results_panel_title,
             results_panel_border_type,
             results_panel_padding,
+            results_panel_show_indices,
             // preview panel
             preview_panel_size,
             preview_panel_header,
             preview_panel_footer,
+            preview_panel_directory_command,
             preview_panel_scrollbar,
+            preview_panel_title_position,
             preview_panel_border_type,
             preview_panel_padding,
             preview_panel_hidden,
@@ -513,16 +723,29 @@ impl ConfigLayers {
             channel_name,
             channel_description,
             channel_requirements,
+            channel_icon,
+            channel_icon_mode,
             // source
             channel_source_command,
-            channel_source_entry_delimiter,
+            channel_source_record_separator,
             channel_source_ansi,
+            channel_source_reverse,
+            channel_source_file_walk,
+            channel_source_dynamic,
+            channel_source_fuzzy_filter,
             channel_source_display,
             channel_source_output,
+            channel_source_output_line,
+            channel_source_delimiter,
+            channel_source_copy,
+            channel_source_group_by,
+            channel_source_sort_fields,
             // preview
             channel_preview_command,
             channel_preview_offset,
             channel_preview_cached,
+            channel_preview_prefetch,
+            channel_preview_limits,
             // actions
             channel_actions,
         }
@@ -538,6 +761,10 @@ pub struct MergedConfig {
     pub data_dir: PathBuf,
     pub config_file: Option<PathBuf>,
     pub cable_dir: PathBuf,
+    /// Where to keep the selected entry and the result counts up to date.
+    pub info_file: Option<PathBuf>,
+    /// The order selected entries are output in.
+    pub output_order: OutputOrder,
     pub tick_rate: u64,
     pub default_channel: String,
     pub history_size: usize,
@@ -546,16 +773,40 @@ pub struct MergedConfig {
     pub autocomplete_prompt: Option<String>,
     // matcher configuration
     pub exact_match: bool,
+    pub matcher_debounce_ms: u64,
+    pub max_entries: Option<u64>,
+    /// The entry to put the cursor on once the channel has finished loading.
+    pub select: Option<String>,
+    pub select_mode: SelectMode,
+    /// Put the cursor on the last entry once the channel has finished
+    /// loading.
+    pub select_last: bool,
     pub select_1: bool,
+    pub select_first: bool,
     pub take_1: bool,
     pub take_1_fast: bool,
     pub input: Option<String>,
 
     // Bindings
     pub input_map: InputMap,
+    /// Keys that quit without selecting anything, whatever else is bound.
+    pub abort_keys: Vec<Key>,
 
     // UI
     pub ui_scale: u16,
+    pub preview_debounce_ms: u64,
+    pub clipboard: ClipboardMode,
+    pub preview_copy_max_bytes: usize,
+    pub unfocused_frame_rate: u16,
+    pub preview_cache_size: usize,
+    pub inline_preview: bool,
+    /// Panels have no borders, see `UiConfig::borderless`.
+    pub borderless: bool,
+    pub hide_input_decorations: bool,
+    pub esc_behavior: EscBehavior,
+    pub color_mode: ColorMode,
+    /// Whether to only draw ASCII characters.
+    pub ascii: bool,
     pub layout: Orientation,
     pub theme: String,
     pub inline: bool,
@@ -567,19 +818,24 @@ pub struct MergedConfig {
     pub input_bar_prompt: Option<String>,
     pub input_bar_border_type: BorderType,
     pub input_bar_padding: Padding,
+    pub input_bar_confirm_pasted_newline: bool,
     // status bar
     pub status_bar_separator_open: String,
     pub status_bar_separator_close: String,
     pub status_bar_hidden: bool,
     pub status_bar_disabled: bool,
     // results panel
+    pub results_panel_title: Option<String>,
     pub results_panel_border_type: BorderType,
     pub results_panel_padding: Padding,
+    pub results_panel_show_indices: bool,
     // preview panel
     pub preview_panel_size: u16,
     pub preview_panel_header: Option<Template>,
     pub preview_panel_footer: Option<Template>,
+    pub preview_panel_directory_command: Option<Template>,
     pub preview_panel_scrollbar: bool,
+    pub preview_panel_title_position: PreviewTitlePosition,
     pub preview_panel_border_type: BorderType,
     pub preview_panel_padding: Padding,
     pub preview_panel_hidden: bool,
@@ -605,15 +861,33 @@ pub struct MergedConfig {
     pub channel_name: String,
     pub channel_description: Option<String>,
     pub channel_requirements: Vec<BinaryRequirement>,
+    pub channel_icon: Option<ChannelIcon>,
+    pub channel_icon_mode: IconMode,
     // source
     pub channel_source_command: CommandSpec,
-    pub channel_source_entry_delimiter: Option<char>,
+    pub channel_source_record_separator: Option<String>,
     pub channel_source_ansi: bool,
+    pub channel_source_reverse: bool,
+    pub channel_source_file_walk: FileWalkOptions,
+    pub channel_source_dynamic: bool,
+    pub channel_source_fuzzy_filter: bool,
     pub channel_source_display: Option<Template>,
     pub channel_source_output: Option<Template>,
+    /// Extracts the line number appended to the output of the entries, see
+    /// `SourceSpec::output_line_numbers`.
+    pub channel_source_output_line: Option<Template>,
+    pub channel_source_delimiter: Delimiter,
+    pub channel_source_copy: Option<Template>,
+    pub channel_source_group_by: Option<usize>,
+    pub channel_source_sort_fields: Vec<SortField>,
     // preview
     pub channel_preview_command: Option<CommandSpec>,
     pub channel_preview_offset: Option<Template>,
     pub channel_preview_cached: bool,
+    /// How many entries ahead of the selection get their preview prepared
+    /// in advance (`0` when the channel doesn't want it).
+    pub channel_preview_prefetch: u32,
+    /// How much of the preview command's output is shown.
+    pub channel_preview_limits: OutputLimits,
     pub channel_actions: FxHashMap<String, ActionSpec>,
 }
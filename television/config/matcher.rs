@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_DEBOUNCE_MS: u64 = 40;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
+#[serde(default, deny_unknown_fields)]
+pub struct MatcherConfig {
+    /// How long to wait after the last keystroke before running the
+    /// matcher on the new pattern (in milliseconds).
+    ///
+    /// `0` disables debouncing.
+    pub debounce_ms: u64,
+    /// Stop loading entries from the source past this many.
+    ///
+    /// Unset by default, meaning the whole source is loaded.
+    pub max_entries: Option<u64>,
+}
+
+impl Default for MatcherConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: DEFAULT_DEBOUNCE_MS,
+            max_entries: None,
+        }
+    }
+}
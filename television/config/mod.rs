@@ -1,8 +1,12 @@
 use crate::{
-    cable::CABLE_DIR_NAME, channels::prototypes::DEFAULT_PROTOTYPE_NAME,
+    action::{ACTION_NAMES, Action, CUSTOM_ACTION_PREFIX},
+    cable::CABLE_DIR_NAME,
+    channels::prototypes::DEFAULT_PROTOTYPE_NAME,
     history::DEFAULT_HISTORY_SIZE,
+    logging::LogLevel,
+    utils::strings::{closest_match, slice_up_to_char_boundary},
 };
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, anyhow, bail};
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
 use shell_integration::ShellIntegrationConfig;
@@ -14,6 +18,7 @@ use std::{
 use tracing::{debug, warn};
 
 pub use keybindings::{Keybindings, merge_keybindings};
+pub use matcher::MatcherConfig;
 pub use themes::Theme;
 pub use ui::UiConfig;
 
@@ -21,6 +26,7 @@ mod themes;
 
 pub mod keybindings;
 pub mod layers;
+pub mod matcher;
 pub mod shell_integration;
 pub mod ui;
 
@@ -45,6 +51,19 @@ pub struct AppConfig {
     /// Whether to use global history (all channels) or channel-specific history (default)
     #[serde(default = "default_global_history")]
     pub global_history: bool,
+    /// The most verbose level written to the log file
+    #[serde(default)]
+    pub log_level: Option<LogLevel>,
+    /// Where to write logs (defaults to `television.log` in the data dir)
+    #[serde(default)]
+    pub log_file: Option<PathBuf>,
+    /// Whether to start where the last session left off
+    #[serde(default)]
+    pub resume: bool,
+    /// The shell commands are run through and the arguments preceding the
+    /// command, e.g. `["pwsh", "-NoProfile", "-Command"]`
+    #[serde(default)]
+    pub shell: Option<Vec<String>>,
 }
 
 impl Default for AppConfig {
@@ -56,6 +75,10 @@ impl Default for AppConfig {
             default_channel: default_channel(),
             history_size: default_history_size(),
             global_history: default_global_history(),
+            log_level: None,
+            log_file: None,
+            resume: false,
+            shell: None,
         }
     }
 }
@@ -97,11 +120,37 @@ pub struct Config {
     /// Shell integration configuration
     #[serde(default)]
     pub shell_integration: ShellIntegrationConfig,
+    /// Matcher configuration
+    #[serde(default)]
+    pub matcher: MatcherConfig,
 }
 
 const PROJECT_NAME: &str = "television";
 pub const CONFIG_FILE_NAME: &str = "config.toml";
 
+/// The keys allowed at the root of the configuration file: `AppConfig`'s
+/// fields along with the sections.
+const CONFIG_KEYS: &[&str] = &[
+    "data_dir",
+    "cable_dir",
+    "tick_rate",
+    "default_channel",
+    "history_size",
+    "global_history",
+    "log_level",
+    "log_file",
+    "resume",
+    "shell",
+    "keybindings",
+    "ui",
+    "shell_integration",
+    "matcher",
+];
+
+/// Sections from older versions of the configuration file, which are
+/// ignored rather than rejected.
+const LEGACY_CONFIG_KEYS: &[&str] = &["previewers"];
+
 pub struct ConfigEnv {
     _data_dir: PathBuf,
     config_dir: PathBuf,
@@ -194,11 +243,24 @@ impl Config {
 
     fn load_user_config(config_file: &Path) -> Result<Self> {
         let contents = std::fs::read_to_string(config_file)?;
-        let user_cfg: Config = toml::from_str(&contents).context(format!(
-            "Error parsing configuration file: {}\n{}",
-            config_file.display(),
-            USER_CONFIG_ERROR_MSG,
-        ))?;
+        let user_cfg: Config = toml::from_str(&contents)
+            .map_err(|e| {
+                anyhow!(describe_toml_error(config_file, &contents, &e))
+            })
+            .and_then(|config: Config| {
+                check_config_keys(config_file, &contents)?;
+                check_action_names(
+                    config_file,
+                    &contents,
+                    &config.keybindings,
+                )?;
+                Ok(config)
+            })
+            .map_err(|e| {
+                anyhow!(
+                    "Error parsing configuration file: {e}\n{USER_CONFIG_ERROR_MSG}"
+                )
+            })?;
         Ok(user_cfg)
     }
 
@@ -233,6 +295,7 @@ impl Config {
             keybindings: new.keybindings,
             ui: new.ui,
             shell_integration: new.shell_integration,
+            matcher: new.matcher,
         }
     }
 
@@ -241,6 +304,142 @@ impl Config {
     }
 }
 
+/// Describes an error found while parsing the toml file at `path`: where it
+/// is, and which key or value was meant in case of a typo.
+pub fn describe_toml_error(
+    path: &Path,
+    content: &str,
+    error: &toml::de::Error,
+) -> String {
+    let message = error.message().trim();
+    let unknown = unknown_name(message);
+    // errors from flattened structs don't carry a span
+    let position = error.span().map(|span| span.start).or_else(|| {
+        unknown
+            .as_ref()
+            .and_then(|(name, _)| find_key(content, name))
+    });
+    let suggestion =
+        unknown.and_then(|(name, candidates)| closest_match(name, candidates));
+    describe_error_at(path, content, position, message, suggestion)
+}
+
+fn describe_error_at(
+    path: &Path,
+    content: &str,
+    position: Option<usize>,
+    message: &str,
+    suggestion: Option<&str>,
+) -> String {
+    let mut description = if let Some(offset) = position {
+        let before = slice_up_to_char_boundary(content, offset);
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        format!("{}:{line}:{column}: {message}", path.display())
+    } else {
+        format!("{}: {message}", path.display())
+    };
+    if let Some(suggestion) = suggestion {
+        description.push_str(&format!("\nhelp: did you mean `{suggestion}`?"));
+    }
+    description
+}
+
+/// The unknown field or variant serde complained about along with the
+/// expected ones, if that's what `message` is about.
+fn unknown_name(message: &str) -> Option<(&str, Vec<&str>)> {
+    let rest = ["unknown field `", "unknown variant `"].iter().find_map(
+        |prefix| message.find(prefix).map(|i| &message[i + prefix.len()..]),
+    )?;
+    let (name, rest) = rest.split_once('`')?;
+    let (_, expected) = rest.split_once("expected")?;
+    Some((name, expected.split('`').skip(1).step_by(2).collect()))
+}
+
+/// The offset of the first line defining `key`, either as a table header
+/// or as a bare key.
+fn find_key(content: &str, key: &str) -> Option<usize> {
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let name = line.trim_start().trim_start_matches('[');
+        if name
+            .strip_prefix(key)
+            .is_some_and(|rest| rest.trim_start().starts_with(['=', '.', ']']))
+        {
+            return Some(offset + line.len() - name.len());
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// Serde can't reject unknown keys at the root of the configuration because
+/// of the flattened `AppConfig`, so they're checked separately.
+fn check_config_keys(path: &Path, content: &str) -> Result<()> {
+    let table: toml::Table = toml::from_str(content)?;
+    for key in table
+        .keys()
+        .filter(|k| LEGACY_CONFIG_KEYS.contains(&k.as_str()))
+    {
+        warn!(
+            "{}: `{key}` is no longer supported and will be ignored",
+            path.display()
+        );
+    }
+    if let Some(key) = table.keys().find(|k| {
+        !CONFIG_KEYS.contains(&k.as_str())
+            && !LEGACY_CONFIG_KEYS.contains(&k.as_str())
+    }) {
+        let expected = CONFIG_KEYS
+            .iter()
+            .map(|k| format!("`{k}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        bail!(describe_error_at(
+            path,
+            content,
+            find_key(content, key),
+            &format!("unknown field `{key}`, expected one of {expected}"),
+            closest_match(key, CONFIG_KEYS.iter().copied()),
+        ));
+    }
+    Ok(())
+}
+
+/// Unknown action names are parsed as custom actions, which are looked up in
+/// the channel's actions when triggered: report the ones that are most
+/// likely typos of built-in actions instead.
+fn check_action_names(
+    path: &Path,
+    content: &str,
+    keybindings: &Keybindings,
+) -> Result<()> {
+    for actions in keybindings.values() {
+        for action in actions.as_slice() {
+            if let Action::ExternalAction(name) = action
+                && !name.starts_with(CUSTOM_ACTION_PREFIX)
+                && let Some(suggestion) =
+                    closest_match(name, ACTION_NAMES.iter().copied())
+            {
+                bail!(describe_error_at(
+                    path,
+                    content,
+                    content.find(&format!("\"{name}\"")),
+                    &format!("unknown action `{name}`"),
+                    Some(suggestion),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn get_data_dir() -> PathBuf {
     // if `TELEVISION_DATA` is set, use that as the data directory
     let data_folder =
@@ -435,6 +634,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_new_merges_single_keybinding() {
+        let dir = tempdir().unwrap();
+        let config_dir = dir.path();
+        std::fs::write(
+            config_dir.join(CONFIG_FILE_NAME),
+            "[keybindings]\nctrl-q = \"quit\"\nesc = \"no_op\"\n",
+        )
+        .unwrap();
+        let config_env = ConfigEnv {
+            _data_dir: get_data_dir(),
+            config_dir: config_dir.to_path_buf(),
+        };
+
+        let config = Config::new(&config_env, None).unwrap();
+        let default_config = default_config_from_file().unwrap();
+
+        assert_eq!(config.keybindings[&Key::Ctrl('q')], Action::Quit.into());
+        assert_eq!(config.keybindings[&Key::Esc], Action::NoOp.into());
+        for (key, actions) in default_config.keybindings.iter() {
+            if ![Key::Ctrl('q'), Key::Esc].contains(key) {
+                assert_eq!(
+                    config.keybindings.get(key),
+                    Some(actions),
+                    "{key}"
+                );
+            }
+        }
+        assert_eq!(config.ui, default_config.ui);
+    }
+
+    fn load_error(content: &str) -> String {
+        let dir = tempdir().unwrap();
+        let config_file = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(&config_file, content).unwrap();
+        Config::load_user_config(&config_file)
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn test_load_user_config_suggests_fields() {
+        let error = load_error("[ui.help_panel]\nhiden = true\n");
+        assert!(error.contains("config.toml:2:1: unknown field `hiden`"));
+        assert!(error.contains("did you mean `hidden`?"));
+
+        // root keys go through the flattened `AppConfig`
+        let error = load_error("tick_rat = 50\n");
+        assert!(error.contains("config.toml:1:1: unknown field `tick_rat`"));
+        assert!(error.contains("did you mean `tick_rate`?"));
+    }
+
+    #[test]
+    fn test_load_user_config_suggests_actions() {
+        let error = load_error("[keybindings]\nctrl-a = \"toggle_previw\"\n");
+        assert!(
+            error.contains("config.toml:2:10: unknown action `toggle_previw`")
+        );
+        assert!(error.contains("did you mean `toggle_preview`?"));
+
+        // custom actions are looked up in the channels
+        let dir = tempdir().unwrap();
+        let config_file = dir.path().join(CONFIG_FILE_NAME);
+        std::fs::write(
+            &config_file,
+            "[keybindings]\nctrl-a = \"actions:edit\"\n",
+        )
+        .unwrap();
+        assert!(Config::load_user_config(&config_file).is_ok());
+    }
+
+    #[test]
+    fn test_config_keys() {
+        let app_config = AppConfig {
+            log_level: Some(LogLevel::Debug),
+            log_file: Some(PathBuf::from("tv.log")),
+            shell: Some(vec!["pwsh".to_string(), "-Command".to_string()]),
+            ..AppConfig::default()
+        };
+        let table = toml::Table::try_from(app_config).unwrap();
+        for key in table.keys() {
+            assert!(CONFIG_KEYS.contains(&key.as_str()), "{key}");
+        }
+    }
+
     const USER_CONFIG_INPUT_PROMPT: &str = r#"
         [ui.input_bar]
         prompt = "❯"
@@ -11,6 +11,9 @@ pub struct ShellIntegrationConfig {
     #[serde(skip)]
     pub commands: FxHashMap<String, String>,
     pub fallback_channel: String,
+    /// The channel used by the shell history widget, defaults to the
+    /// current shell's history channel (e.g. `zsh-history`)
+    pub history_channel: Option<String>,
     pub keybindings: FxHashMap<String, Key>,
 }
 
@@ -20,10 +23,11 @@ impl<'de> Deserialize<'de> for ShellIntegrationConfig {
         D: serde::Deserializer<'de>,
     {
         #[derive(Deserialize, Default)]
-        #[serde(default)]
+        #[serde(default, deny_unknown_fields)]
         struct DeSerHelper {
             channel_triggers: Option<FxHashMap<String, Vec<String>>>,
             fallback_channel: Option<String>,
+            history_channel: Option<String>,
             keybindings: Option<FxHashMap<String, Key>>,
         }
 
@@ -38,6 +42,7 @@ impl<'de> Deserialize<'de> for ShellIntegrationConfig {
         if let Some(fallback) = helper.fallback_channel {
             config.fallback_channel = fallback;
         }
+        config.history_channel = helper.history_channel;
         if let Some(keybindings) = helper.keybindings {
             config.keybindings = keybindings;
         }
@@ -102,6 +107,7 @@ impl Default for ShellIntegrationConfig {
         Self {
             commands: hashmaps::invert_nested_hashmap(&triggers),
             fallback_channel: DEFAULT_FALLBACK_CHANNEL.to_string(),
+            history_channel: None,
             keybindings: DEFAULT_KEYBINDINGS
                 .iter()
                 .map(|(name, key)| ((*name).to_string(), *key))
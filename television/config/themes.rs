@@ -481,6 +481,7 @@ impl Into<ResultsColorscheme> for &Theme {
             result_selected_bg: (&self.selection_bg).into(),
             result_selected_fg: (&self.selection_fg).into(),
             match_foreground_color: (&self.match_fg).into(),
+            result_line_number_fg: (&self.result_line_number_fg).into(),
         }
     }
 }
@@ -1,23 +1,37 @@
 use crate::{
     channels::prototypes::Template,
     config::themes::DEFAULT_THEME,
-    screen::layout::{InputPosition, Orientation},
+    previewer::cache::DEFAULT_CACHE_SIZE,
+    screen::{
+        colors::ColorMode,
+        layout::{InputPosition, Orientation},
+    },
 };
 use serde::{Deserialize, Serialize};
 
 pub const DEFAULT_UI_SCALE: u16 = 100;
 pub const DEFAULT_PREVIEW_SIZE: u16 = 50;
 pub const DEFAULT_PROMPT: &str = ">";
+pub const DEFAULT_PREVIEW_DEBOUNCE_MS: u64 = 100;
+/// Prefetching runs preview commands for entries that might never be looked
+/// at, so it is opt-in.
+pub const DEFAULT_PREVIEW_PREFETCH: u32 = 0;
+pub const DEFAULT_UNFOCUSED_FRAME_RATE: u16 = 2;
+pub const DEFAULT_PREVIEW_COPY_MAX_BYTES: usize = 1 << 20;
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct InputBarConfig {
     pub position: InputPosition,
+    /// Template of the title, which can refer to `{channel}`, `{cwd}`,
+    /// `{version}` and `{mode}`. An empty template means no title.
     pub header: Option<String>,
     #[serde(default = "default_prompt")]
     pub prompt: Option<String>,
     pub border_type: BorderType,
     pub padding: Padding,
+    /// Whether pasting text ending with a newline confirms the selection.
+    pub confirm_pasted_newline: bool,
 }
 
 impl Default for InputBarConfig {
@@ -28,6 +42,7 @@ impl Default for InputBarConfig {
             prompt: Some(String::from(DEFAULT_PROMPT)),
             border_type: BorderType::default(),
             padding: Padding::uniform(0),
+            confirm_pasted_newline: false,
         }
     }
 }
@@ -38,7 +53,7 @@ fn default_prompt() -> Option<String> {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct StatusBarConfig {
     pub separator_open: String,
     pub separator_close: String,
@@ -46,22 +61,30 @@ pub struct StatusBarConfig {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash, Default)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ResultsPanelConfig {
+    /// Template of the title, see `InputBarConfig::header`.
+    pub title: Option<String>,
     pub border_type: BorderType,
     pub padding: Padding,
+    /// Prefix each result with its 1-based rank in the list.
+    pub show_indices: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct PreviewPanelConfig {
     pub size: u16,
     pub header: Option<Template>,
     pub footer: Option<Template>,
+    /// Command previewing directories the preview command fails on, instead
+    /// of the built-in listing.
+    pub directory_command: Option<Template>,
     pub scrollbar: bool,
     pub border_type: BorderType,
     pub padding: Padding,
     pub hidden: bool,
+    pub title_position: PreviewTitlePosition,
 }
 
 impl Default for PreviewPanelConfig {
@@ -70,16 +93,30 @@ impl Default for PreviewPanelConfig {
             size: DEFAULT_PREVIEW_SIZE,
             header: None,
             footer: None,
+            directory_command: None,
             scrollbar: true,
             border_type: BorderType::default(),
             padding: Padding::uniform(0),
             hidden: false,
+            title_position: PreviewTitlePosition::default(),
         }
     }
 }
 
+/// Which border of the preview panel the title is drawn on (the footer goes
+/// on the opposite one).
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PreviewTitlePosition {
+    #[default]
+    Top,
+    Bottom,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct HelpPanelConfig {
     pub show_categories: bool,
     #[serde(default = "default_help_hidden")]
@@ -102,7 +139,7 @@ impl Default for HelpPanelConfig {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct RemoteControlConfig {
     pub show_channel_descriptions: bool,
     pub sort_alphabetically: bool,
@@ -124,7 +161,7 @@ impl Default for RemoteControlConfig {
 #[derive(
     Clone, Debug, Deserialize, Serialize, PartialEq, Eq, Hash, Default,
 )]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct ThemeOverrides {
     // General colors
     pub background: Option<String>,
@@ -185,11 +222,42 @@ impl ThemeOverrides {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Hash)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct UiConfig {
     pub ui_scale: u16,
     pub orientation: Orientation,
     pub theme: String,
+    /// How many colors the terminal displays, detected when unset.
+    pub color_mode: Option<ColorMode>,
+    /// Only draw ASCII characters, detected from the locale when unset.
+    pub ascii: Option<bool>,
+    /// How long the selection must stay on an entry before previewing it
+    /// while navigating (in milliseconds).
+    pub preview_debounce_ms: u64,
+    /// How to copy entries to the clipboard.
+    pub clipboard: ClipboardMode,
+    /// How much of a preview `copy_preview_to_clipboard` copies at most (in
+    /// bytes).
+    pub preview_copy_max_bytes: usize,
+    /// How many frames per second are drawn while the terminal is
+    /// unfocused.
+    pub unfocused_frame_rate: u16,
+    /// How many previews are kept around for channels that cache them.
+    pub preview_cache_size: usize,
+    /// How many of the entries ahead of the selection have their preview
+    /// prepared in advance, in the direction it moves.
+    pub preview_prefetch: u32,
+    /// Whether to show the first line of the preview next to the input bar
+    /// while the preview panel is hidden.
+    pub inline_preview: bool,
+    /// Draw the panels without borders nor titles, with a single line
+    /// setting the input bar and the preview apart from the results.
+    pub borderless: bool,
+    /// Only show the query in the input bar, without the prompt, the result
+    /// count and the spinner.
+    pub hide_input_decorations: bool,
+    /// What the quit keys do while the input holds a query.
+    pub esc_behavior: EscBehavior,
 
     // Feature-specific configurations
     pub input_bar: InputBarConfig,
@@ -210,6 +278,18 @@ impl Default for UiConfig {
             ui_scale: DEFAULT_UI_SCALE,
             orientation: Orientation::Landscape,
             theme: String::from(DEFAULT_THEME),
+            color_mode: None,
+            ascii: None,
+            preview_debounce_ms: DEFAULT_PREVIEW_DEBOUNCE_MS,
+            clipboard: ClipboardMode::default(),
+            preview_copy_max_bytes: DEFAULT_PREVIEW_COPY_MAX_BYTES,
+            unfocused_frame_rate: DEFAULT_UNFOCUSED_FRAME_RATE,
+            preview_cache_size: DEFAULT_CACHE_SIZE,
+            preview_prefetch: DEFAULT_PREVIEW_PREFETCH,
+            inline_preview: false,
+            borderless: false,
+            hide_input_decorations: false,
+            esc_behavior: EscBehavior::default(),
             input_bar: InputBarConfig::default(),
             status_bar: StatusBarConfig::default(),
             preview_panel: PreviewPanelConfig::default(),
@@ -221,6 +301,31 @@ impl Default for UiConfig {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardMode {
+    /// Use the system clipboard tools, falling back to OSC 52.
+    #[default]
+    Auto,
+    /// Only use the OSC 52 escape sequence and let the terminal handle the
+    /// copy (useful over SSH).
+    Osc52,
+}
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum EscBehavior {
+    /// Quit right away.
+    #[default]
+    Quit,
+    /// Clear the query first, and only quit once it's empty.
+    ClearThenQuit,
+}
+
 #[derive(
     Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
 )]
@@ -244,6 +349,12 @@ impl BorderType {
             BorderType::Thick => Some(ratatui::widgets::BorderType::Thick),
         }
     }
+
+    /// No border is drawn in borderless mode.
+    #[must_use]
+    pub fn in_borderless_mode(self, borderless: bool) -> Self {
+        if borderless { BorderType::None } else { self }
+    }
 }
 
 impl From<crate::cli::args::BorderType> for BorderType {
@@ -260,7 +371,7 @@ impl From<crate::cli::args::BorderType> for BorderType {
 #[derive(
     Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Hash, Default, Eq,
 )]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct Padding {
     pub top: u16,
     pub bottom: u16,
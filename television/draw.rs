@@ -2,48 +2,105 @@ use crate::{
     channels::{entry::Entry, remote_control::CableEntry},
     config::layers::MergedConfig,
     picker::Picker,
-    previewer::state::PreviewState,
+    previewer::{
+        image::{GRAPHICS_PROTOCOL, GraphicsProtocol},
+        state::PreviewState,
+    },
     screen::{
-        colors::Colorscheme, help_panel::draw_help_panel,
-        input::draw_input_box, layout::Layout,
-        preview::draw_preview_content_block,
-        remote_control::draw_remote_control, results::draw_results_list,
-        spinner::Spinner, status_bar,
+        colors::Colorscheme,
+        confirm::{Confirmation, draw_confirmation},
+        detail::{EntryDetail, draw_entry_detail},
+        glyphs::Glyphs,
+        help_overlay::{HelpOverlay, draw_help_overlay},
+        help_panel::draw_help_panel,
+        inline_preview::draw_inline_preview,
+        input::draw_input_box,
+        layout::Layout,
+        logs::{LogPanelState, draw_logs},
+        preview::{ImagePlacement, draw_preview_content_block},
+        preview_focus::PreviewFocus,
+        remote_control::draw_remote_control,
+        results::draw_results_list,
+        separator::draw_separator,
+        spinner::Spinner,
+        status_bar,
+        title::{DEFAULT_INPUT_HEADER, DEFAULT_RESULTS_TITLE, TitleVariables},
     },
     television::Mode,
-    utils::metadata::AppMetadata,
+    utils::{hashmaps::FxIndexSet, metadata::AppMetadata},
 };
 use anyhow::Result;
-use ratatui::{Frame, layout::Rect};
+use ratatui::{Frame, layout::Rect, text::Line};
 use rustc_hash::FxHashSet;
 use std::{hash::Hash, sync::Arc, time::Instant};
 
 #[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
 /// The state of the current television channel.
 ///
 /// This struct is passed along to the UI thread as part of the `TvState` struct.
 pub struct ChannelState {
     pub current_channel_name: String,
-    pub selected_entries: FxHashSet<Entry>,
+    pub selected_entries: FxIndexSet<Entry>,
+    /// Entries marked during this session.
+    pub marked_entries: FxHashSet<Entry>,
+    /// Whether the results are narrowed down to the marked entries.
+    pub marked_only: bool,
     pub total_count: u32,
+    /// Whether the source was cut short because of `max_entries`.
+    pub truncated: bool,
+    /// Whether the source is loading or actions are running.
     pub running: bool,
+    /// Whether the matcher is still scoring entries.
+    pub matching: bool,
+    /// Whether the source command is still streaming entries.
+    pub loading: bool,
+    /// Why the source command failed, if it did.
+    pub source_error: Option<String>,
+    /// Name of the field the results are sorted by, if any.
+    pub sort: Option<String>,
     pub current_command: String,
+    /// Names of the channels visited before this one, oldest first.
+    pub previous_channels: Vec<String>,
+    /// This channel's (1-based) position in the channel history and the
+    /// history's length.
+    pub history_position: (usize, usize),
 }
 
 impl ChannelState {
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::fn_params_excessive_bools)]
     pub fn new(
         current_channel_name: String,
-        selected_entries: FxHashSet<Entry>,
+        selected_entries: FxIndexSet<Entry>,
+        marked_entries: FxHashSet<Entry>,
+        marked_only: bool,
         total_count: u32,
+        truncated: bool,
         running: bool,
+        matching: bool,
+        loading: bool,
+        source_error: Option<String>,
+        sort: Option<String>,
         current_command: String,
+        previous_channels: Vec<String>,
+        history_position: (usize, usize),
     ) -> Self {
         Self {
             current_channel_name,
             selected_entries,
+            marked_entries,
+            marked_only,
             total_count,
+            truncated,
             running,
+            matching,
+            loading,
+            source_error,
+            sort,
             current_command,
+            previous_channels,
+            history_position,
         }
     }
 }
@@ -54,9 +111,20 @@ impl Hash for ChannelState {
         self.selected_entries
             .iter()
             .for_each(|entry| entry.hash(state));
+        self.marked_entries
+            .iter()
+            .for_each(|entry| entry.hash(state));
+        self.marked_only.hash(state);
         self.total_count.hash(state);
+        self.truncated.hash(state);
         self.running.hash(state);
+        self.matching.hash(state);
+        self.loading.hash(state);
+        self.source_error.hash(state);
+        self.sort.hash(state);
         self.current_command.hash(state);
+        self.previous_channels.hash(state);
+        self.history_position.hash(state);
     }
 }
 
@@ -72,6 +140,23 @@ pub struct TvState {
     pub channel_state: ChannelState,
     pub spinner: Spinner,
     pub preview_state: PreviewState,
+    pub confirmation: Option<Confirmation>,
+    /// The full selected entry, shown in a popup.
+    pub entry_detail: Option<EntryDetail>,
+    /// A short-lived message for the user (e.g. "Copied 2 entries").
+    pub notice: Option<String>,
+    /// The log panel, when open.
+    pub logs: Option<LogPanelState>,
+    /// The preview pane's search state, when it has the focus.
+    pub preview_focus: Option<PreviewFocus>,
+    /// How far the help panel is scrolled.
+    pub help_scroll: u16,
+    /// The full-screen help overlay, when open.
+    pub help_overlay: Option<HelpOverlay>,
+    /// How many channels the remote control holds.
+    pub remote_channel_count: u32,
+    /// The line of the preview shown while the preview panel is hidden.
+    pub inline_preview: Option<Line<'static>>,
 }
 
 impl TvState {
@@ -84,6 +169,15 @@ impl TvState {
         channel_state: ChannelState,
         spinner: Spinner,
         preview_state: PreviewState,
+        confirmation: Option<Confirmation>,
+        entry_detail: Option<EntryDetail>,
+        notice: Option<String>,
+        logs: Option<LogPanelState>,
+        preview_focus: Option<PreviewFocus>,
+        help_scroll: u16,
+        help_overlay: Option<HelpOverlay>,
+        remote_channel_count: u32,
+        inline_preview: Option<Line<'static>>,
     ) -> Self {
         Self {
             mode,
@@ -93,6 +187,15 @@ impl TvState {
             channel_state,
             spinner,
             preview_state,
+            confirmation,
+            entry_detail,
+            notice,
+            logs,
+            preview_focus,
+            help_scroll,
+            help_overlay,
+            remote_channel_count,
+            inline_preview,
         }
     }
 }
@@ -130,6 +233,31 @@ impl Ctx {
             layout,
         }
     }
+
+    /// The protocol to display the preview's image with, unless there's no
+    /// image, the terminal can't display it or something is drawn over it.
+    pub fn image_protocol(&self) -> Option<GraphicsProtocol> {
+        let protocol = (*GRAPHICS_PROTOCOL)?;
+        let image = self.tv_state.preview_state.preview.image.as_ref()?;
+        let covered = self.tv_state.mode == Mode::RemoteControl
+            || self.tv_state.logs.is_some()
+            || self.tv_state.entry_detail.is_some()
+            || self.tv_state.confirmation.is_some()
+            || self.tv_state.help_overlay.is_some()
+            || self.layout.help_panel.is_some();
+        (protocol.supports(image.format)
+            && !covered
+            && self.layout.preview_window.is_some())
+        .then_some(protocol)
+    }
+}
+
+/// What [`draw`] produced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrawnFrame {
+    pub layout: Layout,
+    /// The image to write over the preview once the frame is on screen.
+    pub image: Option<ImagePlacement>,
 }
 
 /// Trait implemented by every drawable UI component.
@@ -170,11 +298,47 @@ impl UiComponent for StatusBarComponent<'_> {
 /// A `Result` containing the layout of the current frame if the drawing was successful.
 /// This layout can then be sent back to the main thread to serve for tasks where having that
 /// information can be useful or lead to optimizations.
-pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
+///
+/// It comes with the image to display in the preview, if any, which has to be written to the
+/// terminal once the frame is drawn.
+pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<DrawnFrame> {
     let show_remote = matches!(ctx.tv_state.mode, Mode::RemoteControl);
+    let glyphs = Glyphs::new(ctx.config.ascii);
 
-    let layout =
-        Layout::build(area, &ctx.config, ctx.tv_state.mode, &ctx.colorscheme);
+    let layout = Layout::build(
+        area,
+        &ctx.config,
+        ctx.tv_state.mode,
+        &ctx.colorscheme,
+        ctx.tv_state.logs.is_some(),
+        ctx.tv_state.help_overlay.is_some(),
+    );
+
+    let title_variables = TitleVariables {
+        channel: &ctx.tv_state.channel_state.current_channel_name,
+        cwd: &ctx.app_metadata.current_directory,
+        version: &ctx.app_metadata.version,
+        mode: ctx.tv_state.mode,
+    };
+    // borderless panels go without titles
+    let (input_title, results_title) = if ctx.config.borderless {
+        (None, None)
+    } else {
+        (
+            title_variables.expand(
+                ctx.config
+                    .input_bar_header
+                    .as_deref()
+                    .unwrap_or(DEFAULT_INPUT_HEADER),
+            ),
+            title_variables.expand(
+                ctx.config
+                    .results_panel_title
+                    .as_deref()
+                    .unwrap_or(DEFAULT_RESULTS_TITLE),
+            ),
+        )
+    };
 
     // results list
     draw_results_list(
@@ -182,11 +346,25 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
         layout.results,
         &ctx.tv_state.results_picker.entries,
         &ctx.tv_state.channel_state.selected_entries,
+        &ctx.tv_state.channel_state.marked_entries,
         &mut ctx.tv_state.results_picker.relative_state.clone(),
         ctx.config.input_bar_position,
         &ctx.colorscheme,
+        glyphs,
         &ctx.config.results_panel_padding,
         &ctx.config.results_panel_border_type,
+        ctx.config
+            .results_panel_show_indices
+            .then(|| ctx.tv_state.results_picker.offset()),
+        ctx.config.channel_icon.as_ref(),
+        ctx.config
+            .channel_source_group_by
+            .map(|index| (index, &ctx.config.channel_source_delimiter)),
+        ctx.tv_state.channel_state.sort.as_deref(),
+        ctx.tv_state.channel_state.marked_only,
+        &ctx.tv_state.channel_state.previous_channels,
+        &ctx.tv_state.channel_state.current_channel_name,
+        results_title.as_deref(),
     )?;
 
     draw_input_box(
@@ -194,27 +372,42 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
         layout.input,
         ctx.tv_state.results_picker.total_items,
         ctx.tv_state.channel_state.total_count,
+        ctx.tv_state.channel_state.truncated,
+        ctx.tv_state.channel_state.selected_entries.len(),
         &ctx.tv_state.results_picker.input,
         &ctx.tv_state.results_picker.state,
         ctx.tv_state.channel_state.running,
-        &ctx.tv_state.channel_state.current_channel_name,
+        ctx.tv_state.channel_state.matching,
+        ctx.tv_state.channel_state.loading,
+        input_title.as_deref(),
+        ctx.tv_state.mode,
         &ctx.tv_state.spinner,
         &ctx.colorscheme,
+        glyphs,
         ctx.config.input_bar_position,
-        &ctx.config.input_bar_header,
         &ctx.config.input_bar_padding,
         &ctx.config.input_bar_border_type,
         ctx.config.input_bar_prompt.as_ref(),
+        !ctx.config.hide_input_decorations,
     )?;
 
+    for separator in [layout.input_separator, layout.preview_separator]
+        .into_iter()
+        .flatten()
+    {
+        draw_separator(f, separator, &ctx.colorscheme, glyphs);
+    }
+
     // status bar at the bottom
     if let Some(status_bar_area) = layout.status_bar {
         let status_component = StatusBarComponent::new(&ctx);
         status_component.draw(f, status_bar_area);
     }
 
+    let mut image = None;
     if let Some(preview_rect) = layout.preview_window {
-        draw_preview_content_block(
+        let image_protocol = ctx.image_protocol();
+        image = draw_preview_content_block(
             f,
             preview_rect,
             ctx.tv_state.preview_state,
@@ -222,9 +415,26 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.config.preview_panel_border_type,
             &ctx.config.preview_panel_padding,
             ctx.config.preview_panel_scrollbar,
+            ctx.config.preview_panel_title_position,
+            ctx.tv_state.preview_focus.as_ref(),
+            image_protocol,
+            glyphs,
         )?;
     }
 
+    if let Some(inline_preview_rect) = layout.inline_preview {
+        draw_inline_preview(
+            f,
+            inline_preview_rect,
+            ctx.tv_state.inline_preview.as_ref(),
+            &ctx.colorscheme,
+        );
+    }
+
+    if let (Some(logs_area), Some(logs)) = (layout.logs, &ctx.tv_state.logs) {
+        draw_logs(f, logs_area, logs, &ctx.colorscheme, glyphs);
+    }
+
     // remote control
     if show_remote {
         draw_remote_control(
@@ -236,7 +446,13 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &mut ctx.tv_state.rc_picker.relative_state.clone(),
             &mut ctx.tv_state.rc_picker.input.clone(),
             &ctx.colorscheme,
+            glyphs,
             ctx.config.remote_show_channel_descriptions,
+            (
+                ctx.tv_state.rc_picker.total_items,
+                ctx.tv_state.remote_channel_count,
+            ),
+            &ctx.config.input_map,
         )?;
     }
 
@@ -248,8 +464,35 @@ pub fn draw(ctx: Ctx, f: &mut Frame<'_>, area: Rect) -> Result<Layout> {
             &ctx.config,
             ctx.tv_state.mode,
             &ctx.colorscheme,
+            glyphs,
+            ctx.tv_state.help_scroll,
+        );
+    }
+
+    if let Some(detail) = &ctx.tv_state.entry_detail {
+        draw_entry_detail(f, area, detail, &ctx.colorscheme, glyphs);
+    }
+
+    // pending confirmation prompt, on top of everything else
+    if let Some(confirmation) = &ctx.tv_state.confirmation {
+        draw_confirmation(f, area, confirmation, &ctx.colorscheme, glyphs);
+    }
+
+    // the help overlay covers everything while it's open
+    if let (Some(overlay), Some(overlay_area)) =
+        (ctx.tv_state.help_overlay, layout.help_overlay)
+    {
+        draw_help_overlay(
+            f,
+            overlay_area,
+            overlay,
+            &ctx.config,
+            ctx.tv_state.mode,
+            &ctx.colorscheme,
+            glyphs,
+            ctx.tv_state.channel_state.history_position,
         );
     }
 
-    Ok(layout)
+    Ok(DrawnFrame { layout, image })
 }
@@ -0,0 +1,328 @@
+//! Run tv's picker from another Rust application instead of shelling out to
+//! the `tv` binary.
+//!
+//! The picker lists the given entries rather than the output of a source
+//! command, and can preview them with a closure rather than a preview
+//! command. It uses tv's default configuration and doesn't read any
+//! configuration or channel files, nor does it record any history.
+//!
+//! It either takes over the terminal like the `tv` binary does, or draws on
+//! a [`ratatui::Terminal`] the application already owns, see
+//! [`EmbeddedPicker::run_with_terminal`].
+//!
+//! This module is behind the `embed` feature.
+//!
+//! ```no_run
+//! # async fn run() -> anyhow::Result<()> {
+//! use television::EmbeddedPicker;
+//!
+//! let selection = EmbeddedPicker::new(["apple", "banana", "cherry"])
+//!     .query("an")
+//!     .prompt("fruit> ")
+//!     .preview(|entry| format!("{} letters", entry.raw.len()))
+//!     .run()
+//!     .await?;
+//! for fruit in &selection.entries {
+//!     println!("{}", fruit.raw);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::io::{IsTerminal, Write, stdout};
+use std::sync::Arc;
+
+use anyhow::Result;
+use ratatui::{Terminal, backend::CrosstermBackend};
+use rustc_hash::FxHashSet;
+
+use crate::{
+    app::{App, AppOutput},
+    cable::Cable,
+    channels::{
+        channel::Source,
+        entry::Entry,
+        prototypes::{ChannelPrototype, Template},
+    },
+    cli::{ChannelCli, GlobalCli, PostProcessedCli},
+    config::{default_config_from_file, layers::ConfigLayers},
+    previewer::PreviewFn,
+};
+
+/// Name of the channel the picker lists its items in.
+const PICKER_CHANNEL_NAME: &str = "picker";
+
+/// What an embedded picker looks like and how it behaves.
+#[derive(Clone, Default)]
+pub struct PickOptions {
+    /// The initial query.
+    pub query: Option<String>,
+    /// The prompt of the input bar.
+    pub prompt: Option<String>,
+    /// The header of the input bar.
+    pub header: Option<String>,
+    /// Match the query as a substring instead of fuzzily.
+    pub exact: bool,
+    /// Draw the picker under the cursor rather than fullscreen.
+    pub inline: bool,
+    /// Height of the picker, in lines.
+    pub height: Option<u16>,
+    /// Generates the preview of the selected entry.
+    pub preview: Option<PreviewFn>,
+}
+
+/// What the user picked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selection {
+    /// The picked entries, as they were given to the picker and in the same
+    /// order.
+    ///
+    /// This is empty when the picker was cancelled.
+    pub entries: Vec<Entry>,
+}
+
+impl Selection {
+    /// Whether the picker was cancelled.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Builds and runs an embedded picker.
+///
+/// Entries are listed, matched and told apart by their `raw` text; any
+/// other field is handed back untouched in the [`Selection`].
+///
+/// ```no_run
+/// # async fn run() -> anyhow::Result<()> {
+/// let selection = television::EmbeddedPicker::new(vec!["main.rs", "lib.rs"])
+///     .exact(true)
+///     .inline(true)
+///     .run()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct EmbeddedPicker {
+    items: Vec<Entry>,
+    options: PickOptions,
+}
+
+impl EmbeddedPicker {
+    pub fn new<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Entry>,
+    {
+        Self {
+            items: items.into_iter().map(Into::into).collect(),
+            options: PickOptions::default(),
+        }
+    }
+
+    pub fn options(mut self, options: PickOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn query(mut self, query: impl Into<String>) -> Self {
+        self.options.query = Some(query.into());
+        self
+    }
+
+    pub fn prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.options.prompt = Some(prompt.into());
+        self
+    }
+
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.options.header = Some(header.into());
+        self
+    }
+
+    pub fn exact(mut self, exact: bool) -> Self {
+        self.options.exact = exact;
+        self
+    }
+
+    pub fn inline(mut self, inline: bool) -> Self {
+        self.options.inline = inline;
+        self
+    }
+
+    pub fn height(mut self, height: u16) -> Self {
+        self.options.height = Some(height);
+        self
+    }
+
+    /// Preview the selected entry with the text `preview` returns for it,
+    /// which may contain ANSI escape sequences.
+    ///
+    /// `preview` runs on a blocking thread so it is free to do IO.
+    pub fn preview(
+        mut self,
+        preview: impl Fn(&Entry) -> String + Send + Sync + 'static,
+    ) -> Self {
+        self.options.preview = Some(Arc::new(preview));
+        self
+    }
+
+    /// Let the user pick among the items, taking over the terminal until
+    /// they're done.
+    pub async fn run(self) -> Result<Selection> {
+        pick(self.items, self.options).await
+    }
+
+    /// Let the user pick among the items on `terminal`, handing it back
+    /// once they're done.
+    pub async fn run_with_terminal<W>(
+        self,
+        terminal: Terminal<CrosstermBackend<W>>,
+    ) -> Result<(Selection, Terminal<CrosstermBackend<W>>)>
+    where
+        W: Write + Send + 'static,
+    {
+        pick_with_terminal(self.items, self.options, terminal).await
+    }
+}
+
+/// Let the user pick among `items`, taking over the terminal until they're
+/// done.
+///
+/// This needs to run within a multi-threaded tokio runtime.
+pub async fn pick<I, S>(items: I, options: PickOptions) -> Result<Selection>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Entry>,
+{
+    let items = items.into_iter().map(Into::into).collect::<Vec<_>>();
+    let mut app = picker_app(&items, options)?;
+    let output = app.run(stdout().is_terminal(), false).await?;
+    Ok(selection(items, output))
+}
+
+/// Let the user pick among `items` on `terminal`, which the picker draws on
+/// over its whole area and hands back, cleared, once they're done.
+///
+/// The terminal stays on whichever screen it is on, and is left in raw mode
+/// if it already was.
+///
+/// This needs to run within a multi-threaded tokio runtime.
+pub async fn pick_with_terminal<I, S, W>(
+    items: I,
+    options: PickOptions,
+    terminal: Terminal<CrosstermBackend<W>>,
+) -> Result<(Selection, Terminal<CrosstermBackend<W>>)>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<Entry>,
+    W: Write + Send + 'static,
+{
+    let items = items.into_iter().map(Into::into).collect::<Vec<_>>();
+    let mut app = picker_app(&items, options)?;
+    let (output, terminal) = app.run_with_terminal(terminal).await?;
+    Ok((selection(items, output), terminal))
+}
+
+/// Set up the application listing `items`.
+fn picker_app(items: &[Entry], options: PickOptions) -> Result<App> {
+    let mut base_config = default_config_from_file()?;
+    // don't mix the picks of the embedding application into tv's history
+    base_config.application.history_size = 0;
+    let cli = PostProcessedCli {
+        channel: ChannelCli {
+            input: options.query,
+            input_prompt: options.prompt,
+            input_header: options.header,
+            exact: options.exact,
+            // the entry preview, which the preview function then replaces
+            preview_command: options
+                .preview
+                .is_some()
+                .then_some(Template::Entry),
+            ..ChannelCli::default()
+        },
+        global: GlobalCli {
+            inline: options.inline,
+            height: options.height,
+            no_remote: true,
+            ..GlobalCli::default()
+        },
+    };
+    let layered_config = ConfigLayers::new(
+        base_config,
+        ChannelPrototype::new(PICKER_CHANNEL_NAME, ""),
+        cli,
+    );
+
+    let mut app = App::new(layered_config, Cable::default());
+    app.television.channel.set_source(Source::Static(Arc::new(
+        items.iter().map(|item| item.raw.clone()).collect(),
+    )));
+    if let Some(preview) = options.preview {
+        app.television.set_preview_fn(preview);
+    }
+    Ok(app)
+}
+
+fn selection(items: Vec<Entry>, output: AppOutput) -> Selection {
+    Selection {
+        entries: selected_in_order(
+            items,
+            output
+                .selected_entries
+                .unwrap_or_default()
+                .into_iter()
+                .map(|entry| entry.raw),
+        ),
+    }
+}
+
+/// Pick the `selected` raw texts out of `items`, in the order they appear
+/// there.
+fn selected_in_order(
+    items: Vec<Entry>,
+    selected: impl Iterator<Item = String>,
+) -> Vec<Entry> {
+    let selected = selected.collect::<FxHashSet<_>>();
+    items
+        .into_iter()
+        .filter(|item| selected.contains(&item.raw))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selected_in_order() {
+        let items = vec![
+            Entry::from("zsh").with_display("Z shell".to_string()),
+            Entry::from("bash"),
+            Entry::from("fish"),
+        ];
+        let selected = ["fish", "zsh"].map(String::from).into_iter();
+        let entries = selected_in_order(items, selected);
+        assert_eq!(entries, vec![Entry::from("zsh"), Entry::from("fish")]);
+        // the caller's entries are handed back as they were given
+        assert_eq!(entries[0].display.as_deref(), Some("Z shell"));
+    }
+
+    #[test]
+    fn test_picker_builder() {
+        let picker = EmbeddedPicker::new(["a", "b"])
+            .query("a")
+            .prompt("> ")
+            .exact(true)
+            .height(10)
+            .preview(|entry| entry.raw.to_uppercase());
+        assert_eq!(picker.items, vec![Entry::from("a"), Entry::from("b")]);
+        assert_eq!(picker.options.query.as_deref(), Some("a"));
+        assert_eq!(picker.options.prompt.as_deref(), Some("> "));
+        assert!(picker.options.exact);
+        assert_eq!(picker.options.height, Some(10));
+        let preview = picker.options.preview.unwrap();
+        assert_eq!(preview(&Entry::new("a".to_string())), "A");
+    }
+}
@@ -1,4 +1,4 @@
-use crate::tui::{Tui, TuiMode};
+use crate::tui::restore_terminal;
 use anyhow::Result;
 use colored::Colorize;
 use std::panic;
@@ -6,11 +6,9 @@ use tracing::error;
 
 pub fn init() -> Result<()> {
     panic::set_hook(Box::new(move |panic_info| {
-        // Clean up the terminal
-        if let Ok(mut t) = Tui::new(std::io::stderr(), &TuiMode::Fullscreen)
-            && let Err(err) = t.exit()
-        {
-            error!("Unable to exit terminal: {:?}", err);
+        // Clean up the terminal before printing anything
+        if let Err(err) = restore_terminal() {
+            error!("Unable to restore terminal: {:?}", err);
         }
 
         // In release builds, use human-panic to generate a friendly crash report:
@@ -22,10 +22,16 @@ pub enum Event<I> {
     Closed,
     Input(I),
     Mouse(MouseEvent),
+    /// Text pasted all at once (bracketed paste).
+    Paste(String),
     FocusLost,
     FocusGained,
     Resize(u16, u16),
     Tick,
+    /// The process was asked to terminate (SIGINT, SIGTERM).
+    Terminate,
+    /// The process was continued after having been stopped (SIGCONT).
+    Continued,
 }
 
 #[derive(
@@ -80,6 +86,8 @@ impl<'de> Deserialize<'de> for Key {
     }
 }
 
+/// Keys are displayed in the canonical form `parse_key_event` accepts, so
+/// that they can be pasted as is in `config.toml`.
 impl Display for Key {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -96,7 +104,7 @@ impl Display for Key {
             Key::CtrlRight => write!(f, "ctrl-right"),
             Key::CtrlUp => write!(f, "ctrl-up"),
             Key::CtrlDown => write!(f, "ctrl-down"),
-            Key::CtrlDelete => write!(f, "ctrl-del"),
+            Key::CtrlDelete => write!(f, "ctrl-delete"),
             Key::AltSpace => write!(f, "alt-space"),
             Key::AltEnter => write!(f, "alt-enter"),
             Key::AltBackspace => write!(f, "alt-backspace"),
@@ -113,6 +121,10 @@ impl Display for Key {
             Key::Delete => write!(f, "delete"),
             Key::Insert => write!(f, "insert"),
             Key::F(k) => write!(f, "f{k}"),
+            Key::Char(' ') => write!(f, "space"),
+            Key::Char(c) if c.is_ascii_uppercase() => {
+                write!(f, "shift-{}", c.to_ascii_lowercase())
+            }
             Key::Char(c) => write!(f, "{c}"),
             Key::Alt(c) => write!(f, "alt-{c}"),
             Key::Ctrl(c) => write!(f, "ctrl-{c}"),
@@ -170,6 +182,50 @@ fn flush_existing_events() {
     }
 }
 
+#[cfg(unix)]
+type UnixSignal = Option<signal::unix::Signal>;
+#[cfg(not(unix))]
+type UnixSignal = ();
+
+#[cfg(unix)]
+fn terminate_signal() -> UnixSignal {
+    signal::unix::signal(signal::unix::SignalKind::terminate())
+        .inspect_err(|e| warn!("Unable to listen for SIGTERM: {}", e))
+        .ok()
+}
+
+#[cfg(not(unix))]
+fn terminate_signal() -> UnixSignal {}
+
+#[cfg(unix)]
+fn continue_signal() -> UnixSignal {
+    signal::unix::signal(signal::unix::SignalKind::from_raw(
+        signal_hook::consts::signal::SIGCONT,
+    ))
+    .inspect_err(|e| warn!("Unable to listen for SIGCONT: {}", e))
+    .ok()
+}
+
+#[cfg(not(unix))]
+fn continue_signal() -> UnixSignal {}
+
+/// Resolves when the signal is received.
+#[cfg(unix)]
+async fn recv_signal(signal: &mut UnixSignal) {
+    if let Some(signal) = signal
+        && signal.recv().await.is_some()
+    {
+        return;
+    }
+    std::future::pending::<()>().await;
+}
+
+/// Never resolves on platforms without unix signals.
+#[cfg(not(unix))]
+async fn recv_signal(_signal: &mut UnixSignal) {
+    std::future::pending::<()>().await;
+}
+
 pub enum ControlEvent {
     /// Abort the event loop
     Abort,
@@ -189,6 +245,8 @@ impl EventLoop {
         flush_existing_events();
 
         tokio::spawn(async move {
+            let mut sigterm = terminate_signal();
+            let mut sigcont = continue_signal();
             loop {
                 let delay = tokio::time::sleep(tick_interval);
                 let event_available = poll_event(tick_interval);
@@ -230,7 +288,15 @@ impl EventLoop {
                     },
                     _ = signal::ctrl_c() => {
                         debug!("Received SIGINT");
-                        tx.send(Event::Input(Key::Ctrl('c'))).unwrap_or_else(|_| warn!("Unable to send Ctrl-C event"));
+                        tx.send(Event::Terminate).unwrap_or_else(|_| warn!("Unable to send Terminate event"));
+                    },
+                    () = recv_signal(&mut sigterm) => {
+                        debug!("Received SIGTERM");
+                        tx.send(Event::Terminate).unwrap_or_else(|_| warn!("Unable to send Terminate event"));
+                    },
+                    () = recv_signal(&mut sigcont) => {
+                        debug!("Received SIGCONT");
+                        tx.send(Event::Continued).unwrap_or_else(|_| warn!("Unable to send Continued event"));
                     },
                     // if `delay` completes, pass to the next event "frame"
                     () = delay => {
@@ -249,6 +315,9 @@ impl EventLoop {
                             Ok(crossterm::event::Event::Mouse(mouse)) => {
                                 tx.send(Event::Mouse(mouse)).unwrap_or_else(|_| warn!("Unable to send Mouse event"));
                             },
+                            Ok(crossterm::event::Event::Paste(text)) => {
+                                tx.send(Event::Paste(text)).unwrap_or_else(|_| warn!("Unable to send Paste event"));
+                            },
                             Ok(crossterm::event::Event::FocusLost) => {
                                 tx.send(Event::FocusLost).unwrap_or_else(|_| warn!("Unable to send FocusLost event"));
                             },
@@ -397,6 +466,65 @@ mod tests {
         KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers,
     };
 
+    #[test]
+    fn test_key_display_round_trips() {
+        let mut keys = vec![
+            Key::Backspace,
+            Key::Enter,
+            Key::Left,
+            Key::Right,
+            Key::Up,
+            Key::Down,
+            Key::CtrlSpace,
+            Key::CtrlBackspace,
+            Key::CtrlEnter,
+            Key::CtrlLeft,
+            Key::CtrlRight,
+            Key::CtrlUp,
+            Key::CtrlDown,
+            Key::CtrlDelete,
+            Key::AltSpace,
+            Key::AltEnter,
+            Key::AltBackspace,
+            Key::AltDelete,
+            Key::AltUp,
+            Key::AltDown,
+            Key::AltLeft,
+            Key::AltRight,
+            Key::Home,
+            Key::End,
+            Key::PageUp,
+            Key::PageDown,
+            Key::BackTab,
+            Key::Delete,
+            Key::Insert,
+            Key::Esc,
+            Key::Tab,
+            Key::Char('a'),
+            Key::Char('A'),
+            Key::Char(' '),
+            Key::Char('-'),
+            Key::Char('?'),
+            Key::Alt('a'),
+            Key::Alt('-'),
+            Key::Ctrl('a'),
+            Key::Ctrl('-'),
+        ];
+        keys.extend((1..=12).map(Key::F));
+
+        for key in keys {
+            let displayed = key.to_string();
+            assert_eq!(
+                displayed.parse::<Key>(),
+                Ok(key),
+                "`{displayed}` doesn't parse back to {key:?}"
+            );
+        }
+        assert_eq!(Key::Ctrl('a').to_string(), "ctrl-a");
+        assert_eq!(Key::Char('A').to_string(), "shift-a");
+        assert_eq!(Key::Char(' ').to_string(), "space");
+    }
+
     #[test]
     fn test_convert_raw_event_to_key() {
         // character keys
@@ -0,0 +1,217 @@
+//! Keep a file up to date with the selected entry, the result counts and the
+//! query, so that other programs (e.g. a tmux status line) can show them.
+//!
+//! The file holds a single line of JSON:
+//!
+//! ```json
+//! {"selected":"Cargo.toml","matched":12,"total":345,"query":"car"}
+//! ```
+use serde::Serialize;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{sync::watch, task::JoinHandle};
+use tracing::{debug, warn};
+
+/// How long the info has to stay the same before it is written out, so that
+/// scrolling through the results doesn't write the file for every entry.
+const INFO_DEBOUNCE: Duration = Duration::from_millis(50);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Info {
+    /// The raw selected entry, if any.
+    pub selected: Option<String>,
+    pub matched: u32,
+    pub total: u32,
+    pub query: String,
+}
+
+impl Info {
+    /// Whether `self` already describes this state.
+    fn describes(
+        &self,
+        selected: Option<&str>,
+        matched: u32,
+        total: u32,
+        query: &str,
+    ) -> bool {
+        self.selected.as_deref() == selected
+            && self.matched == matched
+            && self.total == total
+            && self.query == query
+    }
+}
+
+/// Writes the latest `Info` to a file from a background task, so that a slow
+/// reader never holds up the UI.
+///
+/// Updates coming in while a write is in progress are collapsed into the
+/// latest one. The file is removed when the writer is dropped.
+pub struct InfoWriter {
+    path: PathBuf,
+    info_tx: watch::Sender<Info>,
+    handle: JoinHandle<()>,
+}
+
+impl InfoWriter {
+    pub fn new(path: PathBuf) -> Self {
+        let (info_tx, info_rx) = watch::channel(Info::default());
+        let handle = tokio::spawn(write_info(path.clone(), info_rx));
+        Self {
+            path,
+            info_tx,
+            handle,
+        }
+    }
+
+    /// Publish the current state, which is written out unless it didn't
+    /// change.
+    pub fn update(
+        &self,
+        selected: Option<&str>,
+        matched: u32,
+        total: u32,
+        query: &str,
+    ) {
+        self.info_tx.send_if_modified(|info| {
+            if info.describes(selected, matched, total, query) {
+                return false;
+            }
+            *info = Info {
+                selected: selected.map(str::to_string),
+                matched,
+                total,
+                query: query.to_string(),
+            };
+            true
+        });
+    }
+}
+
+impl Drop for InfoWriter {
+    fn drop(&mut self) {
+        self.handle.abort();
+        // FIFOs belong to whoever reads them
+        if !is_fifo(&self.path)
+            && let Err(e) = std::fs::remove_file(&self.path)
+        {
+            debug!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+async fn write_info(path: PathBuf, mut info_rx: watch::Receiver<Info>) {
+    while info_rx.changed().await.is_ok() {
+        tokio::time::sleep(INFO_DEBOUNCE).await;
+        let info = info_rx.borrow_and_update().clone();
+        let line = match serde_json::to_string(&info) {
+            Ok(line) => line + "\n",
+            Err(e) => {
+                warn!("Failed to serialize the info: {}", e);
+                continue;
+            }
+        };
+        let path = path.clone();
+        let written =
+            tokio::task::spawn_blocking(move || write_line(&path, &line))
+                .await;
+        if let Ok(Err(e)) = written {
+            debug!("Failed to write the info file: {}", e);
+        }
+    }
+}
+
+/// Replace the content of the file at `path` with `line`.
+///
+/// Regular files are written next to their final location then renamed over
+/// it, so that readers never see a partial line. FIFOs are written to
+/// directly, and only if someone is reading them.
+fn write_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if is_fifo(path) {
+        return write_fifo(path, line);
+    }
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    std::fs::write(&tmp_path, line)?;
+    std::fs::rename(&tmp_path, path)
+}
+
+#[cfg(unix)]
+fn is_fifo(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    std::fs::metadata(path).is_ok_and(|md| md.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+fn is_fifo(_path: &Path) -> bool {
+    false
+}
+
+/// Write `line` to a FIFO without blocking: the update is dropped when no one
+/// is reading or the pipe is full.
+#[cfg(unix)]
+fn write_fifo(path: &Path, line: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::OpenOptionsExt;
+    let mut fifo = std::fs::OpenOptions::new()
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open(path)?;
+    fifo.write_all(line.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_fifo(path: &Path, line: &str) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)?
+        .write_all(line.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn read_info(path: &Path, expected: &str) -> String {
+        for _ in 0..100 {
+            if let Ok(content) = std::fs::read_to_string(path)
+                && content.contains(expected)
+            {
+                return content;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_info_file_keeps_the_latest_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("info.json");
+        let writer = InfoWriter::new(path.clone());
+
+        writer.update(Some("a"), 3, 10, "");
+        writer.update(Some("b"), 2, 10, "x");
+        writer.update(None, 0, 10, "xyz");
+        let content = read_info(&path, "xyz").await;
+        assert_eq!(
+            content,
+            "{\"selected\":null,\"matched\":0,\"total\":10,\"query\":\"xyz\"}\n"
+        );
+
+        drop(writer);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_write_line_replaces_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("info.json");
+        write_line(&path, "first\n").unwrap();
+        write_line(&path, "second\n").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second\n");
+        // the temporary file was renamed over the info file
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+}
@@ -9,8 +9,14 @@ pub fn convert_action_to_input_request(
         Action::DeletePrevWord => Some(InputRequest::DeletePrevWord),
         Action::DeleteNextChar => Some(InputRequest::DeleteNextChar),
         Action::DeleteLine => Some(InputRequest::DeleteLine),
+        Action::DeleteNextWord => Some(InputRequest::DeleteNextWord),
+        Action::DeleteToInputStart => Some(InputRequest::DeleteTillStart),
+        Action::DeleteToInputEnd => Some(InputRequest::DeleteTillEnd),
+        Action::TransposeChars => Some(InputRequest::TransposeChars),
         Action::GoToPrevChar => Some(InputRequest::GoToPrevChar),
         Action::GoToNextChar => Some(InputRequest::GoToNextChar),
+        Action::GoToPrevWord => Some(InputRequest::GoToPrevWord),
+        Action::GoToNextWord => Some(InputRequest::GoToNextWord),
         Action::GoToInputStart => Some(InputRequest::GoToStart),
         Action::GoToInputEnd => Some(InputRequest::GoToEnd),
         _ => None,
@@ -5,10 +5,13 @@ pub mod channels;
 pub mod cli;
 pub mod config;
 pub mod draw;
+#[cfg(feature = "embed")]
+pub mod embed;
 pub mod errors;
 pub mod event;
 pub mod gh;
 pub mod history;
+pub mod info;
 pub mod input;
 pub mod keymap;
 pub mod logging;
@@ -18,6 +21,10 @@ pub mod picker;
 pub mod previewer;
 pub mod render;
 pub mod screen;
+pub mod session;
 pub mod television;
 pub mod tui;
 pub mod utils;
+
+#[cfg(feature = "embed")]
+pub use embed::{EmbeddedPicker, PickOptions, Selection, pick};
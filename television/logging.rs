@@ -1,23 +1,225 @@
 use anyhow::Result;
-use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+use clap::ValueEnum;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    sync::{
+        LazyLock,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+};
+use tracing_appender::{
+    non_blocking::WorkerGuard,
+    rolling::{RollingFileAppender, Rotation},
+};
+use tracing_subscriber::{EnvFilter, Layer, fmt, layer::Context, prelude::*};
 
-use crate::config::get_data_dir;
+use crate::{config::get_data_dir, utils::circular_buffer::CircularBuffer};
 
-pub fn init() -> Result<()> {
-    let directory = get_data_dir();
-    std::fs::create_dir_all(directory.clone())?;
-    let log_path = directory.join(format!("{}.log", env!("CARGO_PKG_NAME")));
-    let log_file = std::fs::File::create(log_path)?;
+/// How many records the log panel keeps around.
+const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// How many daily files of the default log are kept before the oldest is
+/// removed.
+const MAX_LOG_FILES: usize = 7;
+
+/// The most verbose level written to the log file.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Off => "off",
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// The log file used when none is configured.
+pub fn default_log_file() -> PathBuf {
+    get_data_dir().join(format!("{}.log", env!("CARGO_PKG_NAME")))
+}
+
+/// A log record as displayed by the log panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogRecord {
+    pub level: Level,
+    pub message: String,
+}
+
+/// The most recent log records, newest last.
+pub static LOG_BUFFER: LazyLock<Mutex<CircularBuffer<LogRecord>>> =
+    LazyLock::new(|| Mutex::new(CircularBuffer::new(LOG_BUFFER_CAPACITY)));
+
+/// How many records have been logged so far, used to tell when the log
+/// panel needs redrawing.
+static LOG_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+pub fn log_generation() -> u64 {
+    LOG_GENERATION.load(Ordering::Relaxed)
+}
+
+/// Collects an event's message followed by its other fields.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+/// A `tracing` layer feeding the in-memory buffer shown by the log panel.
+pub struct TuiLogLayer;
+
+impl<S: Subscriber> Layer<S> for TuiLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        LOG_BUFFER.lock().push(LogRecord {
+            level: *event.metadata().level(),
+            message: visitor.message + &visitor.fields,
+        });
+        LOG_GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Set up logging to both the log panel and a log file.
+///
+/// Logs go to exactly `file` when one is given, and to the daily rotated
+/// `default_log_file` otherwise. The file only receives records up to
+/// `level`, falling back to `RUST_LOG` when unset. Records are written from
+/// a background thread, so the returned guard must be kept alive (and
+/// dropped before exiting) for them to be flushed.
+pub fn init(
+    level: Option<LogLevel>,
+    file: Option<&Path>,
+) -> Result<WorkerGuard> {
+    let appender = match file {
+        Some(file) => file_appender(file, Rotation::NEVER)?,
+        None => file_appender(&default_log_file(), Rotation::DAILY)?,
+    };
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let file_filter = match level {
+        Some(level) => EnvFilter::new(level.as_str()),
+        None => EnvFilter::from_default_env(),
+    };
     let file_subscriber = fmt::layer()
         .with_file(true)
         .with_line_number(true)
-        .with_writer(log_file)
+        .with_writer(writer)
         .with_target(false)
         .with_ansi(false)
-        .with_filter(EnvFilter::from_default_env());
+        .with_filter(file_filter);
+    let tui_subscriber = TuiLogLayer.with_filter(
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+            EnvFilter::new(format!("{}=debug", env!("CARGO_CRATE_NAME")))
+        }),
+    );
 
     tracing_subscriber::registry()
         .with(file_subscriber)
+        .with(tui_subscriber)
         .try_init()?;
-    Ok(())
+    Ok(guard)
+}
+
+/// An appender writing to `file`, suffixed with the date when rotated.
+fn file_appender(
+    file: &Path,
+    rotation: Rotation,
+) -> Result<RollingFileAppender> {
+    let directory = file
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    std::fs::create_dir_all(directory)?;
+    let prefix = file.file_name().map_or_else(
+        || env!("CARGO_PKG_NAME").into(),
+        |name| name.to_os_string(),
+    );
+    let mut builder = RollingFileAppender::builder()
+        .rotation(rotation.clone())
+        .filename_prefix(prefix.to_string_lossy());
+    if rotation != Rotation::NEVER {
+        builder = builder.max_log_files(MAX_LOG_FILES);
+    }
+    Ok(builder.build(directory)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_level_deserialization() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            level: LogLevel,
+        }
+        let wrapper: Wrapper = toml::from_str(r#"level = "warn""#).unwrap();
+        assert_eq!(wrapper.level, LogLevel::Warn);
+        assert_eq!(wrapper.level.as_str(), "warn");
+    }
+
+    #[test]
+    fn test_tui_layer_records_message_and_fields() {
+        let subscriber = tracing_subscriber::registry().with(TuiLogLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(answer = 42, "tui layer test");
+        });
+
+        let buffer = LOG_BUFFER.lock();
+        let record = buffer
+            .iter()
+            .find(|r| r.message.starts_with("tui layer test"))
+            .expect("the event should have been recorded");
+        assert_eq!(record.level, Level::WARN);
+        assert_eq!(record.message, "tui layer test answer=42");
+    }
+
+    #[test]
+    fn test_explicit_log_file_is_not_rotated() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("logs").join("tv.log");
+        let mut appender = file_appender(&file, Rotation::NEVER).unwrap();
+        appender.write_all(b"hello\n").unwrap();
+        appender.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "hello\n");
+        assert_eq!(
+            std::fs::read_dir(file.parent().unwrap()).unwrap().count(),
+            1
+        );
+    }
 }
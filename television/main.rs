@@ -2,49 +2,76 @@ use anyhow::Result;
 use clap::Parser;
 use std::env;
 use std::io::{BufWriter, IsTerminal, Write, stdout};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use television::cli::ChannelCli;
 use television::config::layers::ConfigLayers;
-use television::config::shell_integration::ShellIntegrationConfig;
 use television::{
     app::App,
     cable::{Cable, load_cable},
     channels::prototypes::ChannelPrototype,
     cli::{
-        args::{Cli, Command},
+        PostProcessedCli,
+        args::{AutocompleteOutput, Cli, Command},
         guess_channel_from_prompt, list_channels, post_process,
     },
-    config::{Config, ConfigEnv},
+    config::{Config, ConfigEnv, default_config_from_file},
     errors::os_error_exit,
     gh::update_local_channels,
+    session::Session,
     television::Mode,
     utils::clipboard::CLIPBOARD,
     utils::{
+        command::{CommandShell, execute_action, set_command_shell},
+        diagnostics::Diagnostics,
+        keys::echo_keys,
         shell::{
             Shell, completion_script, render_autocomplete_script_template,
         },
         stdin::is_readable_stdin,
     },
 };
-use tracing::{debug, info};
+use tracing::{debug, error, info};
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<()> {
     television::errors::init()?;
-    television::logging::init()?;
-
-    debug!("\n\n====  NEW SESSION  =====\n");
 
     let readable_stdin = is_readable_stdin();
 
-    let cli = post_process(Cli::parse(), readable_stdin);
-    debug!("PostProcessedCli: {:?}", cli);
+    let mut cli = post_process(Cli::parse(), readable_stdin);
 
     // load the configuration file
-    debug!("Loading configuration...");
-    let base_config =
-        Config::new(&ConfigEnv::init()?, cli.global.config_file.as_deref())?;
+    let base_config = match Config::new(
+        &ConfigEnv::init()?,
+        cli.global.config_file.as_deref(),
+    ) {
+        Ok(config) => config,
+        // `tv info` reports broken configuration files rather than
+        // refusing to run
+        Err(_) if matches!(cli.global.command, Some(Command::Info { .. })) => {
+            default_config_from_file()?
+        }
+        Err(e) => return Err(e),
+    };
+
+    // logging settings can come from the configuration file, so logging is
+    // only set up once it has been loaded
+    let log_guard = television::logging::init(
+        cli.global.log_level.or(base_config.application.log_level),
+        cli.global
+            .log_file
+            .as_deref()
+            .or(base_config.application.log_file.as_deref()),
+    )?;
+
+    debug!("\n\n====  NEW SESSION  =====\n");
+    debug!("PostProcessedCli: {:?}", cli);
+
+    // run commands through the configured shell
+    if let Some(shell) = &base_config.application.shell {
+        set_command_shell(CommandShell::from_config(shell)?);
+    }
 
     let cable_dir = cli
         .global
@@ -54,11 +81,19 @@ async fn main() -> Result<()> {
 
     debug!("Loading cable channels...");
     let cable = load_cable(&cable_dir);
+    resolve_path_arguments(&mut cli, &cable);
 
     // handle subcommands
     debug!("Handling subcommands...");
     if let Some(subcommand) = &cli.global.command {
-        handle_subcommand(subcommand, &cable, &base_config.shell_integration)?;
+        handle_subcommand(
+            subcommand,
+            &cable,
+            &cable_dir,
+            &base_config,
+            &cli,
+            readable_stdin,
+        )?;
     }
 
     // optionally change the working directory
@@ -67,6 +102,17 @@ async fn main() -> Result<()> {
             .unwrap_or_else(|e| os_error_exit(&e.to_string()));
     }
 
+    // pick up where the last session left off
+    let resume = cli.global.resume || base_config.application.resume;
+    let data_dir = base_config.application.data_dir.clone();
+    if resume
+        && !readable_stdin
+        && let Some(session) = Session::load(&data_dir)
+        && session.restore(&mut cli, &cable)
+    {
+        info!("Restored the last session");
+    }
+
     // determine the base channel prototype
     debug!("Determining base channel prototype...");
     let channel_prototype =
@@ -94,6 +140,14 @@ async fn main() -> Result<()> {
     debug!("Running application...");
     let output = app.run(stdout().is_terminal(), false).await?;
     info!("App output: {:?}", output);
+    if resume
+        && let Err(e) =
+            Session::from_television(&app.television).save(&data_dir)
+    {
+        error!("Failed to save the session: {}", e);
+    }
+    // removes the info file, `exit` doesn't run destructors
+    drop(app);
 
     let stdout_handle = stdout().lock();
     let mut bufwriter = BufWriter::new(stdout_handle);
@@ -101,12 +155,103 @@ async fn main() -> Result<()> {
         writeln!(bufwriter, "{}", key)?;
     }
     if let Some(entries) = output.selected_entries {
-        for entry in &entries {
-            writeln!(bufwriter, "{}", entry.output()?)?;
+        if let Some(prompt) = &cli.channel.autocomplete_prompt
+            && cli.channel.autocomplete_output == AutocompleteOutput::Full
+        {
+            let selections = entries
+                .iter()
+                .map(television::channels::entry::Entry::output)
+                .collect::<Result<Vec<_>>>()?;
+            writeln!(
+                bufwriter,
+                "{}",
+                substitute_into_prompt(prompt, &selections)
+            )?;
+        } else {
+            for entry in &entries {
+                writeln!(bufwriter, "{}", entry.output()?)?;
+            }
         }
     }
+    for command_output in &output.command_outputs {
+        write!(bufwriter, "{}", command_output.summary())?;
+    }
     bufwriter.flush()?;
-    exit(0);
+    // flush pending log records, `exit` doesn't run destructors
+    drop(log_guard);
+    // the terminal has been restored by now, hand it over to the action
+    if let Some((action_spec, entries)) = output.external_action {
+        let status = execute_action(&action_spec, &entries)?;
+        exit(status.code().unwrap_or(1));
+    }
+    exit(output.exit_status.code());
+}
+
+/// The placeholder in an autocomplete prompt standing for the selection.
+const PROMPT_PLACEHOLDER: &str = "{}";
+
+/// Substitute the selected entries (space separated) for the prompt's `{}`
+/// placeholder, or append them to the prompt when it has none.
+fn substitute_into_prompt(prompt: &str, selections: &[String]) -> String {
+    let selection = selections.join(" ");
+    if prompt.contains(PROMPT_PLACEHOLDER) {
+        prompt.replace(PROMPT_PLACEHOLDER, &selection)
+    } else if prompt.is_empty() || prompt.ends_with(char::is_whitespace) {
+        format!("{prompt}{selection}")
+    } else {
+        format!("{prompt} {selection}")
+    }
+}
+
+/// The channel used when `tv` is given a file instead of a channel.
+const FILES_CHANNEL: &str = "files";
+
+/// Make sense of paths given as positional arguments.
+///
+/// - `tv <dir>` starts the default channel in `<dir>` (unless a channel is
+///   named like that).
+/// - `tv <file>` starts the files channel in the file's directory with the
+///   input prefilled with its name.
+/// - `tv <channel> <dir>` starts `<channel>` in `<dir>` and
+///   `tv <channel> <file>` does the same as above with `<channel>`.
+pub fn resolve_path_arguments(cli: &mut PostProcessedCli, cable: &Cable) {
+    if let Some(channel) = cli.channel.channel.clone()
+        && !cable.has_channel(&channel)
+    {
+        let path = PathBuf::from(&channel);
+        if path.is_dir() {
+            debug!("Using {:?} as the working directory", path);
+            cli.channel.channel = None;
+            cli.global.workdir = Some(path);
+        } else if path.is_file() {
+            debug!("Looking for file {:?}", path);
+            cli.channel.channel = cable
+                .has_channel(FILES_CHANNEL)
+                .then(|| FILES_CHANNEL.to_string());
+            cli.global.workdir = None;
+            prefill_with_file(cli, &path);
+        }
+    } else if let Some(path) = cli.global.workdir.clone()
+        && path.is_file()
+    {
+        debug!("Looking for file {:?}", path);
+        cli.global.workdir = None;
+        prefill_with_file(cli, &path);
+    }
+}
+
+/// Start in `file`'s directory with the input prefilled with its name.
+fn prefill_with_file(cli: &mut PostProcessedCli, file: &Path) {
+    if let Some(parent) = file.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        cli.global.workdir = Some(parent.to_path_buf());
+    }
+    if cli.channel.input.is_none() {
+        cli.channel.input = file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned());
+    }
 }
 
 pub fn set_current_dir(path: &PathBuf) -> Result<()> {
@@ -117,7 +262,10 @@ pub fn set_current_dir(path: &PathBuf) -> Result<()> {
 pub fn handle_subcommand(
     command: &Command,
     cable: &Cable,
-    shell_integration_config: &ShellIntegrationConfig,
+    cable_dir: &Path,
+    config: &Config,
+    cli: &PostProcessedCli,
+    readable_stdin: bool,
 ) -> Result<()> {
     match command {
         Command::ListChannels => {
@@ -132,11 +280,29 @@ pub fn handle_subcommand(
             let script = render_autocomplete_script_template(
                 target_shell,
                 completion_script(target_shell)?,
-                shell_integration_config,
+                &config.shell_integration,
             )?;
             println!("{script}");
             exit(0);
         }
+        Command::Info { json } => {
+            let diagnostics = Diagnostics::collect(
+                cli.global.config_file.as_deref(),
+                cable_dir,
+                config,
+                readable_stdin,
+            );
+            if *json {
+                println!("{}", diagnostics.to_json()?);
+            } else {
+                println!("{diagnostics}");
+            }
+            exit(0);
+        }
+        Command::Keys => {
+            echo_keys()?;
+            exit(0);
+        }
         Command::UpdateChannels { force } => {
             update_local_channels(force)?;
             exit(0);
@@ -371,4 +537,99 @@ mod tests {
         assert_eq!(channel.metadata.name, "Custom Channel");
         assert_eq!(channel.source.command.inner[0].raw(), "fd -t f -H");
     }
+
+    fn cli_with(
+        channel: Option<&Path>,
+        workdir: Option<&Path>,
+    ) -> PostProcessedCli {
+        let mut cli = PostProcessedCli::default();
+        cli.channel.channel =
+            channel.map(|path| path.to_string_lossy().into_owned());
+        cli.global.workdir = workdir.map(Path::to_path_buf);
+        cli
+    }
+
+    #[test]
+    fn test_resolve_path_arguments() {
+        let dir = tempfile::tempdir().unwrap();
+        let project = dir.path().join("project");
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        let file = project.join("src").join("main.rs");
+        std::fs::write(&file, "fn main() {}").unwrap();
+        let cable = Cable::from_prototypes(vec![
+            ChannelPrototype::new("files", "fd -t f"),
+            ChannelPrototype::new("dirs", "fd -t d"),
+        ]);
+
+        // `tv <dir>`
+        let mut cli = cli_with(Some(&project), None);
+        resolve_path_arguments(&mut cli, &cable);
+        assert_eq!(cli.channel.channel, None);
+        assert_eq!(cli.global.workdir, Some(project.clone()));
+
+        // `tv <file>`
+        let mut cli = cli_with(Some(&file), None);
+        resolve_path_arguments(&mut cli, &cable);
+        assert_eq!(cli.channel.channel, Some("files".to_string()));
+        assert_eq!(cli.global.workdir, Some(project.join("src")));
+        assert_eq!(cli.channel.input, Some("main.rs".to_string()));
+
+        // `tv <channel> <dir>`
+        let mut cli = cli_with(Some(Path::new("dirs")), Some(&project));
+        resolve_path_arguments(&mut cli, &cable);
+        assert_eq!(cli.channel.channel, Some("dirs".to_string()));
+        assert_eq!(cli.global.workdir, Some(project.clone()));
+        assert_eq!(cli.channel.input, None);
+
+        // `tv <channel> <file>` doesn't override an explicit input
+        let mut cli = cli_with(Some(Path::new("dirs")), Some(&file));
+        cli.channel.input = Some("foo".to_string());
+        resolve_path_arguments(&mut cli, &cable);
+        assert_eq!(cli.channel.channel, Some("dirs".to_string()));
+        assert_eq!(cli.global.workdir, Some(project.join("src")));
+        assert_eq!(cli.channel.input, Some("foo".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_path_arguments_channel_names_win() {
+        let dir = tempfile::tempdir().unwrap();
+        let name = dir.path().to_string_lossy().into_owned();
+        let cable =
+            Cable::from_prototypes(vec![ChannelPrototype::new(&name, "ls")]);
+
+        let mut cli = cli_with(Some(dir.path()), None);
+        resolve_path_arguments(&mut cli, &cable);
+        assert_eq!(cli.channel.channel, Some(name));
+        assert_eq!(cli.global.workdir, None);
+
+        // neither a channel nor a path: left for `determine_channel`
+        let mut cli = cli_with(Some(&dir.path().join("nope")), None);
+        resolve_path_arguments(&mut cli, &cable);
+        assert!(cli.channel.channel.is_some());
+    }
+
+    #[test]
+    fn test_substitute_into_prompt() {
+        let selection = vec!["main".to_string()];
+        assert_eq!(
+            substitute_into_prompt("git checkout ", &selection),
+            "git checkout main"
+        );
+        assert_eq!(
+            substitute_into_prompt("git checkout", &selection),
+            "git checkout main"
+        );
+        assert_eq!(substitute_into_prompt("", &selection), "main");
+        assert_eq!(
+            substitute_into_prompt("git checkout {} --quiet", &selection),
+            "git checkout main --quiet"
+        );
+        assert_eq!(
+            substitute_into_prompt(
+                "cp {} /tmp",
+                &["a.txt".to_string(), "b.txt".to_string()]
+            ),
+            "cp a.txt b.txt /tmp"
+        );
+    }
 }
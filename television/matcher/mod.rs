@@ -7,6 +7,9 @@ pub mod lazy;
 pub mod matched_item;
 
 const MATCHER_TICK_TIMEOUT: u64 = 2;
+/// The maximum number of items a single call to [`Matcher::results`] returns,
+/// larger result sets should be walked with [`Matcher::results_iter`].
+pub const MAX_RESULTS: u32 = 10_000;
 
 /// The status of the fuzzy matcher.
 ///
@@ -83,6 +86,14 @@ where
         self.status = self.inner.tick(MATCHER_TICK_TIMEOUT).into();
     }
 
+    /// Refresh the item counts from the latest snapshot without fetching any
+    /// result.
+    pub fn update_counts(&mut self) {
+        let snapshot = self.inner.snapshot();
+        self.total_item_count = snapshot.item_count();
+        self.matched_item_count = snapshot.matched_item_count();
+    }
+
     /// Get an injector that can be used to push items into the fuzzy matcher.
     ///
     /// This can be used at any time to push items into the fuzzy matcher.
@@ -131,7 +142,7 @@ where
     ///
     /// The `num_entries` parameter specifies the number of entries to return,
     /// and the `offset` parameter specifies the offset of the first entry to
-    /// return.
+    /// return. At most [`MAX_RESULTS`] entries are returned.
     ///
     /// The returned items are `MatchedItem`s that contain the matched item, the
     /// dimension against which it was matched, represented as a string, and the
@@ -159,56 +170,56 @@ where
         num_entries: u32,
         offset: u32,
     ) -> Vec<matched_item::MatchedItem<I>> {
+        self.results_iter(offset)
+            .take(num_entries.min(MAX_RESULTS) as usize)
+            .collect()
+    }
+
+    /// Lazily iterate over the matched items, starting at `offset`.
+    ///
+    /// Unlike [`Self::results`], this isn't capped and only computes the
+    /// match indices of the items actually consumed, which makes it suitable
+    /// for walking very large result sets.
+    /// The iterator holds the global matcher lock until it is dropped.
+    pub fn results_iter(
+        &mut self,
+        offset: u32,
+    ) -> impl Iterator<Item = matched_item::MatchedItem<I>> + '_ {
         let snapshot = self.inner.snapshot();
         self.total_item_count = snapshot.item_count();
         self.matched_item_count = snapshot.matched_item_count();
-
-        // If the offset is greater than the number of matched items, return an empty Vec
-        if offset >= self.matched_item_count {
-            return Vec::new();
-        }
-        // If we're asking for more entries than available, adjust the count
-        let num_entries = if offset + num_entries > self.matched_item_count {
-            self.matched_item_count - offset
-        } else {
-            num_entries
-        };
+        let count = self.matched_item_count;
 
         // Clear the pre-allocated match indices buffer for safety
-        self.col_indices_buffer.clear();
+        let buffer = &mut self.col_indices_buffer;
+        buffer.clear();
         let mut matcher = lazy::MATCHER.lock();
 
-        // PERF: Pre-allocate the results Vec so we avoid repeated reallocations
-        let mut results = Vec::with_capacity(num_entries as usize);
+        snapshot
+            .matched_items(offset.min(count)..count)
+            .map(move |item| {
+                snapshot.pattern().column_pattern(0).indices(
+                    item.matcher_columns[0].slice(..),
+                    &mut matcher,
+                    buffer,
+                );
 
-        for item in snapshot.matched_items(
-            offset..(num_entries + offset).min(self.matched_item_count),
-        ) {
-            snapshot.pattern().column_pattern(0).indices(
-                item.matcher_columns[0].slice(..),
-                &mut matcher,
-                &mut self.col_indices_buffer,
-            );
-
-            // PERF: Avoid unnecessary sorting
-            if self.col_indices_buffer.len() > 1 {
-                self.col_indices_buffer.sort_unstable();
-                self.col_indices_buffer.dedup();
-            }
-
-            let indices: Vec<u32> =
-                self.col_indices_buffer.drain(..).collect();
-            // PERF: this ends up being quite expensive
-            let matched_string = item.matcher_columns[0].to_string();
+                // PERF: Avoid unnecessary sorting
+                if buffer.len() > 1 {
+                    buffer.sort_unstable();
+                    buffer.dedup();
+                }
 
-            results.push(matched_item::MatchedItem {
-                inner: item.data.clone(),
-                matched_string,
-                match_indices: indices,
-            });
-        }
+                let indices: Vec<u32> = buffer.drain(..).collect();
+                // PERF: this ends up being quite expensive
+                let matched_string = item.matcher_columns[0].to_string();
 
-        results
+                matched_item::MatchedItem {
+                    inner: item.data.clone(),
+                    matched_string,
+                    match_indices: indices,
+                }
+            })
     }
 
     /// Get a single matched item.
@@ -254,6 +265,38 @@ where
         })
     }
 
+    /// Keep only the items for which `keep` returns `true`.
+    ///
+    /// `nucleo` has no way of removing items so the item set is rebuilt from
+    /// the current snapshot. Items pushed concurrently through an injector
+    /// obtained before calling this method are lost, so callers wait for
+    /// the source to be done loading.
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&matched_item::MatchedItem<I>) -> bool,
+    {
+        let snapshot = self.inner.snapshot();
+        let kept: Vec<_> = (0..snapshot.item_count())
+            .filter_map(|index| snapshot.get_item(index))
+            .filter_map(|item| {
+                let matched_item = matched_item::MatchedItem {
+                    inner: item.data.clone(),
+                    matched_string: item.matcher_columns[0].to_string(),
+                    match_indices: Vec::new(),
+                };
+                keep(&matched_item).then(|| {
+                    (matched_item.inner, item.matcher_columns[0].clone())
+                })
+            })
+            .collect();
+
+        self.inner.restart(true);
+        let injector = self.inner.injector();
+        for (data, column) in kept {
+            injector.push(data, |_, cols| cols[0] = column);
+        }
+    }
+
     /// Restart the matcher.
     ///
     /// This will reset the matcher to its initial state, clearing all
@@ -267,3 +310,72 @@ where
         self.col_indices_buffer.clear();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settle(matcher: &mut Matcher<()>) {
+        matcher.tick();
+        while matcher.status.running {
+            matcher.tick();
+        }
+    }
+
+    #[test]
+    fn test_retain_removes_items() {
+        let mut matcher = Matcher::<()>::new(&config::Config::default());
+        let injector = matcher.injector();
+        for line in ["alpha", "beta", "gamma"] {
+            injector.push((), |(), cols| cols[0] = line.into());
+        }
+        matcher.find("");
+        settle(&mut matcher);
+        assert_eq!(matcher.results(10, 0).len(), 3);
+        assert_eq!(matcher.total_item_count, 3);
+
+        matcher.retain(|item| item.matched_string != "beta");
+        settle(&mut matcher);
+
+        let results = matcher.results(10, 0);
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|item| item.matched_string != "beta"));
+        assert_eq!(matcher.total_item_count, 2);
+        assert_eq!(matcher.matched_item_count, 2);
+    }
+
+    #[test]
+    fn test_large_result_sets_are_capped_or_paged() {
+        const ITEMS: u32 = 1_000_000;
+        let mut matcher = Matcher::<()>::new(&config::Config::default());
+        let injector = matcher.injector();
+        for i in 0..ITEMS {
+            injector.push((), |(), cols| cols[0] = i.to_string().into());
+        }
+        matcher.find("");
+        settle(&mut matcher);
+        assert_eq!(matcher.matched_item_count, ITEMS);
+
+        // a single call never materializes more than the cap
+        assert_eq!(matcher.results(ITEMS, 0).len(), MAX_RESULTS as usize);
+        assert_eq!(matcher.results(ITEMS, ITEMS - 5).len(), 5);
+
+        // while the iterator only computes what's consumed, page by page
+        let page = matcher
+            .results_iter(ITEMS - 20)
+            .take(10)
+            .map(|item| item.matched_string)
+            .collect::<Vec<_>>();
+        assert_eq!(page.len(), 10);
+        assert_eq!(matcher.results_iter(ITEMS).count(), 0);
+
+        let mut walked = 0;
+        let mut longest = 0;
+        for item in matcher.results_iter(0) {
+            walked += 1;
+            longest = longest.max(item.matched_string.len());
+        }
+        assert_eq!(walked, ITEMS);
+        assert_eq!(longest, (ITEMS - 1).to_string().len());
+    }
+}
@@ -12,6 +12,19 @@ pub fn get_action_for_mouse_event(
 ) -> Action {
     let position = Position::new(event.column, event.row);
 
+    // the help overlay and panel float on top of everything else
+    if ui_layout.help_overlay.is_some()
+        || ui_layout
+            .help_panel
+            .is_some_and(|help| help.contains(position))
+    {
+        return match event.kind {
+            MouseEventKind::ScrollUp => Action::ScrollHelpUp,
+            MouseEventKind::ScrollDown => Action::ScrollHelpDown,
+            _ => Action::NoOp,
+        };
+    }
+
     // if the mouse is over the results or remote control, scroll the selection
     if matches!(mode, Mode::Channel) && ui_layout.results.contains(position)
         || matches!(mode, Mode::RemoteControl)
@@ -2,6 +2,13 @@ use crate::utils::{input::Input, strings::EMPTY_STRING};
 use ratatui::widgets::ListState;
 use std::sync::Arc;
 
+/// A list of entries to pick from, along with its input.
+///
+/// The picker tracks the selected entry both as an index among all the
+/// entries (`state`) and as a row of the list (`relative_state`), the
+/// difference between the two being the offset of the first row. Once the
+/// picker knows its height, every operation keeps the selection in view:
+/// `offset <= selected < offset + height`.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct Picker<T> {
     pub(crate) state: ListState,
@@ -10,6 +17,8 @@ pub struct Picker<T> {
     pub(crate) input: Input,
     pub entries: Arc<Vec<T>>,
     pub total_items: u32,
+    /// How many entries the list shows, as of the last drawn layout.
+    height: usize,
 }
 
 impl<T> Default for Picker<T> {
@@ -27,9 +36,15 @@ impl<T> Picker<T> {
             input: Input::new(input.unwrap_or(EMPTY_STRING.to_string())),
             entries: Arc::new(Vec::new()),
             total_items: 0,
+            height: 0,
         }
     }
 
+    /// How many entries the list shows.
+    pub(crate) fn height(&self) -> usize {
+        self.height
+    }
+
     pub(crate) fn offset(&self) -> usize {
         self.selected()
             .unwrap_or(0)
@@ -41,6 +56,10 @@ impl<T> Picker<T> {
         self
     }
 
+    pub(crate) fn set_inverted(&mut self, inverted: bool) {
+        self.inverted = inverted;
+    }
+
     pub(crate) fn reset_selection(&mut self) {
         self.state.select(Some(0));
         self.relative_state.select(Some(0));
@@ -50,41 +69,37 @@ impl<T> Picker<T> {
         self.input.reset();
     }
 
-    pub(crate) fn select_next(
-        &mut self,
-        step: u32,
-        total_items: usize,
-        height: usize,
-    ) {
+    pub(crate) fn select_next(&mut self, step: u32, total_items: usize) {
         if self.inverted {
             for _ in 0..step {
-                self.inner_prev(total_items, height);
+                self.inner_prev(total_items);
             }
         } else {
             for _ in 0..step {
-                self.inner_next(total_items, height);
+                self.inner_next(total_items);
             }
         }
     }
 
-    pub(crate) fn select_prev(
-        &mut self,
-        step: u32,
-        total_items: usize,
-        height: usize,
-    ) {
+    pub(crate) fn select_prev(&mut self, step: u32, total_items: usize) {
         if self.inverted {
             for _ in 0..step {
-                self.inner_next(total_items, height);
+                self.inner_next(total_items);
             }
         } else {
             for _ in 0..step {
-                self.inner_prev(total_items, height);
+                self.inner_prev(total_items);
             }
         }
     }
 
-    fn inner_next(&mut self, total_items: usize, height: usize) {
+    /// The last row of the list, which is the first one as long as the
+    /// picker hasn't been drawn yet.
+    fn last_row(&self) -> usize {
+        self.height.saturating_sub(1)
+    }
+
+    fn inner_next(&mut self, total_items: usize) {
         let selected = self.selected().unwrap_or(0);
         let relative_selected = self.relative_selected().unwrap_or(0);
 
@@ -95,12 +110,12 @@ impl<T> Picker<T> {
             self.relative_select(Some(0));
         } else {
             self.relative_select(Some(
-                (relative_selected + 1).min(height - 1),
+                (relative_selected + 1).min(self.last_row()),
             ));
         }
     }
 
-    fn inner_prev(&mut self, total_items: usize, height: usize) {
+    fn inner_prev(&mut self, total_items: usize) {
         let selected = self.selected().unwrap_or(0);
         let relative_selected = self.relative_selected().unwrap_or(0);
 
@@ -109,18 +124,18 @@ impl<T> Picker<T> {
         self.select(Some(new_selected));
 
         if new_selected == total_items - 1 {
-            self.relative_select(Some((height - 1).min(total_items - 1)));
+            self.relative_select(Some(self.last_row().min(total_items - 1)));
         } else {
             self.relative_select(Some(relative_selected.saturating_sub(1)));
         }
     }
+
     /// Generic cursor movement helper.
     pub fn move_cursor(
         &mut self,
         movement: Movement,
         step: u32,
         total_items: usize,
-        picker_ui_height: usize,
     ) {
         // Early return for empty collections
         if total_items == 0 {
@@ -129,15 +144,71 @@ impl<T> Picker<T> {
 
         match movement {
             Movement::Next => {
-                self.select_next(step, total_items, picker_ui_height);
+                self.select_next(step, total_items);
             }
             Movement::Prev => {
-                self.select_prev(step, total_items, picker_ui_height);
+                self.select_prev(step, total_items);
             }
         }
     }
 }
 
+impl<T> Picker<T> {
+    /// Select the entry at the given (0-based) index, scrolling the view only
+    /// if that entry is not already visible.
+    pub fn select_index(&mut self, index: usize, total_items: usize) {
+        let height = self.height;
+        if total_items == 0 || height == 0 {
+            return;
+        }
+        let index = index.min(total_items - 1);
+        let offset = self.offset();
+        let relative = if index < offset {
+            0
+        } else if index - offset < height {
+            index - offset
+        } else {
+            height - 1
+        };
+        self.select(Some(index));
+        self.relative_select(Some(relative));
+    }
+
+    /// Show `height` entries from now on (e.g. after a resize), keeping the
+    /// selection visible without leaving empty rows past the last entry.
+    pub fn set_height(&mut self, total_items: usize, height: usize) {
+        self.height = height;
+        if total_items == 0 || height == 0 {
+            return;
+        }
+        let selected = self.selected().unwrap_or(0).min(total_items - 1);
+        let offset = self
+            .offset()
+            .min(total_items.saturating_sub(height))
+            .min(selected)
+            .max(selected.saturating_sub(height - 1));
+        self.select(Some(selected));
+        self.relative_select(Some(selected - offset));
+    }
+
+    /// Show `height` entries from now on, scrolling down just enough to keep
+    /// the selection visible.
+    ///
+    /// Unlike `set_height`, this never scrolls up, so that entries taking up
+    /// several rows (e.g. below a group header) can be made to fit by trying
+    /// again with the entries at the new offset. Returns whether the list
+    /// scrolled.
+    pub fn fit_height(&mut self, height: usize) -> bool {
+        self.height = height;
+        let relative = self.relative_selected().unwrap_or(0);
+        if height == 0 || relative < height {
+            return false;
+        }
+        self.relative_select(Some(height - 1));
+        true
+    }
+}
+
 impl<Entry> Picker<Entry> {
     pub(crate) fn selected(&self) -> Option<usize> {
         self.state.selected()
@@ -179,7 +250,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(0));
         picker.relative_select(Some(0));
-        picker.select_next(1, 4, 3);
+        picker.height = 3;
+        picker.select_next(1, 4);
         assert_eq!(picker.selected(), Some(1), "selected");
         assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
     }
@@ -193,7 +265,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(1));
         picker.relative_select(Some(1));
-        picker.select_next(1, 4, 3);
+        picker.height = 3;
+        picker.select_next(1, 4);
         assert_eq!(picker.selected(), Some(2), "selected");
         assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
     }
@@ -207,7 +280,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(2));
         picker.relative_select(Some(2));
-        picker.select_next(1, 4, 3);
+        picker.height = 3;
+        picker.select_next(1, 4);
         assert_eq!(picker.selected(), Some(3), "selected");
         assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
     }
@@ -221,7 +295,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(3));
         picker.relative_select(Some(2));
-        picker.select_next(1, 4, 3);
+        picker.height = 3;
+        picker.select_next(1, 4);
         assert_eq!(picker.selected(), Some(0), "selected");
         assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
     }
@@ -235,7 +310,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(2));
         picker.relative_select(Some(2));
-        picker.select_next(1, 3, 4);
+        picker.height = 4;
+        picker.select_next(1, 3);
         assert_eq!(picker.selected(), Some(0), "selected");
         assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
     }
@@ -249,7 +325,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(1));
         picker.relative_select(Some(1));
-        picker.select_prev(1, 4, 3);
+        picker.height = 3;
+        picker.select_prev(1, 4);
         assert_eq!(picker.selected(), Some(0), "selected");
         assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
     }
@@ -263,7 +340,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(0));
         picker.relative_select(Some(0));
-        picker.select_prev(1, 4, 3);
+        picker.height = 3;
+        picker.select_prev(1, 4);
         assert_eq!(picker.selected(), Some(3), "selected");
         assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
     }
@@ -277,7 +355,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(3));
         picker.relative_select(Some(2));
-        picker.select_prev(1, 4, 3);
+        picker.height = 3;
+        picker.select_prev(1, 4);
         assert_eq!(picker.selected(), Some(2), "selected");
         assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
     }
@@ -291,7 +370,8 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(2));
         picker.relative_select(Some(2));
-        picker.select_prev(1, 4, 3);
+        picker.height = 3;
+        picker.select_prev(1, 4);
         assert_eq!(picker.selected(), Some(1), "selected");
         assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
     }
@@ -323,11 +403,197 @@ mod tests {
         let mut picker = Picker::<Entry>::default();
         picker.select(Some(0));
         picker.relative_select(Some(0));
-        picker.select_next(1, 4, 2);
+        picker.height = 2;
+        picker.select_next(1, 4);
         picker = picker.inverted();
-        picker.select_next(1, 4, 2);
+        picker.select_next(1, 4);
         assert!(picker.inverted, "inverted");
         assert_eq!(picker.selected(), Some(0), "selected");
         assert_eq!(picker.relative_selected(), Some(0), "relative_selected");
     }
+
+    /// - item 0         *
+    /// - item 1 S     R *
+    /// - item 2         * height
+    /// - item 3
+    /// - item 4 index
+    #[test]
+    fn test_picker_select_index_scrolls_when_out_of_view() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(1));
+        picker.relative_select(Some(1));
+        picker.height = 3;
+        picker.select_index(4, 5);
+        assert_eq!(picker.selected(), Some(4), "selected");
+        assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
+    }
+
+    /// - item 0         *
+    /// - item 1 S     R *
+    /// - item 2 index   * height
+    /// - item 3
+    #[test]
+    fn test_picker_select_index_within_view() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(1));
+        picker.relative_select(Some(1));
+        picker.height = 3;
+        picker.select_index(2, 4);
+        assert_eq!(picker.selected(), Some(2), "selected");
+        assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
+    }
+
+    /// - item 0
+    /// - item 1
+    /// - item 2         *
+    /// - item 3         * old height
+    /// - item 4 S     R *
+    ///
+    /// After growing to a height of 4 the list is shown from item 1 so that
+    /// no empty rows are left at the bottom.
+    #[test]
+    fn test_picker_set_height_after_growing() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(4));
+        picker.relative_select(Some(2));
+        picker.set_height(5, 4);
+        assert_eq!(picker.selected(), Some(4), "selected");
+        assert_eq!(picker.relative_selected(), Some(3), "relative_selected");
+        assert_eq!(picker.offset(), 1, "offset");
+    }
+
+    /// - item 0         *
+    /// - item 1         *
+    /// - item 2         * old height
+    /// - item 3 S     R *
+    /// - item 4
+    ///
+    /// After shrinking to a height of 2 the selection stays in view.
+    #[test]
+    fn test_picker_set_height_after_shrinking() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(3));
+        picker.relative_select(Some(3));
+        picker.set_height(5, 2);
+        assert_eq!(picker.selected(), Some(3), "selected");
+        assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
+        assert_eq!(picker.offset(), 2, "offset");
+    }
+
+    #[test]
+    fn test_picker_set_height_when_results_shrunk() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(8));
+        picker.relative_select(Some(2));
+        picker.set_height(3, 10);
+        assert_eq!(picker.selected(), Some(2), "selected");
+        assert_eq!(picker.relative_selected(), Some(2), "relative_selected");
+    }
+
+    /// - item 0
+    /// - item 1         *
+    /// - item 2         * old height
+    /// - item 3 S     R *
+    ///
+    /// Group headers leave room for 2 entries only: scroll down by one.
+    #[test]
+    fn test_picker_fit_height() {
+        let mut picker = Picker::<Entry>::default();
+        picker.select(Some(3));
+        picker.relative_select(Some(2));
+        assert!(picker.fit_height(2));
+        assert_eq!(picker.relative_selected(), Some(1), "relative_selected");
+        assert_eq!(picker.offset(), 2, "offset");
+        assert!(!picker.fit_height(3));
+        assert_eq!(picker.offset(), 2, "offset");
+    }
+
+    /// A small deterministic pseudo-random number generator, so that the
+    /// property tests below are reproducible.
+    struct XorShift(u64);
+
+    impl XorShift {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            usize::try_from(self.0 % u64::try_from(n).unwrap()).unwrap()
+        }
+    }
+
+    fn assert_selection_in_view(picker: &Picker<Entry>, total_items: usize) {
+        let selected = picker.selected().unwrap();
+        let offset = picker.offset();
+        let height = picker.height();
+        assert!(selected < total_items, "{selected} >= {total_items}");
+        assert!(
+            offset <= selected && selected < offset + height,
+            "{selected} out of view [{offset}, {})",
+            offset + height
+        );
+        assert!(
+            offset + height.min(total_items) <= total_items,
+            "scrolled past the last entry: {offset} + {height}"
+        );
+    }
+
+    #[test]
+    fn test_picker_selection_stays_in_view() {
+        for inverted in [false, true] {
+            let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+            for _ in 0..200 {
+                let mut total_items = 1 + rng.below(30);
+                let mut picker = Picker::<Entry>::default();
+                picker.set_inverted(inverted);
+                picker.reset_selection();
+                picker.set_height(total_items, 1 + rng.below(12));
+                for _ in 0..100 {
+                    let page = u32::try_from(picker.height()).unwrap();
+                    match rng.below(6) {
+                        0 => {
+                            picker.move_cursor(Movement::Next, 1, total_items)
+                        }
+                        1 => {
+                            picker.move_cursor(Movement::Prev, 1, total_items)
+                        }
+                        2 => {
+                            picker.move_cursor(
+                                Movement::Next,
+                                page,
+                                total_items,
+                            );
+                        }
+                        3 => {
+                            picker.move_cursor(
+                                Movement::Prev,
+                                page,
+                                total_items,
+                            );
+                        }
+                        4 => {
+                            let index = rng.below(total_items);
+                            picker.select_index(index, total_items);
+                        }
+                        _ => {
+                            // the results changed and the terminal resized
+                            total_items = 1 + rng.below(30);
+                            picker.set_height(total_items, 1 + rng.below(12));
+                        }
+                    }
+                    assert_selection_in_view(&picker, total_items);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_picker_prefilled_input_cursor_at_end() {
+        let picker = Picker::<Entry>::new(Some("héllo".to_string()));
+        assert_eq!(picker.input.value(), "héllo");
+        assert_eq!(picker.input.cursor(), 5);
+
+        let picker = Picker::<Entry>::new(None);
+        assert_eq!(picker.input.value(), "");
+        assert_eq!(picker.input.cursor(), 0);
+    }
 }
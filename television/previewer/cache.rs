@@ -9,14 +9,18 @@ use tracing::debug;
 /// This does seem kind of arbitrary for now, will need to play around with it.
 /// Assuming a worst case scenario where files are an average of 1 MB this means
 /// the cache will never exceed 50 MB which sounds safe enough.
-const DEFAULT_CACHE_SIZE: usize = 50;
+pub const DEFAULT_CACHE_SIZE: usize = 50;
 
 /// A cache for previews.
 /// The cache is implemented as an LRU cache with a fixed size.
+///
+/// Previews are only valid for the preview pane width they were generated
+/// for, since commands may wrap or truncate their output to fit it.
 #[derive(Debug)]
 pub struct Cache {
     entries: FxHashMap<String, Text<'static>>,
     ring_set: RingSet<String>,
+    width: u16,
 }
 
 impl Cache {
@@ -25,17 +29,27 @@ impl Cache {
         Cache {
             entries: FxHashMap::default(),
             ring_set: RingSet::with_capacity(capacity),
+            width: 0,
         }
     }
 
-    pub fn get(&self, key: &str) -> Option<Text<'static>> {
+    pub fn get(&self, key: &str, width: u16) -> Option<Text<'static>> {
+        if width != self.width {
+            return None;
+        }
         self.entries.get(key).cloned()
     }
 
-    /// Insert a new preview into the cache.
+    /// Insert a new preview generated for a pane `width` columns wide into
+    /// the cache.
     /// If the cache is full, the oldest entry will be removed.
     /// If the key is already in the cache, the preview will be updated.
-    pub fn insert(&mut self, key: &str, text: &Text<'static>) {
+    /// Previews generated for any other width are evicted.
+    pub fn insert(&mut self, key: &str, width: u16, text: &Text<'static>) {
+        if width != self.width {
+            self.clear();
+            self.width = width;
+        }
         debug!("Inserting preview into cache for key: {:?}", key);
         let key = key.to_string();
         self.entries.insert(key.clone(), text.clone());
@@ -76,36 +90,56 @@ mod tests {
         let entry = "test";
         let preview = Text::raw("preview");
 
-        cache.insert(entry, &preview);
-        assert_eq!(cache.get(entry).unwrap(), preview);
+        cache.insert(entry, 80, &preview);
+        assert_eq!(cache.get(entry, 80).unwrap(), preview);
         assert_eq!(cache.size(), 1);
 
         // override cache content for the same key
         let other_preview = Text::raw("some content");
-        cache.insert(entry, &other_preview);
-        assert_eq!(cache.get(entry).unwrap(), other_preview);
+        cache.insert(entry, 80, &other_preview);
+        assert_eq!(cache.get(entry, 80).unwrap(), other_preview);
         assert_eq!(cache.size(), 1);
 
         // insert new entries to trigger eviction
         let new_entry = "new_test";
         let new_preview = Text::raw("new preview");
-        cache.insert(new_entry, &new_preview);
+        cache.insert(new_entry, 80, &new_preview);
         // the two previews should still be available
         assert_eq!(cache.size(), 2);
-        assert_eq!(cache.get(new_entry).unwrap(), new_preview);
-        assert_eq!(cache.get(entry).unwrap(), other_preview);
+        assert_eq!(cache.get(new_entry, 80).unwrap(), new_preview);
+        assert_eq!(cache.get(entry, 80).unwrap(), other_preview);
         // this one should trigger eviction
         let another_entry = "another_test";
-        cache.insert(another_entry, &Text::raw("another preview"));
+        cache.insert(another_entry, 80, &Text::raw("another preview"));
 
         assert_eq!(cache.size(), 2);
-        assert!(cache.get(entry).is_none());
-        assert!(cache.get(new_entry).is_some());
-        assert!(cache.get(another_entry).is_some());
-        assert_eq!(cache.get(new_entry).unwrap(), Text::raw("new preview"));
+        assert!(cache.get(entry, 80).is_none());
+        assert!(cache.get(new_entry, 80).is_some());
+        assert!(cache.get(another_entry, 80).is_some());
+        assert_eq!(
+            cache.get(new_entry, 80).unwrap(),
+            Text::raw("new preview")
+        );
         assert_eq!(
-            cache.get(another_entry).unwrap(),
+            cache.get(another_entry, 80).unwrap(),
             Text::raw("another preview")
         );
     }
+
+    #[test]
+    fn test_preview_cache_is_keyed_on_width() {
+        let mut cache = Cache::new(2);
+        let wide = Text::raw("a line that fits in 80 columns");
+        cache.insert("test", 80, &wide);
+        assert_eq!(cache.get("test", 80).unwrap(), wide);
+        // after a resize the preview wrapped for 80 columns isn't reused
+        assert!(cache.get("test", 20).is_none());
+
+        let narrow = Text::from("a line that\nfits in 20\ncolumns");
+        cache.insert("test", 20, &narrow);
+        assert_eq!(cache.get("test", 20).unwrap(), narrow);
+        assert_eq!(cache.size(), 1);
+        // previews for the previous width were evicted
+        assert!(cache.get("test", 80).is_none());
+    }
 }
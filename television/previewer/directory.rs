@@ -0,0 +1,154 @@
+use std::{cmp::Ordering, path::Path, str::FromStr};
+
+use devicons::FileIcon;
+use ratatui::{
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+};
+
+use crate::channels::prototypes::Template;
+
+/// Directories with more entries than this are cut short.
+const MAX_LISTED_ENTRIES: usize = 1000;
+
+/// How directories are previewed when the preview command fails on them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DirectoryPreview {
+    /// The command to preview directories with instead of the built-in
+    /// listing.
+    pub command: Option<Template>,
+    /// Whether to decorate the built-in listing with (nerd font) icons.
+    pub icons: bool,
+}
+
+struct Item {
+    name: String,
+    is_dir: bool,
+    size: u64,
+}
+
+/// A size in bytes written the way `ls -h` does, e.g. `4.0K`.
+pub fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["K", "M", "G", "T"];
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+    let mut tenths = bytes * 10 / 1024;
+    let mut unit = 0;
+    while tenths >= 10 * 1024 && unit + 1 < UNITS.len() {
+        tenths /= 1024;
+        unit += 1;
+    }
+    format!("{}.{}{}", tenths / 10, tenths % 10, UNITS[unit])
+}
+
+/// Lists the directory at `path`: sub-directories first, then files with
+/// their sizes, each sorted by name.
+pub fn directory_listing(path: &Path, icons: bool) -> Text<'static> {
+    let entries = match std::fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => return Text::from(format!("Can't list directory: {e}")),
+    };
+    let mut items: Vec<Item> = entries
+        .filter_map(Result::ok)
+        .map(|entry| {
+            // follow symlinks to tell directories apart
+            let metadata = std::fs::metadata(entry.path()).ok();
+            Item {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                is_dir: metadata.as_ref().is_some_and(|m| m.is_dir()),
+                size: metadata.map_or(0, |m| m.len()),
+            }
+        })
+        .collect();
+    if items.is_empty() {
+        return Text::from("(empty directory)".dim());
+    }
+    items.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+
+    let total = items.len();
+    let mut lines: Vec<Line<'static>> = items
+        .iter()
+        .take(MAX_LISTED_ENTRIES)
+        .map(|item| item_line(item, icons))
+        .collect();
+    if total > MAX_LISTED_ENTRIES {
+        lines.push(Line::from(
+            format!("… and {} more", total - MAX_LISTED_ENTRIES).dim(),
+        ));
+    }
+    Text::from(lines)
+}
+
+fn item_line(item: &Item, icons: bool) -> Line<'static> {
+    let size = if item.is_dir {
+        "-".to_string()
+    } else {
+        human_size(item.size)
+    };
+    let mut spans = vec![Span::from(format!("{size:>6}  ")).dim()];
+    if icons {
+        let icon = if item.is_dir {
+            FileIcon::from("/")
+        } else {
+            FileIcon::from(item.name.as_str())
+        };
+        spans.push(Span::styled(
+            format!("{} ", icon.icon),
+            Style::default()
+                .fg(Color::from_str(icon.color).unwrap_or_default()),
+        ));
+    }
+    if item.is_dir {
+        spans.push(Span::from(format!("{}/", item.name)).blue().bold());
+    } else {
+        spans.push(Span::from(item.name.clone()));
+    }
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0B");
+        assert_eq!(human_size(1023), "1023B");
+        assert_eq!(human_size(1024), "1.0K");
+        assert_eq!(human_size(1536), "1.5K");
+        assert_eq!(human_size(5 * 1024 * 1024), "5.0M");
+        assert_eq!(human_size(3 * 1024 * 1024 * 1024), "3.0G");
+    }
+
+    #[test]
+    fn test_directory_listing_puts_directories_first() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.txt"), "hello").unwrap();
+        std::fs::write(dir.path().join("A.md"), vec![0; 2048]).unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+
+        let lines: Vec<String> = directory_listing(dir.path(), false)
+            .lines
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            lines,
+            vec!["     -  src/", "  2.0K  A.md", "    5B  b.txt"]
+        );
+    }
+
+    #[test]
+    fn test_empty_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            directory_listing(dir.path(), true).lines[0].to_string(),
+            "(empty directory)"
+        );
+    }
+}
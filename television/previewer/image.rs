@@ -0,0 +1,668 @@
+use std::{
+    fmt::Write,
+    io::Read,
+    sync::{Arc, LazyLock},
+};
+
+use base64::{Engine, engine::general_purpose};
+use parking_lot::Mutex;
+use ratatui::text::{Line, Text};
+use rustc_hash::FxHashMap;
+
+/// Files larger than this aren't previewed as images.
+const MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+
+/// How much of a file is read to tell whether it is an image.
+const HEADER_BYTES: u64 = 32;
+
+/// The assumed size of a terminal cell in pixels, used to keep images from
+/// being upscaled and to preserve their aspect ratio.
+const CELL_PIXELS: (u32, u32) = (8, 16);
+
+/// The largest payload the kitty graphics protocol accepts per escape
+/// sequence.
+const KITTY_CHUNK_SIZE: usize = 4096;
+
+/// Removes every image placed with the kitty graphics protocol.
+pub const KITTY_DELETE_ALL: &str = "\x1b_Ga=d,d=A,q=2\x1b\\";
+
+/// Overrides the graphics protocol detection: `kitty`, `iterm`, `sixel` or
+/// `none`.
+pub const IMAGE_PROTOCOL_ENV_VAR: &str = "TV_IMAGE_PROTOCOL";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    Webp,
+}
+
+impl ImageFormat {
+    /// Recognizes an image from the magic bytes at the start of the file.
+    pub fn sniff(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a")
+        {
+            Some(Self::Gif)
+        } else if bytes.starts_with(b"BM") && bytes.len() > 26 {
+            Some(Self::Bmp)
+        } else if bytes.starts_with(b"RIFF")
+            && bytes.get(8..12) == Some(&b"WEBP"[..])
+        {
+            Some(Self::Webp)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::Gif => "GIF",
+            Self::Bmp => "BMP",
+            Self::Webp => "WebP",
+        }
+    }
+
+    /// The width and height of the image in pixels, read from its header.
+    fn dimensions(self, bytes: &[u8]) -> Option<(u32, u32)> {
+        let u16_le = |at: usize| {
+            bytes
+                .get(at..at + 2)
+                .map(|b| u32::from(u16::from_le_bytes([b[0], b[1]])))
+        };
+        let u16_be = |at: usize| {
+            bytes
+                .get(at..at + 2)
+                .map(|b| u32::from(u16::from_be_bytes([b[0], b[1]])))
+        };
+        let u24_le = |at: usize| {
+            bytes
+                .get(at..at + 3)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], 0]))
+        };
+        let u32_le = |at: usize| {
+            bytes
+                .get(at..at + 4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        };
+        let u32_be = |at: usize| {
+            bytes
+                .get(at..at + 4)
+                .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        };
+        match self {
+            // the IHDR chunk comes first
+            Self::Png => Some((u32_be(16)?, u32_be(20)?)),
+            Self::Gif => Some((u16_le(6)?, u16_le(8)?)),
+            // the height is negative for top-down bitmaps
+            Self::Bmp => {
+                Some((u32_le(18)?, u32_le(22)?.cast_signed().unsigned_abs()))
+            }
+            Self::Webp => match bytes.get(12..16)? {
+                b"VP8X" => Some((u24_le(24)? + 1, u24_le(27)? + 1)),
+                b"VP8L" => {
+                    let bits = u32_le(21)?;
+                    Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+                }
+                b"VP8 " => Some((u16_le(26)? & 0x3fff, u16_le(28)? & 0x3fff)),
+                _ => None,
+            },
+            Self::Jpeg => {
+                // walk the segments up to the start of frame
+                let mut at = 2;
+                loop {
+                    if *bytes.get(at)? != 0xff {
+                        return None;
+                    }
+                    let marker = *bytes.get(at + 1)?;
+                    let length = usize::try_from(u16_be(at + 2)?).ok()?;
+                    if matches!(marker, 0xc0..=0xcf)
+                        && !matches!(marker, 0xc4 | 0xc8 | 0xcc)
+                    {
+                        return Some((u16_be(at + 7)?, u16_be(at + 5)?));
+                    }
+                    at += 2 + length;
+                }
+            }
+        }
+    }
+}
+
+/// An image file to show in the preview pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewImage {
+    pub path: String,
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+    data: Arc<[u8]>,
+}
+
+impl PreviewImage {
+    /// Load the image at `path`, if it is a file recognized as an image.
+    ///
+    /// Only the header is read from files that aren't images.
+    pub fn load(path: &str) -> Option<Self> {
+        let mut file = std::fs::File::open(path).ok()?;
+        let metadata = file.metadata().ok()?;
+        if !metadata.is_file() || metadata.len() > MAX_IMAGE_BYTES {
+            return None;
+        }
+        let mut bytes = Vec::new();
+        (&mut file)
+            .take(HEADER_BYTES)
+            .read_to_end(&mut bytes)
+            .ok()?;
+        ImageFormat::sniff(&bytes)?;
+        file.read_to_end(&mut bytes).ok()?;
+        Self::from_bytes(path, bytes)
+    }
+
+    pub fn from_bytes(path: &str, bytes: Vec<u8>) -> Option<Self> {
+        let format = ImageFormat::sniff(&bytes)?;
+        let (width, height) = format.dimensions(&bytes)?;
+        Some(Self {
+            path: path.to_string(),
+            format,
+            width,
+            height,
+            data: bytes.into(),
+        })
+    }
+
+    /// What is shown instead of the image when it can't be displayed.
+    pub fn placeholder(&self) -> Text<'static> {
+        Text::from(vec![
+            Line::from(format!(
+                "{} image, {}×{}",
+                self.format.name(),
+                self.width,
+                self.height
+            )),
+            Line::from(format!("{} bytes", self.data.len())),
+        ])
+    }
+}
+
+/// A terminal graphics protocol images can be displayed with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+impl GraphicsProtocol {
+    /// Whether images of this format can be displayed: the terminal decodes
+    /// them on its own, except with sixels which tv decodes itself.
+    pub fn supports(self, format: ImageFormat) -> bool {
+        match self {
+            Self::Kitty => format == ImageFormat::Png,
+            Self::Iterm2 | Self::Sixel => true,
+        }
+    }
+}
+
+/// The graphics protocol of the terminal, detected once at startup.
+pub static GRAPHICS_PROTOCOL: LazyLock<Option<GraphicsProtocol>> =
+    LazyLock::new(|| protocol_from_env(|name| std::env::var(name).ok()));
+
+/// Guess the graphics protocol of the terminal from the environment.
+fn protocol_from_env(
+    var: impl Fn(&str) -> Option<String>,
+) -> Option<GraphicsProtocol> {
+    match var(IMAGE_PROTOCOL_ENV_VAR).as_deref() {
+        Some("kitty") => return Some(GraphicsProtocol::Kitty),
+        Some("iterm") => return Some(GraphicsProtocol::Iterm2),
+        Some("sixel") => return Some(GraphicsProtocol::Sixel),
+        Some("none") => return None,
+        _ => {}
+    }
+    // escape sequences would have to be wrapped to get through tmux
+    if var("TMUX").is_some() {
+        return None;
+    }
+    let term = var("TERM").unwrap_or_default();
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    if var("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term_program == "ghostty"
+    {
+        Some(GraphicsProtocol::Kitty)
+    } else if matches!(term_program.as_str(), "iTerm.app" | "WezTerm")
+        || var("LC_TERMINAL").as_deref() == Some("iTerm2")
+    {
+        Some(GraphicsProtocol::Iterm2)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// The number of columns and rows to show an image `width`x`height` pixels
+/// large in, fitting in `cols`x`rows` cells, keeping its aspect ratio and
+/// never scaling it up.
+pub fn fit_cells(width: u32, height: u32, cols: u16, rows: u16) -> (u16, u16) {
+    let natural_cols = u64::from(width.div_ceil(CELL_PIXELS.0).max(1));
+    let natural_rows = u64::from(height.div_ceil(CELL_PIXELS.1).max(1));
+    let (cols, rows) = (u64::from(cols), u64::from(rows));
+    let (fit_cols, fit_rows) = if natural_cols <= cols && natural_rows <= rows
+    {
+        (natural_cols, natural_rows)
+    } else if natural_cols * rows <= natural_rows * cols {
+        // the height is the limiting factor
+        (natural_cols * rows / natural_rows, rows)
+    } else {
+        (cols, natural_rows * cols / natural_cols)
+    };
+    (
+        u16::try_from(fit_cols.max(1)).unwrap_or(u16::MAX),
+        u16::try_from(fit_rows.max(1)).unwrap_or(u16::MAX),
+    )
+}
+
+/// The escape sequence displaying `image` over `cols`x`rows` cells from the
+/// cursor position, unless it can't be decoded.
+pub fn escape_sequence(
+    protocol: GraphicsProtocol,
+    image: &PreviewImage,
+    cols: u16,
+    rows: u16,
+) -> Option<String> {
+    let payload = || general_purpose::STANDARD.encode(&image.data);
+    match protocol {
+        GraphicsProtocol::Kitty => {
+            let payload = payload();
+            // replace the previous image rather than stacking on top of it
+            let mut sequence = KITTY_DELETE_ALL.to_string();
+            let chunks: Vec<&[u8]> =
+                payload.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+            for (i, chunk) in chunks.iter().enumerate() {
+                let more = u8::from(i + 1 < chunks.len());
+                if i == 0 {
+                    let _ = write!(
+                        sequence,
+                        "\x1b_Ga=T,f=100,q=2,C=1,c={cols},r={rows},m={more};"
+                    );
+                } else {
+                    let _ = write!(sequence, "\x1b_Gm={more};");
+                }
+                // base64 is ascii
+                sequence.push_str(std::str::from_utf8(chunk).unwrap_or(""));
+                sequence.push_str("\x1b\\");
+            }
+            Some(sequence)
+        }
+        GraphicsProtocol::Iterm2 => Some(format!(
+            "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{}\x07",
+            image.data.len(),
+            payload()
+        )),
+        GraphicsProtocol::Sixel => sixel_sequence(image, cols, rows),
+    }
+}
+
+/// `image` decoded, scaled down to fit `cols`x`rows` cells and encoded as
+/// sixels.
+fn sixel_sequence(
+    image: &PreviewImage,
+    cols: u16,
+    rows: u16,
+) -> Option<String> {
+    let decoded = ::image::load_from_memory(&image.data).ok()?;
+    let (max_width, max_height) = (
+        u32::from(cols) * CELL_PIXELS.0,
+        u32::from(rows) * CELL_PIXELS.1,
+    );
+    let decoded =
+        if decoded.width() > max_width || decoded.height() > max_height {
+            decoded.resize(
+                max_width,
+                max_height,
+                ::image::imageops::FilterType::Triangle,
+            )
+        } else {
+            decoded
+        };
+    let pixels = decoded.to_rgba8();
+    Some(encode_sixel(
+        pixels.width() as usize,
+        pixels.height() as usize,
+        pixels.as_raw(),
+    ))
+}
+
+/// The index of the color of `pixel` in a 6×6×6 color cube, unless it is
+/// mostly transparent.
+fn cube_index(pixel: &[u8]) -> Option<usize> {
+    let level = |c: u8| (usize::from(c) * 5 + 127) / 255;
+    (pixel[3] >= 128)
+        .then(|| level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2]))
+}
+
+/// Encode `width`x`height` RGBA pixels as a sixel image in the colors of a
+/// 6×6×6 cube, transparent pixels leaving the screen as it is.
+fn encode_sixel(width: usize, height: usize, rgba: &[u8]) -> String {
+    let colors: Vec<Option<usize>> =
+        rgba.chunks_exact(4).map(cube_index).collect();
+    let mut sequence = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+
+    let mut used = [false; 216];
+    for &color in colors.iter().flatten() {
+        used[color] = true;
+    }
+    let percent = |level: usize| level * 100 / 5;
+    for color in (0..216).filter(|&color| used[color]) {
+        let _ = write!(
+            sequence,
+            "#{color};2;{};{};{}",
+            percent(color / 36),
+            percent(color / 6 % 6),
+            percent(color % 6)
+        );
+    }
+
+    // each band of 6 rows is drawn once per color, going back to its start
+    // in between
+    for top in (0..height).step_by(6) {
+        let band_rows = 6.min(height - top);
+        let band = &colors[top * width..(top + band_rows) * width];
+        let mut band_colors = [false; 216];
+        for &color in band.iter().flatten() {
+            band_colors[color] = true;
+        }
+        let mut first = true;
+        for color in (0..216).filter(|&color| band_colors[color]) {
+            if !first {
+                sequence.push('$');
+            }
+            first = false;
+            let _ = write!(sequence, "#{color}");
+            let mut run: Option<(u8, usize)> = None;
+            for x in 0..width {
+                let mut bits: u8 = 0;
+                for dy in 0..band_rows {
+                    if band[dy * width + x] == Some(color) {
+                        bits |= 1u8 << dy;
+                    }
+                }
+                let sixel = 63 + bits;
+                match &mut run {
+                    Some((previous, count)) if *previous == sixel => {
+                        *count += 1;
+                    }
+                    _ => {
+                        if let Some((previous, count)) = run {
+                            push_sixel_run(&mut sequence, previous, count);
+                        }
+                        run = Some((sixel, 1));
+                    }
+                }
+            }
+            // trailing empty sixels can be left out
+            if let Some((sixel, count)) = run.filter(|(sixel, _)| *sixel != 63)
+            {
+                push_sixel_run(&mut sequence, sixel, count);
+            }
+        }
+        sequence.push('-');
+    }
+    sequence.push_str("\x1b\\");
+    sequence
+}
+
+/// Append `count` times the `sixel` character, run-length encoded.
+fn push_sixel_run(sequence: &mut String, sixel: u8, count: usize) {
+    let sixel = char::from(sixel);
+    if count > 3 {
+        let _ = write!(sequence, "!{count}{sixel}");
+    } else {
+        sequence.extend(std::iter::repeat_n(sixel, count));
+    }
+}
+
+/// Escape sequences for the images shown recently, which are only valid for
+/// the size they were generated for.
+#[derive(Debug, Default)]
+struct SequenceCache {
+    size: (u16, u16),
+    sequences: FxHashMap<String, Option<Arc<str>>>,
+}
+
+/// How many escape sequences are kept around.
+const SEQUENCE_CACHE_SIZE: usize = 16;
+
+impl SequenceCache {
+    fn get_or_insert_with(
+        &mut self,
+        path: &str,
+        size: (u16, u16),
+        sequence: impl FnOnce() -> Option<String>,
+    ) -> Option<Arc<str>> {
+        // the pane was resized
+        if size != self.size || self.sequences.len() >= SEQUENCE_CACHE_SIZE {
+            self.sequences.clear();
+            self.size = size;
+        }
+        self.sequences
+            .entry(path.to_string())
+            .or_insert_with(|| sequence().map(Into::into))
+            .clone()
+    }
+}
+
+static SEQUENCES: LazyLock<Mutex<SequenceCache>> =
+    LazyLock::new(|| Mutex::new(SequenceCache::default()));
+
+/// Same as `escape_sequence` but encodes each image only once per size.
+pub fn cached_escape_sequence(
+    protocol: GraphicsProtocol,
+    image: &PreviewImage,
+    cols: u16,
+    rows: u16,
+) -> Option<Arc<str>> {
+    SEQUENCES
+        .lock()
+        .get_or_insert_with(&image.path, (cols, rows), || {
+            escape_sequence(protocol, image, cols, rows)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        bytes.extend(width.to_be_bytes());
+        bytes.extend(height.to_be_bytes());
+        bytes.extend([8, 6, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn test_sniff_and_dimensions() {
+        let image = PreviewImage::from_bytes("a.png", png(640, 480)).unwrap();
+        assert_eq!(image.format, ImageFormat::Png);
+        assert_eq!((image.width, image.height), (640, 480));
+
+        let gif = b"GIF89a\x20\x00\x10\x00".to_vec();
+        let image = PreviewImage::from_bytes("a.gif", gif).unwrap();
+        assert_eq!((image.width, image.height), (32, 16));
+
+        // an APP0 segment then a baseline start of frame
+        let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00];
+        jpeg.extend([0xff, 0xc0, 0x00, 0x11, 0x08, 0x01, 0xe0, 0x02, 0x80]);
+        let image = PreviewImage::from_bytes("a.jpg", jpeg).unwrap();
+        assert_eq!(image.format, ImageFormat::Jpeg);
+        assert_eq!((image.width, image.height), (640, 480));
+
+        assert!(
+            PreviewImage::from_bytes("a.txt", b"hello".to_vec()).is_none()
+        );
+        // truncated header
+        assert!(
+            PreviewImage::from_bytes("a.png", png(1, 1)[..18].to_vec())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_protocol_from_env() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| (*v).to_string())
+            }
+        };
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "xterm-kitty")])),
+            Some(GraphicsProtocol::Kitty)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM_PROGRAM", "iTerm.app")])),
+            Some(GraphicsProtocol::Iterm2)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "foot")])),
+            Some(GraphicsProtocol::Sixel)
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "xterm-256color")])),
+            None
+        );
+        assert_eq!(
+            protocol_from_env(env(&[("TERM", "xterm-kitty"), ("TMUX", "1")])),
+            None
+        );
+        assert_eq!(
+            protocol_from_env(env(&[
+                ("TERM", "xterm-kitty"),
+                (IMAGE_PROTOCOL_ENV_VAR, "none")
+            ])),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fit_cells() {
+        // 80x30 cells at the assumed cell size
+        assert_eq!(fit_cells(640, 480, 40, 40), (40, 15));
+        assert_eq!(fit_cells(640, 480, 100, 20), (53, 20));
+        assert_eq!(fit_cells(640, 480, 100, 100), (80, 30));
+        // small images aren't scaled up
+        assert_eq!(fit_cells(16, 16, 100, 100), (2, 1));
+        assert_eq!(fit_cells(1, 10_000, 100, 10), (1, 10));
+    }
+
+    #[test]
+    fn test_kitty_sequence_is_chunked() {
+        let mut bytes = png(10, 10);
+        bytes.resize(10_000, 0);
+        let image = PreviewImage::from_bytes("a.png", bytes).unwrap();
+        let sequence =
+            escape_sequence(GraphicsProtocol::Kitty, &image, 20, 10).unwrap();
+        assert!(sequence.starts_with(KITTY_DELETE_ALL));
+        assert!(sequence.contains("a=T,f=100,q=2,C=1,c=20,r=10,m=1;"));
+        // 10000 bytes take 13336 base64 characters, i.e. 4 chunks
+        assert_eq!(sequence.matches("\x1b_Gm=1;").count(), 2);
+        assert_eq!(sequence.matches("\x1b_Gm=0;").count(), 1);
+        assert!(sequence.ends_with("\x1b\\"));
+
+        let sequence =
+            escape_sequence(GraphicsProtocol::Iterm2, &image, 20, 10).unwrap();
+        assert!(sequence.starts_with(
+            "\x1b]1337;File=inline=1;size=10000;width=20;height=10;"
+        ));
+    }
+
+    #[test]
+    fn test_sixel_encoding() {
+        let red = [255, 0, 0, 255];
+        let transparent = [0, 0, 0, 0];
+        let pixels = [red, red, transparent].concat();
+        assert_eq!(
+            encode_sixel(3, 1, &pixels),
+            "\x1bP0;1;0q\"1;1;3;1#180;2;100;0;0#180@@-\x1b\\"
+        );
+
+        // runs are compressed and colors drawn over the same band
+        let blue = [0, 0, 255, 255];
+        let pixels = [[red; 5].concat(), [blue; 5].concat()].concat();
+        assert_eq!(
+            encode_sixel(5, 2, &pixels),
+            "\x1bP0;1;0q\"1;1;5;2#5;2;0;0;100#180;2;100;0;0#5!5A$#180!5@-\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_sixel_sequence_decodes_the_image() {
+        let mut encoded = Vec::new();
+        ::image::RgbaImage::from_pixel(
+            32,
+            32,
+            ::image::Rgba([0, 255, 0, 255]),
+        )
+        .write_to(
+            &mut std::io::Cursor::new(&mut encoded),
+            ::image::ImageFormat::Png,
+        )
+        .unwrap();
+        let image = PreviewImage::from_bytes("a.png", encoded).unwrap();
+        // scaled down to 2x1 cells, i.e. 16x16 pixels
+        let sequence =
+            escape_sequence(GraphicsProtocol::Sixel, &image, 2, 1).unwrap();
+        assert!(sequence.starts_with("\x1bP0;1;0q\"1;1;16;16#30;2;0;100;0"));
+        assert_eq!(sequence.matches('-').count(), 3);
+        assert!(sequence.ends_with("\x1b\\"));
+
+        // not an image after all
+        let mut bytes = png(10, 10);
+        bytes.resize(100, 0);
+        let image = PreviewImage::from_bytes("a.png", bytes).unwrap();
+        assert!(
+            escape_sequence(GraphicsProtocol::Sixel, &image, 2, 1).is_none()
+        );
+    }
+
+    #[test]
+    fn test_load_only_reads_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("a.txt");
+        std::fs::write(&text, "hello").unwrap();
+        assert!(PreviewImage::load(&text.to_string_lossy()).is_none());
+
+        let image = dir.path().join("a.png");
+        std::fs::write(&image, png(640, 480)).unwrap();
+        let loaded = PreviewImage::load(&image.to_string_lossy()).unwrap();
+        assert_eq!((loaded.width, loaded.height), (640, 480));
+        assert_eq!(loaded.data.len(), png(640, 480).len());
+    }
+
+    #[test]
+    fn test_sequence_cache_is_evicted_on_resize() {
+        let mut cache = SequenceCache::default();
+        let first = cache
+            .get_or_insert_with("a.png", (20, 10), || Some("a".into()))
+            .unwrap();
+        let again = cache
+            .get_or_insert_with("a.png", (20, 10), || unreachable!())
+            .unwrap();
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let resized = cache
+            .get_or_insert_with("a.png", (30, 10), || Some("b".into()))
+            .unwrap();
+        assert_eq!(&*resized, "b");
+        assert_eq!(cache.sequences.len(), 1);
+    }
+}
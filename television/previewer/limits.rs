@@ -0,0 +1,176 @@
+use std::borrow::Cow;
+
+/// Preview output beyond this many bytes is cut off.
+pub const DEFAULT_MAX_BYTES: usize = 2 * 1024 * 1024;
+
+/// Preview lines beyond this many characters are cut off.
+pub const DEFAULT_MAX_LINE_LENGTH: usize = 4096;
+
+/// How much of a preview command's output is shown, so that a huge file, or
+/// a single huge line, can't stall the UI while it is parsed and drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputLimits {
+    /// The output is cut off past this many bytes, behind a trailer telling
+    /// how many were left out.
+    pub max_bytes: usize,
+    /// Lines are cut off past this many characters.
+    pub max_line_length: usize,
+}
+
+impl Default for OutputLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_line_length: DEFAULT_MAX_LINE_LENGTH,
+        }
+    }
+}
+
+/// Cut `output` down to `limits`.
+///
+/// Whole lines are kept when the output is too large, and lines that are too
+/// long are cut before any escape sequence they'd end in the middle of.
+pub fn limit_output(output: &[u8], limits: OutputLimits) -> Cow<'_, [u8]> {
+    let too_large = output.len() > limits.max_bytes;
+    if !too_large
+        && output
+            .split(|&b| b == b'\n')
+            .all(|line| line.len() <= limits.max_line_length)
+    {
+        return Cow::Borrowed(output);
+    }
+
+    let kept = if too_large {
+        let cut = &output[..limits.max_bytes];
+        match cut.iter().rposition(|&b| b == b'\n') {
+            Some(end) => &cut[..end],
+            None => cut,
+        }
+    } else {
+        output
+    };
+    let kept_text = String::from_utf8_lossy(kept);
+    let mut limited = String::with_capacity(kept_text.len() + 64);
+    for (i, line) in kept_text.split('\n').enumerate() {
+        if i > 0 {
+            limited.push('\n');
+        }
+        limited.push_str(&truncate_line(line, limits.max_line_length));
+    }
+    if too_large {
+        let omitted = output.len() - kept.len();
+        limited.push_str(&format!("\n… output truncated ({omitted} bytes)"));
+    }
+    Cow::Owned(limited.into_bytes())
+}
+
+/// The first `max_length` characters of `line`, followed by an ellipsis if
+/// any were left out.
+fn truncate_line(line: &str, max_length: usize) -> Cow<'_, str> {
+    match line.char_indices().nth(max_length) {
+        Some((cut, _)) => {
+            Cow::Owned(format!("{}…", without_partial_escape(&line[..cut])))
+        }
+        None => Cow::Borrowed(line),
+    }
+}
+
+/// `s` short of the escape sequence it ends in the middle of, if any.
+fn without_partial_escape(s: &str) -> &str {
+    let Some(start) = s.rfind('\x1b') else {
+        return s;
+    };
+    let rest = &s[start + 1..];
+    let terminated = match rest.as_bytes().first() {
+        // CSI sequences (colors...) end with a byte from `@` to `~`
+        Some(b'[') => rest.bytes().skip(1).any(|b| (0x40..=0x7e).contains(&b)),
+        // OSC sequences (hyperlinks...) end with BEL, or with an ESC `\`
+        // which would then be the last escape
+        Some(b']') => rest.contains('\x07'),
+        Some(_) => true,
+        None => false,
+    };
+    if terminated { s } else { &s[..start] }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use ansi_to_tui::IntoText;
+
+    use super::*;
+
+    fn limits(max_bytes: usize, max_line_length: usize) -> OutputLimits {
+        OutputLimits {
+            max_bytes,
+            max_line_length,
+        }
+    }
+
+    #[test]
+    fn test_small_outputs_are_kept_as_is() {
+        let output = b"first\nsecond\n";
+        assert!(matches!(
+            limit_output(output, OutputLimits::default()),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_large_outputs_are_truncated() {
+        let output = "line\n".repeat(100);
+        let limited = limit_output(output.as_bytes(), limits(22, 100));
+        assert_eq!(
+            String::from_utf8_lossy(&limited),
+            "line\nline\nline\nline\n… output truncated (481 bytes)"
+        );
+        // without a line to stop at
+        let output = "a".repeat(100);
+        let limited = limit_output(output.as_bytes(), limits(10, 100));
+        assert_eq!(
+            String::from_utf8_lossy(&limited),
+            "aaaaaaaaaa\n… output truncated (90 bytes)"
+        );
+    }
+
+    #[test]
+    fn test_long_lines_are_truncated() {
+        let output = format!("short\n{}\nshort", "日".repeat(10));
+        let limited = limit_output(output.as_bytes(), limits(1000, 6));
+        assert_eq!(
+            String::from_utf8_lossy(&limited),
+            "short\n日日日日日日…\nshort"
+        );
+    }
+
+    #[test]
+    fn test_lines_are_not_truncated_within_escape_sequences() {
+        assert_eq!(truncate_line("ab\x1b[31mcd", 5), "ab…");
+        assert_eq!(truncate_line("ab\x1b[31mcd", 7), "ab\x1b[31m…");
+        assert_eq!(
+            truncate_line("\x1b]8;;https://example.com\x07link", 12),
+            "…"
+        );
+        assert_eq!(truncate_line("abc", 3), "abc");
+    }
+
+    #[test]
+    fn test_parsing_a_huge_line_stays_bounded() {
+        let output = format!("\x1b[32m{}\x1b[0m", "a".repeat(10_000_000));
+        let start = Instant::now();
+        let limited = limit_output(output.as_bytes(), OutputLimits::default());
+        let text = limited.into_owned().into_text().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        assert_eq!(text.lines.len(), 2);
+        assert!(text.lines[0].width() <= DEFAULT_MAX_LINE_LENGTH + 1);
+        assert_eq!(
+            text.lines[1].to_string(),
+            format!(
+                "… output truncated ({} bytes)",
+                output.len() - DEFAULT_MAX_BYTES
+            )
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use std::{
     cmp::Ordering,
+    path::Path,
+    process::Stdio,
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -8,10 +10,12 @@ use ansi_to_tui::IntoText;
 use anyhow::{Context, Result};
 use parking_lot::Mutex;
 use ratatui::text::Text;
+use rustc_hash::FxHashMap;
+use tokio::io::AsyncWriteExt;
 use tokio::process::Command as TokioCommand;
 use tokio::{
-    sync::mpsc::{UnboundedReceiver, UnboundedSender},
-    task::spawn,
+    sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    task::{JoinHandle, spawn},
     time::timeout,
 };
 use tracing::{debug, trace, warn};
@@ -19,9 +23,14 @@ use tracing::{debug, trace, warn};
 use crate::{
     channels::{
         entry::Entry,
-        prototypes::{CommandSpec, Template},
+        prototypes::{CommandSpec, Delimiter, Template},
+    },
+    previewer::{
+        cache::Cache,
+        directory::{DirectoryPreview, directory_listing},
+        image::PreviewImage,
+        limits::{OutputLimits, limit_output},
     },
-    previewer::cache::Cache,
     utils::{
         command::shell_command,
         strings::{
@@ -31,26 +40,51 @@ use crate::{
     },
 };
 
-mod cache;
+pub mod cache;
+pub mod directory;
+pub mod image;
+pub mod limits;
 pub mod state;
 
 pub struct Config {
     request_max_age: Duration,
     job_timeout: Duration,
+    output_limits: OutputLimits,
 }
 
 pub const DEFAULT_REQUEST_MAX_AGE: Duration = Duration::from_millis(1000);
 pub const DEFAULT_JOB_TIMEOUT: Duration = Duration::from_millis(500);
 
+/// How many previews may be prefetched at once, so that prefetching never
+/// gets in the way of the selected entry's preview.
+const PREFETCH_SLOTS: usize = 2;
+
+/// Tells preview commands how many columns they have to fit their output
+/// in, e.g. `bat --wrap=character --terminal-width=$TV_PREVIEW_COLUMNS`.
+pub const PREVIEW_COLUMNS_ENV_VAR: &str = "TV_PREVIEW_COLUMNS";
+
+/// Produces the preview of an entry in-process instead of running a preview
+/// command, for applications embedding tv (see [`crate::embed::EmbeddedPicker`]).
+pub type PreviewFn = Arc<dyn Fn(&Entry) -> String + Send + Sync>;
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             request_max_age: DEFAULT_REQUEST_MAX_AGE,
             job_timeout: DEFAULT_JOB_TIMEOUT,
+            output_limits: OutputLimits::default(),
         }
     }
 }
 
+impl Config {
+    #[must_use]
+    pub fn with_output_limits(mut self, output_limits: OutputLimits) -> Self {
+        self.output_limits = output_limits;
+        self
+    }
+}
+
 #[allow(
     clippy::large_enum_variant,
     reason = "requests are almost exclusively preview jobs"
@@ -58,8 +92,15 @@ impl Default for Config {
 #[derive(PartialEq, Eq)]
 pub enum Request {
     Preview(Ticket),
+    /// Prepare the previews of the entries the selection is heading to, in
+    /// the order it will reach them, so they're cached by then.
+    Prefetch {
+        tickets: Vec<Ticket>,
+        forward: bool,
+    },
     Shutdown,
     CycleCommand,
+    ClearCache,
 }
 
 impl PartialOrd for Request {
@@ -71,9 +112,17 @@ impl PartialOrd for Request {
 impl Ord for Request {
     fn cmp(&self, other: &Self) -> Ordering {
         match (self, other) {
-            // Shutdown/Cycle signals always have priority
-            (Self::Shutdown | Self::CycleCommand, _) => Ordering::Greater,
-            (_, Self::Shutdown | Self::CycleCommand) => Ordering::Less,
+            // Shutdown/Cycle/Clear signals always have priority
+            (Self::Shutdown | Self::CycleCommand | Self::ClearCache, _) => {
+                Ordering::Greater
+            }
+            (_, Self::Shutdown | Self::CycleCommand | Self::ClearCache) => {
+                Ordering::Less
+            }
+            // prefetching comes last
+            (Self::Prefetch { .. }, Self::Prefetch { .. }) => Ordering::Equal,
+            (Self::Prefetch { .. }, _) => Ordering::Less,
+            (_, Self::Prefetch { .. }) => Ordering::Greater,
             // Otherwise fall back to ticket age comparison
             (Self::Preview(t1), Self::Preview(t2)) => t1.cmp(t2),
         }
@@ -83,6 +132,8 @@ impl Ord for Request {
 #[derive(PartialEq, Eq)]
 pub struct Ticket {
     entry: Entry,
+    /// The inner width of the preview pane.
+    width: u16,
     timestamp: Instant,
 }
 
@@ -99,9 +150,10 @@ impl Ord for Ticket {
 }
 
 impl Ticket {
-    pub fn new(entry: Entry) -> Self {
+    pub fn new(entry: Entry, width: u16) -> Self {
         Self {
             entry,
+            width,
             timestamp: Instant::now(),
         }
     }
@@ -122,6 +174,13 @@ pub struct Preview {
     pub target_line: Option<u16>,
     pub total_lines: u16,
     pub footer: Option<String>,
+    /// Which preview command produced this preview, e.g. `[1/2 file]`.
+    pub badge: Option<String>,
+    /// The inner width of the preview pane this preview was generated for.
+    pub width: u16,
+    /// The image the entry points to, shown instead of `content` when the
+    /// terminal can display it.
+    pub image: Option<PreviewImage>,
 }
 
 const DEFAULT_PREVIEW_TITLE: &str = "Select an entry to preview";
@@ -136,12 +195,15 @@ impl Default for Preview {
             target_line: None,
             total_lines: 1,
             footer: None,
+            badge: None,
+            width: 0,
+            image: None,
         }
     }
 }
 
 impl Preview {
-    fn new(
+    pub(crate) fn new(
         entry_raw: String,
         formatted_command: String,
         title: &str,
@@ -158,8 +220,29 @@ impl Preview {
             target_line: line_number,
             total_lines,
             footer,
+            badge: None,
+            width: 0,
+            image: None,
         }
     }
+
+    #[must_use]
+    pub(crate) fn with_badge(mut self, badge: Option<String>) -> Self {
+        self.badge = badge;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_width(mut self, width: u16) -> Self {
+        self.width = width;
+        self
+    }
+
+    #[must_use]
+    pub(crate) fn with_image(mut self, image: Option<PreviewImage>) -> Self {
+        self.image = image;
+        self
+    }
 }
 
 pub struct Previewer {
@@ -167,14 +250,21 @@ pub struct Previewer {
     requests_tx: UnboundedSender<Request>,
     requests_rx: UnboundedReceiver<Request>,
     last_job_entry: Option<Entry>,
+    last_job_width: u16,
     command: CommandSpec,
     /// The current cycle index for commands with multiple variants.
     cycle_index: usize,
     title_template: Option<Template>,
     footer_template: Option<Template>,
     offset_expr: Option<Template>,
+    directory_preview: DirectoryPreview,
     results: UnboundedSender<Preview>,
     cache: Option<Arc<Mutex<Cache>>>,
+    preview_fn: Option<PreviewFn>,
+    /// Previews being prepared in advance.
+    prefetch_jobs: Vec<JoinHandle<()>>,
+    /// The direction the selection was moving in when they were requested.
+    prefetch_forward: bool,
 }
 
 impl Previewer {
@@ -184,14 +274,16 @@ impl Previewer {
         offset_expr: Option<Template>,
         title_template: Option<Template>,
         footer_template: Option<Template>,
+        directory_preview: DirectoryPreview,
         config: Config,
         requests_rx: UnboundedReceiver<Request>,
         requests_tx: UnboundedSender<Request>,
         results_tx: UnboundedSender<Preview>,
         cache: bool,
+        cache_size: usize,
     ) -> Self {
         let cache = if cache {
-            Some(Arc::new(Mutex::new(Cache::default())))
+            Some(Arc::new(Mutex::new(Cache::new(cache_size))))
         } else {
             None
         };
@@ -200,46 +292,82 @@ impl Previewer {
             requests_tx,
             requests_rx,
             last_job_entry: None,
+            last_job_width: 0,
             command: command.clone(),
             cycle_index: 0,
             title_template,
             footer_template,
             offset_expr,
+            directory_preview,
             results: results_tx,
             cache,
+            preview_fn: None,
+            prefetch_jobs: Vec::new(),
+            prefetch_forward: true,
         }
     }
 
+    /// Generate previews with `preview_fn`, if any, rather than with the
+    /// preview command.
+    #[must_use]
+    pub fn with_preview_fn(mut self, preview_fn: Option<PreviewFn>) -> Self {
+        self.preview_fn = preview_fn;
+        self
+    }
+
     pub async fn run(mut self) {
         let mut buffer = Vec::with_capacity(32);
         loop {
             let num = self.requests_rx.recv_many(&mut buffer, 32).await;
             if num > 0 {
                 debug!("Previewer received {num} request(s)!");
+                // prefetching waits for whatever else was requested
+                let prefetch = buffer
+                    .iter()
+                    .rposition(|r| matches!(r, Request::Prefetch { .. }))
+                    .map(|i| buffer.remove(i));
+                buffer.retain(|r| !matches!(r, Request::Prefetch { .. }));
                 // only keep the newest request
-                match buffer.drain(..).max().unwrap() {
-                    Request::Preview(ticket) => {
+                match buffer.drain(..).max() {
+                    Some(Request::Preview(ticket)) => {
                         if ticket.age() > self.config.request_max_age {
                             debug!("Preview request is stale, skipping");
                             continue;
                         }
                         let results_handle = self.results.clone();
                         self.last_job_entry = Some(ticket.entry.clone());
+                        self.last_job_width = ticket.width;
                         let preview_command = self.command.clone();
                         let cache = self.cache.clone();
                         let offset_expr = self.offset_expr.clone();
                         let title_template = self.title_template.clone();
                         let footer_template = self.footer_template.clone();
-                        let job = spawn(try_preview(
-                            preview_command,
-                            self.cycle_index,
-                            title_template,
-                            footer_template,
-                            offset_expr,
-                            ticket.entry,
-                            results_handle,
-                            cache,
-                        ));
+                        let directory_preview = self.directory_preview.clone();
+                        let job = if let Some(preview_fn) = &self.preview_fn {
+                            spawn(try_preview_fn(
+                                preview_fn.clone(),
+                                title_template,
+                                footer_template,
+                                offset_expr,
+                                ticket.entry,
+                                ticket.width,
+                                results_handle,
+                            ))
+                        } else {
+                            spawn(try_preview(
+                                preview_command,
+                                self.cycle_index,
+                                title_template,
+                                footer_template,
+                                offset_expr,
+                                directory_preview,
+                                self.config.output_limits,
+                                ticket.entry,
+                                ticket.width,
+                                results_handle,
+                                cache,
+                            ))
+                        };
                         match timeout(self.config.job_timeout, job).await {
                             Ok(Ok(Ok(()))) => {
                                 trace!("Preview job completed successfully");
@@ -261,16 +389,25 @@ impl Previewer {
                             }
                         }
                     }
-                    Request::CycleCommand => {
+                    Some(Request::CycleCommand) => {
                         trace!("Cycling preview command.");
                         self.cycle_command();
                     }
-                    Request::Shutdown => {
+                    Some(Request::ClearCache) => {
+                        trace!("Clearing the preview cache.");
+                        self.clear_cache();
+                    }
+                    Some(Request::Shutdown) => {
                         trace!(
                             "Received shutdown signal, breaking out of the previewer loop."
                         );
                         break;
                     }
+                    Some(Request::Prefetch { .. }) | None => {}
+                }
+                if let Some(Request::Prefetch { tickets, forward }) = prefetch
+                {
+                    self.prefetch(tickets, forward);
                 }
             } else {
                 trace!(
@@ -281,13 +418,83 @@ impl Previewer {
         }
     }
 
+    /// Prepare the previews of `tickets` in the background, as long as
+    /// prefetching slots are free, so that they're cached by the time the
+    /// selection gets to them.
+    fn prefetch(&mut self, tickets: Vec<Ticket>, forward: bool) {
+        // there's nowhere to keep them otherwise
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        if self.preview_fn.is_some() {
+            return;
+        }
+        if forward != self.prefetch_forward {
+            // the selection turned around, these won't be needed anytime soon
+            for job in self.prefetch_jobs.drain(..) {
+                job.abort();
+            }
+            self.prefetch_forward = forward;
+        }
+        self.prefetch_jobs.retain(|job| !job.is_finished());
+        for ticket in tickets {
+            if self.prefetch_jobs.len() >= PREFETCH_SLOTS {
+                break;
+            }
+            if ticket.age() > self.config.request_max_age {
+                continue;
+            }
+            trace!("Prefetching the preview of '{}'", ticket.entry.raw);
+            let job = try_preview(
+                self.command.clone(),
+                self.cycle_index,
+                self.title_template.clone(),
+                self.footer_template.clone(),
+                self.offset_expr.clone(),
+                self.directory_preview.clone(),
+                self.config.output_limits,
+                ticket.entry,
+                ticket.width,
+                // nobody's waiting for these, they're only cached
+                unbounded_channel().0,
+                Some(cache.clone()),
+            );
+            let job_timeout = self.config.job_timeout;
+            self.prefetch_jobs.push(spawn(async move {
+                let _ = timeout(job_timeout, job).await;
+            }));
+        }
+    }
+
+    /// Switch to the next preview command applying to the last entry.
     pub fn cycle_command(&mut self) {
-        self.cycle_index = (self.cycle_index + 1) % self.command.inner.len();
-        // re-request preview for the last entry if any
+        let current = self
+            .last_job_entry
+            .as_ref()
+            .and_then(|entry| {
+                self.command.nth_for_entry(self.cycle_index, &entry.raw)
+            })
+            .unwrap_or(self.cycle_index);
+        self.cycle_index = (current + 1) % self.command.inner.len();
+        self.request_last_entry();
+    }
+
+    /// Forget all cached previews (e.g. because the underlying files
+    /// changed) and regenerate the last one.
+    pub fn clear_cache(&mut self) {
+        if let Some(cache) = &self.cache {
+            cache.lock().clear();
+        }
+        self.request_last_entry();
+    }
+
+    /// Re-request a preview for the last entry if any.
+    fn request_last_entry(&self) {
         if let Some(entry) = &self.last_job_entry {
-            let _ = self
-                .requests_tx
-                .send(Request::Preview(Ticket::new(entry.clone())));
+            let _ = self.requests_tx.send(Request::Preview(Ticket::new(
+                entry.clone(),
+                self.last_job_width,
+            )));
         }
     }
 }
@@ -307,6 +514,27 @@ fn sanitize_text(text: &mut Text<'static>) {
     });
 }
 
+/// The content of `text` without its styling, one line per line.
+pub fn plain_text(text: &Text) -> String {
+    text.lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether a command's output can't be shown as text.
+fn is_binary_or_empty(output: &[u8]) -> bool {
+    output.is_empty()
+        || output.contains(&0)
+        || std::str::from_utf8(output).is_err()
+}
+
 fn build_preview_from_text(
     formatted_command: &str,
     entry: &Entry,
@@ -328,7 +556,13 @@ fn build_preview_from_text(
     let title = if let Some(title_template) = title_template.as_ref() {
         title_template.format(&entry.raw)?
     } else {
-        entry.display().to_string()
+        // multi-line records are titled after their first line
+        entry
+            .display()
+            .lines()
+            .next()
+            .unwrap_or_default()
+            .to_string()
     };
     let footer = if let Some(footer_template) = footer_template.as_ref() {
         Some(footer_template.format(&entry.raw)?)
@@ -354,15 +588,76 @@ pub async fn try_preview(
     title_template: Option<Template>,
     footer_template: Option<Template>,
     offset_expr: Option<Template>,
+    directory_preview: DirectoryPreview,
+    output_limits: OutputLimits,
     entry: Entry,
+    width: u16,
     results_handle: UnboundedSender<Preview>,
     cache: Option<Arc<Mutex<Cache>>>,
 ) -> Result<()> {
-    let formatted_command = command.get_nth(cycle_index).format(&entry.raw)?;
+    let Some(index) = command.nth_for_entry(cycle_index, &entry.raw) else {
+        // none of the conditional commands apply to this entry
+        let preview = build_preview_from_text(
+            EMPTY_STRING,
+            &entry,
+            Text::from(EMPTY_STRING),
+            title_template.as_ref(),
+            footer_template.as_ref(),
+            None,
+        )?
+        .with_width(width);
+        results_handle.send(preview).with_context(
+            || "Failed to send empty preview result to main thread.",
+        )?;
+        return Ok(());
+    };
+    if command.is_entry_preview_nth(index) {
+        let mut text = entry
+            .raw
+            .clone()
+            .into_text()
+            .unwrap_or_else(|_| Text::from(entry.raw.clone()));
+        sanitize_text(&mut text);
+        let preview = build_preview_from_text(
+            EMPTY_STRING,
+            &entry,
+            text,
+            title_template.as_ref(),
+            footer_template.as_ref(),
+            offset_expr.as_ref(),
+        )?
+        .with_width(width);
+        return results_handle.send(preview).with_context(
+            || "Failed to send entry preview result to main thread.",
+        );
+    }
+    let template = command.get_nth(index);
+    // commands reading the entry on their stdin run as they are written
+    let stdin = command.stdin_nth(index).map(|input| {
+        input.for_entry(
+            &entry.raw,
+            command
+                .delimiter_nth(index)
+                .unwrap_or(&Delimiter::default()),
+        )
+    });
+    let formatted_command = if stdin.is_some() {
+        template.raw().to_string()
+    } else {
+        match command.delimiter_nth(index) {
+            Some(delimiter) => entry.format(template, delimiter)?,
+            None => template.format(&entry.raw)?,
+        }
+    };
+    let cache_key = match &stdin {
+        Some(input) => format!("{formatted_command}\0{input}"),
+        None => formatted_command.clone(),
+    };
+    let badge = command.badge_for_entry(index, &entry.raw);
 
     // Check if the entry is already cached
     if let Some(cache) = &cache
-        && let Some(text) = cache.lock().get(&formatted_command)
+        && let Some(text) = cache.lock().get(&cache_key, width)
     {
         trace!("Preview for command '{}' found in cache", formatted_command);
         let preview = build_preview_from_text(
@@ -372,7 +667,9 @@ pub async fn try_preview(
             title_template.as_ref(),
             footer_template.as_ref(),
             offset_expr.as_ref(),
-        )?;
+        )?
+        .with_badge(badge)
+        .with_width(width);
         results_handle.send(preview).with_context(
             || "Failed to send cached preview result to main thread.",
         )?;
@@ -380,22 +677,63 @@ pub async fn try_preview(
     }
 
     debug!("Executing preview command: {}", &formatted_command);
-    let command =
+    let mut command =
         shell_command(&formatted_command, command.interactive, &command.env);
+    command.env(PREVIEW_COLUMNS_ENV_VAR, width.to_string());
 
-    let child = TokioCommand::from(command).output().await?;
+    let child = match stdin {
+        Some(input) => output_with_stdin(command, input).await?,
+        None => TokioCommand::from(command).output().await?,
+    };
 
-    let mut text = if child.status.success() {
-        child
-            .stdout
-            .into_text()
-            .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+    // commands failing on directories (e.g. `bat`) fall back to listing them
+    if !child.status.success() && Path::new(&entry.raw).is_dir() {
+        let (directory_command, text) =
+            preview_directory(&directory_preview, &entry.raw, width).await?;
+        let preview = build_preview_from_text(
+            &directory_command,
+            &entry,
+            text,
+            title_template.as_ref(),
+            footer_template.as_ref(),
+            None,
+        )?
+        .with_width(width);
+        return results_handle.send(preview).with_context(
+            || "Failed to send directory preview result to main thread.",
+        );
+    }
+
+    // commands show binary garbage, or nothing at all, for images
+    if is_binary_or_empty(&child.stdout)
+        && let Some(image) = PreviewImage::load(&entry.raw)
+    {
+        let preview = build_preview_from_text(
+            &formatted_command,
+            &entry,
+            image.placeholder(),
+            title_template.as_ref(),
+            footer_template.as_ref(),
+            None,
+        )?
+        .with_badge(badge)
+        .with_width(width)
+        .with_image(Some(image));
+        return results_handle.send(preview).with_context(
+            || "Failed to send image preview result to main thread.",
+        );
+    }
+
+    // huge outputs would stall the UI while being parsed and drawn
+    let output = if child.status.success() {
+        limit_output(&child.stdout, output_limits)
     } else {
-        child
-            .stderr
-            .into_text()
-            .unwrap_or_else(|_| Text::from(EMPTY_STRING))
+        limit_output(&child.stderr, output_limits)
     };
+    let mut text = output
+        .into_owned()
+        .into_text()
+        .unwrap_or_else(|_| Text::from(EMPTY_STRING));
 
     sanitize_text(&mut text);
 
@@ -408,7 +746,7 @@ pub async fn try_preview(
             footer_template.as_ref(),
             offset_expr.as_ref(),
         )?;
-        cache.lock().insert(&formatted_command, &text);
+        cache.lock().insert(&cache_key, width, &text);
         preview
     } else {
         build_preview_from_text(
@@ -421,7 +759,490 @@ pub async fn try_preview(
         )?
     };
     // FIXME: ... and just send an Arc here as well
+    results_handle
+        .send(preview.with_badge(badge).with_width(width))
+        .with_context(|| "Failed to send preview result to main thread.")
+}
+
+/// Preview the directory at `path` with the configured command or the
+/// built-in listing, returning the command that was run (if any) along with
+/// the preview.
+async fn preview_directory(
+    directory_preview: &DirectoryPreview,
+    path: &str,
+    width: u16,
+) -> Result<(String, Text<'static>)> {
+    let Some(template) = &directory_preview.command else {
+        return Ok((
+            EMPTY_STRING.to_string(),
+            directory_listing(Path::new(path), directory_preview.icons),
+        ));
+    };
+    let formatted_command = template.format(path)?;
+    debug!(
+        "Executing directory preview command: {}",
+        &formatted_command
+    );
+    let mut command =
+        shell_command(&formatted_command, false, &FxHashMap::default());
+    command.env(PREVIEW_COLUMNS_ENV_VAR, width.to_string());
+    let child = TokioCommand::from(command).output().await?;
+    let mut text = child
+        .stdout
+        .into_text()
+        .unwrap_or_else(|_| Text::from(EMPTY_STRING));
+    sanitize_text(&mut text);
+    Ok((formatted_command, text))
+}
+
+/// Run `command` with `input` written to its stdin and collect its output.
+async fn output_with_stdin(
+    mut command: std::process::Command,
+    input: String,
+) -> Result<std::process::Output> {
+    command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let mut child = TokioCommand::from(command).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // write from another task so that a command which doesn't read all
+        // of its input can't dead-lock on a full pipe
+        spawn(async move {
+            // commands are free to exit before reading it all
+            let _ = stdin.write_all(input.as_bytes()).await;
+        });
+    }
+    Ok(child.wait_with_output().await?)
+}
+
+/// Generate the preview of `entry` with an in-process [`PreviewFn`].
+pub async fn try_preview_fn(
+    preview_fn: PreviewFn,
+    title_template: Option<Template>,
+    footer_template: Option<Template>,
+    offset_expr: Option<Template>,
+    entry: Entry,
+    width: u16,
+    results_handle: UnboundedSender<Preview>,
+) -> Result<()> {
+    let previewed = entry.clone();
+    // the callback is free to block, e.g. on IO
+    let output =
+        tokio::task::spawn_blocking(move || preview_fn(&previewed)).await?;
+    let mut text = output
+        .clone()
+        .into_text()
+        .unwrap_or_else(|_| Text::from(output));
+    sanitize_text(&mut text);
+    let preview = build_preview_from_text(
+        EMPTY_STRING,
+        &entry,
+        text,
+        title_template.as_ref(),
+        footer_template.as_ref(),
+        offset_expr.as_ref(),
+    )?
+    .with_width(width);
     results_handle
         .send(preview)
         .with_context(|| "Failed to send preview result to main thread.")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::prototypes::{EntryCondition, StdinInput};
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn previewer(cache: bool, cache_size: usize) -> Previewer {
+        let (requests_tx, requests_rx) = unbounded_channel();
+        let (results_tx, _) = unbounded_channel();
+        Previewer::new(
+            &CommandSpec::from(Template::parse("cat {}").unwrap()),
+            None,
+            None,
+            None,
+            DirectoryPreview::default(),
+            Config::default(),
+            requests_rx,
+            requests_tx,
+            results_tx,
+            cache,
+            cache_size,
+        )
+    }
+
+    #[test]
+    fn test_plain_text() {
+        let text = "\x1b[1;31mred\x1b[0m and \x1b[32mgreen\x1b[0m\nplain"
+            .into_text()
+            .unwrap();
+        assert_eq!(plain_text(&text), "red and green\nplain");
+        assert_eq!(plain_text(&Text::default()), "");
+    }
+
+    #[test]
+    fn test_cache_size_is_configurable() {
+        assert!(previewer(false, 10).cache.is_none());
+
+        let previewer = previewer(true, 2);
+        let mut cache = previewer.cache.as_ref().unwrap().lock();
+        for key in ["a", "b", "c"] {
+            cache.insert(key, 80, &Text::raw(key));
+        }
+        assert_eq!(cache.size(), 2);
+    }
+
+    #[test]
+    fn test_clear_cache_regenerates_last_preview() {
+        let mut previewer = previewer(true, 10);
+        previewer.cache.as_ref().unwrap().lock().insert(
+            "cat a",
+            80,
+            &Text::raw("a"),
+        );
+        previewer.last_job_entry = Some(Entry::new("a".to_string()));
+        previewer.last_job_width = 80;
+
+        previewer.clear_cache();
+        assert_eq!(previewer.cache.as_ref().unwrap().lock().size(), 0);
+        let Ok(Request::Preview(ticket)) = previewer.requests_rx.try_recv()
+        else {
+            panic!("the last entry should have been previewed again");
+        };
+        assert_eq!(ticket.entry.raw, "a");
+        assert_eq!(ticket.width, 80);
+    }
+
+    #[tokio::test]
+    async fn test_prefetched_previews_are_cached() {
+        let mut previewer = previewer(true, 10);
+        previewer.command =
+            CommandSpec::from(Template::parse("echo {}").unwrap());
+        let tickets = ["a", "b", "c"]
+            .map(|raw| Ticket::new(Entry::new(raw.to_string()), 80));
+
+        previewer.prefetch(tickets.into(), true);
+        // never more at once than there are slots for
+        assert_eq!(previewer.prefetch_jobs.len(), PREFETCH_SLOTS);
+        for job in previewer.prefetch_jobs.drain(..) {
+            job.await.unwrap();
+        }
+        assert_eq!(
+            previewer.cache.as_ref().unwrap().lock().size(),
+            PREFETCH_SLOTS
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetching_stops_when_the_selection_turns_around() {
+        let mut previewer = previewer(true, 10);
+        previewer.command =
+            CommandSpec::from(Template::parse("sleep 1; echo {}").unwrap());
+        let ticket = |raw: &str| Ticket::new(Entry::new(raw.to_string()), 80);
+
+        previewer.prefetch(vec![ticket("b")], true);
+        assert_eq!(previewer.prefetch_jobs.len(), 1);
+        previewer.prefetch(Vec::new(), false);
+        assert!(previewer.prefetch_jobs.is_empty());
+        assert!(!previewer.prefetch_forward);
+    }
+
+    #[test]
+    fn test_prefetching_comes_after_other_requests() {
+        let prefetch = Request::Prefetch {
+            tickets: Vec::new(),
+            forward: true,
+        };
+        let preview =
+            Request::Preview(Ticket::new(Entry::new("a".to_string()), 80));
+        assert!(prefetch < preview);
+        assert!(prefetch < Request::ClearCache);
+    }
+
+    fn conditional_command() -> CommandSpec {
+        let mut command = CommandSpec::new(
+            vec![
+                Template::parse("ls {}").unwrap(),
+                Template::parse("git diff {}").unwrap(),
+                Template::parse("cat {}").unwrap(),
+            ],
+            false,
+            FxHashMap::default(),
+        );
+        command.conditions = vec![
+            Some(EntryCondition::new("/$").unwrap()),
+            Some(EntryCondition::new("^M ").unwrap()),
+            None,
+        ];
+        command
+    }
+
+    #[test]
+    fn test_cycle_command_skips_commands_not_applying() {
+        let mut previewer = previewer(false, 10);
+        previewer.command = conditional_command();
+        previewer.last_job_entry = Some(Entry::new("src/".to_string()));
+
+        previewer.cycle_command();
+        assert_eq!(previewer.cycle_index, 1);
+        // `git diff` doesn't apply to directories
+        assert_eq!(previewer.command.nth_for_entry(1, "src/"), Some(2));
+        previewer.cycle_command();
+        assert_eq!(previewer.cycle_index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_no_preview_when_no_command_applies() {
+        let mut command = conditional_command();
+        command.inner.truncate(2);
+        command.conditions.truncate(2);
+        let (results_tx, mut results_rx) = unbounded_channel();
+
+        try_preview(
+            command,
+            0,
+            None,
+            None,
+            None,
+            DirectoryPreview::default(),
+            OutputLimits::default(),
+            Entry::new("main.rs".to_string()),
+            80,
+            results_tx,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let preview = results_rx.try_recv().unwrap();
+        assert_eq!(preview.entry_raw, "main.rs");
+        assert!(preview.formatted_command.is_empty());
+        assert_eq!(preview.width, 80);
+    }
+
+    #[tokio::test]
+    async fn test_entry_preview_shows_the_full_record() {
+        let (results_tx, mut results_rx) = unbounded_channel();
+
+        try_preview(
+            CommandSpec::entry_preview(),
+            0,
+            None,
+            None,
+            None,
+            DirectoryPreview::default(),
+            OutputLimits::default(),
+            Entry::new("commit abc\n\nfix the thing".to_string()),
+            80,
+            results_tx,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let preview = results_rx.try_recv().unwrap();
+        assert_eq!(preview.title, "commit abc");
+        assert_eq!(preview.total_lines, 3);
+        assert_eq!(preview.content.lines[2].to_string(), "fix the thing");
+        assert!(preview.formatted_command.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_preview_fn_replaces_the_preview_command() {
+        let (results_tx, mut results_rx) = unbounded_channel();
+        let preview_fn: PreviewFn =
+            Arc::new(|entry| format!("size of {}\n42 bytes", entry.raw));
+
+        try_preview_fn(
+            preview_fn,
+            None,
+            None,
+            Some(Template::parse("2").unwrap()),
+            Entry::new("main.rs".to_string()),
+            80,
+            results_tx,
+        )
+        .await
+        .unwrap();
+
+        let preview = results_rx.try_recv().unwrap();
+        assert_eq!(preview.title, "main.rs");
+        assert_eq!(preview.content.lines[0].to_string(), "size of main.rs");
+        assert_eq!(preview.total_lines, 2);
+        assert_eq!(preview.target_line, Some(2));
+        assert_eq!(preview.width, 80);
+    }
+
+    #[tokio::test]
+    async fn test_preview_commands_split_entries_with_their_delimiter() {
+        let mut command =
+            CommandSpec::from(Template::parse("echo {1}").unwrap());
+        let mut formatted = Vec::new();
+        for delimiter in [":", " "] {
+            command.delimiters =
+                vec![Some(Delimiter::Fixed(delimiter.to_string()))];
+            let (results_tx, mut results_rx) = unbounded_channel();
+            try_preview(
+                command.clone(),
+                0,
+                None,
+                None,
+                None,
+                DirectoryPreview::default(),
+                OutputLimits::default(),
+                Entry::new("a:b c".to_string()),
+                80,
+                results_tx,
+                None,
+            )
+            .await
+            .unwrap();
+            formatted.push(results_rx.try_recv().unwrap().formatted_command);
+        }
+        // which also keeps their previews apart in the cache
+        assert_eq!(formatted, vec!["echo b c", "echo c"]);
+    }
+
+    #[tokio::test]
+    async fn test_preview_commands_read_the_entry_on_stdin() {
+        let mut command = CommandSpec::from(Template::parse("cat").unwrap());
+        command.delimiters = vec![Some(Delimiter::Fixed(":".to_string()))];
+        let mut contents = Vec::new();
+        for stdin in [StdinInput::Entry, StdinInput::Field(1)] {
+            command.stdin = vec![Some(stdin)];
+            let (results_tx, mut results_rx) = unbounded_channel();
+            try_preview(
+                command.clone(),
+                0,
+                None,
+                None,
+                None,
+                DirectoryPreview::default(),
+                OutputLimits::default(),
+                Entry::new("it's:$(here)".to_string()),
+                80,
+                results_tx,
+                None,
+            )
+            .await
+            .unwrap();
+            let preview = results_rx.try_recv().unwrap();
+            assert_eq!(preview.formatted_command, "cat");
+            contents.push(preview.content.lines[0].to_string());
+        }
+        // no quoting needed, nor any shell expansion going on
+        assert_eq!(contents, vec!["it's:$(here)", "$(here)"]);
+    }
+
+    #[tokio::test]
+    async fn test_preview_output_is_limited() {
+        let (results_tx, mut results_rx) = unbounded_channel();
+        try_preview(
+            CommandSpec::from(Template::parse("seq 1 1000").unwrap()),
+            0,
+            None,
+            None,
+            None,
+            DirectoryPreview::default(),
+            OutputLimits {
+                max_bytes: 20,
+                max_line_length: 80,
+            },
+            Entry::new("numbers".to_string()),
+            80,
+            results_tx,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let preview = results_rx.try_recv().unwrap();
+        let content = plain_text(&preview.content);
+        // only whole lines are kept: 1 to 9, the first 17 of 3893 bytes
+        assert_eq!(
+            content.lines().last().unwrap(),
+            "… output truncated (3876 bytes)"
+        );
+        assert_eq!(preview.total_lines, 10);
+    }
+
+    #[tokio::test]
+    async fn test_directories_are_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+        let raw = dir.path().to_string_lossy().to_string();
+
+        let echo = DirectoryPreview {
+            command: Some(Template::parse("echo dir").unwrap()),
+            icons: false,
+        };
+        // the channel's command is used unless it fails on the directory
+        for (preview_command, directory_preview, expected) in [
+            ("cat {}", DirectoryPreview::default(), "    1B  a.txt"),
+            ("cat {}", echo.clone(), "dir"),
+            ("ls {}", echo, "a.txt"),
+        ] {
+            let (results_tx, mut results_rx) = unbounded_channel();
+            try_preview(
+                CommandSpec::from(Template::parse(preview_command).unwrap()),
+                0,
+                None,
+                None,
+                None,
+                directory_preview,
+                OutputLimits::default(),
+                Entry::new(raw.clone()),
+                80,
+                results_tx,
+                None,
+            )
+            .await
+            .unwrap();
+
+            let preview = results_rx.try_recv().unwrap();
+            assert_eq!(preview.content.lines[0].to_string(), expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_images_are_previewed_as_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image.png");
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        png.extend(640u32.to_be_bytes());
+        png.extend(480u32.to_be_bytes());
+        std::fs::write(&path, png).unwrap();
+        let text_path = dir.path().join("text.txt");
+        std::fs::write(&text_path, "hello").unwrap();
+
+        for (path, image) in [(path, true), (text_path, false)] {
+            let (results_tx, mut results_rx) = unbounded_channel();
+            try_preview(
+                CommandSpec::from(Template::parse("cat {}").unwrap()),
+                0,
+                None,
+                None,
+                None,
+                DirectoryPreview::default(),
+                OutputLimits::default(),
+                Entry::new(path.to_string_lossy().to_string()),
+                80,
+                results_tx,
+                None,
+            )
+            .await
+            .unwrap();
+
+            let preview = results_rx.try_recv().unwrap();
+            assert_eq!(preview.image.is_some(), image);
+            if image {
+                assert_eq!(
+                    preview.content.lines[0].to_string(),
+                    "PNG image, 640×480"
+                );
+            }
+        }
+    }
+}
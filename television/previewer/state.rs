@@ -2,37 +2,61 @@ use ratatui::text::Text;
 
 use crate::previewer::Preview;
 
+/// The number of lines a half-page scroll moves in a pane `height` lines
+/// tall.
+pub fn half_page(height: u16) -> u16 {
+    (height / 2).max(1)
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct PreviewState {
     pub enabled: bool,
     // FIXME: this should probably be an Arc<Preview>
     pub preview: Preview,
     pub scroll: u16,
+    /// Whether the preview belongs to a previously selected entry.
+    pub stale: bool,
 }
 
-const PREVIEW_MIN_SCROLL_LINES: u16 = 3;
-
 impl PreviewState {
     pub fn new(enabled: bool, preview: Preview, scroll: u16) -> Self {
         PreviewState {
             enabled,
             preview,
             scroll,
+            stale: false,
         }
     }
 
-    pub fn scroll_down(&mut self, offset: u16) {
-        self.scroll = self.scroll.saturating_add(offset).min(
-            self.preview
-                .total_lines
-                .saturating_sub(PREVIEW_MIN_SCROLL_LINES),
-        );
+    /// Scroll down by `offset` lines, stopping once the last line reaches
+    /// the bottom of a pane `height` lines tall.
+    pub fn scroll_down(&mut self, offset: u16, height: u16) {
+        self.scroll = self
+            .scroll
+            .saturating_add(offset)
+            .min(self.preview.total_lines.saturating_sub(height));
     }
 
     pub fn scroll_up(&mut self, offset: u16) {
         self.scroll = self.scroll.saturating_sub(offset);
     }
 
+    /// Make sure the last line doesn't end up above the bottom of a pane
+    /// `height` lines tall (e.g. after a resize).
+    pub fn clamp_scroll(&mut self, height: u16) {
+        self.scroll_down(0, height);
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Scroll so that the last line sits at the bottom of a pane `height`
+    /// lines tall.
+    pub fn scroll_to_bottom(&mut self, height: u16) {
+        self.scroll = self.preview.total_lines.saturating_sub(height);
+    }
+
     pub fn reset(&mut self) {
         self.preview = Preview::default();
         self.scroll = 0;
@@ -42,6 +66,7 @@ impl PreviewState {
         if self.preview.entry_raw != preview.entry_raw
             || self.preview.content != preview.content
             || self.preview.target_line != preview.target_line
+            || self.preview.width != preview.width
         {
             self.preview = preview;
             self.scroll = scroll;
@@ -66,19 +91,110 @@ impl PreviewState {
             .target_line
             .map(|line| line.saturating_sub(self.scroll));
 
-        PreviewState::new(
-            self.enabled,
-            // PERF: this allocates every time
-            Preview::new(
-                self.preview.entry_raw.clone(),
-                self.preview.formatted_command.clone(),
-                &self.preview.title,
-                cropped_content,
-                adjusted_line_number,
-                self.preview.total_lines,
-                self.preview.footer.clone(),
-            ),
-            self.scroll,
-        )
+        PreviewState {
+            stale: self.stale,
+            ..PreviewState::new(
+                self.enabled,
+                // PERF: this allocates every time
+                Preview::new(
+                    self.preview.entry_raw.clone(),
+                    self.preview.formatted_command.clone(),
+                    &self.preview.title,
+                    cropped_content,
+                    adjusted_line_number,
+                    self.preview.total_lines,
+                    self.preview.footer.clone(),
+                )
+                .with_badge(self.preview.badge.clone())
+                .with_width(self.preview.width)
+                .with_image(self.preview.image.clone()),
+                self.scroll,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_with_lines(total_lines: u16) -> PreviewState {
+        let mut state = PreviewState::default();
+        state.preview.total_lines = total_lines;
+        state
+    }
+
+    #[test]
+    fn test_scroll_down_stops_with_last_line_at_the_bottom() {
+        let mut state = state_with_lines(100);
+        state.scroll_down(20, 30);
+        assert_eq!(state.scroll, 20);
+        state.scroll_down(200, 30);
+        assert_eq!(state.scroll, 70);
+        state.scroll_up(5);
+        assert_eq!(state.scroll, 65);
+    }
+
+    #[test]
+    fn test_half_page() {
+        assert_eq!(half_page(0), 1);
+        assert_eq!(half_page(1), 1);
+        assert_eq!(half_page(7), 3);
+        assert_eq!(half_page(40), 20);
+    }
+
+    #[test]
+    fn test_page_down_lands_on_the_last_page() {
+        for (height, total_lines) in [(10, 35), (7, 100), (24, 24), (1, 3)] {
+            let mut state = state_with_lines(total_lines);
+            for _ in 0..total_lines {
+                state.scroll_down(height, height);
+            }
+            assert_eq!(state.scroll, total_lines - height);
+
+            let mut bottom = state_with_lines(total_lines);
+            bottom.scroll_to_bottom(height);
+            assert_eq!(bottom.scroll, state.scroll);
+
+            state.scroll_up(height);
+            assert_eq!(
+                state.scroll,
+                (total_lines - height).saturating_sub(height)
+            );
+        }
+    }
+
+    #[test]
+    fn test_half_page_scrolls_and_returns_to_top() {
+        let mut state = state_with_lines(50);
+        state.scroll_down(half_page(9), 9);
+        assert_eq!(state.scroll, 4);
+        state.scroll_down(half_page(9), 9);
+        assert_eq!(state.scroll, 8);
+        state.scroll_up(half_page(9));
+        assert_eq!(state.scroll, 4);
+        state.scroll_to_top();
+        assert_eq!(state.scroll, 0);
+    }
+
+    #[test]
+    fn test_clamp_scroll_after_the_pane_grew() {
+        let mut state = state_with_lines(100);
+        state.scroll_to_bottom(10);
+        assert_eq!(state.scroll, 90);
+        state.clamp_scroll(40);
+        assert_eq!(state.scroll, 60);
+        // shrinking never needs to scroll
+        state.clamp_scroll(5);
+        assert_eq!(state.scroll, 60);
+    }
+
+    #[test]
+    fn test_scroll_down_content_fits() {
+        let mut state = state_with_lines(10);
+        state.scroll_down(1, 30);
+        assert_eq!(state.scroll, 0);
+        state.scroll_to_bottom(30);
+        assert_eq!(state.scroll, 0);
     }
 }
@@ -1,13 +1,20 @@
-use std::io::Write;
+use std::{
+    io::Write,
+    time::{Duration, Instant},
+};
 
 use crate::{
     action::Action,
     draw::{Ctx, draw},
-    screen::layout::Layout,
+    previewer::image::{
+        GRAPHICS_PROTOCOL, GraphicsProtocol, KITTY_DELETE_ALL,
+    },
+    screen::{layout::Layout, preview::ImagePlacement},
     tui::Tui,
 };
 use anyhow::Result;
 use crossterm::{
+    cursor::{MoveTo, RestorePosition, SavePosition},
     execute, queue,
     terminal::{BeginSynchronizedUpdate, EndSynchronizedUpdate},
 };
@@ -17,10 +24,18 @@ use tracing::{debug, warn};
 #[derive(Debug, Clone)]
 pub enum RenderingTask {
     ClearScreen,
+    /// Ring the terminal bell.
+    Bell,
     Render(Box<Ctx>),
     Resize(u16, u16),
     Resume,
     Suspend,
+    /// Whether the terminal has the focus, which sets the frame rate.
+    Focus(bool),
+    /// Restore the terminal so that another program can take it over.
+    ///
+    /// The sender is notified once the terminal has been fully restored.
+    Release(std::sync::mpsc::Sender<()>),
     Quit,
 }
 
@@ -40,11 +55,16 @@ impl UiState {
     }
 }
 
-/// The maximum frame rate for the UI rendering loop (in milliseconds).
+/// The minimum duration of a frame of the UI rendering loop.
 ///
 /// This is used to limit the frame rate of the UI rendering loop to avoid consuming
 /// unnecessary CPU resources.
-const MAX_FRAME_RATE: u128 = 1000 / 60; // 60 FPS
+const FRAME_DURATION: Duration = Duration::from_millis(1000 / 60); // 60 FPS
+
+/// The duration of a frame while the terminal is unfocused.
+fn unfocused_frame_duration(frame_rate: u16) -> Duration {
+    Duration::from_millis(1000 / u64::from(frame_rate.max(1)))
+}
 
 /// The main UI rendering task loop.
 ///
@@ -58,19 +78,34 @@ const MAX_FRAME_RATE: u128 = 1000 / 60; // 60 FPS
 ///
 /// When starting the rendering loop, a choice is made to either render to stdout or stderr based
 /// on if the output is believed to be a TTY or not.
+///
+/// While the terminal is unfocused, at most `unfocused_frame_rate` frames are drawn per second.
+///
+/// The `Tui` is handed back once the loop is done with it.
 pub async fn render<W: Write>(
     mut render_rx: mpsc::UnboundedReceiver<RenderingTask>,
     action_tx: mpsc::UnboundedSender<Action>,
     ui_state_tx: mpsc::UnboundedSender<UiState>,
     mut tui: Tui<W>,
-) -> Result<()> {
+    unfocused_frame_rate: u16,
+) -> Result<Tui<W>> {
     let mut buffer = Vec::with_capacity(256);
     let mut num_instructions;
     let mut frame_start;
+    let mut focused = true;
+    // the image displayed over the last frame
+    let mut shown_image: Option<ImagePlacement> = None;
 
     // Rendering loop
-    'rendering: while render_rx.recv_many(&mut buffer, 256).await > 0 {
-        frame_start = std::time::Instant::now();
+    'rendering: loop {
+        // tasks received while waiting out an unfocused frame are already
+        // buffered
+        if buffer.is_empty()
+            && render_rx.recv_many(&mut buffer, 256).await == 0
+        {
+            break;
+        }
+        frame_start = Instant::now();
         num_instructions = buffer.len();
         // we only keep the last render instruction in the buffer
         if let Some(last_render) = buffer
@@ -91,7 +126,12 @@ pub async fn render<W: Write>(
         {
             match event {
                 RenderingTask::ClearScreen => {
-                    tui.terminal.clear()?;
+                    tui.clear()?;
+                    shown_image = None;
+                }
+                RenderingTask::Bell => {
+                    tui.backend_mut().write_all(b"\x07")?;
+                    tui.backend_mut().flush()?;
                 }
                 RenderingTask::Render(context) => {
                     if let Ok(size) = tui.size() {
@@ -102,23 +142,37 @@ pub async fn render<W: Write>(
                         if size.width.checked_mul(size.height).is_some() {
                             queue!(tui.backend_mut(), BeginSynchronizedUpdate)
                                 .ok();
-                            tui.terminal.draw(|frame| {
-                                let current_layout = context.layout;
-                                match draw(*context, frame, frame.area()) {
-                                    Ok(layout) => {
-                                        if layout != current_layout {
-                                            let _ = ui_state_tx
-                                                .send(UiState::new(layout));
-                                        }
-                                    }
-                                    Err(err) => {
-                                        warn!("Failed to draw: {:?}", err);
-                                        let _ = action_tx.send(Action::Error(
-                                            format!("Failed to draw: {err:?}"),
-                                        ));
-                                    }
+                            let mut image = draw_frame(
+                                &mut tui,
+                                (*context).clone(),
+                                &ui_state_tx,
+                                &action_tx,
+                            )?;
+                            if shown_image.is_some() && image != shown_image {
+                                // kitty images stay on screen until deleted,
+                                // and the cells under any image were never
+                                // written to: start over from a blank screen
+                                if *GRAPHICS_PROTOCOL
+                                    == Some(GraphicsProtocol::Kitty)
+                                {
+                                    tui.backend_mut().write_all(
+                                        KITTY_DELETE_ALL.as_bytes(),
+                                    )?;
                                 }
-                            })?;
+                                tui.clear()?;
+                                image = draw_frame(
+                                    &mut tui,
+                                    *context,
+                                    &ui_state_tx,
+                                    &action_tx,
+                                )?;
+                            }
+                            if let Some(placement) = &image
+                                && image != shown_image
+                            {
+                                write_image(tui.backend_mut(), placement)?;
+                            }
+                            shown_image = image;
                             execute!(tui.backend_mut(), EndSynchronizedUpdate)
                                 .ok();
                         } else {
@@ -128,16 +182,30 @@ pub async fn render<W: Write>(
                 }
                 RenderingTask::Resize(w, h) => {
                     tui.resize_viewport(w, h)?;
+                    shown_image = None;
                     action_tx.send(Action::Render)?;
                 }
                 RenderingTask::Suspend => {
                     tui.suspend()?;
-                    action_tx.send(Action::Resume)?;
-                    action_tx.send(Action::ClearScreen)?;
-                    tui.enter()?;
+                    // elsewhere, the SIGCONT listener resumes once the
+                    // process is continued
+                    #[cfg(windows)]
+                    {
+                        action_tx.send(Action::Resume)?;
+                        action_tx.send(Action::ClearScreen)?;
+                    }
                 }
                 RenderingTask::Resume => {
-                    tui.enter()?;
+                    tui.resume()?;
+                    shown_image = None;
+                }
+                RenderingTask::Focus(has_focus) => {
+                    focused = has_focus;
+                }
+                RenderingTask::Release(ack) => {
+                    tui.exit()?;
+                    shown_image = None;
+                    let _ = ack.send(());
                 }
                 RenderingTask::Quit => {
                     debug!("Exiting rendering loop");
@@ -147,15 +215,93 @@ pub async fn render<W: Write>(
             }
         }
         // yield back to the scheduler until the next frame
-        let elapsed = frame_start.elapsed();
-        if elapsed.as_millis() < MAX_FRAME_RATE {
-            let sleep_duration = std::time::Duration::from_millis(
-                u64::try_from(MAX_FRAME_RATE - elapsed.as_millis())
-                    .unwrap_or(0),
-            );
-            tokio::time::sleep(sleep_duration).await;
+        if focused {
+            tokio::time::sleep_until((frame_start + FRAME_DURATION).into())
+                .await;
+        } else {
+            // queued renders are coalesced into a single one at the next
+            // frame, but getting the focus back (or quitting) doesn't wait
+            let deadline =
+                frame_start + unfocused_frame_duration(unfocused_frame_rate);
+            while let Ok(Some(task)) =
+                tokio::time::timeout_at(deadline.into(), render_rx.recv())
+                    .await
+            {
+                let urgent = matches!(
+                    task,
+                    RenderingTask::Focus(true) | RenderingTask::Quit
+                );
+                buffer.push(task);
+                if urgent {
+                    break;
+                }
+            }
         }
     }
 
+    Ok(tui)
+}
+
+/// Draw a frame from `context`, sending its layout back to the main thread
+/// if it changed, and return the image to display over it.
+fn draw_frame<W: Write>(
+    tui: &mut Tui<W>,
+    context: Ctx,
+    ui_state_tx: &mpsc::UnboundedSender<UiState>,
+    action_tx: &mpsc::UnboundedSender<Action>,
+) -> Result<Option<ImagePlacement>> {
+    let current_layout = context.layout;
+    let mut image = None;
+    tui.draw(|frame| match draw(context, frame, frame.area()) {
+        Ok(drawn) => {
+            if drawn.layout != current_layout {
+                let _ = ui_state_tx.send(UiState::new(drawn.layout));
+            }
+            image = drawn.image;
+        }
+        Err(err) => {
+            warn!("Failed to draw: {:?}", err);
+            let _ = action_tx
+                .send(Action::Error(format!("Failed to draw: {err:?}")));
+        }
+    })?;
+    Ok(image)
+}
+
+/// Write the escape sequence displaying `image` at its position, leaving the
+/// cursor where it was.
+fn write_image(
+    backend: &mut impl Write,
+    image: &ImagePlacement,
+) -> Result<()> {
+    queue!(backend, SavePosition, MoveTo(image.x, image.y))?;
+    backend.write_all(image.sequence.as_bytes())?;
+    queue!(backend, RestorePosition)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_image_keeps_the_cursor() {
+        let image = ImagePlacement {
+            x: 3,
+            y: 2,
+            sequence: "\x1b_Gimage\x1b\\".into(),
+        };
+        let mut out = Vec::new();
+        write_image(&mut out, &image).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b7\x1b[3;4H\x1b_Gimage\x1b\\\x1b8"
+        );
+    }
+
+    #[test]
+    fn test_unfocused_frame_duration() {
+        assert_eq!(unfocused_frame_duration(2), Duration::from_millis(500));
+        assert_eq!(unfocused_frame_duration(0), Duration::from_secs(1));
+    }
+}
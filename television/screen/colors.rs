@@ -1,4 +1,7 @@
+use std::{fmt::Display, path::PathBuf};
+
 use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Colorscheme {
@@ -28,6 +31,7 @@ pub struct ResultsColorscheme {
     pub result_selected_bg: Color,
     pub result_selected_fg: Color,
     pub match_foreground_color: Color,
+    pub result_line_number_fg: Color,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -52,3 +56,476 @@ pub struct ModeColorscheme {
     pub remote_control: Color,
     pub remote_control_fg: Color,
 }
+
+/// How many colors the terminal can display.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize, Serialize,
+)]
+pub enum ColorMode {
+    /// Any RGB color.
+    #[default]
+    #[serde(rename = "truecolor")]
+    Truecolor,
+    /// The xterm 256-color palette.
+    #[serde(rename = "256")]
+    Ansi256,
+    /// The 16 ANSI colors.
+    #[serde(rename = "16")]
+    Ansi16,
+}
+
+impl ColorMode {
+    /// Guess what the terminal supports from `COLORTERM`, the terminfo
+    /// entry for `TERM` and `TERM` itself.
+    pub fn detect() -> Self {
+        let term = std::env::var("TERM").ok();
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            term.as_deref(),
+            term.as_deref()
+                .and_then(read_terminfo)
+                .and_then(|entry| Self::from_terminfo(&entry)),
+        )
+    }
+
+    fn from_env(
+        colorterm: Option<&str>,
+        term: Option<&str>,
+        terminfo: Option<Self>,
+    ) -> Self {
+        if matches!(colorterm, Some("truecolor" | "24bit")) {
+            return Self::Truecolor;
+        }
+        if let Some(mode) = terminfo {
+            return mode;
+        }
+        match term {
+            Some(term)
+                if ["direct", "truecolor", "24bit"]
+                    .iter()
+                    .any(|suffix| term.ends_with(suffix)) =>
+            {
+                Self::Truecolor
+            }
+            Some(term) if term.contains("256") => Self::Ansi256,
+            // the Windows console doesn't set TERM but handles RGB fine
+            None if cfg!(windows) => Self::Truecolor,
+            _ => Self::Ansi16,
+        }
+    }
+
+    /// What a compiled terminfo entry (see `term(5)`) says about colors:
+    /// truecolor if it sets the `RGB` or `Tc` extended capability,
+    /// otherwise going by `colors`.
+    fn from_terminfo(entry: &[u8]) -> Option<Self> {
+        let short = |at: usize| {
+            let bytes = entry.get(at..at + 2)?;
+            usize::try_from(i16::from_le_bytes([bytes[0], bytes[1]])).ok()
+        };
+        // the legacy format stores numbers on 16 bits, the newer one on 32
+        let number_size = match short(0)? {
+            0o432 => 2,
+            0o1036 => 4,
+            _ => return None,
+        };
+        let number = |at: usize| -> Option<i32> {
+            let bytes = entry.get(at..at + number_size)?;
+            Some(if number_size == 2 {
+                i16::from_le_bytes([bytes[0], bytes[1]]).into()
+            } else {
+                i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+            })
+        };
+        let (names_size, booleans, numbers, strings, table_size) =
+            (short(2)?, short(4)?, short(6)?, short(8)?, short(10)?);
+
+        let numbers_at = (12 + names_size + booleans).next_multiple_of(2);
+        // `colors` is the 14th number
+        let colors = if numbers > 13 {
+            number(numbers_at + 13 * number_size)?
+        } else {
+            -1
+        };
+
+        let extended_at =
+            (numbers_at + numbers * number_size + strings * 2 + table_size)
+                .next_multiple_of(2);
+        if let Some(ext_table_size) = short(extended_at + 8) {
+            let (ext_booleans, ext_numbers, ext_strings) = (
+                short(extended_at)?,
+                short(extended_at + 2)?,
+                short(extended_at + 4)?,
+            );
+            let booleans_at = extended_at + 10;
+            let ext_names = ext_booleans + ext_numbers + ext_strings;
+            let table_at = (booleans_at + ext_booleans).next_multiple_of(2)
+                + ext_numbers * number_size
+                + (ext_strings + ext_names) * 2;
+            let ext_table = entry.get(table_at..table_at + ext_table_size)?;
+            // the table ends with the names of every extended capability,
+            // those of the booleans first
+            let mut values: Vec<&[u8]> =
+                ext_table.split(|b| *b == 0).collect();
+            values.pop();
+            let names = &values[values.len().checked_sub(ext_names)?..];
+            let rgb = names.iter().take(ext_booleans).enumerate().any(
+                |(i, name)| {
+                    matches!(*name, b"RGB" | b"Tc")
+                        && entry.get(booleans_at + i) == Some(&1)
+                },
+            );
+            if rgb {
+                return Some(Self::Truecolor);
+            }
+        }
+
+        match colors {
+            0x0100_0000.. => Some(Self::Truecolor),
+            256.. => Some(Self::Ansi256),
+            1.. => Some(Self::Ansi16),
+            _ => None,
+        }
+    }
+
+    /// The closest color to `color` the terminal can display.
+    pub fn map(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::Ansi256, Color::Rgb(r, g, b)) => {
+                Color::Indexed(rgb_to_ansi256(r, g, b))
+            }
+            (Self::Ansi16, Color::Rgb(r, g, b)) => rgb_to_ansi16(r, g, b),
+            (Self::Ansi16, Color::Indexed(index)) if index >= 16 => {
+                let (r, g, b) = ansi256_to_rgb(index);
+                rgb_to_ansi16(r, g, b)
+            }
+            _ => color,
+        }
+    }
+}
+
+/// The compiled terminfo entry for `term`, looked up where ncurses does.
+fn read_terminfo(term: &str) -> Option<Vec<u8>> {
+    let first = term.chars().next()?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(dir) = std::env::var_os("TERMINFO") {
+        dirs.push(dir.into());
+    }
+    if let Some(base_dirs) = directories::BaseDirs::new() {
+        dirs.push(base_dirs.home_dir().join(".terminfo"));
+    }
+    if let Ok(terminfo_dirs) = std::env::var("TERMINFO_DIRS") {
+        dirs.extend(
+            terminfo_dirs
+                .split(':')
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from),
+        );
+    }
+    dirs.extend(
+        ["/etc/terminfo", "/lib/terminfo", "/usr/share/terminfo"]
+            .map(PathBuf::from),
+    );
+    dirs.iter().find_map(|dir| {
+        // entries are filed under their first letter, or its hex code on
+        // case-insensitive filesystems
+        std::fs::read(dir.join(first.to_string()).join(term))
+            .or_else(|_| {
+                std::fs::read(
+                    dir.join(format!("{:x}", u32::from(first))).join(term),
+                )
+            })
+            .ok()
+    })
+}
+
+impl Display for ColorMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truecolor => write!(f, "truecolor"),
+            Self::Ansi256 => write!(f, "256"),
+            Self::Ansi16 => write!(f, "16"),
+        }
+    }
+}
+
+/// The intensities of the 6x6x6 color cube of the xterm 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// The default RGB values of the 16 ANSI colors, as xterm has them.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let d = |x: u8, y: u8| u32::from(x.abs_diff(y)).pow(2);
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+/// The index of the closest color of the xterm 256-color palette, among
+/// its color cube and grayscale ramp.
+pub fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0u8..)
+            .zip(CUBE_LEVELS)
+            .min_by_key(|(_, level)| c.abs_diff(*level))
+            .map_or(0, |(i, _)| i)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+    let cube_rgb = (
+        CUBE_LEVELS[usize::from(ri)],
+        CUBE_LEVELS[usize::from(gi)],
+        CUBE_LEVELS[usize::from(bi)],
+    );
+
+    // the grayscale ramp goes from 8 to 238 by steps of 10
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let step = u8::try_from(average.saturating_sub(3) / 10)
+        .unwrap_or(23)
+        .min(23);
+    let gray = 8 + 10 * step;
+
+    if distance((r, g, b), (gray, gray, gray)) < distance((r, g, b), cube_rgb)
+    {
+        232 + step
+    } else {
+        cube
+    }
+}
+
+/// The closest of the 16 ANSI colors.
+pub fn rgb_to_ansi16(r: u8, g: u8, b: u8) -> Color {
+    ANSI16
+        .iter()
+        .min_by_key(|(_, rgb)| distance((r, g, b), *rgb))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// The RGB value of a color of the xterm 256-color palette.
+fn ansi256_to_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..16 => ANSI16[usize::from(index)].1,
+        16..232 => {
+            let i = index - 16;
+            (
+                CUBE_LEVELS[usize::from(i / 36)],
+                CUBE_LEVELS[usize::from(i / 6 % 6)],
+                CUBE_LEVELS[usize::from(i % 6)],
+            )
+        }
+        _ => {
+            let gray = 8 + 10 * (index - 232);
+            (gray, gray, gray)
+        }
+    }
+}
+
+impl Colorscheme {
+    /// Replace the colors the terminal can't display with the closest ones
+    /// it can.
+    #[must_use]
+    pub fn with_color_mode(self, mode: ColorMode) -> Self {
+        if mode == ColorMode::Truecolor {
+            return self;
+        }
+        let map = |color| mode.map(color);
+        Self {
+            general: GeneralColorscheme {
+                border_fg: map(self.general.border_fg),
+                background: self.general.background.map(map),
+            },
+            help: HelpColorscheme {
+                metadata_field_name_fg: map(self.help.metadata_field_name_fg),
+                metadata_field_value_fg: map(self
+                    .help
+                    .metadata_field_value_fg),
+            },
+            results: ResultsColorscheme {
+                result_fg: map(self.results.result_fg),
+                result_selected_bg: map(self.results.result_selected_bg),
+                result_selected_fg: map(self.results.result_selected_fg),
+                match_foreground_color: map(self
+                    .results
+                    .match_foreground_color),
+                result_line_number_fg: map(self.results.result_line_number_fg),
+            },
+            preview: PreviewColorscheme {
+                title_fg: map(self.preview.title_fg),
+                highlight_bg: map(self.preview.highlight_bg),
+                content_fg: map(self.preview.content_fg),
+                gutter_fg: map(self.preview.gutter_fg),
+                gutter_selected_fg: map(self.preview.gutter_selected_fg),
+            },
+            input: InputColorscheme {
+                input_fg: map(self.input.input_fg),
+                results_count_fg: map(self.input.results_count_fg),
+            },
+            mode: ModeColorscheme {
+                channel: map(self.mode.channel),
+                channel_fg: map(self.mode.channel_fg),
+                remote_control: map(self.mode.remote_control),
+                remote_control_fg: map(self.mode.remote_control_fg),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::themes::Theme;
+
+    #[test]
+    fn test_rgb_to_ansi256() {
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(95, 135, 175), 67);
+        // grays land on the grayscale ramp
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+        assert_eq!(rgb_to_ansi256(238, 238, 238), 255);
+        assert_eq!(rgb_to_ansi256(30, 30, 30), 234);
+    }
+
+    #[test]
+    fn test_rgb_to_ansi16() {
+        assert_eq!(rgb_to_ansi16(0, 0, 0), Color::Black);
+        assert_eq!(rgb_to_ansi16(255, 0, 0), Color::LightRed);
+        assert_eq!(rgb_to_ansi16(200, 10, 10), Color::Red);
+        assert_eq!(rgb_to_ansi16(250, 250, 250), Color::White);
+        assert_eq!(rgb_to_ansi16(120, 120, 130), Color::DarkGray);
+    }
+
+    #[test]
+    fn test_ansi256_round_trips() {
+        for index in 16..=255 {
+            let (r, g, b) = ansi256_to_rgb(index);
+            assert_eq!(rgb_to_ansi256(r, g, b), index, "{index}");
+        }
+    }
+
+    #[test]
+    fn test_color_mode_from_env() {
+        let detect =
+            |colorterm, term| ColorMode::from_env(colorterm, term, None);
+        assert_eq!(detect(Some("truecolor"), None), ColorMode::Truecolor);
+        assert_eq!(detect(None, Some("xterm-direct")), ColorMode::Truecolor);
+        assert_eq!(detect(None, Some("xterm-256color")), ColorMode::Ansi256);
+        assert_eq!(detect(Some(""), Some("linux")), ColorMode::Ansi16);
+
+        // terminfo wins over the name of the terminal, but not COLORTERM
+        let terminfo = Some(ColorMode::Truecolor);
+        assert_eq!(
+            ColorMode::from_env(None, Some("xterm-256color"), terminfo),
+            ColorMode::Truecolor
+        );
+        assert_eq!(
+            ColorMode::from_env(
+                Some("truecolor"),
+                Some("xterm"),
+                Some(ColorMode::Ansi16)
+            ),
+            ColorMode::Truecolor
+        );
+    }
+
+    /// A compiled terminfo entry in the legacy format with `colors` and
+    /// the given extended boolean capabilities set.
+    fn terminfo_entry(colors: i16, ext_booleans: &[&str]) -> Vec<u8> {
+        fn short(entry: &mut Vec<u8>, n: usize) {
+            entry.extend(i16::try_from(n).unwrap().to_le_bytes());
+        }
+        let mut entry = Vec::new();
+        let names = b"test|test terminal\0";
+        for n in [0o432, names.len(), 0, 14, 0, 0] {
+            short(&mut entry, n);
+        }
+        entry.extend(names);
+        if entry.len() % 2 == 1 {
+            entry.push(0);
+        }
+        for i in 0..14 {
+            let number: i16 = if i == 13 { colors } else { -1 };
+            entry.extend(number.to_le_bytes());
+        }
+        if ext_booleans.is_empty() {
+            return entry;
+        }
+        let table: Vec<u8> = ext_booleans
+            .iter()
+            .flat_map(|name| name.bytes().chain([0]))
+            .collect();
+        let count = ext_booleans.len();
+        for n in [count, 0, 0, count, table.len()] {
+            short(&mut entry, n);
+        }
+        entry.extend(std::iter::repeat_n(1, count));
+        if entry.len() % 2 == 1 {
+            entry.push(0);
+        }
+        let mut offset = 0;
+        for name in ext_booleans {
+            short(&mut entry, offset);
+            offset += name.len() + 1;
+        }
+        entry.extend(table);
+        entry
+    }
+
+    #[test]
+    fn test_color_mode_from_terminfo() {
+        let detect = |entry: Vec<u8>| ColorMode::from_terminfo(&entry);
+        assert_eq!(detect(terminfo_entry(8, &[])), Some(ColorMode::Ansi16));
+        assert_eq!(detect(terminfo_entry(256, &[])), Some(ColorMode::Ansi256));
+        assert_eq!(
+            detect(terminfo_entry(256, &["AX", "RGB"])),
+            Some(ColorMode::Truecolor)
+        );
+        assert_eq!(
+            detect(terminfo_entry(256, &["Tc"])),
+            Some(ColorMode::Truecolor)
+        );
+        assert_eq!(detect(terminfo_entry(-1, &[])), None);
+        assert_eq!(detect(b"not terminfo".to_vec()), None);
+    }
+
+    #[test]
+    fn test_color_mode_map() {
+        let rgb = Color::Rgb(255, 0, 0);
+        assert_eq!(ColorMode::Truecolor.map(rgb), rgb);
+        assert_eq!(ColorMode::Ansi256.map(rgb), Color::Indexed(196));
+        assert_eq!(ColorMode::Ansi16.map(rgb), Color::LightRed);
+        assert_eq!(
+            ColorMode::Ansi16.map(Color::Indexed(196)),
+            Color::LightRed
+        );
+        // named colors are left alone
+        assert_eq!(ColorMode::Ansi16.map(Color::Blue), Color::Blue);
+    }
+
+    #[test]
+    fn test_colorscheme_with_color_mode() {
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+        let degraded = colorscheme.clone().with_color_mode(ColorMode::Ansi256);
+        assert!(!format!("{degraded:?}").contains("Rgb"));
+        assert_eq!(
+            colorscheme.clone().with_color_mode(ColorMode::Truecolor),
+            colorscheme
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::{
+    channels::{entry::Entry, prototypes::ActionSpec},
+    screen::{colors::Colorscheme, glyphs::Glyphs},
+    utils::{command::format_command, hashmaps::FxIndexSet},
+};
+use anyhow::Result;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+const MAX_PANEL_WIDTH: u16 = 80;
+
+/// A pending confirmation for an external action marked with `confirm = true`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Confirmation {
+    /// The name of the action waiting to be confirmed.
+    pub action_name: String,
+    /// The exact command(s) that will be run once confirmed.
+    pub commands: Vec<String>,
+    /// The entries the action will be run on.
+    pub entries: FxIndexSet<Entry>,
+}
+
+impl Confirmation {
+    pub fn new(
+        action_name: &str,
+        action_spec: &ActionSpec,
+        entries: FxIndexSet<Entry>,
+    ) -> Result<Self> {
+        let commands = vec![format_command(
+            &entries,
+            action_spec.command.get_nth(0),
+            &action_spec.separator,
+        )?];
+        Ok(Self {
+            action_name: action_name.to_string(),
+            commands,
+            entries,
+        })
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+/// Draws a small centered block asking the user to confirm an action.
+pub fn draw_confirmation(
+    f: &mut Frame<'_>,
+    area: Rect,
+    confirmation: &Confirmation,
+    colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+) {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!(
+                "Run on {} entr{}?",
+                confirmation.entry_count(),
+                if confirmation.entry_count() == 1 {
+                    "y"
+                } else {
+                    "ies"
+                }
+            ),
+            Style::default().fg(colorscheme.help.metadata_field_name_fg),
+        )),
+        Line::default(),
+    ];
+    lines.extend(confirmation.commands.iter().map(|command| {
+        Line::from(Span::styled(
+            command.clone(),
+            Style::default().fg(colorscheme.help.metadata_field_value_fg),
+        ))
+    }));
+    lines.push(Line::default());
+    lines.push(
+        Line::from("[y]es / [n]o")
+            .style(Style::default().fg(colorscheme.general.border_fg))
+            .alignment(Alignment::Center),
+    );
+
+    let width = area.width.min(MAX_PANEL_WIDTH);
+    let inner_width = width.saturating_sub(4).max(1);
+    let height = lines
+        .iter()
+        .map(|line| {
+            u16::try_from(line.width().max(1).div_ceil(inner_width as usize))
+                .unwrap_or(u16::MAX)
+        })
+        .sum::<u16>()
+        .saturating_add(2)
+        .min(area.height);
+    let [popup] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(popup);
+    if popup.area() == 0 {
+        return;
+    }
+
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(glyphs.popup_border())
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .title_top(
+            Line::from(format!(" {} ", confirmation.action_name))
+                .bold()
+                .alignment(Alignment::Center),
+        )
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .padding(Padding::horizontal(1));
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::prototypes::{
+        CommandSpec, ExecutionMode, OutputMode, Template,
+    };
+
+    #[test]
+    fn test_confirmation_lists_formatted_command_for_multi_selection() {
+        let action_spec = ActionSpec {
+            description: None,
+            command: CommandSpec {
+                inner: vec![Template::parse("git branch -D {}").unwrap()],
+                interactive: false,
+                env: rustc_hash::FxHashMap::default(),
+                labels: Vec::new(),
+                conditions: Vec::new(),
+                delimiters: Vec::new(),
+                stdin: Vec::new(),
+            },
+            mode: ExecutionMode::Fork,
+            separator: " ".to_string(),
+            confirm: true,
+            output: OutputMode::default(),
+            reload: false,
+            remove: Vec::new(),
+        };
+        let entries = FxIndexSet::from_iter([
+            Entry::new("feature".to_string()),
+            Entry::new("fix".to_string()),
+        ]);
+
+        let confirmation =
+            Confirmation::new("delete", &action_spec, entries).unwrap();
+
+        assert_eq!(confirmation.entry_count(), 2);
+        assert_eq!(confirmation.commands.len(), 1);
+        let command = &confirmation.commands[0];
+        assert!(command.starts_with("git branch -D "));
+        assert!(command.contains("'feature'"));
+        assert!(command.contains("'fix'"));
+    }
+}
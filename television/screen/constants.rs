@@ -1,4 +1 @@
-pub const POINTER_SYMBOL: &str = "> ";
-pub const SELECTED_SYMBOL: &str = "● ";
-pub const DESELECTED_SYMBOL: &str = "  ";
 pub const LOGO_WIDTH: u16 = 24;
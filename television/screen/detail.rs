@@ -0,0 +1,233 @@
+use crate::{
+    channels::{
+        entry::Entry,
+        prototypes::{ActionSpec, CommandSpec, Delimiter, Template},
+    },
+    screen::{colors::Colorscheme, glyphs::Glyphs},
+    utils::{command::format_command, hashmaps::FxIndexSet},
+};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Flex, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+use rustc_hash::FxHashMap;
+
+const MAX_PANEL_WIDTH: u16 = 100;
+
+/// Everything there is to know about the selected entry, shown in a popup
+/// since long entries get truncated in the results list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryDetail {
+    /// The full, untruncated entry.
+    pub raw: String,
+    /// The entry split on the channel's delimiter, as used by positional
+    /// placeholders like `{0}`.
+    pub fields: Vec<String>,
+    /// The delimiter the entry was split on.
+    pub delimiter: String,
+    /// The line the preview is scrolled to, if any.
+    pub line_number: Option<u16>,
+    /// The preview and action commands as they would run on this entry,
+    /// along with their name.
+    pub commands: Vec<(String, String)>,
+}
+
+impl EntryDetail {
+    pub fn new(
+        entry: &Entry,
+        delimiter: &Delimiter,
+        preview_command: Option<&CommandSpec>,
+        preview_offset: Option<&Template>,
+        actions: &FxHashMap<String, ActionSpec>,
+    ) -> Self {
+        let fields = if *delimiter == Delimiter::Fixed(String::new()) {
+            vec![entry.raw.clone()]
+        } else {
+            delimiter
+                .split(&entry.raw)
+                .into_iter()
+                .map(str::to_string)
+                .collect()
+        };
+        let line_number = preview_offset
+            .and_then(|offset| offset.format(&entry.raw).ok())
+            .and_then(|offset| offset.parse().ok());
+
+        let entries = FxIndexSet::from_iter([entry.clone()]);
+        let mut commands: Vec<(String, String)> = preview_command
+            .into_iter()
+            .flat_map(|command| {
+                command.inner.iter().enumerate().filter(|(i, _)| {
+                    command.applies_nth(*i, &entry.raw)
+                        && !command.is_entry_preview_nth(*i)
+                })
+            })
+            .filter_map(|(i, template)| {
+                let command = template.format(&entry.raw).ok()?;
+                Some((format!("preview {}", i + 1), command))
+            })
+            .collect();
+        let mut action_names: Vec<&String> = actions.keys().collect();
+        action_names.sort();
+        commands.extend(action_names.into_iter().filter_map(|name| {
+            let spec = &actions[name];
+            let command = format_command(
+                &entries,
+                spec.command.get_nth(0),
+                &spec.separator,
+            )
+            .ok()?;
+            Some((name.clone(), command))
+        }));
+
+        Self {
+            raw: entry.raw.clone(),
+            fields,
+            delimiter: delimiter.to_string(),
+            line_number,
+            commands,
+        }
+    }
+
+    fn lines(&self, colorscheme: &Colorscheme) -> Vec<Line<'static>> {
+        let name_style =
+            Style::default().fg(colorscheme.help.metadata_field_name_fg);
+        let value_style =
+            Style::default().fg(colorscheme.help.metadata_field_value_fg);
+        let heading =
+            |text: String| Line::from(Span::styled(text, name_style.bold()));
+
+        let mut lines = vec![
+            Line::from(Span::styled(self.raw.clone(), value_style)),
+            Line::default(),
+            heading(format!("Fields (split on {:?})", self.delimiter)),
+        ];
+        lines.extend(self.fields.iter().enumerate().map(|(i, field)| {
+            Line::from(vec![
+                Span::styled(format!("{i:>3}  "), name_style),
+                Span::styled(field.clone(), value_style),
+            ])
+        }));
+        if let Some(line_number) = self.line_number {
+            lines.push(Line::default());
+            lines.push(Line::from(vec![
+                Span::styled("Line  ", name_style.bold()),
+                Span::styled(line_number.to_string(), value_style),
+            ]));
+        }
+        if !self.commands.is_empty() {
+            lines.push(Line::default());
+            lines.push(heading("Commands".to_string()));
+            lines.extend(self.commands.iter().map(|(name, command)| {
+                Line::from(vec![
+                    Span::styled(format!("{name}: "), name_style),
+                    Span::styled(command.clone(), value_style),
+                ])
+            }));
+        }
+        lines
+    }
+}
+
+/// Draws the entry detail as a centered popup.
+pub fn draw_entry_detail(
+    f: &mut Frame<'_>,
+    area: Rect,
+    detail: &EntryDetail,
+    colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+) {
+    let lines = detail.lines(colorscheme);
+
+    let width = area.width.min(MAX_PANEL_WIDTH);
+    let inner_width = width.saturating_sub(4).max(1);
+    let height = lines
+        .iter()
+        .map(|line| {
+            u16::try_from(line.width().max(1).div_ceil(inner_width as usize))
+                .unwrap_or(u16::MAX)
+        })
+        .sum::<u16>()
+        .saturating_add(2)
+        .min(area.height);
+    let [popup] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [popup] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(popup);
+    if popup.area() == 0 {
+        return;
+    }
+
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(glyphs.popup_border())
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .title_top(Line::from(" Entry ").bold().alignment(Alignment::Center))
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .padding(Padding::horizontal(1));
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        popup,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::prototypes::{ExecutionMode, OutputMode};
+
+    #[test]
+    fn test_entry_detail() {
+        let entry = Entry::new("src/main.rs:42:fn main() {".to_string());
+        let preview =
+            CommandSpec::from(Template::parse("bat {split:\\::0}").unwrap());
+        let offset = Template::parse("{split:\\::1}").unwrap();
+        let mut actions = FxHashMap::default();
+        actions.insert(
+            "edit".to_string(),
+            ActionSpec {
+                description: None,
+                command: CommandSpec::from(
+                    Template::parse("nvim +{split:\\::1} {split:\\::0}")
+                        .unwrap(),
+                ),
+                mode: ExecutionMode::Execute,
+                separator: " ".to_string(),
+                confirm: false,
+                output: OutputMode::default(),
+                reload: false,
+                remove: Vec::new(),
+            },
+        );
+
+        let detail = EntryDetail::new(
+            &entry,
+            &Delimiter::Fixed(":".to_string()),
+            Some(&preview),
+            Some(&offset),
+            &actions,
+        );
+
+        assert_eq!(detail.raw, "src/main.rs:42:fn main() {");
+        assert_eq!(detail.fields, vec!["src/main.rs", "42", "fn main() {"]);
+        assert_eq!(detail.line_number, Some(42));
+        assert_eq!(
+            detail.commands,
+            vec![
+                ("preview 1".to_string(), "bat src/main.rs".to_string()),
+                ("edit".to_string(), "nvim +42 src/main.rs".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use ratatui::{symbols::border, widgets::BorderType};
+
+/// Borders drawn with ASCII characters only.
+pub const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// The symbols the UI is drawn with, which terminals without unicode
+/// support get ASCII equivalents of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Glyphs {
+    /// In front of the entry under the cursor.
+    pub pointer: &'static str,
+    /// In front of the selected entries.
+    pub selected: &'static str,
+    pub deselected: &'static str,
+    /// In front of the entries marked during the session.
+    pub marked: &'static str,
+    pub unmarked: &'static str,
+    /// Stands for the lines of multi-line records that aren't shown.
+    pub record: &'static str,
+    /// Shown in place of the spinner while only the matcher is busy, a
+    /// single column wide.
+    pub matching: &'static str,
+    pub spinner: &'static [&'static str],
+    /// Stands for the text cut out of titles.
+    pub ellipsis: &'static str,
+    /// Leads from one channel to the next in the results title.
+    pub arrow: &'static str,
+    /// Between the items of hint lines and counters.
+    pub separator: &'static str,
+    /// Between the sections of the status bar.
+    pub bullet: &'static str,
+    /// In front of the most verbose level shown in the logs.
+    pub at_most: &'static str,
+    /// The cursor of the filters typed in panel titles.
+    pub cursor: &'static str,
+    /// The borders drawn whatever the configured border type, if any.
+    pub border: Option<border::Set>,
+    /// Whether file and channel icons can be shown.
+    pub icons: bool,
+}
+
+pub const UNICODE_GLYPHS: Glyphs = Glyphs {
+    pointer: "> ",
+    selected: "● ",
+    deselected: "  ",
+    marked: "◆ ",
+    unmarked: "  ",
+    record: "⏎",
+    matching: "…",
+    spinner: &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+    ellipsis: "…",
+    arrow: "→",
+    separator: "·",
+    bullet: "•",
+    at_most: "≤",
+    cursor: "▏",
+    border: None,
+    icons: true,
+};
+
+pub const ASCII_GLYPHS: Glyphs = Glyphs {
+    pointer: "> ",
+    selected: "* ",
+    deselected: "  ",
+    marked: "+ ",
+    unmarked: "  ",
+    record: "~",
+    matching: ".",
+    spinner: &["|", "/", "-", "\\"],
+    ellipsis: "...",
+    arrow: "->",
+    separator: "-",
+    bullet: "*",
+    at_most: "<=",
+    cursor: "|",
+    border: Some(ASCII_BORDER),
+    icons: false,
+};
+
+impl Glyphs {
+    pub fn new(ascii: bool) -> &'static Self {
+        if ascii {
+            &ASCII_GLYPHS
+        } else {
+            &UNICODE_GLYPHS
+        }
+    }
+
+    /// The symbols drawing borders of the given type.
+    pub fn border_set(&self, border_type: BorderType) -> border::Set {
+        self.border.unwrap_or_else(|| border_type.to_border_set())
+    }
+
+    /// The borders of popups and panels without a configurable border type.
+    pub fn popup_border(&self) -> border::Set {
+        self.border_set(BorderType::Rounded)
+    }
+}
+
+/// Whether the locale can't be trusted to display unicode, e.g. on minimal
+/// TTYs or in CI logs.
+pub fn ascii_locale() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()));
+    match locale {
+        Some(locale) => !is_utf8_locale(&locale),
+        // the Windows console doesn't use these
+        None => !cfg!(windows),
+    }
+}
+
+fn is_utf8_locale(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+    locale.contains("utf-8") || locale.contains("utf8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_utf8_locale() {
+        assert!(is_utf8_locale("en_US.UTF-8"));
+        assert!(is_utf8_locale("C.utf8"));
+        assert!(!is_utf8_locale("C"));
+        assert!(!is_utf8_locale("POSIX"));
+    }
+
+    #[test]
+    fn test_ascii_glyphs_are_ascii() {
+        let glyphs = Glyphs::new(true);
+        let symbols = [
+            glyphs.pointer,
+            glyphs.selected,
+            glyphs.deselected,
+            glyphs.marked,
+            glyphs.unmarked,
+            glyphs.record,
+            glyphs.matching,
+            glyphs.ellipsis,
+            glyphs.arrow,
+            glyphs.separator,
+            glyphs.bullet,
+            glyphs.at_most,
+            glyphs.cursor,
+        ];
+        let border = glyphs.border_set(BorderType::Thick);
+        let borders = [
+            border.top_left,
+            border.top_right,
+            border.bottom_left,
+            border.bottom_right,
+            border.vertical_left,
+            border.vertical_right,
+            border.horizontal_top,
+            border.horizontal_bottom,
+        ];
+        assert!(
+            symbols
+                .iter()
+                .chain(glyphs.spinner)
+                .chain(&borders)
+                .all(|symbol| symbol.is_ascii())
+        );
+        // selection markers are all as wide, so entries stay aligned
+        assert_eq!(glyphs.selected.len(), glyphs.deselected.len());
+        assert_eq!(glyphs.marked.len(), glyphs.unmarked.len());
+    }
+}
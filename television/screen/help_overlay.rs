@@ -0,0 +1,264 @@
+use crate::{
+    channels::prototypes::{CommandSpec, Template},
+    config::layers::MergedConfig,
+    screen::{
+        colors::Colorscheme, glyphs::Glyphs, help_panel::generate_help_content,
+    },
+    television::Mode,
+};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+/// The full-screen help overlay, listing every keybinding along with what
+/// the current channel is made of.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HelpOverlay {
+    /// The first line shown.
+    pub scroll: usize,
+}
+
+impl HelpOverlay {
+    /// Scroll up by `lines`.
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    /// Scroll down by `lines`, given the number of `total` lines shown in an
+    /// overlay `height` lines tall.
+    pub fn scroll_down(&mut self, lines: usize, total: usize, height: usize) {
+        self.scroll = (self.scroll + lines).min(total.saturating_sub(height));
+    }
+}
+
+/// The number of lines of content that fit in the overlay.
+pub fn help_overlay_height(area: Rect) -> usize {
+    usize::from(area.height.saturating_sub(2)) // -2 for borders
+}
+
+/// Generates the overlay's content: the channel description and its
+/// position in the channel history, every keybinding for the current mode
+/// and the channel's commands.
+pub fn help_overlay_lines(
+    config: &MergedConfig,
+    mode: Mode,
+    colorscheme: &Colorscheme,
+    (position, history_len): (usize, usize),
+) -> Vec<Line<'static>> {
+    let name_style =
+        Style::default().fg(colorscheme.help.metadata_field_name_fg);
+    let value_style =
+        Style::default().fg(colorscheme.help.metadata_field_value_fg);
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            name_style.bold().underlined(),
+        ))
+    };
+
+    let mut lines = Vec::new();
+    if mode == Mode::Channel {
+        lines.push(Line::from(Span::styled(
+            config.channel_name.clone(),
+            name_style.bold(),
+        )));
+        if let Some(description) = &config.channel_description {
+            lines.extend(description.lines().map(|l| {
+                Line::from(Span::styled(l.to_string(), value_style))
+            }));
+        }
+        if history_len > 1 {
+            lines.push(Line::from(vec![
+                Span::styled("History: ", name_style),
+                Span::styled(format!("{position}/{history_len}"), value_style),
+            ]));
+        }
+        lines.push(Line::default());
+    }
+
+    lines.extend(generate_help_content(config, mode, colorscheme, true));
+
+    if mode == Mode::Channel {
+        let mut add_commands = |title: &str, command: &CommandSpec| {
+            lines.push(Line::default());
+            lines.push(heading(title));
+            for (i, template) in command.inner.iter().enumerate() {
+                if command.is_entry_preview_nth(i) {
+                    continue;
+                }
+                let label = command
+                    .labels
+                    .get(i)
+                    .and_then(Option::as_ref)
+                    .map_or_else(
+                        || (i + 1).to_string(),
+                        |l| format!("{} {l}", i + 1),
+                    );
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{label}: "), name_style),
+                    Span::styled(template.raw().to_string(), value_style),
+                ]));
+            }
+        };
+        add_commands("Source", &config.channel_source_command);
+        if let Some(preview) = &config.channel_preview_command
+            && !preview.inner.is_empty()
+        {
+            add_commands("Preview", preview);
+        }
+
+        if !config.channel_actions.is_empty() {
+            lines.push(Line::default());
+            lines.push(heading("Actions"));
+            let mut names: Vec<&String> =
+                config.channel_actions.keys().collect();
+            names.sort();
+            for name in names {
+                let spec = &config.channel_actions[name];
+                let mut spans = vec![
+                    Span::styled(format!("{name}: "), name_style),
+                    Span::styled(
+                        spec.command
+                            .inner
+                            .iter()
+                            .map(Template::raw)
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                        value_style,
+                    ),
+                ];
+                if let Some(description) = &spec.description {
+                    spans.push(Span::styled(
+                        format!(" ({description})"),
+                        value_style.italic(),
+                    ));
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+    lines
+}
+
+/// Draws the help overlay over the given area.
+pub fn draw_help_overlay(
+    f: &mut Frame<'_>,
+    area: Rect,
+    overlay: HelpOverlay,
+    config: &MergedConfig,
+    mode: Mode,
+    colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+    history_position: (usize, usize),
+) {
+    if area.area() == 0 {
+        return;
+    }
+    let lines =
+        help_overlay_lines(config, mode, colorscheme, history_position);
+    let height = help_overlay_height(area);
+    let scroll = overlay.scroll.min(lines.len().saturating_sub(height));
+    let position = if lines.len() > height {
+        Line::from(format!(" {}/{} ", scroll + height, lines.len()))
+            .alignment(Alignment::Right)
+    } else {
+        Line::default()
+    };
+
+    f.render_widget(Clear, area);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(glyphs.popup_border())
+        .border_style(Style::default().fg(colorscheme.general.border_fg))
+        .title_top(Line::from(" Help ").bold().alignment(Alignment::Center))
+        .title_bottom(position)
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .padding(Padding::horizontal(1));
+    f.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0)),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        channels::prototypes::ChannelPrototype,
+        cli::PostProcessedCli,
+        config::{Theme, default_config_from_file, layers::ConfigLayers},
+    };
+
+    #[test]
+    fn test_help_overlay_lines() {
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "files"
+            description = "A channel to find files"
+
+            [source]
+            command = "fd -t f"
+
+            [preview]
+            command = "bat {}"
+
+            [actions.edit]
+            description = "Edit the file"
+            command = "nvim {}"
+            "#,
+        )
+        .unwrap();
+        let config = ConfigLayers::new(
+            default_config_from_file().unwrap(),
+            prototype,
+            PostProcessedCli::default(),
+        )
+        .merge();
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+
+        let lines: Vec<String> =
+            help_overlay_lines(&config, Mode::Channel, &colorscheme, (1, 1))
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+
+        assert_eq!(lines[0], "files");
+        assert_eq!(lines[1], "A channel to find files");
+        // all bindings are listed, even those the channel can't use
+        assert!(lines.iter().any(|l| l.starts_with("Cycle previews:")));
+        assert!(lines.iter().any(|l| l == "1: fd -t f"));
+        assert!(lines.iter().any(|l| l == "1: bat {}"));
+        assert!(lines.iter().any(|l| l == "edit: nvim {} (Edit the file)"));
+        assert!(!lines.iter().any(|l| l.starts_with("History:")));
+
+        let lines: Vec<String> =
+            help_overlay_lines(&config, Mode::Channel, &colorscheme, (2, 3))
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+        assert_eq!(lines[2], "History: 2/3");
+    }
+
+    #[test]
+    fn test_help_overlay_scroll() {
+        let mut overlay = HelpOverlay::default();
+        overlay.scroll_up(1);
+        assert_eq!(overlay.scroll, 0);
+        overlay.scroll_down(5, 20, 10);
+        assert_eq!(overlay.scroll, 5);
+        overlay.scroll_down(50, 20, 10);
+        assert_eq!(overlay.scroll, 10);
+        overlay.scroll_up(3);
+        assert_eq!(overlay.scroll, 7);
+    }
+}
@@ -2,8 +2,9 @@ use crate::event::Key;
 use crate::utils::strings::SPACE;
 use crate::{
     action::{Action, Actions},
-    config::{Keybindings, layers::MergedConfig},
-    screen::colors::Colorscheme,
+    channels::prototypes::CommandSpec,
+    config::layers::MergedConfig,
+    screen::{colors::Colorscheme, glyphs::Glyphs},
     television::Mode,
     utils::strings::to_title_case,
 };
@@ -12,7 +13,7 @@ use ratatui::{
     layout::{Alignment, Rect},
     style::{Style, Stylize},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
 };
 use rustc_hash::FxHashMap;
 use tracing::{debug, trace};
@@ -27,23 +28,40 @@ pub fn draw_help_panel(
     config: &MergedConfig,
     tv_mode: Mode,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+    scroll: u16,
 ) {
     if area.width < MIN_PANEL_WIDTH || area.height < MIN_PANEL_HEIGHT {
         return; // Too small to display anything meaningful
     }
 
     // Generate content
-    let content = generate_help_content(config, tv_mode, colorscheme);
+    let content = generate_help_content(config, tv_mode, colorscheme, false);
 
     // Clear the area first to create the floating effect
     f.render_widget(Clear, area);
 
+    // Show the scroll position when the content doesn't fit
+    let max_scroll = max_help_scroll(content.len(), area);
+    let scroll = scroll.min(max_scroll);
+    let position = if max_scroll > 0 {
+        Line::from(format!(
+            " {}/{} ",
+            usize::from(scroll) + usize::from(area.height.saturating_sub(2)),
+            content.len()
+        ))
+        .alignment(Alignment::Right)
+    } else {
+        Line::default()
+    };
+
     // Create the main block with consistent styling
     let block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_set(glyphs.popup_border())
         .border_style(Style::default().fg(colorscheme.general.border_fg))
         .title_top(Line::from(" Help ").alignment(Alignment::Center))
+        .title_bottom(position)
         .style(
             Style::default()
                 .bg(colorscheme.general.background.unwrap_or_default()),
@@ -52,7 +70,8 @@ pub fn draw_help_panel(
 
     let paragraph = Paragraph::new(content)
         .block(block)
-        .alignment(Alignment::Left);
+        .alignment(Alignment::Left)
+        .scroll((scroll, 0));
 
     f.render_widget(paragraph, area);
 }
@@ -69,8 +88,14 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::DeletePrevWord
                 | Action::DeleteNextChar
                 | Action::DeleteLine
+                | Action::DeleteNextWord
+                | Action::DeleteToInputStart
+                | Action::DeleteToInputEnd
+                | Action::TransposeChars
                 | Action::GoToPrevChar
                 | Action::GoToNextChar
+                | Action::GoToPrevWord
+                | Action::GoToNextWord
                 | Action::GoToInputStart
                 | Action::GoToInputEnd
                 // Navigation actions - available in both modes
@@ -78,49 +103,84 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::SelectPrevEntry
                 | Action::SelectNextPage
                 | Action::SelectPrevPage
+                | Action::SelectIndex(_)
                 // Selection actions - channel specific (multi-select)
                 | Action::ToggleSelectionDown
                 | Action::ToggleSelectionUp
+                | Action::ToggleMark
+                | Action::FilterMarked
+                | Action::EditEntryInline
                 | Action::ConfirmSelection
                 // Preview actions - channel specific
                 | Action::ScrollPreviewUp
                 | Action::ScrollPreviewDown
                 | Action::ScrollPreviewHalfPageUp
+                | Action::ScrollPreviewPageDown
+                | Action::ScrollPreviewPageUp
+                | Action::GoToPreviewTop
+                | Action::GoToPreviewBottom
                 | Action::ScrollPreviewHalfPageDown
                 | Action::TogglePreview
+                | Action::FocusPreview
                 // Channel-specific actions
                 | Action::CopyEntryToClipboard
+                | Action::CopyFormatted
+                | Action::CopyPreviewToClipboard
+                | Action::OpenEntry
                 | Action::ReloadSource
                 | Action::CycleSources
+                | Action::CycleSort
                 | Action::CyclePreviews
+                | Action::ClearPreviewCache
+                | Action::ChannelBack
+                | Action::ChannelForward
+                | Action::ShowEntryDetail
                 | Action::SelectPrevHistory
                 | Action::SelectNextHistory
                 // UI toggles - global
                 | Action::ToggleRemoteControl
                 | Action::ToggleHelp
+                | Action::ToggleHelpOverlay
+                | Action::ScrollHelpUp
+                | Action::ScrollHelpDown
                 | Action::ToggleStatusBar
+                | Action::ToggleLogs
+                | Action::ScrollLogUp
+                | Action::ScrollLogDown
+                | Action::GoToLogTop
+                | Action::GoToLogBottom
                 // Channel-mode layout
                 | Action::ToggleOrientation
                 // Application actions - global
                 | Action::Quit
                 // External actions
+                | Action::Execute(_)
+                | Action::ExecuteSilent(_)
                 | Action::ExternalAction(_) => true,
 
                 // Skip actions not relevant to help or internal actions
                 Action::NoOp
+                | Action::AddInputString(_)
                 | Action::Render
                 | Action::Resize(_, _)
                 | Action::ClearScreen
                 | Action::Tick
                 | Action::Suspend
                 | Action::Resume
+                | Action::FocusLost
+                | Action::FocusGained
                 | Action::Error(_)
-                | Action::OpenEntry
+                | Action::Notify(_)
+                | Action::LogPanelKey(_)
+                | Action::PreviewPanelKey(_)
+                | Action::AnswerConfirmation(_)
                 | Action::SwitchToChannel(_)
                 | Action::WatchTimer
                 | Action::SelectEntryAtPosition(_, _)
                 | Action::MouseClickAt(_, _)
                 | Action::Expect(_)
+                | Action::Abort
+                | Action::EditChannelConfig
                 | Action::SelectAndExit => false,
             }
         }
@@ -133,8 +193,14 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::DeletePrevWord
                 | Action::DeleteNextChar
                 | Action::DeleteLine
+                | Action::DeleteNextWord
+                | Action::DeleteToInputStart
+                | Action::DeleteToInputEnd
+                | Action::TransposeChars
                 | Action::GoToPrevChar
                 | Action::GoToNextChar
+                | Action::GoToPrevWord
+                | Action::GoToNextWord
                 | Action::GoToInputStart
                 | Action::GoToInputEnd
                 // Navigation actions - available in both modes
@@ -144,10 +210,15 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
                 | Action::SelectPrevPage
                 // Selection in remote mode - just confirm (no multi-select)
                 | Action::ConfirmSelection
+                | Action::EditChannelConfig
                 // UI toggles - global
                 | Action::ToggleRemoteControl
                 | Action::ToggleHelp
+                | Action::ToggleHelpOverlay
+                | Action::ScrollHelpUp
+                | Action::ScrollHelpDown
                 | Action::ToggleStatusBar
+                | Action::ToggleLogs
                 // Application actions - global
                 | Action::Quit => true,
 
@@ -158,45 +229,211 @@ fn is_action_relevant_for_mode(action: &Action, mode: Mode) -> bool {
     }
 }
 
-/// Adds keybinding lines for specific keys to the given lines vector
-fn add_keybinding_lines_for_keys(
+/// Whether the action runs a command, listed in the help panel's own section.
+fn is_external(action: &Action) -> bool {
+    matches!(
+        action,
+        Action::ExternalAction(_)
+            | Action::Execute(_)
+            | Action::ExecuteSilent(_)
+    )
+}
+
+/// The order in which categories are listed in the help panel.
+const CATEGORIES: [&str; 7] = [
+    "Navigation",
+    "Selection",
+    "Preview",
+    "Channel",
+    "Input",
+    "Interface",
+    "Application",
+];
+
+/// The category an action is listed under in the help panel.
+fn action_category(action: &Action) -> &'static str {
+    match action {
+        Action::SelectNextEntry
+        | Action::SelectPrevEntry
+        | Action::SelectNextPage
+        | Action::SelectPrevPage
+        | Action::SelectIndex(_) => "Navigation",
+        Action::ToggleSelectionDown
+        | Action::ToggleSelectionUp
+        | Action::ToggleMark
+        | Action::FilterMarked
+        | Action::EditEntryInline
+        | Action::ConfirmSelection
+        | Action::CopyEntryToClipboard
+        | Action::CopyFormatted
+        | Action::CopyPreviewToClipboard
+        | Action::OpenEntry
+        | Action::ShowEntryDetail => "Selection",
+        Action::ScrollPreviewUp
+        | Action::ScrollPreviewDown
+        | Action::ScrollPreviewHalfPageUp
+        | Action::ScrollPreviewHalfPageDown
+        | Action::ScrollPreviewPageUp
+        | Action::ScrollPreviewPageDown
+        | Action::GoToPreviewTop
+        | Action::GoToPreviewBottom
+        | Action::TogglePreview
+        | Action::FocusPreview
+        | Action::CyclePreviews
+        | Action::ClearPreviewCache => "Preview",
+        Action::ReloadSource
+        | Action::CycleSources
+        | Action::CycleSort
+        | Action::ChannelBack
+        | Action::ChannelForward
+        | Action::EditChannelConfig => "Channel",
+        Action::DeletePrevChar
+        | Action::DeletePrevWord
+        | Action::DeleteNextChar
+        | Action::DeleteLine
+        | Action::DeleteNextWord
+        | Action::DeleteToInputStart
+        | Action::DeleteToInputEnd
+        | Action::TransposeChars
+        | Action::GoToPrevChar
+        | Action::GoToNextChar
+        | Action::GoToPrevWord
+        | Action::GoToNextWord
+        | Action::GoToInputStart
+        | Action::GoToInputEnd
+        | Action::SelectPrevHistory
+        | Action::SelectNextHistory => "Input",
+        Action::ToggleRemoteControl
+        | Action::ToggleHelp
+        | Action::ToggleHelpOverlay
+        | Action::ScrollHelpUp
+        | Action::ScrollHelpDown
+        | Action::ToggleStatusBar
+        | Action::ToggleLogs
+        | Action::ScrollLogUp
+        | Action::ScrollLogDown
+        | Action::GoToLogTop
+        | Action::GoToLogBottom
+        | Action::ToggleOrientation => "Interface",
+        _ => "Application",
+    }
+}
+
+/// Whether the current channel and configuration support the action at all,
+/// e.g. there's no point in advertising preview cycling for a channel with a
+/// single preview command.
+fn is_action_available(action: &Action, config: &MergedConfig) -> bool {
+    let has_preview = config.channel_preview_command.is_some()
+        && !config.preview_panel_disabled;
+    match action {
+        Action::CyclePreviews => {
+            has_preview
+                && config
+                    .channel_preview_command
+                    .as_ref()
+                    .is_some_and(CommandSpec::has_multiple_commands)
+        }
+        Action::ScrollPreviewUp
+        | Action::ScrollPreviewDown
+        | Action::ScrollPreviewHalfPageUp
+        | Action::ScrollPreviewHalfPageDown
+        | Action::ScrollPreviewPageUp
+        | Action::ScrollPreviewPageDown
+        | Action::GoToPreviewTop
+        | Action::GoToPreviewBottom
+        | Action::TogglePreview
+        | Action::FocusPreview
+        | Action::ClearPreviewCache => has_preview,
+        Action::CycleSources => {
+            config.channel_source_command.has_multiple_commands()
+        }
+        Action::CycleSort => !config.channel_source_sort_fields.is_empty(),
+        Action::ToggleRemoteControl
+        | Action::ChannelBack
+        | Action::ChannelForward => !config.remote_disabled,
+        Action::ToggleStatusBar => !config.status_bar_disabled,
+        _ => true,
+    }
+}
+
+/// Adds one line per bound action to the given lines vector, grouped by
+/// category if `show_categories` is set. Actions the current channel can't
+/// perform are left out unless `all` is set.
+fn add_keybinding_lines(
     lines: &mut Vec<Line<'static>>,
-    keybindings: &Keybindings,
+    config: &MergedConfig,
     mode: Mode,
     colorscheme: &Colorscheme,
-    category_name: &str,
+    all: bool,
 ) {
-    // Collect all valid keybinding entries
-    let mut entries: Vec<(String, String)> = Vec::new();
+    // all the keys bound to each action, by category
+    let mut entries: FxHashMap<
+        &'static str,
+        FxHashMap<&'static str, Vec<String>>,
+    > = FxHashMap::default();
 
-    for (key, actions) in keybindings.iter() {
+    for (key, actions) in &config.input_map.global_keybindings {
         for action in actions.as_slice() {
-            // Filter out NoOp actions (unbound keys)
-            // Filter out actions not relevant for current mode
+            // Filter out NoOp actions (unbound keys), external actions (which
+            // get their own section) and actions not relevant for the current
+            // mode and channel
             if matches!(action, Action::NoOp)
+                || is_external(action)
                 || !is_action_relevant_for_mode(action, mode)
+                || !(all || is_action_available(action, config))
             {
                 continue;
             }
 
             let description = action.description();
-            let key_string = key.to_string();
-            entries.push((description.to_string(), key_string.clone()));
-            trace!(
-                "Added keybinding: {} -> {} ({})",
-                key_string, description, category_name
-            );
+            trace!("Added keybinding: {} -> {}", key, description);
+            entries
+                .entry(action_category(action))
+                .or_default()
+                .entry(description)
+                .or_default()
+                .push(key.to_string());
         }
     }
 
-    // Sort entries alphabetically by description
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    if config.help_panel_show_categories {
+        for category in CATEGORIES {
+            if let Some(category_entries) = entries.remove(category) {
+                lines.push(Line::from(Span::styled(
+                    category,
+                    Style::default()
+                        .fg(colorscheme.help.metadata_field_name_fg)
+                        .italic(),
+                )));
+                push_keybinding_lines(
+                    lines,
+                    category_entries,
+                    mode,
+                    colorscheme,
+                );
+            }
+        }
+    } else {
+        let all = entries.into_values().flatten().collect();
+        push_keybinding_lines(lines, all, mode, colorscheme);
+    }
+}
 
-    // Create lines from sorted entries
-    for (description, key_string) in entries {
+/// Adds one line per action, sorted alphabetically by description, listing
+/// all the keys bound to it.
+fn push_keybinding_lines(
+    lines: &mut Vec<Line<'static>>,
+    entries: FxHashMap<&'static str, Vec<String>>,
+    mode: Mode,
+    colorscheme: &Colorscheme,
+) {
+    let mut entries = entries.into_iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(description, _)| *description);
+    for (description, mut keys) in entries {
+        keys.sort();
         lines.push(create_compact_keybinding_line(
-            &key_string,
-            &description,
+            &keys.join(", "),
+            description,
             mode,
             colorscheme,
         ));
@@ -215,11 +452,7 @@ fn add_actions_keybindings_section(
 
     for (key, actions) in key_actions {
         // Skip keys without external actions
-        if !actions
-            .as_slice()
-            .iter()
-            .any(|a| matches!(a, Action::ExternalAction(_)))
-        {
+        if !actions.as_slice().iter().any(is_external) {
             continue;
         }
 
@@ -233,6 +466,9 @@ fn add_actions_keybindings_section(
             })
             .map(|a| match a {
                 Action::ExternalAction(name) => to_title_case(name),
+                Action::Execute(command) | Action::ExecuteSilent(command) => {
+                    command.clone()
+                }
                 _ => to_title_case(a.description()),
             })
             .collect::<Vec<_>>()
@@ -256,10 +492,14 @@ fn add_actions_keybindings_section(
 }
 
 /// Generates the help content organized into global and mode-specific groups
-fn generate_help_content(
+///
+/// If `all` is set, actions that aren't available for the current channel are
+/// listed as well.
+pub fn generate_help_content(
     config: &MergedConfig,
     mode: Mode,
     colorscheme: &Colorscheme,
+    all: bool,
 ) -> Vec<Line<'static>> {
     let mut lines = Vec::new();
 
@@ -279,32 +519,14 @@ fn generate_help_content(
             .underlined(),
     )]));
 
-    add_keybinding_lines_for_keys(
-        &mut lines,
-        &config
-            .input_map
-            .global_keybindings
-            .iter()
-            .map(|(key, actions)| (*key, actions.first().unwrap().clone()))
-            .collect::<Vec<_>>()
-            .into(),
-        mode,
-        colorscheme,
-        mode_name,
-    );
+    add_keybinding_lines(&mut lines, config, mode, colorscheme, all);
 
     // Check if we have external actions before adding the section
-    let has_external_actions =
-        config
-            .input_map
-            .global_keybindings
-            .iter()
-            .any(|(_, actions)| {
-                actions
-                    .as_slice()
-                    .iter()
-                    .any(|a| matches!(a, Action::ExternalAction(_)))
-            });
+    let has_external_actions = config
+        .input_map
+        .global_keybindings
+        .iter()
+        .any(|(_, actions)| actions.as_slice().iter().any(is_external));
 
     if has_external_actions {
         lines.push(Line::from(""));
@@ -369,6 +591,27 @@ fn create_external_action_line(
     ])
 }
 
+/// How far the help panel content can be scrolled given its area.
+#[allow(clippy::cast_possible_truncation)]
+fn max_help_scroll(content_len: usize, area: Rect) -> u16 {
+    // -2 for borders
+    content_len.saturating_sub(usize::from(area.height.saturating_sub(2)))
+        as u16
+}
+
+/// Clamps the help panel scroll offset so that the last line of content
+/// stays at the bottom of the panel.
+pub fn clamp_help_scroll(
+    scroll: u16,
+    config: &MergedConfig,
+    mode: Mode,
+    colorscheme: &Colorscheme,
+    area: Rect,
+) -> u16 {
+    let content = generate_help_content(config, mode, colorscheme, false);
+    scroll.min(max_help_scroll(content.len(), area))
+}
+
 /// Calculates the required dimensions for the help panel based on content
 #[allow(clippy::cast_possible_truncation)]
 pub fn calculate_help_panel_size(
@@ -377,7 +620,7 @@ pub fn calculate_help_panel_size(
     colorscheme: &Colorscheme,
 ) -> (u16, u16) {
     // Generate content to count items and calculate width
-    let content = generate_help_content(config, mode, colorscheme);
+    let content = generate_help_content(config, mode, colorscheme, false);
 
     // Calculate required width based on actual content
     let max_content_width = content
@@ -402,3 +645,97 @@ pub fn calculate_help_panel_size(
 
     (required_width, required_height)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        channels::prototypes::ChannelPrototype,
+        cli::PostProcessedCli,
+        config::{Theme, default_config_from_file, layers::ConfigLayers},
+    };
+
+    fn help_text(prototype: &str, mode: Mode) -> Vec<String> {
+        let prototype = toml::from_str::<ChannelPrototype>(prototype).unwrap();
+        let config = ConfigLayers::new(
+            default_config_from_file().unwrap(),
+            prototype,
+            PostProcessedCli::default(),
+        )
+        .merge();
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+        generate_help_content(&config, mode, &colorscheme, false)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_help_hides_unavailable_actions() {
+        let lines = help_text(
+            r#"
+            [metadata]
+            name = "test"
+
+            [source]
+            command = "echo 1"
+
+            [preview]
+            command = "echo {}"
+            "#,
+            Mode::Channel,
+        );
+
+        assert!(lines.iter().any(|l| l.starts_with("Toggle preview:")));
+        // a single preview command and a single source command
+        assert!(!lines.iter().any(|l| l.starts_with("Cycle previews:")));
+        assert!(!lines.iter().any(|l| l.starts_with("Cycle sources:")));
+    }
+
+    #[test]
+    fn test_help_groups_actions_by_category() {
+        let lines = help_text(
+            r#"
+            [metadata]
+            name = "test"
+
+            [source]
+            command = ["echo 1", "echo 2"]
+            "#,
+            Mode::Channel,
+        );
+
+        let position = |line: &str| {
+            lines
+                .iter()
+                .position(|l| l == line)
+                .unwrap_or_else(|| panic!("missing {line:?} in {lines:#?}"))
+        };
+        assert!(position("Navigation") < position("Channel"));
+        let cycle_sources = lines
+            .iter()
+            .position(|l| l.starts_with("Cycle sources:"))
+            .unwrap();
+        assert!(position("Channel") < cycle_sources);
+        assert!(cycle_sources < position("Input"));
+        // no preview command
+        assert!(!lines.iter().any(|l| l == "Preview"));
+    }
+
+    #[test]
+    fn test_help_remote_control_mode() {
+        let lines = help_text(
+            r#"
+            [metadata]
+            name = "test"
+
+            [source]
+            command = "echo 1"
+            "#,
+            Mode::RemoteControl,
+        );
+
+        assert_eq!(lines[0], "Remote Control Mode");
+        assert!(!lines.iter().any(|l| l.starts_with("Toggle selection")));
+    }
+}
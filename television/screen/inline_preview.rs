@@ -0,0 +1,68 @@
+use crate::screen::colors::Colorscheme;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span, Text},
+    widgets::{Block, Padding, Paragraph},
+};
+
+/// The first line of a preview with something to show, used as a glimpse of
+/// the preview while the preview panel is hidden.
+pub fn first_preview_line(content: &Text<'_>) -> Option<Line<'static>> {
+    content
+        .lines
+        .iter()
+        .find(|line| {
+            line.spans
+                .iter()
+                .any(|span| !span.content.trim().is_empty())
+        })
+        .map(|line| {
+            Line::from(
+                line.spans
+                    .iter()
+                    .map(|span| {
+                        Span::styled(span.content.to_string(), span.style)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .style(line.style)
+        })
+}
+
+/// Draw the inline preview in its single line strip, cut at its width.
+pub fn draw_inline_preview(
+    f: &mut Frame<'_>,
+    area: Rect,
+    line: Option<&Line<'static>>,
+    colorscheme: &Colorscheme,
+) {
+    let paragraph = Paragraph::new(line.cloned().unwrap_or_default())
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        )
+        .block(Block::default().padding(Padding::horizontal(1)));
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_preview_line_skips_blank_lines() {
+        let content = Text::from(vec![
+            Line::from(""),
+            Line::from("   "),
+            Line::from(vec![Span::raw("  "), Span::raw("fn main() {")]),
+            Line::from("}"),
+        ]);
+        assert_eq!(
+            first_preview_line(&content).unwrap().to_string(),
+            "  fn main() {"
+        );
+        assert_eq!(first_preview_line(&Text::from("\n \n")), None);
+    }
+}
@@ -1,6 +1,10 @@
 use crate::{
     config::ui::{BorderType, DEFAULT_PROMPT, Padding},
-    screen::{colors::Colorscheme, layout::InputPosition, spinner::Spinner},
+    screen::{
+        colors::Colorscheme, glyphs::Glyphs, layout::InputPosition,
+        mode::mode_color, spinner::Spinner, title::fit_title,
+    },
+    television::Mode,
     utils::input::Input,
 };
 use anyhow::Result;
@@ -23,39 +27,65 @@ pub fn draw_input_box(
     rect: Rect,
     results_count: u32,
     total_count: u32,
+    truncated: bool,
+    selected_count: usize,
     input_state: &Input,
     results_picker_state: &ListState,
-    matcher_running: bool,
-    channel_name: &str,
+    running: bool,
+    matching: bool,
+    loading: bool,
+    title: Option<&str>,
+    mode: Mode,
     spinner: &Spinner,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
     position: InputPosition,
-    header: &Option<String>,
     padding: &Padding,
     border_type: &BorderType,
     prompt: Option<&String>,
+    decorations: bool,
 ) -> Result<()> {
-    let header = header.as_ref().map_or(channel_name, |v| v);
     let mut input_block = Block::default()
         .title_position(match position {
             InputPosition::Top => Position::Top,
             InputPosition::Bottom => Position::Bottom,
         })
-        .title(
-            Line::from(format!(" {} ", header))
-                .style(Style::default().fg(colorscheme.mode.channel).bold())
-                .centered(),
-        )
         .style(
             Style::default()
                 .bg(colorscheme.general.background.unwrap_or_default()),
         )
         .padding(RatatuiPadding::from(*padding));
+    if let Some(title) = title {
+        input_block = input_block.title(
+            Line::from(fit_title(
+                title,
+                usize::from(rect.width.saturating_sub(2)),
+                glyphs.ellipsis,
+            ))
+            .style(Style::default().fg(colorscheme.mode.channel).bold())
+            .centered(),
+        );
+    }
+    // the prompt always takes the color of the mode, the border and the
+    // mode's name only once out of the channel mode
+    let mode_fg = mode_color(mode, &colorscheme.mode);
     if let Some(b) = border_type.to_ratatui_border_type() {
+        let border_fg = if mode == Mode::Channel {
+            colorscheme.general.border_fg
+        } else {
+            mode_fg
+        };
         input_block = input_block
             .borders(Borders::ALL)
-            .border_type(b)
-            .border_style(Style::default().fg(colorscheme.general.border_fg));
+            .border_set(glyphs.border_set(b))
+            .border_style(Style::default().fg(border_fg));
+        if mode != Mode::Channel {
+            input_block = input_block.title(
+                Line::from(format!(" {mode} "))
+                    .style(Style::default().fg(mode_fg).bold())
+                    .right_aligned(),
+            );
+        }
     }
 
     let input_block_inner = input_block.inner(rect);
@@ -65,12 +95,31 @@ pub fn draw_input_box(
 
     f.render_widget(input_block, rect);
 
-    // split input block into 4 parts: prompt symbol, input, result count, spinner
+    // carried over and current selections
+    let selected_label = if selected_count > 0 {
+        format!(" [{selected_count}]")
+    } else {
+        String::new()
+    };
+
+    // more entries were available than what was loaded
+    let truncated_marker = if truncated { "+" } else { "" };
+
+    // ingest progress while the source is still streaming
+    let loaded_label = if loading {
+        format!("loaded {total_count} {} ", glyphs.separator)
+    } else {
+        String::new()
+    };
+
+    // split input block into 4 parts: prompt symbol, input, result count,
+    // spinner, all of them but the input going away without decorations
+    let decoration = |width: u16| if decorations { width } else { 0 };
     let inner_input_chunks = RatatuiLayout::default()
         .direction(Direction::Horizontal)
         .constraints([
             // prompt symbol + space
-            Constraint::Length(
+            Constraint::Length(decoration(
                 prompt
                     .as_ref()
                     .map(|p| {
@@ -78,26 +127,31 @@ pub fn draw_input_box(
                             .expect("Prompt length should fit in u16")
                     })
                     .unwrap_or(2),
-            ),
+            )),
             // input field
             Constraint::Fill(1),
             // result count
-            Constraint::Length(
+            Constraint::Length(decoration(
                 3 * (u16::try_from(total_count.max(1).ilog10()).unwrap() + 1)
-                    + 3,
-            ),
+                    + 3
+                    + u16::try_from(selected_label.len()).unwrap_or(0)
+                    + u16::try_from(truncated_marker.len()).unwrap_or(0)
+                    + u16::try_from(loaded_label.chars().count()).unwrap_or(0),
+            )),
             // spinner
-            Constraint::Length(1),
+            Constraint::Length(decoration(1)),
         ])
         .split(input_block_inner);
 
     let arrow_block = Block::default();
     let arrow = Paragraph::new(Span::styled(
         format!("{} ", prompt.unwrap_or(&DEFAULT_PROMPT.to_string())),
-        Style::default().fg(colorscheme.input.input_fg).bold(),
+        Style::default().fg(mode_fg).bold(),
     ))
     .block(arrow_block);
-    f.render_widget(arrow, inner_input_chunks[0]);
+    if decorations {
+        f.render_widget(arrow, inner_input_chunks[0]);
+    }
 
     let interactive_input_block = Block::default();
     // keep 2 for borders and 1 for cursor
@@ -115,28 +169,52 @@ pub fn draw_input_box(
         .alignment(Alignment::Left);
     f.render_widget(input, inner_input_chunks[1]);
 
-    if matcher_running {
+    // the source loading is worth a spinner, the matcher catching up with
+    // the pattern only a subtle hint
+    if decorations && running {
         f.render_widget(spinner, inner_input_chunks[3]);
+    } else if decorations && matching {
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                glyphs.matching,
+                Style::default()
+                    .fg(colorscheme.input.results_count_fg)
+                    .dim(),
+            )),
+            inner_input_chunks[3],
+        );
     }
 
     let result_count_block = Block::default();
-    let result_count_paragraph = Paragraph::new(Span::styled(
-        format!(
-            " {} / {} ",
-            if results_count == 0 {
-                0
-            } else {
-                results_picker_state.selected().unwrap_or(0) + 1
-            },
-            results_count,
+    let result_count_paragraph = Paragraph::new(Line::from(vec![
+        Span::styled(
+            loaded_label,
+            Style::default()
+                .fg(colorscheme.input.results_count_fg)
+                .dim(),
         ),
-        Style::default()
-            .fg(colorscheme.input.results_count_fg)
-            .italic(),
-    ))
+        Span::styled(
+            format!(
+                "{} {} / {}{} ",
+                selected_label,
+                if results_count == 0 {
+                    0
+                } else {
+                    results_picker_state.selected().unwrap_or(0) + 1
+                },
+                results_count,
+                truncated_marker,
+            ),
+            Style::default()
+                .fg(colorscheme.input.results_count_fg)
+                .italic(),
+        ),
+    ]))
     .block(result_count_block)
     .alignment(Alignment::Right);
-    f.render_widget(result_count_paragraph, inner_input_chunks[2]);
+    if decorations {
+        f.render_widget(result_count_paragraph, inner_input_chunks[2]);
+    }
 
     // Make the cursor visible and ask tui-rs to put it at the
     // specified coordinates after rendering
@@ -150,3 +228,67 @@ pub fn draw_input_box(
     ));
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Theme;
+    use ratatui::{Terminal, backend::TestBackend, buffer::Buffer};
+
+    fn draw(mode: Mode, colorscheme: &Colorscheme) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(50, 3)).unwrap();
+        terminal
+            .draw(|f| {
+                draw_input_box(
+                    f,
+                    f.area(),
+                    3,
+                    3,
+                    false,
+                    0,
+                    &Input::new("foo".to_string()),
+                    &ListState::default(),
+                    false,
+                    false,
+                    false,
+                    Some("files"),
+                    mode,
+                    &Spinner::default(),
+                    colorscheme,
+                    Glyphs::new(false),
+                    InputPosition::Top,
+                    &Padding::default(),
+                    &BorderType::Rounded,
+                    None,
+                    true,
+                )
+                .unwrap();
+            })
+            .unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn row(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, y)].symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_input_box_colored_by_mode() {
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+        let channel = draw(Mode::Channel, &colorscheme);
+        let remote = draw(Mode::RemoteControl, &colorscheme);
+
+        // the border
+        assert_eq!(channel[(0, 0)].fg, colorscheme.general.border_fg);
+        assert_eq!(remote[(0, 0)].fg, colorscheme.mode.remote_control);
+        // the prompt arrow
+        assert_eq!(channel[(1, 1)].fg, colorscheme.mode.channel);
+        assert_eq!(remote[(1, 1)].fg, colorscheme.mode.remote_control);
+        assert_ne!(channel[(1, 1)].fg, remote[(1, 1)].fg);
+        // the mode is only named out of the channel mode
+        assert!(!row(&channel, 0).contains("Channel"));
+        assert!(row(&remote, 0).contains("Remote Control"));
+    }
+}
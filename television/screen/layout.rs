@@ -103,9 +103,24 @@ pub struct Layout {
     pub remote_control: Option<Rect>,
     pub help_panel: Option<Rect>,
     pub status_bar: Option<Rect>,
+    pub logs: Option<Rect>,
+    pub help_overlay: Option<Rect>,
+    /// A single line glimpse of the preview next to the input bar, shown
+    /// while the preview panel is hidden (`ui.inline_preview`).
+    pub inline_preview: Option<Rect>,
+    /// The lines setting the input bar and the preview apart from the
+    /// results in borderless mode (`ui.borderless`).
+    pub input_separator: Option<Rect>,
+    pub preview_separator: Option<Rect>,
 }
 
 const REMOTE_PANEL_WIDTH_PERCENTAGE: u16 = 62;
+/// The results panel needs to keep at least this many rows (borders
+/// included) to give one up to the inline preview.
+const INLINE_PREVIEW_MIN_RESULTS_HEIGHT: u16 = 4;
+const LOGS_PANEL_HEIGHT_PERCENTAGE: u16 = 40;
+const LOGS_PANEL_MIN_HEIGHT: u16 = 5;
+const HELP_OVERLAY_PERCENTAGE: u16 = 90;
 
 impl Default for Layout {
     /// Having a default layout with a non-zero height for the results area
@@ -121,11 +136,17 @@ impl Default for Layout {
             None,
             None,
             None,
+            None,
+            None,
+            None,
+            None,
+            None,
         )
     }
 }
 
 impl Layout {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         results: Rect,
         input: Rect,
@@ -133,6 +154,11 @@ impl Layout {
         remote_control: Option<Rect>,
         help_panel: Option<Rect>,
         status_bar: Option<Rect>,
+        logs: Option<Rect>,
+        help_overlay: Option<Rect>,
+        inline_preview: Option<Rect>,
+        input_separator: Option<Rect>,
+        preview_separator: Option<Rect>,
     ) -> Self {
         Self {
             results,
@@ -141,6 +167,11 @@ impl Layout {
             remote_control,
             help_panel,
             status_bar,
+            logs,
+            help_overlay,
+            inline_preview,
+            input_separator,
+            preview_separator,
         }
     }
 
@@ -149,6 +180,8 @@ impl Layout {
         merged_config: &MergedConfig,
         mode: Mode,
         colorscheme: &Colorscheme,
+        show_logs: bool,
+        show_help_overlay: bool,
     ) -> Self {
         let dimensions = Dimensions::from(merged_config.ui_scale);
 
@@ -178,6 +211,7 @@ impl Layout {
             Constraint::Length(input_bar_height(
                 merged_config.input_bar_padding,
                 merged_config.input_bar_border_type,
+                merged_config.borderless,
             )),
         ];
 
@@ -381,13 +415,85 @@ impl Layout {
             }
         };
 
+        // without borders, a single line sets the input bar and the preview
+        // apart from the results
+        let (input, input_separator, preview_window, preview_separator) =
+            if merged_config.borderless {
+                let (input, input_separator) = split_separator(
+                    input,
+                    match merged_config.input_bar_position {
+                        InputPosition::Top => Side::Bottom,
+                        InputPosition::Bottom => Side::Top,
+                    },
+                );
+                let preview_side = match (
+                    merged_config.layout,
+                    merged_config.input_bar_position,
+                ) {
+                    (Orientation::Landscape, _) => Side::Left,
+                    (Orientation::Portrait, InputPosition::Top) => Side::Top,
+                    (Orientation::Portrait, InputPosition::Bottom) => {
+                        Side::Bottom
+                    }
+                };
+                let (preview_window, preview_separator) = preview_window
+                    .map(|preview| split_separator(preview, preview_side))
+                    .unzip();
+                (
+                    input,
+                    Some(input_separator),
+                    preview_window,
+                    preview_separator,
+                )
+            } else {
+                (input, None, preview_window, None)
+            };
+
+        // while the preview panel is hidden, the results give a row next to
+        // the input bar up to a glimpse of the preview
+        let (results, inline_preview) = if preview_hidden
+            && merged_config.inline_preview
+            && merged_config.channel_preview_command.is_some()
+            && !merged_config.preview_panel_disabled
+            && results.height >= INLINE_PREVIEW_MIN_RESULTS_HEIGHT
+        {
+            let (results, strip) = match merged_config.input_bar_position {
+                InputPosition::Top => (
+                    Rect {
+                        y: results.y + 1,
+                        height: results.height - 1,
+                        ..results
+                    },
+                    Rect {
+                        height: 1,
+                        ..results
+                    },
+                ),
+                InputPosition::Bottom => (
+                    Rect {
+                        height: results.height - 1,
+                        ..results
+                    },
+                    Rect {
+                        y: results.y + results.height - 1,
+                        height: 1,
+                        ..results
+                    },
+                ),
+            };
+            (results, Some(strip))
+        } else {
+            (results, None)
+        };
+
         // the remote control is a centered popup
         let remote_control = if !merged_config.remote_disabled
             && mode == Mode::RemoteControl
         {
             let remote_control_rect = centered_rect_with_dimensions(
                 &Dimensions::new(
-                    area.width * REMOTE_PANEL_WIDTH_PERCENTAGE / 100,
+                    area.width.saturating_mul(REMOTE_PANEL_WIDTH_PERCENTAGE)
+                        / 100,
                     // on smaller screens (< logo + 3 vert padding top & btm), we won't display the
                     // logo
                     REMOTE_LOGO_HEIGHT_U16.min(area.height.saturating_sub(6)),
@@ -423,7 +529,8 @@ impl Layout {
         };
 
         // Create status bar at the bottom if enabled
-        let status_bar = if merged_config.status_bar_hidden {
+        let status_bar = if merged_config.status_bar_hidden || area.height == 0
+        {
             None
         } else {
             Some(Rect {
@@ -434,6 +541,29 @@ impl Layout {
             })
         };
 
+        // the log panel floats over the bottom of the screen
+        let logs = show_logs.then(|| {
+            let height = (working_area.height * LOGS_PANEL_HEIGHT_PERCENTAGE
+                / 100)
+                .max(LOGS_PANEL_MIN_HEIGHT)
+                .min(working_area.height);
+            Rect {
+                x: working_area.x,
+                y: working_area.y + working_area.height - height,
+                width: working_area.width,
+                height,
+            }
+        });
+
+        // the help overlay is centered above everything else
+        let help_overlay = show_help_overlay.then(|| {
+            centered_rect(
+                HELP_OVERLAY_PERCENTAGE,
+                HELP_OVERLAY_PERCENTAGE,
+                area,
+            )
+        });
+
         Self::new(
             results,
             input,
@@ -441,10 +571,63 @@ impl Layout {
             remote_control,
             help_panel,
             status_bar,
+            logs,
+            help_overlay,
+            inline_preview,
+            input_separator,
+            preview_separator,
         )
     }
 }
 
+/// A side of a panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Side {
+    Top,
+    Bottom,
+    Left,
+}
+
+/// Split the line along the `side` of `rect` off it, returning what's left
+/// of `rect` and the line.
+fn split_separator(rect: Rect, side: Side) -> (Rect, Rect) {
+    match side {
+        Side::Top => (
+            Rect {
+                y: rect.y + rect.height.min(1),
+                height: rect.height.saturating_sub(1),
+                ..rect
+            },
+            Rect {
+                height: rect.height.min(1),
+                ..rect
+            },
+        ),
+        Side::Bottom => (
+            Rect {
+                height: rect.height.saturating_sub(1),
+                ..rect
+            },
+            Rect {
+                y: rect.y + rect.height.saturating_sub(1),
+                height: rect.height.min(1),
+                ..rect
+            },
+        ),
+        Side::Left => (
+            Rect {
+                x: rect.x + rect.width.min(1),
+                width: rect.width.saturating_sub(1),
+                ..rect
+            },
+            Rect {
+                width: rect.width.min(1),
+                ..rect
+            },
+        ),
+    }
+}
+
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let height = r.height.saturating_mul(percent_y) / 100;
@@ -488,7 +671,15 @@ fn bottom_right_rect(width: u16, height: u16, r: Rect) -> Rect {
     }
 }
 
-fn input_bar_height(padding: Padding, border_type: BorderType) -> u16 {
+fn input_bar_height(
+    padding: Padding,
+    border_type: BorderType,
+    borderless: bool,
+) -> u16 {
+    // input line + separator + vertical padding, without any header
+    if borderless {
+        return 1 + 1 + padding.top + padding.bottom;
+    }
     // input line + header + vertical padding
     let mut h = 1 + 1 + padding.top + padding.bottom;
 
@@ -511,7 +702,7 @@ mod tests {
     /// --------
     fn test_input_bar_height_with_borders() {
         assert_eq!(
-            input_bar_height(Padding::default(), BorderType::Rounded),
+            input_bar_height(Padding::default(), BorderType::Rounded, false),
             3
         );
     }
@@ -520,7 +711,10 @@ mod tests {
     ///      h
     ///    input
     fn test_input_bar_height_without_borders() {
-        assert_eq!(input_bar_height(Padding::default(), BorderType::None,), 2);
+        assert_eq!(
+            input_bar_height(Padding::default(), BorderType::None, false),
+            2
+        );
     }
 
     #[test]
@@ -533,9 +727,97 @@ mod tests {
                     left: 0,
                     right: 0,
                 },
-                BorderType::None
+                BorderType::None,
+                false
             ),
             5
         );
     }
+
+    #[test]
+    ///   input
+    /// --------
+    fn test_input_bar_height_borderless() {
+        assert_eq!(
+            input_bar_height(Padding::default(), BorderType::None, true),
+            2
+        );
+    }
+
+    #[test]
+    fn test_split_separator() {
+        let rect = Rect::new(2, 3, 10, 5);
+        assert_eq!(
+            split_separator(rect, Side::Top),
+            (Rect::new(2, 4, 10, 4), Rect::new(2, 3, 10, 1))
+        );
+        assert_eq!(
+            split_separator(rect, Side::Bottom),
+            (Rect::new(2, 3, 10, 4), Rect::new(2, 7, 10, 1))
+        );
+        assert_eq!(
+            split_separator(rect, Side::Left),
+            (Rect::new(3, 3, 9, 5), Rect::new(2, 3, 1, 5))
+        );
+    }
+
+    #[test]
+    fn test_inline_preview_strip_next_to_input_bar() {
+        use crate::{
+            channels::prototypes::ChannelPrototype,
+            cli::PostProcessedCli,
+            config::{Config, Theme, layers::ConfigLayers},
+        };
+
+        let prototype = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "files"
+            [source]
+            command = "fd"
+            [preview]
+            command = "bat {}"
+            "#,
+        )
+        .unwrap();
+        let mut config = Config::default();
+        config.ui.inline_preview = true;
+        let mut merged =
+            ConfigLayers::new(config, prototype, PostProcessedCli::default())
+                .merge();
+        merged.status_bar_hidden = true;
+        merged.help_panel_hidden = true;
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+        let area = Rect::new(0, 0, 80, 20);
+        let build = |config: &MergedConfig| {
+            Layout::build(
+                area,
+                config,
+                Mode::Channel,
+                &colorscheme,
+                false,
+                false,
+            )
+        };
+
+        // the preview panel is shown: no strip
+        assert_eq!(build(&merged).inline_preview, None);
+
+        merged.preview_panel_hidden = true;
+        merged.input_bar_position = InputPosition::Bottom;
+        let layout = build(&merged);
+        let strip = layout.inline_preview.unwrap();
+        assert_eq!(strip.height, 1);
+        assert_eq!(strip.y + 1, layout.input.y);
+        assert_eq!(layout.results.y + layout.results.height, strip.y);
+
+        merged.input_bar_position = InputPosition::Top;
+        let layout = build(&merged);
+        let strip = layout.inline_preview.unwrap();
+        assert_eq!(layout.input.y + layout.input.height, strip.y);
+        assert_eq!(strip.y + 1, layout.results.y);
+
+        merged.inline_preview = false;
+        assert_eq!(build(&merged).inline_preview, None);
+    }
 }
@@ -0,0 +1,440 @@
+use crate::{
+    event::Key,
+    logging::{LOG_BUFFER, LogRecord},
+    screen::{colors::Colorscheme, glyphs::Glyphs},
+};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+use tracing::Level;
+
+/// Levels the log panel cycles through, least verbose first.
+const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// The state of the log panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogPanelState {
+    /// The most verbose level shown.
+    pub level: Level,
+    /// Only records containing this (ignoring case) are shown.
+    pub filter: String,
+    /// Whether keystrokes are currently typed into `filter`.
+    pub editing_filter: bool,
+    /// Whether the view sticks to the newest records.
+    pub follow: bool,
+    /// The first visible line when not following.
+    pub scroll: usize,
+    /// Whether the panel, rather than the input bar, receives keystrokes.
+    pub focused: bool,
+}
+
+impl Default for LogPanelState {
+    fn default() -> Self {
+        Self {
+            level: Level::TRACE,
+            filter: String::new(),
+            editing_filter: false,
+            follow: true,
+            scroll: 0,
+            focused: true,
+        }
+    }
+}
+
+impl LogPanelState {
+    /// Show one more level of verbosity, wrapping around to errors only.
+    pub fn cycle_level(&mut self) {
+        let current = LEVELS.iter().position(|l| *l == self.level);
+        self.level = LEVELS[current.map_or(0, |i| (i + 1) % LEVELS.len())];
+    }
+
+    pub fn matches(&self, record: &LogRecord) -> bool {
+        record.level <= self.level
+            && (self.filter.is_empty()
+                || record
+                    .message
+                    .to_lowercase()
+                    .contains(&self.filter.to_lowercase()))
+    }
+
+    /// The first visible line out of `total` in a panel `height` lines tall.
+    pub fn top(&self, total: usize, height: usize) -> usize {
+        let bottom = total.saturating_sub(height);
+        if self.follow {
+            bottom
+        } else {
+            self.scroll.min(bottom)
+        }
+    }
+
+    /// Scroll up by `lines`, which stops following the newest records.
+    pub fn scroll_up(&mut self, lines: usize, total: usize, height: usize) {
+        if total <= height {
+            return;
+        }
+        self.scroll = self.top(total, height).saturating_sub(lines);
+        self.follow = false;
+    }
+
+    /// Scroll down by `lines`, following the newest records again once the
+    /// bottom is reached.
+    pub fn scroll_down(&mut self, lines: usize, total: usize, height: usize) {
+        let bottom = total.saturating_sub(height);
+        self.scroll = (self.top(total, height) + lines).min(bottom);
+        self.follow = self.scroll == bottom;
+    }
+
+    /// Scroll to the oldest records.
+    pub fn scroll_to_top(&mut self, total: usize, height: usize) {
+        self.scroll_up(total, total, height);
+    }
+
+    /// Scroll to the newest records and follow them.
+    pub fn scroll_to_bottom(&mut self, total: usize, height: usize) {
+        self.scroll_down(total, total, height);
+    }
+
+    pub fn toggle_follow(&mut self, total: usize, height: usize) {
+        self.scroll = self.top(total, height);
+        self.follow = !self.follow;
+    }
+
+    /// Handle a key press while the panel is focused, given the number of
+    /// `total` lines shown in a panel `height` lines tall.
+    pub fn handle_key(&mut self, key: Key, total: usize, height: usize) {
+        if self.editing_filter {
+            match key {
+                Key::Char(c) => self.filter.push(c),
+                Key::Backspace => {
+                    self.filter.pop();
+                }
+                Key::Enter => self.editing_filter = false,
+                Key::Esc => {
+                    self.filter.clear();
+                    self.editing_filter = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+        match key {
+            Key::Char('/') => self.editing_filter = true,
+            Key::Char('l') => self.cycle_level(),
+            Key::Char('f') => self.toggle_follow(total, height),
+            Key::Up | Key::Char('k') => self.scroll_up(1, total, height),
+            Key::Down | Key::Char('j') => self.scroll_down(1, total, height),
+            Key::PageUp => self.scroll_up(height, total, height),
+            Key::PageDown => self.scroll_down(height, total, height),
+            Key::Home | Key::Char('g') => self.scroll_to_top(total, height),
+            Key::End | Key::Char('G') => self.scroll_to_bottom(total, height),
+            Key::Esc | Key::Tab => self.focused = false,
+            _ => {}
+        }
+    }
+}
+
+/// How many lines the scroll actions move the panel by, in a panel
+/// `height` lines tall.
+pub fn scroll_step(height: usize) -> usize {
+    (height / 2).max(1)
+}
+
+/// How many lines a record takes up in the panel.
+fn record_height(record: &LogRecord) -> usize {
+    record.message.lines().count().max(1)
+}
+
+/// The number of lines the panel has to show.
+pub fn log_line_count(state: &LogPanelState) -> usize {
+    LOG_BUFFER
+        .lock()
+        .iter()
+        .filter(|record| state.matches(record))
+        .map(record_height)
+        .sum()
+}
+
+/// The number of lines that fit inside the log panel.
+pub fn log_panel_height(area: Rect) -> usize {
+    usize::from(area.height.saturating_sub(2)) // -2 for borders
+}
+
+fn level_color(level: Level) -> Color {
+    match level {
+        Level::ERROR => Color::Red,
+        Level::WARN => Color::Yellow,
+        Level::INFO => Color::Green,
+        Level::DEBUG => Color::Blue,
+        _ => Color::DarkGray,
+    }
+}
+
+/// The lines in view in a panel `height` lines tall, going through the
+/// records in place rather than copying them.
+fn visible_lines(state: &LogPanelState, height: usize) -> Vec<Line<'static>> {
+    let buffer = LOG_BUFFER.lock();
+    let shown = || buffer.iter().filter(|record| state.matches(record));
+    let total = shown().map(record_height).sum();
+    let top = state.top(total, height);
+
+    let mut lines = Vec::with_capacity(height);
+    // the index of the first line of the current record
+    let mut line = 0;
+    for record in shown() {
+        if lines.len() >= height {
+            break;
+        }
+        let record_height = record_height(record);
+        if line + record_height > top {
+            lines.extend(
+                record_lines(record)
+                    .into_iter()
+                    .skip(top.saturating_sub(line))
+                    .take(height - lines.len()),
+            );
+        }
+        line += record_height;
+    }
+    lines
+}
+
+fn record_lines(record: &LogRecord) -> Vec<Line<'static>> {
+    let label = Span::styled(
+        format!("{:<6}", record.level.as_str()),
+        Style::default().fg(level_color(record.level)).bold(),
+    );
+    let mut lines: Vec<Line<'static>> = record
+        .message
+        .lines()
+        .enumerate()
+        .map(|(i, text)| {
+            Line::from(vec![
+                if i == 0 {
+                    label.clone()
+                } else {
+                    Span::raw(" ".repeat(6))
+                },
+                Span::raw(text.to_string()),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(label));
+    }
+    lines
+}
+
+/// Draw the log panel as a floating window.
+pub fn draw_logs(
+    f: &mut Frame<'_>,
+    area: Rect,
+    state: &LogPanelState,
+    colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+) {
+    let lines = visible_lines(state, log_panel_height(area));
+
+    let mut status = format!(" {} {} ", glyphs.at_most, state.level.as_str());
+    if state.follow {
+        status.push_str(&format!("{} follow ", glyphs.separator));
+    }
+    let border_fg = if state.focused {
+        colorscheme.mode.channel
+    } else {
+        colorscheme.general.border_fg
+    };
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(glyphs.popup_border())
+        .border_style(Style::default().fg(border_fg))
+        .title_top(Line::from(" Logs ").alignment(Alignment::Center))
+        .title_top(Line::from(status).alignment(Alignment::Right))
+        .style(
+            Style::default()
+                .bg(colorscheme.general.background.unwrap_or_default()),
+        );
+    if state.editing_filter || !state.filter.is_empty() {
+        let cursor = if state.editing_filter {
+            glyphs.cursor
+        } else {
+            ""
+        };
+        block = block.title_bottom(
+            Line::from(format!(" /{}{cursor} ", state.filter))
+                .alignment(Alignment::Left),
+        );
+    }
+
+    let paragraph = Paragraph::new(lines).block(block);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: Level, message: &str) -> LogRecord {
+        LogRecord {
+            level,
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_cycle_level() {
+        let mut state = LogPanelState::default();
+        assert_eq!(state.level, Level::TRACE);
+        state.cycle_level();
+        assert_eq!(state.level, Level::ERROR);
+        state.cycle_level();
+        assert_eq!(state.level, Level::WARN);
+    }
+
+    #[test]
+    fn test_matches_level_and_filter() {
+        let mut state = LogPanelState {
+            level: Level::INFO,
+            ..Default::default()
+        };
+        assert!(state.matches(&record(Level::WARN, "Loading cable")));
+        assert!(!state.matches(&record(Level::DEBUG, "Loading cable")));
+
+        state.filter = "CABLE".to_string();
+        assert!(state.matches(&record(Level::INFO, "Loading cable")));
+        assert!(!state.matches(&record(Level::INFO, "Loading config")));
+    }
+
+    #[test]
+    fn test_scrolling_up_stops_following() {
+        let mut state = LogPanelState::default();
+        assert_eq!(state.top(100, 10), 90);
+
+        state.scroll_up(5, 100, 10);
+        assert!(!state.follow);
+        assert_eq!(state.top(100, 10), 85);
+        // new records don't move the view
+        assert_eq!(state.top(120, 10), 85);
+
+        state.scroll_down(3, 120, 10);
+        assert_eq!(state.top(120, 10), 88);
+        state.scroll_down(50, 120, 10);
+        assert!(state.follow);
+        assert_eq!(state.top(130, 10), 120);
+    }
+
+    #[test]
+    fn test_scrolling_when_everything_fits() {
+        let mut state = LogPanelState::default();
+        state.scroll_up(1, 5, 10);
+        assert!(state.follow);
+        assert_eq!(state.top(5, 10), 0);
+    }
+
+    #[test]
+    fn test_handle_key_edits_filter() {
+        let mut state = LogPanelState::default();
+        state.handle_key(Key::Char('/'), 0, 10);
+        state.handle_key(Key::Char('f'), 0, 10);
+        state.handle_key(Key::Char('o'), 0, 10);
+        assert_eq!(state.filter, "fo");
+        assert!(state.follow);
+
+        state.handle_key(Key::Enter, 0, 10);
+        assert!(!state.editing_filter);
+        state.handle_key(Key::Char('f'), 0, 10);
+        assert!(!state.follow);
+
+        state.handle_key(Key::Esc, 0, 10);
+        assert!(!state.focused);
+    }
+
+    #[test]
+    fn test_scrolling_is_bounded_by_multiline_records() {
+        let records = [
+            record(Level::INFO, "one"),
+            record(Level::WARN, "two\nlines"),
+            record(Level::ERROR, "three\nmore\nlines"),
+            record(Level::DEBUG, "four"),
+        ];
+        let total = records.iter().map(record_height).sum::<usize>();
+        assert_eq!(total, 7);
+        let height = 4;
+        let mut state = LogPanelState::default();
+
+        state.scroll_to_top(total, height);
+        assert!(!state.follow);
+        assert_eq!(state.top(total, height), 0);
+        state.scroll_up(scroll_step(height), total, height);
+        assert_eq!(state.top(total, height), 0);
+
+        // no scrolling past the last line
+        state.scroll_down(scroll_step(height), total, height);
+        assert_eq!(state.top(total, height), 2);
+        state.scroll_down(scroll_step(height), total, height);
+        assert_eq!(state.top(total, height), 3);
+        assert!(state.follow);
+        state.scroll_down(scroll_step(height), total, height);
+        assert_eq!(state.top(total, height), 3);
+
+        state.handle_key(Key::Char('g'), total, height);
+        assert_eq!(state.top(total, height), 0);
+        state.handle_key(Key::End, total, height);
+        assert!(state.follow);
+        assert_eq!(state.top(total, height), 3);
+    }
+
+    #[test]
+    fn test_visible_lines_start_within_multiline_records() {
+        {
+            let mut buffer = LOG_BUFFER.lock();
+            buffer.push(record(Level::INFO, "visible one"));
+            buffer.push(record(Level::WARN, "visible two\nlines"));
+            buffer.push(record(Level::ERROR, "visible three"));
+        }
+        let texts = |state: &LogPanelState, height| {
+            visible_lines(state, height)
+                .iter()
+                .map(|line| line.to_string().trim_end().to_string())
+                .collect::<Vec<_>>()
+        };
+        let mut state = LogPanelState {
+            filter: "visible".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(texts(&state, 2), ["      lines", "ERROR visible three"]);
+        state.scroll_to_top(4, 2);
+        assert_eq!(
+            texts(&state, 2),
+            ["INFO  visible one", "WARN  visible two"]
+        );
+        state.scroll_down(1, 4, 2);
+        assert_eq!(texts(&state, 2), ["WARN  visible two", "      lines"]);
+    }
+
+    #[test]
+    fn test_scroll_step() {
+        assert_eq!(scroll_step(20), 10);
+        assert_eq!(scroll_step(1), 1);
+        assert_eq!(scroll_step(0), 1);
+    }
+
+    #[test]
+    fn test_multiline_records_take_several_lines() {
+        assert_eq!(record_height(&record(Level::INFO, "a\nb\nc")), 3);
+        assert_eq!(record_height(&record(Level::INFO, "")), 1);
+        assert_eq!(record_lines(&record(Level::INFO, "a\nb")).len(), 2);
+    }
+}
@@ -1,14 +1,23 @@
 pub mod colors;
+pub mod confirm;
 pub mod constants;
+pub mod detail;
+pub mod glyphs;
+pub mod help_overlay;
 pub mod help_panel;
+pub mod inline_preview;
 pub mod input;
 pub mod keybindings;
 pub mod layout;
 pub mod logo;
+pub mod logs;
 pub mod mode;
 pub mod preview;
+pub mod preview_focus;
 pub mod remote_control;
 pub mod result_item;
 pub mod results;
+pub mod separator;
 pub mod spinner;
 pub mod status_bar;
+pub mod title;
@@ -1,7 +1,16 @@
 use crate::{
-    config::ui::{BorderType, Padding},
-    previewer::state::PreviewState,
-    screen::colors::Colorscheme,
+    config::ui::{BorderType, Padding, PreviewTitlePosition},
+    previewer::{
+        image::{
+            GraphicsProtocol, PreviewImage, cached_escape_sequence, fit_cells,
+        },
+        state::PreviewState,
+    },
+    screen::{
+        colors::Colorscheme,
+        glyphs::Glyphs,
+        preview_focus::{PreviewFocus, highlight_range},
+    },
     utils::strings::{
         ReplaceNonPrintableConfig, SPACE, replace_non_printable_bulk,
         shrink_with_ellipsis,
@@ -17,7 +26,18 @@ use ratatui::{
         Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
     },
 };
+use std::sync::Arc;
+
+/// An image to write to the terminal at `x`, `y` once the frame is drawn,
+/// its escape sequence being far too large to go through a buffer cell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePlacement {
+    pub x: u16,
+    pub y: u16,
+    pub sequence: Arc<str>,
+}
 
+/// Draw the preview panel, returning the image to display in it, if any.
 #[allow(clippy::too_many_arguments)]
 pub fn draw_preview_content_block(
     f: &mut Frame,
@@ -27,7 +47,14 @@ pub fn draw_preview_content_block(
     border_type: &BorderType,
     padding: &Padding,
     scrollbar: bool,
-) -> Result<()> {
+    title_position: PreviewTitlePosition,
+    focus: Option<&PreviewFocus>,
+    image_protocol: Option<GraphicsProtocol>,
+    glyphs: &Glyphs,
+) -> Result<Option<ImagePlacement>> {
+    if rect.is_empty() {
+        return Ok(None);
+    }
     let inner = draw_content_outer_block(
         f,
         rect,
@@ -36,17 +63,36 @@ pub fn draw_preview_content_block(
         *padding,
         &preview_state.preview.title,
         preview_state.preview.footer,
+        preview_state.preview.badge.as_deref(),
+        title_position,
+        (preview_state.scroll, preview_state.preview.total_lines),
+        focus,
+        glyphs,
     );
     let total_lines =
         preview_state.preview.total_lines.saturating_sub(1) as usize;
     let scroll = preview_state.scroll;
 
+    if let (Some(protocol), Some(image)) =
+        (image_protocol, &preview_state.preview.image)
+        && let Some(placement) = draw_image(f, inner, protocol, image)
+    {
+        return Ok(Some(placement));
+    }
+
+    let mut content = preview_state.preview.content;
+    if let Some(focus) = focus {
+        highlight_search_matches(&mut content, focus, scroll);
+    }
+
     // render the preview content
     let rp = build_preview_paragraph(
-        preview_state.preview.content,
+        content,
         preview_state.preview.target_line,
         colorscheme.preview.highlight_bg,
     );
+    // keep showing the previous preview, dimmed, until the new one is ready
+    let rp = if preview_state.stale { rp.dim() } else { rp };
     f.render_widget(Clear, inner);
     f.render_widget(rp, inner);
 
@@ -64,12 +110,76 @@ pub fn draw_preview_content_block(
             y: inner.y,
             width: 1, // Scrollbar width
             height: inner.height,
-        };
+        }
+        // without borders, that's past the edge of the screen
+        .intersection(f.area());
+
+        if !scrollbar_rect.is_empty() {
+            scrollbar.render(
+                scrollbar_rect,
+                f.buffer_mut(),
+                &mut scrollbar_state,
+            );
+        }
+    }
 
-        scrollbar.render(scrollbar_rect, f.buffer_mut(), &mut scrollbar_state);
+    Ok(None)
+}
+
+/// Make room for `image` at the top left of `area`: the cells it covers are
+/// skipped when drawing the frame, and the escape sequence displaying it with
+/// the terminal's graphics protocol is written over them afterwards.
+///
+/// Nothing is drawn if the image can't be decoded.
+fn draw_image(
+    f: &mut Frame,
+    area: Rect,
+    protocol: GraphicsProtocol,
+    image: &PreviewImage,
+) -> Option<ImagePlacement> {
+    if area.is_empty() {
+        return None;
+    }
+    let (cols, rows) =
+        fit_cells(image.width, image.height, area.width, area.height);
+    let sequence = cached_escape_sequence(protocol, image, cols, rows)?;
+    f.render_widget(Clear, area);
+    let buf = f.buffer_mut();
+    for y in area.y..area.y + rows {
+        for x in area.x..area.x + cols {
+            if let Some(cell) = buf.cell_mut((x, y)) {
+                cell.set_skip(true);
+            }
+        }
     }
+    Some(ImagePlacement {
+        x: area.x,
+        y: area.y,
+        sequence,
+    })
+}
 
-    Ok(())
+/// Highlight the search matches found in the lines of `content`, which
+/// starts at line `scroll` of the preview, the current one in bold.
+fn highlight_search_matches(
+    content: &mut Text<'static>,
+    focus: &PreviewFocus,
+    scroll: u16,
+) {
+    for (i, m) in focus.matches.iter().enumerate() {
+        let Some(line) = m
+            .line
+            .checked_sub(scroll)
+            .and_then(|line| content.lines.get_mut(usize::from(line)))
+        else {
+            continue;
+        };
+        let mut style = Style::default().reversed();
+        if i == focus.current {
+            style = style.bold();
+        }
+        highlight_range(line, m.start, m.end, style);
+    }
 }
 
 pub fn build_preview_paragraph(
@@ -114,6 +224,7 @@ fn build_ansi_text_paragraph<'a>(
     Paragraph::new(text).block(preview_block)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn draw_content_outer_block(
     f: &mut Frame,
     rect: Rect,
@@ -122,6 +233,11 @@ fn draw_content_outer_block(
     padding: Padding,
     preview_title: &str,
     preview_footer: Option<String>,
+    badge: Option<&str>,
+    title_position: PreviewTitlePosition,
+    (scroll, total_lines): (u16, u16),
+    focus: Option<&PreviewFocus>,
+    glyphs: &Glyphs,
 ) -> Rect {
     let mut preview_title_spans = vec![Span::from(SPACE)];
     // preview header
@@ -138,23 +254,51 @@ fn draw_content_outer_block(
     ));
     preview_title_spans.push(Span::from(SPACE));
 
-    let mut block = Block::default();
-    block = block.title_top(
-        Line::from(preview_title_spans)
-            .alignment(Alignment::Center)
-            .style(Style::default().fg(colorscheme.preview.title_fg)),
-    );
-
-    // preview footer
-    if let Some(preview_footer) = preview_footer {
-        let footer_line = Line::from(vec![
+    let title_line = Line::from(preview_title_spans)
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(colorscheme.preview.title_fg));
+    // the active preview command, next to the title
+    let badge_line = badge.map(|badge| {
+        Line::from(vec![
+            Span::from(SPACE),
+            Span::styled(
+                badge.to_string(),
+                Style::default().fg(colorscheme.preview.title_fg).italic(),
+            ),
+            Span::from(SPACE),
+        ])
+        .alignment(Alignment::Right)
+    });
+    let footer_line = preview_footer.map(|preview_footer| {
+        Line::from(vec![
             Span::from(SPACE),
             Span::from(preview_footer),
             Span::from(SPACE),
         ])
         .alignment(Alignment::Center)
-        .style(Style::default().fg(colorscheme.preview.title_fg));
-        block = block.title_bottom(footer_line);
+        .style(Style::default().fg(colorscheme.preview.title_fg))
+    });
+
+    let mut block = Block::default();
+    match title_position {
+        PreviewTitlePosition::Top => {
+            block = block.title_top(title_line);
+            if let Some(badge_line) = badge_line {
+                block = block.title_top(badge_line);
+            }
+            if let Some(footer_line) = footer_line {
+                block = block.title_bottom(footer_line);
+            }
+        }
+        PreviewTitlePosition::Bottom => {
+            block = block.title_bottom(title_line);
+            if let Some(badge_line) = badge_line {
+                block = block.title_bottom(badge_line);
+            }
+            if let Some(footer_line) = footer_line {
+                block = block.title_top(footer_line);
+            }
+        }
     }
 
     let mut preview_outer_block = block
@@ -164,13 +308,101 @@ fn draw_content_outer_block(
         )
         .padding(RatatuiPadding::from(padding));
     if let Some(border_type) = border_type.to_ratatui_border_type() {
+        // a focused preview stands out like the focused log panel
+        let border_fg = if focus.is_some() {
+            colorscheme.mode.channel
+        } else {
+            colorscheme.general.border_fg
+        };
         preview_outer_block = preview_outer_block
             .borders(Borders::ALL)
-            .border_type(border_type)
-            .border_style(Style::default().fg(colorscheme.general.border_fg));
+            .border_set(glyphs.border_set(border_type))
+            .border_style(Style::default().fg(border_fg));
+    }
+    if let Some(prompt) =
+        focus.and_then(|focus| search_prompt(focus, glyphs.cursor))
+    {
+        preview_outer_block = preview_outer_block.title_bottom(
+            Line::from(prompt)
+                .alignment(Alignment::Left)
+                .style(Style::default().fg(colorscheme.preview.title_fg)),
+        );
+    }
+
+    // where we are in the preview, unless it fits entirely
+    let height = preview_outer_block.inner(rect).height;
+    if let Some(indicator) = scroll_indicator(scroll, height, total_lines) {
+        preview_outer_block = preview_outer_block.title_bottom(
+            Line::from(vec![
+                Span::from(SPACE),
+                Span::from(indicator),
+                Span::from(SPACE),
+            ])
+            .alignment(Alignment::Right)
+            .style(Style::default().fg(colorscheme.general.border_fg)),
+        );
     }
 
     let inner = preview_outer_block.inner(rect);
     f.render_widget(preview_outer_block, rect);
     inner
 }
+
+/// The range of lines shown out of the total, e.g. `21-50/120`, or `None`
+/// when the whole content fits in the pane.
+fn scroll_indicator(
+    scroll: u16,
+    height: u16,
+    total_lines: u16,
+) -> Option<String> {
+    if total_lines <= height {
+        return None;
+    }
+    let last = scroll.saturating_add(height).min(total_lines);
+    Some(format!("{}-{last}/{total_lines}", scroll.saturating_add(1)))
+}
+
+/// The search prompt shown while searching the focused preview, e.g.
+/// ` /foo (2/5) `.
+fn search_prompt(focus: &PreviewFocus, cursor: &str) -> Option<String> {
+    if !focus.editing_search && focus.search.is_empty() {
+        return None;
+    }
+    let cursor = if focus.editing_search { cursor } else { "" };
+    let count = if focus.matches.is_empty() {
+        String::new()
+    } else {
+        format!(" ({}/{})", focus.current + 1, focus.matches.len())
+    };
+    Some(format!(" /{}{cursor}{count} ", focus.search))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scroll_indicator() {
+        assert_eq!(scroll_indicator(0, 30, 120).unwrap(), "1-30/120");
+        assert_eq!(scroll_indicator(20, 30, 120).unwrap(), "21-50/120");
+        assert_eq!(scroll_indicator(90, 30, 120).unwrap(), "91-120/120");
+        assert_eq!(scroll_indicator(0, 30, 30), None);
+        assert_eq!(scroll_indicator(0, 30, 12), None);
+    }
+
+    #[test]
+    fn test_search_prompt() {
+        let mut focus = PreviewFocus::default();
+        assert_eq!(search_prompt(&focus, "▏"), None);
+        focus.editing_search = true;
+        focus.search = "fo".to_string();
+        assert_eq!(search_prompt(&focus, "▏").unwrap(), " /fo▏ ");
+        focus.editing_search = false;
+        focus.matches = crate::screen::preview_focus::find_matches(
+            &Text::from("foo fo"),
+            "fo",
+        );
+        focus.current = 1;
+        assert_eq!(search_prompt(&focus, "▏").unwrap(), " /fo (2/2) ");
+    }
+}
@@ -0,0 +1,353 @@
+use crate::{
+    event::Key,
+    previewer::state::{PreviewState, half_page},
+};
+use ratatui::{
+    style::Style,
+    text::{Line, Span, Text},
+};
+
+/// An occurrence of the search query in the preview, in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchMatch {
+    /// The line the match is on, starting at 0.
+    pub line: u16,
+    /// The first character of the match.
+    pub start: usize,
+    /// The character right after the match.
+    pub end: usize,
+}
+
+/// The state of the preview pane while it has the keyboard focus.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreviewFocus {
+    /// Occurrences of this (ignoring case) are highlighted.
+    pub search: String,
+    /// Whether keystrokes are currently typed into `search`.
+    pub editing_search: bool,
+    pub matches: Vec<SearchMatch>,
+    /// The index of the match last jumped to.
+    pub current: usize,
+}
+
+impl PreviewFocus {
+    pub fn current_match(&self) -> Option<&SearchMatch> {
+        self.matches.get(self.current)
+    }
+
+    /// Look the query up again in `text` and jump to the first match at or
+    /// after the `scroll` line.
+    fn update_matches(&mut self, text: &Text<'_>, scroll: u16) {
+        self.matches = find_matches(text, &self.search);
+        self.current = first_match_from(&self.matches, scroll);
+    }
+
+    pub fn next_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.current = (self.current + 1) % self.matches.len();
+        }
+    }
+
+    pub fn prev_match(&mut self) {
+        if !self.matches.is_empty() {
+            self.current =
+                (self.current + self.matches.len() - 1) % self.matches.len();
+        }
+    }
+
+    /// Scroll `state` so that the current match is visible in a pane
+    /// `height` lines tall.
+    fn reveal_current_match(&self, state: &mut PreviewState, height: u16) {
+        if let Some(m) = self.current_match() {
+            state.scroll = scroll_to_reveal(
+                m.line,
+                state.scroll,
+                height,
+                state.preview.total_lines,
+            );
+        }
+    }
+
+    /// Handle a key press while the preview is focused, scrolling `state`
+    /// in a pane `height` lines tall. Returns whether the preview keeps the
+    /// focus.
+    pub fn handle_key(
+        &mut self,
+        key: Key,
+        state: &mut PreviewState,
+        height: u16,
+    ) -> bool {
+        if self.editing_search {
+            match key {
+                Key::Char(c) => self.search.push(c),
+                Key::Backspace => {
+                    self.search.pop();
+                }
+                Key::Enter => self.editing_search = false,
+                Key::Esc => {
+                    self.search.clear();
+                    self.editing_search = false;
+                }
+                _ => return true,
+            }
+            self.update_matches(&state.preview.content, state.scroll);
+            self.reveal_current_match(state, height);
+            return true;
+        }
+        match key {
+            Key::Char('/') => self.editing_search = true,
+            Key::Char('n') => {
+                self.next_match();
+                self.reveal_current_match(state, height);
+            }
+            Key::Char('N') => {
+                self.prev_match();
+                self.reveal_current_match(state, height);
+            }
+            Key::Down | Key::Char('j') => state.scroll_down(1, height),
+            Key::Up | Key::Char('k') => state.scroll_up(1),
+            Key::Char('d') => state.scroll_down(half_page(height), height),
+            Key::Char('u') => state.scroll_up(half_page(height)),
+            Key::PageDown => state.scroll_down(height.max(1), height),
+            Key::PageUp => state.scroll_up(height.max(1)),
+            Key::Char('g') | Key::Home => state.scroll_to_top(),
+            Key::Char('G') | Key::End => state.scroll_to_bottom(height),
+            Key::Esc | Key::Tab => return false,
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Every occurrence of `query` in `text`, ignoring case.
+pub fn find_matches(text: &Text<'_>, query: &str) -> Vec<SearchMatch> {
+    let query: Vec<char> = query.chars().map(lowercase).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for (i, line) in text.lines.iter().enumerate() {
+        let Ok(line_number) = u16::try_from(i) else {
+            break;
+        };
+        let chars: Vec<char> = line
+            .spans
+            .iter()
+            .flat_map(|span| span.content.chars())
+            .map(lowercase)
+            .collect();
+        let mut start = 0;
+        while start + query.len() <= chars.len() {
+            if chars[start..start + query.len()] == query[..] {
+                matches.push(SearchMatch {
+                    line: line_number,
+                    start,
+                    end: start + query.len(),
+                });
+                start += query.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+    matches
+}
+
+/// Lowercases a single character, keeping character offsets intact.
+fn lowercase(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// The index of the first match at or after the `line`, wrapping around to
+/// the first one.
+fn first_match_from(matches: &[SearchMatch], line: u16) -> usize {
+    matches.iter().position(|m| m.line >= line).unwrap_or(0)
+}
+
+/// The scroll that shows `line` in a pane `height` lines tall: the current
+/// `scroll` if it is already visible, else one that centers it.
+fn scroll_to_reveal(
+    line: u16,
+    scroll: u16,
+    height: u16,
+    total_lines: u16,
+) -> u16 {
+    if line >= scroll && line < scroll.saturating_add(height) {
+        return scroll;
+    }
+    line.saturating_sub(height / 2)
+        .min(total_lines.saturating_sub(height))
+}
+
+/// Patch the characters from `start` to `end` of `line` with `style`,
+/// splitting its spans where needed.
+pub fn highlight_range(
+    line: &mut Line<'static>,
+    start: usize,
+    end: usize,
+    style: Style,
+) {
+    let mut spans = Vec::with_capacity(line.spans.len() + 2);
+    let mut offset = 0;
+    for span in line.spans.drain(..) {
+        let len = span.content.chars().count();
+        if offset + len <= start || offset >= end {
+            offset += len;
+            spans.push(span);
+            continue;
+        }
+        let from = start.saturating_sub(offset);
+        let to = (end - offset).min(len);
+        let chars: Vec<char> = span.content.chars().collect();
+        for (range, patched) in
+            [(0..from, false), (from..to, true), (to..len, false)]
+        {
+            if range.is_empty() {
+                continue;
+            }
+            let content: String = chars[range].iter().collect();
+            let span_style = if patched {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(content, span_style));
+        }
+        offset += len;
+    }
+    line.spans = spans;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn text(lines: &[&str]) -> Text<'static> {
+        Text::from(
+            lines
+                .iter()
+                .map(|l| Line::from(l.to_string()))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn state_with_lines(total_lines: u16) -> PreviewState {
+        let mut state = PreviewState::default();
+        state.preview.total_lines = total_lines;
+        state.preview.content = text(
+            &(0..total_lines)
+                .map(|i| if i % 10 == 0 { "needle" } else { "hay" })
+                .collect::<Vec<_>>(),
+        );
+        state
+    }
+
+    #[test]
+    fn test_find_matches_ignores_case_and_spans() {
+        let mut content = text(&["Foo foo", "bar"]);
+        content.lines[1] = Line::from(vec![Span::raw("ba"), Span::raw("r")]);
+        let matches = find_matches(&content, "FOO");
+        assert_eq!(
+            matches,
+            vec![
+                SearchMatch {
+                    line: 0,
+                    start: 0,
+                    end: 3
+                },
+                SearchMatch {
+                    line: 0,
+                    start: 4,
+                    end: 7
+                },
+            ]
+        );
+        assert_eq!(find_matches(&content, "ar")[0].line, 1);
+        assert!(find_matches(&content, "").is_empty());
+        // matches don't overlap
+        assert_eq!(find_matches(&text(&["aaaa"]), "aa").len(), 2);
+    }
+
+    #[test]
+    fn test_next_and_prev_match_wrap_around() {
+        let mut focus = PreviewFocus::default();
+        focus.next_match();
+        assert_eq!(focus.current, 0);
+
+        focus.matches = find_matches(&text(&["a", "a", "a"]), "a");
+        focus.prev_match();
+        assert_eq!(focus.current, 2);
+        focus.next_match();
+        assert_eq!(focus.current, 0);
+        focus.next_match();
+        assert_eq!(focus.current, 1);
+    }
+
+    #[test]
+    fn test_first_match_from() {
+        let matches = find_matches(&text(&["a", "b", "a", "b"]), "a");
+        assert_eq!(first_match_from(&matches, 0), 0);
+        assert_eq!(first_match_from(&matches, 1), 1);
+        assert_eq!(first_match_from(&matches, 2), 1);
+        // nothing further down, back to the first one
+        assert_eq!(first_match_from(&matches, 3), 0);
+    }
+
+    #[test]
+    fn test_scroll_to_reveal() {
+        // already visible
+        assert_eq!(scroll_to_reveal(15, 10, 20, 100), 10);
+        // centered
+        assert_eq!(scroll_to_reveal(50, 10, 20, 100), 40);
+        assert_eq!(scroll_to_reveal(3, 10, 20, 100), 0);
+        // the last line stays at the bottom
+        assert_eq!(scroll_to_reveal(98, 10, 20, 100), 80);
+    }
+
+    #[test]
+    fn test_search_jumps_between_matches() {
+        let mut state = state_with_lines(100);
+        let mut focus = PreviewFocus::default();
+        for key in [Key::Char('/'), Key::Char('n'), Key::Char('e')] {
+            assert!(focus.handle_key(key, &mut state, 20));
+        }
+        assert!(focus.editing_search);
+        assert_eq!(focus.matches.len(), 10);
+        focus.handle_key(Key::Enter, &mut state, 20);
+        assert!(!focus.editing_search);
+
+        focus.handle_key(Key::Char('N'), &mut state, 20);
+        assert_eq!(focus.current_match().unwrap().line, 90);
+        assert_eq!(state.scroll, 80);
+        focus.handle_key(Key::Char('n'), &mut state, 20);
+        assert_eq!(state.scroll, 0);
+
+        focus.handle_key(Key::Char('G'), &mut state, 20);
+        assert_eq!(state.scroll, 80);
+        focus.handle_key(Key::Char('u'), &mut state, 20);
+        assert_eq!(state.scroll, 70);
+        assert!(!focus.handle_key(Key::Esc, &mut state, 20));
+    }
+
+    #[test]
+    fn test_highlight_range_splits_spans() {
+        let style = Style::default().bg(Color::Yellow);
+        let mut line = Line::from(vec![Span::raw("abc"), Span::raw("def")]);
+        highlight_range(&mut line, 2, 4, style);
+        let spans: Vec<(&str, Style)> = line
+            .spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("ab", Style::default()),
+                ("c", style),
+                ("d", style),
+                ("ef", Style::default()),
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
+    action::Action,
     channels::{prototypes::BinaryRequirement, remote_control::CableEntry},
+    keymap::InputMap,
     screen::{
         colors::{Colorscheme, GeneralColorscheme},
+        glyphs::Glyphs,
         logo::{
             REMOTE_LOGO_HEIGHT_U16, REMOTE_LOGO_WIDTH_U16,
             build_remote_logo_paragraph,
@@ -17,8 +20,8 @@ use ratatui::{
     prelude::{Color, Line, Span, Style},
     style::Stylize,
     widgets::{
-        Block, BorderType, Borders, Clear, ListDirection, ListState, Padding,
-        Paragraph, Wrap,
+        Block, Borders, Clear, ListDirection, ListState, Padding, Paragraph,
+        Wrap,
     },
 };
 
@@ -30,8 +33,14 @@ pub fn draw_remote_control(
     picker_state: &mut ListState,
     input_state: &mut Input,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
     show_channel_descriptions: bool,
+    (matched, total): (u32, u32),
+    input_map: &InputMap,
 ) -> Result<()> {
+    if rect.is_empty() {
+        return Ok(());
+    }
     let show_logo = rect.height >= REMOTE_LOGO_HEIGHT_U16;
     let mut constraints = vec![Constraint::Fill(1)];
     if show_channel_descriptions {
@@ -57,11 +66,19 @@ pub fn draw_remote_control(
         entries,
         picker_state,
         colorscheme,
+        glyphs,
         input_state,
+        &RcFooter::new(matched, total, input_map),
     )?;
 
     if show_channel_descriptions {
-        draw_information_panel(f, layout[1], selected_entry, colorscheme);
+        draw_information_panel(
+            f,
+            layout[1],
+            selected_entry,
+            colorscheme,
+            glyphs,
+        );
     }
     if show_logo {
         draw_rc_logo(f, layout[layout.len() - 1], &colorscheme.general);
@@ -75,14 +92,15 @@ fn draw_information_panel(
     rect: Rect,
     selected_entry: Option<&CableEntry>,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
 ) {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Fill(1), Constraint::Length(3)].as_ref())
         .split(rect);
 
-    draw_description_block(f, layout[0], selected_entry, colorscheme);
-    draw_requirements_block(f, layout[1], selected_entry, colorscheme);
+    draw_description_block(f, layout[0], selected_entry, colorscheme, glyphs);
+    draw_requirements_block(f, layout[1], selected_entry, colorscheme, glyphs);
 }
 
 fn draw_description_block(
@@ -90,10 +108,11 @@ fn draw_description_block(
     area: Rect,
     selected_entry: Option<&CableEntry>,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
 ) {
     let description_block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_set(glyphs.popup_border())
         .border_style(Style::default().fg(colorscheme.general.border_fg))
         .title_top(Line::from(" Description ").alignment(Alignment::Center))
         .style(
@@ -102,18 +121,42 @@ fn draw_description_block(
         )
         .padding(Padding::right(1));
 
-    let description = if let Some(entry) = selected_entry {
-        entry
-            .description
-            .clone()
-            .unwrap_or_else(|| "No description available.".to_string())
-    } else {
-        String::new()
-    };
+    let mut lines = Vec::new();
+    if let Some(entry) = selected_entry {
+        lines.push(Line::from(Span::styled(
+            entry
+                .description
+                .clone()
+                .unwrap_or_else(|| "No description available.".to_string()),
+            Style::default().italic(),
+        )));
+        let command_sections = [
+            ("Source", &entry.source_commands),
+            ("Preview", &entry.preview_commands),
+        ];
+        for (name, commands) in command_sections {
+            if commands.is_empty() {
+                continue;
+            }
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled(
+                format!("{name}:"),
+                Style::default()
+                    .fg(colorscheme.help.metadata_field_name_fg)
+                    .bold(),
+            )));
+            lines.extend(commands.iter().map(|command| {
+                Line::from(Span::styled(
+                    command.clone(),
+                    Style::default()
+                        .fg(colorscheme.help.metadata_field_value_fg),
+                ))
+            }));
+        }
+    }
 
-    let description_paragraph = Paragraph::new(description)
+    let description_paragraph = Paragraph::new(lines)
         .block(description_block)
-        .style(Style::default().italic())
         .alignment(Alignment::Left)
         .wrap(Wrap { trim: true });
 
@@ -125,10 +168,11 @@ fn draw_requirements_block(
     area: Rect,
     selected_entry: Option<&CableEntry>,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
 ) {
     let mut requirements_block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_set(glyphs.popup_border())
         .border_style(Style::default().fg(colorscheme.general.border_fg))
         .style(
             Style::default()
@@ -195,21 +239,85 @@ fn draw_requirements_block(
     f.render_widget(requirements_paragraph, area);
 }
 
+/// What's shown at the bottom of the channel list: how many channels match
+/// and the keys to leave the remote control or edit the highlighted channel.
+struct RcFooter {
+    matched: u32,
+    total: u32,
+    hints: Vec<(String, &'static str)>,
+}
+
+impl RcFooter {
+    fn new(matched: u32, total: u32, input_map: &InputMap) -> Self {
+        let hints = [
+            (Action::ToggleRemoteControl, "close"),
+            (Action::EditChannelConfig, "edit"),
+        ]
+        .into_iter()
+        .filter_map(|(action, hint)| {
+            input_map
+                .get_key_for_action(&action)
+                .map(|key| (key.to_string(), hint))
+        })
+        .collect();
+        Self {
+            matched,
+            total,
+            hints,
+        }
+    }
+
+    fn count_line(&self) -> Line<'static> {
+        Line::from(format!(" {}/{} ", self.matched, self.total))
+            .alignment(Alignment::Left)
+    }
+
+    fn hints_line(
+        &self,
+        colorscheme: &Colorscheme,
+        glyphs: &Glyphs,
+    ) -> Line<'static> {
+        let mut spans = vec![Span::raw(" ")];
+        for (i, (key, hint)) in self.hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(format!(" {} ", glyphs.separator)));
+            }
+            spans.push(Span::styled(
+                key.clone(),
+                Style::default().fg(colorscheme.mode.remote_control).bold(),
+            ));
+            spans.push(Span::raw(format!(" {hint}")));
+        }
+        spans.push(Span::raw(" "));
+        Line::from(spans).alignment(Alignment::Right)
+    }
+}
+
 fn draw_search_panel(
     f: &mut Frame,
     area: Rect,
     entries: &[CableEntry],
     picker_state: &mut ListState,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
     input: &mut Input,
+    footer: &RcFooter,
 ) -> Result<()> {
     let layout = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Fill(1), Constraint::Length(3)].as_ref())
         .split(area);
 
-    draw_rc_channels(f, layout[0], entries, picker_state, colorscheme);
-    draw_rc_input(f, layout[1], input, colorscheme)
+    draw_rc_channels(
+        f,
+        layout[0],
+        entries,
+        picker_state,
+        colorscheme,
+        glyphs,
+        footer,
+    );
+    draw_rc_input(f, layout[1], input, colorscheme, glyphs)
 }
 
 fn draw_rc_channels(
@@ -218,16 +326,20 @@ fn draw_rc_channels(
     entries: &[CableEntry],
     picker_state: &mut ListState,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+    footer: &RcFooter,
 ) {
     let rc_block = Block::default()
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_set(glyphs.popup_border())
         .border_style(Style::default().fg(colorscheme.general.border_fg))
         .title_top(
             Line::from(" Channels ")
                 .alignment(Alignment::Center)
                 .italic(),
         )
+        .title_bottom(footer.count_line())
+        .title_bottom(footer.hints_line(colorscheme, glyphs))
         .style(
             Style::default()
                 .bg(colorscheme.general.background.unwrap_or_default()),
@@ -241,6 +353,11 @@ fn draw_rc_channels(
         ListDirection::TopToBottom,
         &colorscheme.results,
         area.width,
+        None,
+        None,
+        None,
+        glyphs,
+        |_| None,
         |_| None,
     );
 
@@ -252,13 +369,14 @@ fn draw_rc_input(
     area: Rect,
     input: &mut Input,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
 ) -> Result<()> {
     let input_block = Block::default()
         .title_top(
             Line::from(" Search ").alignment(Alignment::Center).italic(),
         )
         .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
+        .border_set(glyphs.popup_border())
         .border_style(Style::default().fg(colorscheme.general.border_fg))
         .style(
             Style::default()
@@ -266,6 +384,9 @@ fn draw_rc_input(
         );
 
     let input_block_inner = input_block.inner(area);
+    if input_block_inner.area() == 0 {
+        return Ok(());
+    }
 
     f.render_widget(input_block, area);
 
@@ -308,8 +429,9 @@ fn draw_rc_input(
     // specified coordinates after rendering
     f.set_cursor_position((
         // Put cursor past the end of the input text
-        inner_input_chunks[1].x
-            + u16::try_from(input.visual_cursor().max(scroll) - scroll)?,
+        inner_input_chunks[1].x.saturating_add(u16::try_from(
+            input.visual_cursor().max(scroll) - scroll,
+        )?),
         // Move one line down, from the border to the input line
         inner_input_chunks[1].y,
     ));
@@ -1,14 +1,12 @@
 use crate::{
+    channels::prototypes::{ChannelIcon, Delimiter},
     event::Key,
-    screen::{
-        colors::ResultsColorscheme,
-        constants::{DESELECTED_SYMBOL, POINTER_SYMBOL, SELECTED_SYMBOL},
-    },
+    screen::{colors::ResultsColorscheme, glyphs::Glyphs},
     utils::{
         indices::truncate_highlighted_string,
         strings::{
-            ReplaceNonPrintableConfig, make_result_item_printable,
-            replace_non_printable_bulk,
+            ReplaceNonPrintableConfig, grapheme_ranges_to_char_ranges,
+            make_result_item_printable, replace_non_printable_bulk,
         },
     },
 };
@@ -20,6 +18,7 @@ use ratatui::{
     style::Stylize,
     widgets::{Block, List, ListDirection, ListState},
 };
+use std::str::FromStr;
 use unicode_width::UnicodeWidthStr;
 
 /// Trait implemented by any item that can be displayed in the results or remote-control list.
@@ -54,6 +53,16 @@ pub trait ResultItem {
     fn ansi(&self) -> bool {
         false
     }
+
+    /// Optional description rendered (dimmed) after the item (remote-control entries).
+    fn description(&self) -> Option<&str> {
+        None
+    }
+
+    /// Optional channel icon used when the item doesn't have a [`FileIcon`].
+    fn channel_icon(&self) -> Option<&ChannelIcon> {
+        None
+    }
 }
 
 /// Build a single `Line` for a [`ResultItem`].
@@ -67,6 +76,7 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
     area_width: u16,
     // Some(true)=selected ●, Some(false)=unselected, None=no prefix
     prefix: Option<bool>,
+    glyphs: &Glyphs,
 ) -> Line<'a> {
     // PERF: Pre-allocate spans vector with estimated capacity
     let mut spans = Vec::<Span<'a>>::with_capacity(16);
@@ -75,11 +85,11 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
     if let Some(selected) = prefix {
         if selected {
             spans.push(Span::styled(
-                SELECTED_SYMBOL,
+                glyphs.selected,
                 Style::default().fg(selection_fg),
             ));
         } else {
-            spans.push(Span::raw(DESELECTED_SYMBOL));
+            spans.push(Span::raw(glyphs.deselected));
         }
     }
 
@@ -90,11 +100,19 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
         .map(|k| 2 + k.to_string().len() as u16) // space + key
         .unwrap_or(0);
 
+    let line_count = item.display().lines().count();
+    let lines_suffix = (line_count > 1)
+        .then(|| format!(" {} {line_count} lines", glyphs.record));
+    let lines_suffix_width = lines_suffix
+        .as_deref()
+        .map_or(0, |suffix| UnicodeWidthStr::width(suffix) as u16);
+
     let item_max_width = area_width
         .saturating_sub(2) // pointer + space (kept for caller)
         .saturating_sub(2) // borders
         .saturating_sub(selection_prefix_width)
-        .saturating_sub(shortcut_extra);
+        .saturating_sub(shortcut_extra)
+        .saturating_sub(lines_suffix_width);
 
     if item.ansi() {
         spans.extend(build_entry_spans_ansi(
@@ -112,6 +130,11 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
         ));
     }
 
+    // Multi-line records only show their first line.
+    if let Some(suffix) = lines_suffix {
+        spans.push(Span::styled(suffix, Style::default().fg(result_fg).dim()));
+    }
+
     // Show shortcut if present.
     if let Some(key) = item.shortcut() {
         spans.push(Span::raw(" "));
@@ -121,6 +144,17 @@ pub fn build_result_line<'a, T: ResultItem + ?Sized>(
         ));
     }
 
+    // Show the (first line of the) description if present, anything that
+    // overflows the area is clipped by the list widget.
+    if let Some(description) =
+        item.description().and_then(|d| d.lines().next())
+    {
+        spans.push(Span::styled(
+            format!(" {description}"),
+            Style::default().fg(result_fg).dim(),
+        ));
+    }
+
     Line::from(spans)
 }
 
@@ -187,6 +221,7 @@ fn build_entry_spans<T: ResultItem + ?Sized>(
 ///   it falls back to the simpler `build_entry_spans` function.
 ///
 /// - 3/ Otherwise, it iterates over the parsed spans and overlays the match highlight ranges onto them.
+///    - The match ranges, which count grapheme clusters, are first converted to character ranges.
 ///    - It tracks the current character position across all spans.
 ///    - For each span, it walks through its characters, checking if the current position falls within
 ///      any of the match highlight ranges.
@@ -228,6 +263,16 @@ fn build_entry_spans_ansi<T: ResultItem + ?Sized>(
         return build_entry_spans(item, max_width, result_fg, match_fg);
     }
 
+    // the matcher counts a letter and its combining accents, or an emoji ZWJ
+    // sequence, as a single character
+    let match_ranges = if spans.iter().all(|span| span.content.is_ascii()) {
+        match_ranges.to_vec()
+    } else {
+        let plain: String =
+            spans.iter().map(|span| span.content.as_ref()).collect();
+        grapheme_ranges_to_char_ranges(&plain, match_ranges)
+    };
+
     // hypothesis: ~ 2 to 3 highlighted clusters + in the worst case scenario
     // each cluster splits its containing span into 3 parts -> + 6 spans so we
     // should be fine pre-allocating `spans.len() + 8`
@@ -312,43 +357,219 @@ fn build_entry_spans_ansi<T: ResultItem + ?Sized>(
     highlighted_spans
 }
 
+/// Resolve the icon span for an item.
+///
+/// The item's own [`FileIcon`] takes precedence, then the item's channel icon and finally the
+/// provided fallback (usually the icon of the current channel).
+fn build_icon_span<T: ResultItem + ?Sized>(
+    item: &T,
+    fallback: Option<&ChannelIcon>,
+    default_fg: Color,
+) -> Option<Span<'static>> {
+    let (glyph, color) = if let Some(icon) = item.icon() {
+        (icon.icon, Color::from_str(icon.color).ok())
+    } else {
+        let icon = item.channel_icon().or(fallback)?;
+        (
+            icon.glyph,
+            icon.color.as_deref().and_then(|c| Color::from_str(c).ok()),
+        )
+    };
+    Some(Span::styled(
+        format!("{glyph} "),
+        Style::default().fg(color.unwrap_or(default_fg)),
+    ))
+}
+
+/// A row of the results list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResultRow<'a> {
+    /// A non-selectable header introducing a group of entries.
+    Header(&'a str),
+    /// The entry at this index of the displayed slice.
+    Entry(usize),
+}
+
+/// Extract the group key of an entry, i.e. its `index`-th field.
+pub fn group_key<'a>(
+    raw: &'a str,
+    index: usize,
+    delimiter: &Delimiter,
+) -> &'a str {
+    delimiter.field(raw, index).unwrap_or_default()
+}
+
+/// Lay out the rows of the results list.
+///
+/// When `group_by` is set to a field index and a delimiter, a header row is
+/// inserted whenever the group key changes between consecutive entries.
+/// Rows are listed in drawing order: for lists drawn bottom to top, headers
+/// come after the entries of their group so that they end up above them.
+pub fn build_result_rows<'a, T: ResultItem>(
+    entries: &'a [T],
+    group_by: Option<(usize, &Delimiter)>,
+    list_direction: ListDirection,
+) -> Vec<ResultRow<'a>> {
+    let Some((index, delimiter)) = group_by else {
+        return (0..entries.len()).map(ResultRow::Entry).collect();
+    };
+    let bottom_to_top = list_direction == ListDirection::BottomToTop;
+
+    let mut rows = Vec::with_capacity(entries.len() * 2);
+    let mut current: Option<&str> = None;
+    for (i, entry) in entries.iter().enumerate() {
+        let key = group_key(entry.raw(), index, delimiter);
+        if current != Some(key) {
+            if bottom_to_top {
+                if let Some(previous) = current {
+                    rows.push(ResultRow::Header(previous));
+                }
+            } else {
+                rows.push(ResultRow::Header(key));
+            }
+            current = Some(key);
+        }
+        rows.push(ResultRow::Entry(i));
+    }
+    if bottom_to_top && let Some(last) = current {
+        rows.push(ResultRow::Header(last));
+    }
+    rows
+}
+
+/// The position of the `entry`-th entry among `rows`.
+pub fn entry_row(rows: &[ResultRow], entry: usize) -> Option<usize> {
+    rows.iter().position(|row| *row == ResultRow::Entry(entry))
+}
+
+/// How many entries fit in the first `height` rows.
+pub fn visible_entries(rows: &[ResultRow], height: usize) -> usize {
+    rows.iter()
+        .take(height)
+        .filter(|row| matches!(row, ResultRow::Entry(_)))
+        .count()
+}
+
 /// Build a `List` widget from a slice of [`ResultItem`]s.
+///
+/// When `index_offset` is set, each line is prefixed with the right-aligned 1-based rank
+/// of the entry (`index_offset + position + 1`). Since entries are always ordered by rank,
+/// index 1 stays the best match regardless of the list direction.
+///
+/// `channel_icon` is used for items that don't resolve to an icon of their own.
+///
+/// `group_by` inserts group headers, see [`build_result_rows`]. The list's
+/// selection then refers to rows rather than entries, use [`entry_row`] to
+/// translate it.
+///
+/// `prefix_fn` and `mark_fn` respectively tell whether an entry is selected
+/// and marked, `None` leaving out the corresponding column.
 #[allow(clippy::too_many_arguments)]
-pub fn build_results_list<'a, 'b, T, F>(
+#[allow(clippy::cast_possible_truncation)]
+pub fn build_results_list<'a, 'b, T, F, M>(
     block: Block<'b>,
     entries: &'a [T],
     relative_picker_state: &ListState,
     list_direction: ListDirection,
     colorscheme: &ResultsColorscheme,
     area_width: u16,
+    index_offset: Option<usize>,
+    channel_icon: Option<&ChannelIcon>,
+    group_by: Option<(usize, &Delimiter)>,
+    glyphs: &Glyphs,
     mut prefix_fn: F,
+    mut mark_fn: M,
 ) -> List<'a>
 where
     'b: 'a,
     T: ResultItem,
     F: FnMut(&T) -> Option<bool>,
+    M: FnMut(&T) -> Option<bool>,
 {
-    List::new(entries.iter().enumerate().map(|(i, e)| {
+    // all indices share the width of the largest one so they stay right-aligned
+    let index_width =
+        index_offset.map(|offset| (offset + entries.len()).to_string().len());
+
+    let rows = build_result_rows(entries, group_by, list_direction);
+
+    List::new(rows.into_iter().map(|row| {
+        let i = match row {
+            ResultRow::Header(key) => {
+                return Line::from(Span::styled(
+                    key.to_string(),
+                    Style::default()
+                        .fg(colorscheme.result_line_number_fg)
+                        .bold()
+                        .underlined(),
+                ));
+            }
+            ResultRow::Entry(i) => i,
+        };
+        let e = &entries[i];
         let prefix = prefix_fn(e);
         let result_fg = if relative_picker_state.selected() == Some(i) {
             colorscheme.result_selected_fg
         } else {
             colorscheme.result_fg
         };
-        build_result_line(
+
+        let index_span =
+            index_offset.zip(index_width).map(|(offset, width)| {
+                Span::styled(
+                    format!("{:>width$} ", offset + i + 1),
+                    Style::default().fg(colorscheme.result_line_number_fg),
+                )
+            });
+        let mark_span = mark_fn(e).map(|marked| {
+            if marked {
+                Span::styled(
+                    glyphs.marked,
+                    Style::default().fg(colorscheme.match_foreground_color),
+                )
+            } else {
+                Span::raw(glyphs.unmarked)
+            }
+        });
+        let icon_span = glyphs
+            .icons
+            .then(|| build_icon_span(e, channel_icon, result_fg))
+            .flatten();
+        let leading_width: u16 = mark_span
+            .iter()
+            .chain(index_span.iter())
+            .chain(icon_span.iter())
+            .map(|s| s.width() as u16)
+            .sum();
+
+        let mut line = build_result_line(
             e,
             colorscheme.result_selected_fg,
             result_fg,
             colorscheme.match_foreground_color,
-            area_width,
+            area_width.saturating_sub(leading_width),
             prefix,
-        )
+            glyphs,
+        );
+        // mark first, then the index, then the selection marker, then the icon
+        let mut icon_position = usize::from(prefix.is_some());
+        if let Some(index_span) = index_span {
+            line.spans.insert(0, index_span);
+            icon_position += 1;
+        }
+        if let Some(mark_span) = mark_span {
+            line.spans.insert(0, mark_span);
+            icon_position += 1;
+        }
+        if let Some(icon_span) = icon_span {
+            line.spans.insert(icon_position, icon_span);
+        }
+        line
     }))
     .direction(list_direction)
     .highlight_style(
         Style::default().bg(colorscheme.result_selected_bg).bold(),
     )
-    .highlight_symbol(POINTER_SYMBOL)
+    .highlight_symbol(glyphs.pointer)
     .block(block)
 }
 
@@ -356,6 +577,7 @@ where
 mod tests {
     use super::*;
     use crate::channels::entry::Entry;
+    use crate::screen::glyphs::{ASCII_GLYPHS, UNICODE_GLYPHS};
     use ratatui::prelude::{Color, Span};
     use ratatui::text::Line;
 
@@ -370,6 +592,7 @@ mod tests {
             Color::Reset,
             200,
             None,
+            &UNICODE_GLYPHS,
         );
 
         let expected = Line::from(vec![
@@ -383,6 +606,151 @@ mod tests {
         assert_eq!(line, expected);
     }
 
+    #[test]
+    fn test_build_result_line_multi_line_record() {
+        let entry = Entry::new("commit abc\n\nfix the thing".to_string())
+            .with_match_indices(&[7, 8, 14]);
+        let line = build_result_line(
+            &entry,
+            Color::Reset,
+            Color::Reset,
+            Color::Red,
+            200,
+            None,
+            &UNICODE_GLYPHS,
+        );
+
+        let expected = Line::from(vec![
+            Span::raw("commit ").fg(Color::Reset),
+            Span::raw("ab").fg(Color::Red),
+            Span::raw("c").fg(Color::Reset),
+            Span::raw(" ⏎ 3 lines").fg(Color::Reset).dim(),
+        ]);
+
+        assert_eq!(line, expected);
+    }
+
+    #[test]
+    fn test_build_result_line_ascii_glyphs() {
+        let entry = Entry::new("commit abc\nfix".to_string());
+        let line = build_result_line(
+            &entry,
+            Color::Reset,
+            Color::Reset,
+            Color::Reset,
+            200,
+            Some(true),
+            &ASCII_GLYPHS,
+        );
+
+        let rendered: String =
+            line.spans.iter().map(|s| s.content.clone()).collect();
+        assert_eq!(rendered, "* commit abc ~ 2 lines");
+    }
+
+    fn grouped_entries() -> Vec<Entry> {
+        ["tv:src/main.rs:1", "tv:src/app.rs:2", "nucleo:lib.rs:3"]
+            .into_iter()
+            .map(|raw| Entry::new(raw.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_build_result_rows_without_grouping() {
+        let entries = grouped_entries();
+        assert_eq!(
+            build_result_rows(&entries, None, ListDirection::TopToBottom),
+            vec![
+                ResultRow::Entry(0),
+                ResultRow::Entry(1),
+                ResultRow::Entry(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_result_rows_grouped() {
+        let entries = grouped_entries();
+        let rows = build_result_rows(
+            &entries,
+            Some((0, &Delimiter::Fixed(":".to_string()))),
+            ListDirection::TopToBottom,
+        );
+        assert_eq!(
+            rows,
+            vec![
+                ResultRow::Header("tv"),
+                ResultRow::Entry(0),
+                ResultRow::Entry(1),
+                ResultRow::Header("nucleo"),
+                ResultRow::Entry(2),
+            ]
+        );
+        assert_eq!(entry_row(&rows, 0), Some(1));
+        assert_eq!(entry_row(&rows, 2), Some(4));
+        assert_eq!(visible_entries(&rows, 3), 2);
+        assert_eq!(visible_entries(&rows, 4), 2);
+        assert_eq!(visible_entries(&rows, 10), 3);
+    }
+
+    #[test]
+    fn test_build_result_rows_grouped_bottom_to_top() {
+        let entries = grouped_entries();
+        assert_eq!(
+            build_result_rows(
+                &entries,
+                Some((0, &Delimiter::Fixed(":".to_string()))),
+                ListDirection::BottomToTop,
+            ),
+            vec![
+                ResultRow::Entry(0),
+                ResultRow::Entry(1),
+                ResultRow::Header("tv"),
+                ResultRow::Entry(2),
+                ResultRow::Header("nucleo"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_result_rows_repeated_group_gets_new_header() {
+        let entries: Vec<Entry> = ["a b", "c d", "a e"]
+            .into_iter()
+            .map(|raw| Entry::new(raw.to_string()))
+            .collect();
+        let rows = build_result_rows(
+            &entries,
+            Some((0, &Delimiter::default())),
+            ListDirection::TopToBottom,
+        );
+        assert_eq!(
+            rows.iter()
+                .filter(|row| matches!(row, ResultRow::Header(_)))
+                .count(),
+            3
+        );
+        // missing fields group together under an empty key
+        assert_eq!(group_key("abc", 3, &Delimiter::default()), "");
+    }
+
+    #[test]
+    fn test_build_icon_span_falls_back_to_channel_icon() {
+        let entry = Entry::new("main".to_string());
+        let icon = ChannelIcon {
+            glyph: '\u{e725}',
+            color: Some("#f05032".to_string()),
+        };
+
+        assert_eq!(build_icon_span(&entry, None, Color::Reset), None);
+        assert_eq!(
+            build_icon_span(&entry, Some(&icon), Color::Reset),
+            Some(Span::styled(
+                "\u{e725} ",
+                Style::default().fg(Color::from_str("#f05032").unwrap())
+            ))
+        );
+    }
+
     #[test]
     fn test_build_result_line_truncate_multibyte() {
         let entry = Entry::new("ジェイムス下地 - REDLINE Original Soundtrack - 06 - ROBOWORLD TV.mp3".to_string())
@@ -395,6 +763,7 @@ mod tests {
             Color::Reset,
             20, // small width
             None,
+            &UNICODE_GLYPHS,
         );
 
         // We expect the resulting string to contain the ellipsis char
@@ -555,4 +924,19 @@ mod tests {
             Span::styled("b", Style::default().fg(Color::Blue))
         );
     }
+
+    #[test]
+    fn test_build_entry_spans_ansi_combining_characters() {
+        // "é" is an "e" followed by a combining accent, a single grapheme for
+        // the matcher, so "ete" is highlighted as graphemes 5 to 7
+        let entry = Entry::new("\x1b[31mcafe\u{301}\x1b[0m ete".to_string())
+            .with_match_indices(&[3, 5, 6, 7]);
+        let spans =
+            build_entry_spans_ansi(&entry, 200, Color::Blue, Color::Yellow);
+
+        assert_eq!(spans[0], Span::raw("caf").fg(Color::Red));
+        assert_eq!(spans[1], Span::raw("e\u{301}").fg(Color::Yellow));
+        assert_eq!(spans[2].content, " ");
+        assert_eq!(spans[3], Span::raw("ete").reset().fg(Color::Yellow));
+    }
 }
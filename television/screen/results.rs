@@ -1,7 +1,14 @@
 use crate::{
-    channels::entry::Entry,
+    channels::{
+        entry::Entry,
+        prototypes::{ChannelIcon, Delimiter},
+    },
     config::ui::{BorderType, Padding},
-    screen::{colors::Colorscheme, layout::InputPosition, result_item},
+    screen::{
+        colors::Colorscheme, glyphs::Glyphs, layout::InputPosition,
+        result_item, title::fit_title,
+    },
+    utils::hashmaps::FxIndexSet,
 };
 use anyhow::Result;
 use ratatui::{
@@ -18,26 +25,56 @@ pub fn draw_results_list(
     f: &mut Frame,
     rect: Rect,
     entries: &[Entry],
-    selected_entries: &FxHashSet<Entry>,
+    selected_entries: &FxIndexSet<Entry>,
+    marked_entries: &FxHashSet<Entry>,
     relative_picker_state: &mut ListState,
     input_bar_position: InputPosition,
     colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
     results_panel_padding: &Padding,
     results_panel_border_type: &BorderType,
+    index_offset: Option<usize>,
+    channel_icon: Option<&ChannelIcon>,
+    group_by: Option<(usize, &Delimiter)>,
+    sort: Option<&str>,
+    marked_only: bool,
+    previous_channels: &[String],
+    current_channel: &str,
+    title: Option<&str>,
 ) -> Result<()> {
+    if rect.is_empty() {
+        return Ok(());
+    }
     let mut results_block = Block::default()
-        .title_top(Line::from(" Results ").alignment(Alignment::Center))
         .style(
             Style::default()
                 .bg(colorscheme.general.background.unwrap_or_default()),
         )
         .padding(RatatuiPadding::from(*results_panel_padding));
+    if let Some(title) = title {
+        let title = results_title(
+            title,
+            sort,
+            marked_only,
+            previous_channels,
+            current_channel,
+            glyphs,
+        );
+        results_block = results_block.title_top(
+            Line::from(fit_title(
+                &title,
+                usize::from(rect.width.saturating_sub(2)),
+                glyphs.ellipsis,
+            ))
+            .alignment(Alignment::Center),
+        );
+    }
     if let Some(border_type) =
         results_panel_border_type.to_ratatui_border_type()
     {
         results_block = results_block
             .borders(Borders::ALL)
-            .border_type(border_type)
+            .border_set(glyphs.border_set(border_type))
             .border_style(Style::default().fg(colorscheme.general.border_fg));
     }
 
@@ -47,6 +84,7 @@ pub fn draw_results_list(
     };
 
     let has_multi_select = !selected_entries.is_empty();
+    let has_marks = !marked_entries.is_empty();
 
     let results_list = result_item::build_results_list(
         results_block,
@@ -54,7 +92,11 @@ pub fn draw_results_list(
         relative_picker_state,
         list_direction,
         &colorscheme.results,
-        rect.width - 1, // right padding
+        rect.width.saturating_sub(1), // right padding
+        index_offset,
+        channel_icon,
+        group_by,
+        glyphs,
         |entry| {
             if has_multi_select {
                 Some(selected_entries.contains(entry))
@@ -62,8 +104,144 @@ pub fn draw_results_list(
                 None
             }
         },
+        |entry| has_marks.then(|| marked_entries.contains(entry)),
     );
 
+    // the list's selection points at rows, which include group headers
+    if group_by.is_some()
+        && let Some(selected) = relative_picker_state.selected()
+    {
+        let rows =
+            result_item::build_result_rows(entries, group_by, list_direction);
+        relative_picker_state.select(result_item::entry_row(&rows, selected));
+    }
+
     f.render_stateful_widget(results_list, rect, relative_picker_state);
     Ok(())
 }
+
+/// How many previously visited channels the results title lists before
+/// eliding the older ones.
+const MAX_PROVENANCE: usize = 3;
+
+/// The results panel title, e.g. `Results (files → text, sorted by size)`.
+fn results_title(
+    title: &str,
+    sort: Option<&str>,
+    marked_only: bool,
+    previous_channels: &[String],
+    current_channel: &str,
+    glyphs: &Glyphs,
+) -> String {
+    let mut parts = Vec::new();
+    if !previous_channels.is_empty() {
+        let skipped = previous_channels.len().saturating_sub(MAX_PROVENANCE);
+        let mut channels = previous_channels[skipped..]
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(current_channel))
+            .collect::<Vec<_>>()
+            .join(&format!(" {} ", glyphs.arrow));
+        if skipped > 0 {
+            channels.insert_str(
+                0,
+                &format!("{} {} ", glyphs.ellipsis, glyphs.arrow),
+            );
+        }
+        parts.push(channels);
+    }
+    if marked_only {
+        parts.push("marked".to_string());
+    }
+    if let Some(field) = sort {
+        parts.push(format!("sorted by {field}"));
+    }
+    if parts.is_empty() {
+        title.to_string()
+    } else {
+        format!("{title} ({})", parts.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::themes::Theme,
+        screen::glyphs::{ASCII_GLYPHS, Glyphs, UNICODE_GLYPHS},
+    };
+    use ratatui::{Terminal, backend::TestBackend};
+
+    /// The rendered results panel, one string per row.
+    fn render(glyphs: &Glyphs, border_type: BorderType) -> Vec<String> {
+        let entries = vec![
+            Entry::new("alpha".to_string()),
+            Entry::new("beta\nmore".to_string()),
+        ];
+        let selected = FxIndexSet::from_iter([entries[1].clone()]);
+        let icon = ChannelIcon {
+            glyph: '\u{f07b}',
+            color: None,
+        };
+        let colorscheme: Colorscheme = (&Theme::default()).into();
+        let mut state = ListState::default().with_selected(Some(0));
+        let mut terminal = Terminal::new(TestBackend::new(30, 4)).unwrap();
+        terminal
+            .draw(|f| {
+                draw_results_list(
+                    f,
+                    f.area(),
+                    &entries,
+                    &selected,
+                    &FxHashSet::default(),
+                    &mut state,
+                    InputPosition::Top,
+                    &colorscheme,
+                    glyphs,
+                    &Padding::uniform(0),
+                    &border_type,
+                    None,
+                    Some(&icon),
+                    None,
+                    None,
+                    false,
+                    &[],
+                    "files",
+                    Some("Results"),
+                )
+                .unwrap();
+            })
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_draw_results_list_in_ascii_mode() {
+        let rows = render(&ASCII_GLYPHS, BorderType::Rounded);
+
+        assert_eq!(
+            rows,
+            vec![
+                "+--------- Results ----------+",
+                "|>   alpha                   |",
+                "|  * beta ~ 2 lines          |",
+                "+----------------------------+",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_draw_results_list_with_unicode_glyphs() {
+        let rows = render(&UNICODE_GLYPHS, BorderType::Rounded);
+
+        assert!(rows[0].starts_with('╭'));
+        assert!(rows[2].contains("● \u{f07b} beta ⏎ 2 lines"));
+    }
+}
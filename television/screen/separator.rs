@@ -0,0 +1,34 @@
+//! The lines setting the panels apart in borderless mode.
+use crate::screen::{colors::Colorscheme, glyphs::Glyphs};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::Style,
+    widgets::{Block, BorderType, Borders},
+};
+
+/// Draw a line across `area`, horizontal when it's a single row tall and
+/// wider than that, vertical otherwise.
+pub fn draw_separator(
+    f: &mut Frame<'_>,
+    area: Rect,
+    colorscheme: &Colorscheme,
+    glyphs: &Glyphs,
+) {
+    let borders = if area.height == 1 && area.width > 1 {
+        Borders::TOP
+    } else {
+        Borders::LEFT
+    };
+    f.render_widget(
+        Block::default()
+            .borders(borders)
+            .border_set(glyphs.border_set(BorderType::Plain))
+            .border_style(Style::default().fg(colorscheme.general.border_fg))
+            .style(
+                Style::default()
+                    .bg(colorscheme.general.background.unwrap_or_default()),
+            ),
+        area,
+    );
+}
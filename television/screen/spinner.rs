@@ -1,6 +1,6 @@
 use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
 
-const FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+use crate::screen::glyphs::UNICODE_GLYPHS;
 
 /// A spinner widget.
 #[derive(Debug, Clone, Copy, PartialEq, Hash)]
@@ -28,7 +28,7 @@ impl Spinner {
 
 impl Default for Spinner {
     fn default() -> Spinner {
-        Spinner::new(FRAMES)
+        Spinner::new(UNICODE_GLYPHS.spinner)
     }
 }
 
@@ -1,5 +1,6 @@
 use crate::{
-    action::Action, draw::Ctx, television::Mode, utils::strings::SPACE,
+    action::Action, draw::Ctx, screen::glyphs::Glyphs, television::Mode,
+    utils::strings::SPACE,
 };
 use ratatui::{
     Frame,
@@ -13,6 +14,7 @@ use ratatui::{
 
 /// Draw the status bar at the bottom of the screen
 pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
+    let glyphs = Glyphs::new(ctx.config.ascii);
     // Split status bar into three sections
     let chunks = RatatuiLayout::default()
         .direction(Direction::Horizontal)
@@ -83,7 +85,7 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
         if selected_count > 0 {
             left_spans.extend([
                 Span::styled(
-                    " • ",
+                    format!(" {} ", glyphs.bullet),
                     Style::default().fg(ctx.colorscheme.general.border_fg),
                 ),
                 Span::styled(
@@ -109,7 +111,7 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
     // Helper to add a hint with consistent styling
     let mut add_hint = |description: &str, keybinding: &str| {
         if !hint_spans.is_empty() {
-            hint_spans.push(Span::raw(" • "));
+            hint_spans.push(Span::raw(format!(" {} ", glyphs.bullet)));
         }
         hint_spans.extend([
             Span::styled(
@@ -148,7 +150,9 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
             .input_map
             .get_key_for_action(&Action::TogglePreview);
         if let Some(k) = key {
-            let hint_text = if ctx.config.preview_panel_hidden {
+            let hint_text = if ctx.config.channel_preview_command.is_none() {
+                "No Preview"
+            } else if ctx.config.preview_panel_hidden {
                 "Show Preview"
             } else {
                 "Hide Preview"
@@ -163,8 +167,21 @@ pub fn draw_status_bar(f: &mut Frame<'_>, area: Rect, ctx: &Ctx) {
         add_hint("Help", &k.to_string());
     }
 
-    // Build middle section if we have hints
-    if !hint_spans.is_empty() {
+    // Notices take precedence over the hints while they're displayed
+    if let Some(notice) = &ctx.tv_state.notice {
+        middle_spans.push(Span::styled(
+            notice.clone(),
+            Style::default()
+                .fg(ctx.colorscheme.help.metadata_field_name_fg)
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else if let Some(error) = &ctx.tv_state.channel_state.source_error {
+        // only the last line fits in the status bar
+        middle_spans.push(Span::styled(
+            error.lines().last().unwrap_or_default().to_string(),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    } else if !hint_spans.is_empty() {
         middle_spans.extend([
             Span::styled(
                 "[Hint]",
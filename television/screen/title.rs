@@ -0,0 +1,132 @@
+use crate::television::Mode;
+use lazy_regex::{Lazy, Regex, regex};
+
+/// The input bar's title unless configured otherwise.
+pub const DEFAULT_INPUT_HEADER: &str = "{channel}";
+/// The results panel's title unless configured otherwise.
+pub const DEFAULT_RESULTS_TITLE: &str = "Results";
+
+static TITLE_VARIABLE_RE: &Lazy<Regex> =
+    regex!(r"\{(channel|cwd|version|mode)\}");
+
+/// The values the title templates of the panels can refer to.
+#[derive(Debug, Clone, Copy)]
+pub struct TitleVariables<'a> {
+    pub channel: &'a str,
+    pub cwd: &'a str,
+    pub version: &'a str,
+    pub mode: Mode,
+}
+
+impl TitleVariables<'_> {
+    /// Replace the `{channel}`, `{cwd}`, `{version}` and `{mode}` variables
+    /// of `template`, leaving any other brace alone.
+    ///
+    /// An empty template means no title at all.
+    pub fn expand(&self, template: &str) -> Option<String> {
+        if template.is_empty() {
+            return None;
+        }
+        Some(
+            TITLE_VARIABLE_RE
+                .replace_all(template, |caps: &regex::Captures| {
+                    match &caps[1] {
+                        "channel" => self.channel.to_string(),
+                        "cwd" => self.cwd.to_string(),
+                        "version" => self.version.to_string(),
+                        _ => self.mode.to_string(),
+                    }
+                })
+                .into_owned(),
+        )
+    }
+}
+
+/// Pad `title` with a space on each side, replacing its middle with
+/// `ellipsis` if that doesn't fit in `width` columns.
+pub fn fit_title(title: &str, width: usize, ellipsis: &str) -> String {
+    let max = width.saturating_sub(2);
+    let count = title.chars().count();
+    if count <= max {
+        return format!(" {title} ");
+    }
+    let ellipsis_width = ellipsis.chars().count();
+    if max == 0 || max < ellipsis_width {
+        return String::new();
+    }
+    let head = (max - ellipsis_width).div_ceil(2);
+    let tail = max - ellipsis_width - head;
+    let head = title.chars().take(head).collect::<String>();
+    let tail = title.chars().skip(count - tail).collect::<String>();
+    format!(" {head}{ellipsis}{tail} ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VARIABLES: TitleVariables = TitleVariables {
+        channel: "git-log",
+        cwd: "/home/user/projects/television",
+        version: "0.13.0",
+        mode: Mode::Channel,
+    };
+
+    #[test]
+    fn test_expand_title_variables() {
+        assert_eq!(
+            VARIABLES.expand("{channel} in {cwd}").as_deref(),
+            Some("git-log in /home/user/projects/television")
+        );
+        assert_eq!(
+            VARIABLES.expand("tv {version} ({mode})").as_deref(),
+            Some("tv 0.13.0 (Channel)")
+        );
+        assert_eq!(
+            TitleVariables {
+                mode: Mode::RemoteControl,
+                ..VARIABLES
+            }
+            .expand("{mode}")
+            .as_deref(),
+            Some("Remote Control")
+        );
+        assert_eq!(
+            VARIABLES.expand(DEFAULT_RESULTS_TITLE).as_deref(),
+            Some("Results")
+        );
+    }
+
+    #[test]
+    fn test_expand_title_leaves_other_braces() {
+        assert_eq!(
+            VARIABLES.expand("{} {1} {branch}").as_deref(),
+            Some("{} {1} {branch}")
+        );
+        // substituted values aren't expanded again
+        let variables = TitleVariables {
+            cwd: "/tmp/{channel}",
+            ..VARIABLES
+        };
+        assert_eq!(
+            variables.expand("{cwd}").as_deref(),
+            Some("/tmp/{channel}")
+        );
+    }
+
+    #[test]
+    fn test_empty_title_template_means_no_title() {
+        assert_eq!(VARIABLES.expand(""), None);
+    }
+
+    #[test]
+    fn test_fit_title() {
+        assert_eq!(fit_title("files", 20, "…"), " files ");
+        assert_eq!(fit_title("files", 7, "…"), " files ");
+        assert_eq!(fit_title("/home/user/projects", 10, "…"), " /hom…cts ");
+        assert_eq!(fit_title("héllo wörld", 8, "…"), " hél…ld ");
+        assert_eq!(fit_title("files", 2, "…"), "");
+        assert_eq!(fit_title("/home/user/projects", 10, "..."), " /ho...ts ");
+        assert_eq!(fit_title("files", 4, "..."), "");
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    cable::Cable, cli::PostProcessedCli, screen::layout::InputPosition,
+    television::Television,
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::{debug, warn};
+
+const SESSION_FILE_NAME: &str = "session.json";
+
+/// What tv was left with on exit, restored on the next run with `--resume`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Session {
+    /// The channel that was open
+    pub channel: String,
+    /// The query typed in that channel
+    #[serde(default)]
+    pub query: String,
+    #[serde(default)]
+    pub input_bar_position: InputPosition,
+    /// Whether the preview panel was toggled off
+    #[serde(default)]
+    pub preview_hidden: bool,
+}
+
+impl Session {
+    pub fn from_television(television: &Television) -> Self {
+        Self {
+            channel: television.current_channel(),
+            query: television.results_picker.input.value().to_string(),
+            input_bar_position: television.merged_config.input_bar_position,
+            preview_hidden: television.merged_config.preview_panel_hidden,
+        }
+    }
+
+    /// Load the last session from the data directory.
+    ///
+    /// A missing session is expected (e.g. on the first run) while a corrupt
+    /// one is logged and ignored.
+    pub fn load(data_dir: &Path) -> Option<Self> {
+        let path = data_dir.join(SESSION_FILE_NAME);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                debug!("No session to restore at {}: {}", path.display(), e);
+                return None;
+            }
+        };
+        serde_json::from_str(&content)
+            .inspect_err(|e| {
+                warn!(
+                    "Ignoring corrupt session file {}: {}",
+                    path.display(),
+                    e
+                );
+            })
+            .ok()
+    }
+
+    /// Persist the session to the data directory.
+    ///
+    /// The file is written next to its final location then renamed over it,
+    /// so that a crash halfway through can't leave a truncated session.
+    pub fn save(&self, data_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(data_dir)?;
+        let path = data_dir.join(SESSION_FILE_NAME);
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+
+    /// Start tv where the session left off, unless a channel, a query or a
+    /// source was given on the command line.
+    ///
+    /// Returns whether the session was restored, which it isn't if its
+    /// channel doesn't exist anymore.
+    pub fn restore(self, cli: &mut PostProcessedCli, cable: &Cable) -> bool {
+        let channel_cli = &mut cli.channel;
+        if channel_cli.channel.is_some()
+            || channel_cli.input.is_some()
+            || channel_cli.source_command.is_some()
+            || channel_cli.autocomplete_prompt.is_some()
+        {
+            debug!("Explicit channel or input given, not restoring session");
+            return false;
+        }
+        if !cable.has_channel(&self.channel) {
+            warn!(
+                "Not restoring session: channel `{}` doesn't exist anymore",
+                self.channel
+            );
+            return false;
+        }
+        channel_cli.channel = Some(self.channel);
+        if !self.query.is_empty() {
+            channel_cli.input = Some(self.query);
+        }
+        channel_cli
+            .input_position
+            .get_or_insert(self.input_bar_position);
+        if !channel_cli.hide_preview && !channel_cli.show_preview {
+            channel_cli.hide_preview = self.preview_hidden;
+            channel_cli.show_preview = !self.preview_hidden;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::prototypes::ChannelPrototype;
+    use tempfile::tempdir;
+
+    fn session() -> Session {
+        Session {
+            channel: "files".to_string(),
+            query: "main.rs".to_string(),
+            input_bar_position: InputPosition::Bottom,
+            preview_hidden: true,
+        }
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let dir = tempdir().unwrap();
+        assert_eq!(Session::load(dir.path()), None);
+
+        session().save(dir.path()).unwrap();
+        assert_eq!(Session::load(dir.path()), Some(session()));
+        // the temporary file was renamed over the session
+        assert!(!dir.path().join("session.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_corrupt_session_is_ignored() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join(SESSION_FILE_NAME), "{\"chan").unwrap();
+        assert_eq!(Session::load(dir.path()), None);
+    }
+
+    #[test]
+    fn test_restore_session() {
+        let cable =
+            Cable::from_prototypes(vec![ChannelPrototype::new("files", "fd")]);
+
+        let mut cli = PostProcessedCli::default();
+        assert!(session().restore(&mut cli, &cable));
+        assert_eq!(cli.channel.channel.as_deref(), Some("files"));
+        assert_eq!(cli.channel.input.as_deref(), Some("main.rs"));
+        assert_eq!(cli.channel.input_position, Some(InputPosition::Bottom));
+        assert!(cli.channel.hide_preview);
+
+        // the command line wins
+        let mut cli = PostProcessedCli::default();
+        cli.channel.input = Some("lib.rs".to_string());
+        assert!(!session().restore(&mut cli, &cable));
+        assert_eq!(cli.channel.channel, None);
+
+        // stale sessions are ignored
+        let mut cli = PostProcessedCli::default();
+        let stale = Session {
+            channel: "gone".to_string(),
+            ..session()
+        };
+        assert!(!stale.restore(&mut cli, &cable));
+        assert_eq!(cli.channel.channel, None);
+    }
+}
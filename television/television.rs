@@ -1,41 +1,66 @@
 use crate::{
     action::Action,
-    cable::Cable,
+    cable::{Cable, load_channel_file},
     channels::{
-        channel::ChannelKind as CableChannel,
+        channel::{ChannelKind as CableChannel, ResultsSort},
         entry::Entry,
-        prototypes::{ChannelPrototype, CommandSpec, Template},
+        icons::EntryIcons,
+        prototypes::{ActionSpec, ChannelPrototype, CommandSpec, Template},
         remote_control::{CableEntry, RemoteControl},
     },
+    cli::args::{OutputOrder, SelectMode},
     config::{
         Theme,
         layers::{ConfigLayers, MergedConfig},
     },
     draw::{ChannelState, Ctx, TvState},
     errors::os_error_exit,
+    info::InfoWriter,
     input::convert_action_to_input_request,
+    logging::log_generation,
     picker::{Movement, Picker},
     previewer::{
-        Config as PreviewerConfig, Preview, Previewer,
-        Request as PreviewRequest, Ticket, state::PreviewState,
+        Config as PreviewerConfig, Preview, PreviewFn, Previewer,
+        Request as PreviewRequest, Ticket,
+        directory::DirectoryPreview,
+        plain_text,
+        state::{PreviewState, half_page},
     },
     render::UiState,
     screen::{
         colors::Colorscheme,
+        confirm::Confirmation,
+        detail::EntryDetail,
+        glyphs::Glyphs,
+        help_overlay::{HelpOverlay, help_overlay_height, help_overlay_lines},
+        help_panel::clamp_help_scroll,
+        inline_preview::first_preview_line,
         layout::{InputPosition, Orientation},
+        logs::{LogPanelState, log_line_count, log_panel_height, scroll_step},
+        preview_focus::PreviewFocus,
+        result_item::{build_result_rows, visible_entries},
         spinner::{Spinner, SpinnerState},
     },
     utils::{
         clipboard::CLIPBOARD,
+        command::CommandOutput,
+        hashmaps::FxIndexSet,
+        input::{Input, InputRequest},
         metadata::AppMetadata,
-        strings::{EMPTY_STRING, SPACE},
+        strings::{EMPTY_STRING, prev_char_boundary},
     },
 };
 use anyhow::Result;
-use ratatui::layout::Rect;
-use rustc_hash::FxHashSet;
+use lazy_regex::Regex;
+use ratatui::{layout::Rect, text::Text, widgets::ListDirection};
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Display, sync::Arc};
+use std::{
+    fmt::Display,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::sync::mpsc::{
     UnboundedReceiver, UnboundedSender, unbounded_channel,
 };
@@ -62,6 +87,122 @@ pub enum MatchingMode {
     Fuzzy,
 }
 
+/// The parts of the visible state that may change without any action, see
+/// `Television::render_state`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct RenderState {
+    total_count: u32,
+    result_count: u32,
+    running: bool,
+    source_error: bool,
+    notice: bool,
+    /// How many records were logged, while the log panel is open.
+    logs: Option<u64>,
+}
+
+/// How long notices stay on screen.
+const NOTICE_DURATION: Duration = Duration::from_secs(2);
+
+/// How many channels the channel history keeps in either direction.
+const MAX_CHANNEL_STACK: usize = 32;
+
+/// A channel in the channel history along with the state it was left in.
+#[derive(Debug, Clone)]
+struct ChannelFrame {
+    prototype: ChannelPrototype,
+    pattern: String,
+    /// The selected entry and its index.
+    selected: Option<(String, usize)>,
+}
+
+/// Push a frame onto one of the channel history stacks, dropping the oldest
+/// one if it's full.
+fn push_bounded(stack: &mut Vec<ChannelFrame>, frame: ChannelFrame) {
+    if stack.len() == MAX_CHANNEL_STACK {
+        stack.remove(0);
+    }
+    stack.push(frame);
+}
+
+/// The notice shown once `count` entries were copied to the clipboard.
+fn entries_copied(count: usize) -> String {
+    format!(
+        "Copied {count} entr{} to the clipboard",
+        if count == 1 { "y" } else { "ies" }
+    )
+}
+
+/// Where to put the cursor once the results settled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PendingSelection {
+    /// The entry recorded in the channel history and the index it was at.
+    Restore(String, usize),
+    /// The first entry matching the string given to `--select`.
+    Matching(String, SelectMode),
+    /// The last entry.
+    Last,
+    /// The entry highlighted before the query changed, kept highlighted if
+    /// it's still among the visible results.
+    Keep(String),
+}
+
+impl PendingSelection {
+    /// The initial cursor placement asked for on the command line.
+    fn from_config(config: &MergedConfig) -> Option<Self> {
+        if config.select_last {
+            return Some(Self::Last);
+        }
+        config
+            .select
+            .clone()
+            .map(|text| Self::Matching(text, config.select_mode))
+    }
+}
+
+/// Delays running the matcher until the user stops typing for a while.
+///
+/// Input actions schedule the pattern, which is then applied either on the
+/// first tick past the debounce window or right away when any other action
+/// (navigation, confirmation...) needs up-to-date results.
+#[derive(Debug, Clone)]
+struct PatternDebounce {
+    delay: Duration,
+    pending: Option<(String, Instant)>,
+}
+
+impl PatternDebounce {
+    fn new(delay_ms: u64) -> Self {
+        Self {
+            delay: Duration::from_millis(delay_ms),
+            pending: None,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        !self.delay.is_zero()
+    }
+
+    /// Record a pattern change, restarting the debounce window.
+    fn schedule(&mut self, pattern: String, now: Instant) {
+        self.pending = Some((pattern, now));
+    }
+
+    /// Take the pending pattern if its debounce window has elapsed at `now`.
+    fn poll(&mut self, now: Instant) -> Option<String> {
+        match &self.pending {
+            Some((_, since)) if now.duration_since(*since) >= self.delay => {
+                self.flush()
+            }
+            _ => None,
+        }
+    }
+
+    /// Take the pending pattern, regardless of timing.
+    fn flush(&mut self) -> Option<String> {
+        self.pending.take().map(|(pattern, _)| pattern)
+    }
+}
+
 pub struct Television {
     action_tx: UnboundedSender<Action>,
     pub layered_config: ConfigLayers,
@@ -83,6 +224,68 @@ pub struct Television {
     pub colorscheme: Arc<Colorscheme>,
     pub ticks: u64,
     pub ui_state: UiState,
+    /// Number of external actions currently running in the background.
+    pub running_actions: usize,
+    /// An external action waiting for the user's confirmation.
+    pub confirmation: Option<Confirmation>,
+    /// The full selected entry, shown in a popup.
+    pub entry_detail: Option<EntryDetail>,
+    /// Preview visibility chosen manually by the user (`toggle_preview`).
+    ///
+    /// This takes precedence over any channel-level UI configuration for the
+    /// rest of the session.
+    preview_hidden_override: Option<bool>,
+    /// Selections made in previously visited channels, keyed by channel
+    /// name along with the source command that produced them.
+    carried_selections: FxHashMap<String, (CommandSpec, FxIndexSet<Entry>)>,
+    /// The channels visited before the current one, most recent last.
+    channel_stack: Vec<ChannelFrame>,
+    /// The channels left with `channel_back`, most recent last.
+    channel_forward: Vec<ChannelFrame>,
+    /// Where to put the cursor once the channel is done loading, either the
+    /// entry of a channel restored from the history or the one asked for on
+    /// the command line.
+    pending_selection: Option<PendingSelection>,
+    /// The entry whose preview is copied to the clipboard once it's ready.
+    pending_preview_copy: Option<String>,
+    /// Entries marked during this session, which unlike selections survive
+    /// query changes and source reloads.
+    marked_entries: FxHashSet<Entry>,
+    /// Whether the results are narrowed down to the marked entries.
+    marked_only: bool,
+    /// The query typed before the selected entry was copied into the input
+    /// to be edited inline, restored if the edit is cancelled.
+    pub inline_edit: Option<String>,
+    pattern_debounce: PatternDebounce,
+    /// When the user last moved the selection around, used to hold off
+    /// previews while navigating quickly.
+    navigated_at: Option<Instant>,
+    /// Which way the selection last moved, which is where previews are
+    /// prefetched.
+    travel: Movement,
+    /// Index of the channel sort field the results are sorted by, if any.
+    sort_index: Option<usize>,
+    /// A short-lived message for the user and when it was emitted.
+    notice: Option<(String, Instant)>,
+    /// The log panel, when open.
+    pub log_panel: Option<LogPanelState>,
+    /// The preview pane's search and scroll state while it has the focus.
+    pub preview_focus: Option<PreviewFocus>,
+    /// How far the help panel is scrolled.
+    help_scroll: u16,
+    /// The full-screen help overlay, when open.
+    pub help_overlay: Option<HelpOverlay>,
+    /// Whether something visible changed since the last render.
+    dirty: bool,
+    /// What the visible state looked like when last checked.
+    render_state: RenderState,
+    /// Whether the terminal has the focus (previews are put on hold
+    /// otherwise).
+    focused: bool,
+    /// Generates previews in-process instead of the preview command.
+    preview_fn: Option<PreviewFn>,
+    /// Keeps the `--info-file` up to date, if any.
+    info_writer: Option<InfoWriter>,
 }
 
 impl Television {
@@ -115,27 +318,32 @@ impl Television {
         };
 
         // previewer
-        let preview_handles = merged_config
-            .channel_preview_command
-            .as_ref()
-            .map(|command| {
-                Self::setup_previewer(
-                    command,
-                    merged_config.channel_preview_cached,
-                    merged_config.channel_preview_offset.clone(),
-                    merged_config.preview_panel_header.clone(),
-                    merged_config.preview_panel_footer.clone(),
-                )
-            });
+        let preview_handles = Self::setup_previewer(&merged_config, None);
 
         let mut channel = CableChannel::new(
             merged_config.channel_source_command.clone(),
-            merged_config.channel_source_entry_delimiter,
+            merged_config.channel_source_record_separator.clone(),
             merged_config.channel_source_ansi,
             merged_config.channel_source_display,
             merged_config.channel_source_output,
             merged_config.channel_preview_command.is_some(),
+            merged_config.max_entries,
+        );
+        channel.reverse_order(merged_config.channel_source_reverse);
+        channel.file_walk_options(merged_config.channel_source_file_walk);
+        channel.dynamic_source(
+            merged_config.channel_source_dynamic,
+            merged_config.channel_source_fuzzy_filter,
+        );
+        channel.entry_icons(EntryIcons::new(
+            merged_config.channel_icon_mode,
+            merged_config.channel_source_delimiter.clone(),
+        ));
+        channel.output_line_numbers(
+            merged_config.channel_source_output_line.clone(),
         );
+        channel
+            .entry_delimiter(merged_config.channel_source_delimiter.clone());
         let app_metadata = AppMetadata::new(
             env!("CARGO_PKG_VERSION").to_string(),
             std::env::current_dir()
@@ -150,15 +358,19 @@ impl Television {
                 error!("Failed to apply theme overrides: {}", e);
                 base_theme
             });
-        let colorscheme = (&theme).into();
+        let colorscheme: Colorscheme = (&theme).into();
+        let colorscheme =
+            colorscheme.with_color_mode(merged_config.color_mode);
 
-        let pattern = Television::preprocess_pattern(
-            matching_mode,
-            &merged_config.input.unwrap_or(EMPTY_STRING.to_string()),
-        );
+        let input = merged_config.input.unwrap_or(EMPTY_STRING.to_string());
+        let pattern = if merged_config.channel_source_dynamic {
+            input
+        } else {
+            Television::preprocess_pattern(matching_mode, &input)
+        };
 
         channel.find(&pattern);
-        let spinner = Spinner::default();
+        let spinner = Spinner::new(Glyphs::new(merged_config.ascii).spinner);
 
         let preview_state = PreviewState::new(
             channel.supports_preview(),
@@ -175,6 +387,11 @@ impl Television {
             ))
         };
 
+        let pattern_debounce =
+            PatternDebounce::new(merged_config.matcher_debounce_ms);
+        let info_writer = merged_config.info_file.clone().map(InfoWriter::new);
+        let pending_selection = PendingSelection::from_config(&merged_config);
+
         Self {
             action_tx,
             merged_config: layered_config.merge(),
@@ -195,45 +412,154 @@ impl Television {
             colorscheme: Arc::new(colorscheme),
             ticks: 0,
             ui_state: UiState::default(),
+            running_actions: 0,
+            confirmation: None,
+            entry_detail: None,
+            preview_hidden_override: None,
+            carried_selections: FxHashMap::default(),
+            channel_stack: Vec::new(),
+            channel_forward: Vec::new(),
+            pending_selection,
+            pending_preview_copy: None,
+            marked_entries: FxHashSet::default(),
+            marked_only: false,
+            inline_edit: None,
+            pattern_debounce,
+            navigated_at: None,
+            travel: Movement::Next,
+            sort_index: None,
+            notice: None,
+            log_panel: None,
+            preview_focus: None,
+            help_scroll: 0,
+            help_overlay: None,
+            dirty: true,
+            render_state: RenderState::default(),
+            focused: true,
+            preview_fn: None,
+            info_writer,
         }
     }
 
+    /// Start the previewer of the channel described by `config`, if it has
+    /// a preview command.
     fn setup_previewer(
-        command: &CommandSpec,
-        cached: bool,
-        offset_expr: Option<Template>,
-        title_template: Option<Template>,
-        footer_template: Option<Template>,
-    ) -> (UnboundedSender<PreviewRequest>, UnboundedReceiver<Preview>) {
+        config: &MergedConfig,
+        preview_fn: Option<PreviewFn>,
+    ) -> Option<(UnboundedSender<PreviewRequest>, UnboundedReceiver<Preview>)>
+    {
+        let command = config.channel_preview_command.as_ref()?;
         let (preview_requests_tx, preview_requests_rx) = unbounded_channel();
         let (preview_results_tx, preview_results_rx) = unbounded_channel();
         let previewer = Previewer::new(
             command,
-            offset_expr,
-            title_template,
-            footer_template,
-            // NOTE: this could be a per-channel configuration option in the future
-            PreviewerConfig::default(),
+            config.channel_preview_offset.clone(),
+            config.preview_panel_header.clone(),
+            config.preview_panel_footer.clone(),
+            Self::directory_preview(config),
+            PreviewerConfig::default()
+                .with_output_limits(config.channel_preview_limits),
             preview_requests_rx,
             preview_requests_tx.clone(),
             preview_results_tx,
-            cached,
-        );
+            config.channel_preview_cached,
+            config.preview_cache_size,
+        )
+        .with_preview_fn(preview_fn);
         tokio::spawn(async move { previewer.run().await });
-        (preview_requests_tx, preview_results_rx)
+        Some((preview_requests_tx, preview_results_rx))
+    }
+
+    /// Generate previews with `preview_fn` rather than with the channel's
+    /// preview command, restarting the previewer.
+    pub fn set_preview_fn(&mut self, preview_fn: PreviewFn) {
+        if let Some((sender, _)) = &self.preview_handles {
+            let _ = sender.send(PreviewRequest::Shutdown);
+        }
+        self.preview_fn = Some(preview_fn);
+        self.preview_handles = Self::setup_previewer(
+            &self.merged_config,
+            self.preview_fn.clone(),
+        );
+    }
+
+    /// How the channel's previewer shows directories. Channels with an icon
+    /// are assumed to be used with a nerd font, unless in ASCII mode.
+    fn directory_preview(config: &MergedConfig) -> DirectoryPreview {
+        DirectoryPreview {
+            command: config.preview_panel_directory_command.clone(),
+            icons: config.channel_icon.is_some() && !config.ascii,
+        }
     }
 
     pub fn update_ui_state(&mut self, ui_state: UiState) {
+        let previous = self.ui_state.layout;
         self.ui_state = ui_state;
+        self.dirty = true;
+
+        // the terminal was resized (or panels toggled): make sure the
+        // selection and the preview still fit
+        let layout = self.ui_state.layout;
+        if layout.results.height != previous.results.height {
+            self.results_picker.set_height(
+                self.channel.result_count() as usize,
+                self.results_area_height(),
+            );
+        }
+        if layout.remote_control.map(|r| r.height)
+            != previous.remote_control.map(|r| r.height)
+        {
+            let total = self
+                .remote_control
+                .as_ref()
+                .map_or(0, RemoteControl::result_count);
+            self.rc_picker
+                .set_height(total as usize, self.rc_area_height());
+        }
+        if layout.preview_window.map(|r| r.height)
+            != previous.preview_window.map(|r| r.height)
+        {
+            let height = self.preview_content_height();
+            self.preview_state.clamp_scroll(height);
+        }
+    }
+
+    /// Snapshot the parts of the visible state that change on their own
+    /// (i.e. without any action being handled).
+    fn render_state(&self) -> RenderState {
+        RenderState {
+            total_count: self.channel.total_count(),
+            result_count: self.channel.result_count(),
+            running: self.channel.running(),
+            source_error: self.channel.source_error().is_some(),
+            notice: self
+                .notice
+                .as_ref()
+                .is_some_and(|(_, at)| at.elapsed() < NOTICE_DURATION),
+            logs: self.log_panel.as_ref().map(|_| log_generation()),
+        }
     }
 
     pub fn dump_context(&self) -> Ctx {
         let channel_state = ChannelState::new(
             self.current_channel(),
             self.channel.selected_entries().clone(),
+            self.marked_entries.clone(),
+            self.marked_only,
             self.channel.total_count(),
-            self.channel.running(),
+            self.channel.truncated(),
+            self.channel.loading() || self.running_actions > 0,
+            self.channel.matching(),
+            self.channel.loading(),
+            self.channel.source_error(),
+            self.sort_index.map(|i| {
+                self.merged_config.channel_source_sort_fields[i]
+                    .name
+                    .clone()
+            }),
             self.channel.current_command().to_string(),
+            self.channel_provenance(),
+            self.channel_history_position(),
         );
         let tv_state = TvState::new(
             self.mode,
@@ -249,6 +575,26 @@ impl Television {
                     .as_ref()
                     .map_or(0, |r| r.height as usize),
             ),
+            self.confirmation.clone(),
+            self.entry_detail.clone(),
+            self.notice
+                .as_ref()
+                .filter(|(_, at)| at.elapsed() < NOTICE_DURATION)
+                .map(|(message, _)| message.clone()),
+            self.log_panel.clone(),
+            self.preview_focus.clone(),
+            self.help_scroll,
+            self.help_overlay,
+            self.remote_control
+                .as_ref()
+                .map_or(0, RemoteControl::total_count),
+            self.ui_state
+                .layout
+                .inline_preview
+                .filter(|_| !self.preview_state.stale)
+                .and_then(|_| {
+                    first_preview_line(&self.preview_state.preview.content)
+                }),
         );
 
         Ctx::new(
@@ -266,11 +612,17 @@ impl Television {
     }
 
     pub fn change_channel(&mut self, channel_prototype: &ChannelPrototype) {
+        self.stash_selections();
+        self.pending_selection = None;
         // shutdown the current channel and reset state
         self.preview_state.reset();
+        self.preview_focus = None;
         self.reset_picker_selection();
         self.reset_picker_input();
         self.current_pattern = EMPTY_STRING.to_string();
+        self.sort_index = None;
+        self.marked_only = false;
+        self.inline_edit = None;
         self.channel.shutdown();
         if let Some((sender, _)) = &self.preview_handles {
             sender
@@ -282,6 +634,16 @@ impl Television {
         self.layered_config
             .update_channel(channel_prototype.clone());
         self.merged_config = self.layered_config.merge();
+        // a manual preview toggle wins over the new channel's ui configuration
+        if let Some(hidden) = self.preview_hidden_override
+            && !self.merged_config.preview_panel_disabled
+        {
+            self.merged_config.preview_panel_hidden = hidden;
+        }
+        // the new channel may place the input bar elsewhere
+        self.results_picker.set_inverted(
+            self.merged_config.input_bar_position == InputPosition::Bottom,
+        );
         // merge channel shortcuts if remote control is enabled
         if let Some(rc) = &mut self.remote_control {
             self.merged_config.input_map.merge_globals_with(
@@ -289,34 +651,240 @@ impl Television {
             );
         }
 
-        self.preview_handles =
-            self.merged_config.channel_preview_command.as_ref().map(
-                |command| {
-                    Self::setup_previewer(
-                        command,
-                        self.merged_config.channel_preview_cached,
-                        self.merged_config.channel_preview_offset.clone(),
-                        self.merged_config.preview_panel_header.clone(),
-                        self.merged_config.preview_panel_footer.clone(),
-                    )
-                },
-            );
+        self.preview_handles = Self::setup_previewer(
+            &self.merged_config,
+            self.preview_fn.clone(),
+        );
         // Set preview state enabled based on both channel capability and UI configuration
-        self.preview_state.enabled = channel_prototype.preview.is_some()
-            && !self.merged_config.preview_panel_hidden;
+        self.preview_state.enabled =
+            self.merged_config.channel_preview_command.is_some()
+                && !self.merged_config.preview_panel_hidden;
         self.channel = CableChannel::new(
             self.merged_config.channel_source_command.clone(),
-            self.merged_config.channel_source_entry_delimiter,
+            self.merged_config.channel_source_record_separator.clone(),
             self.merged_config.channel_source_ansi,
             self.merged_config.channel_source_display.clone(),
             self.merged_config.channel_source_output.clone(),
             self.merged_config.channel_preview_command.is_some(),
+            self.merged_config.max_entries,
         );
+        self.channel
+            .reverse_order(self.merged_config.channel_source_reverse);
+        self.channel
+            .file_walk_options(self.merged_config.channel_source_file_walk);
+        self.channel.dynamic_source(
+            self.merged_config.channel_source_dynamic,
+            self.merged_config.channel_source_fuzzy_filter,
+        );
+        self.channel.entry_icons(EntryIcons::new(
+            self.merged_config.channel_icon_mode,
+            self.merged_config.channel_source_delimiter.clone(),
+        ));
+        self.channel.output_line_numbers(
+            self.merged_config.channel_source_output_line.clone(),
+        );
+        self.channel.entry_delimiter(
+            self.merged_config.channel_source_delimiter.clone(),
+        );
+        self.restore_selections();
         self.channel.load();
     }
 
+    /// Switch to another channel, remembering the current one (its query
+    /// and selected entry) so `channel_back` can return to it.
+    pub fn push_channel(&mut self, channel_prototype: &ChannelPrototype) {
+        let frame = self.channel_frame();
+        push_bounded(&mut self.channel_stack, frame);
+        self.channel_forward.clear();
+        self.change_channel(channel_prototype);
+    }
+
+    /// Return to the previously visited channel.
+    pub fn channel_back(&mut self) -> Result<()> {
+        let Some(frame) = self.channel_stack.pop() else {
+            self.action_tx
+                .send(Action::Error("No previous channel".to_string()))?;
+            return Ok(());
+        };
+        let current = self.channel_frame();
+        push_bounded(&mut self.channel_forward, current);
+        self.restore_channel_frame(frame);
+        Ok(())
+    }
+
+    /// Return to the channel left with `channel_back`.
+    pub fn channel_forward(&mut self) -> Result<()> {
+        let Some(frame) = self.channel_forward.pop() else {
+            self.action_tx
+                .send(Action::Error("No next channel".to_string()))?;
+            return Ok(());
+        };
+        let current = self.channel_frame();
+        push_bounded(&mut self.channel_stack, current);
+        self.restore_channel_frame(frame);
+        Ok(())
+    }
+
+    /// The current channel's state, as recorded in the channel history.
+    fn channel_frame(&self) -> ChannelFrame {
+        ChannelFrame {
+            prototype: self.layered_config.channel().clone(),
+            pattern: self.results_picker.input.value().to_string(),
+            selected: self.currently_selected.as_ref().map(|entry| {
+                (
+                    entry.raw.clone(),
+                    self.results_picker.selected().unwrap_or(0),
+                )
+            }),
+        }
+    }
+
+    /// Re-create a channel from the history and replay its query. Its
+    /// selections are carried over by `change_channel` while the selected
+    /// entry is looked up once the source is done loading.
+    fn restore_channel_frame(&mut self, frame: ChannelFrame) {
+        self.mode = Mode::Channel;
+        self.change_channel(&frame.prototype);
+        if !frame.pattern.is_empty() {
+            self.set_pattern(&frame.pattern);
+        }
+        self.pending_selection = frame
+            .selected
+            .map(|(raw, index)| PendingSelection::Restore(raw, index));
+    }
+
+    /// Move the cursor to the pending selection once the results settled.
+    ///
+    /// The entry recorded in the channel history is selected at the same
+    /// index if it's still there, wherever it moved to otherwise (among the
+    /// first `MAX_RESULTS`). The cursor stays on the first entry when the
+    /// entry is gone, nothing matches `--select` or the entry highlighted
+    /// before the query changed went out of sight.
+    fn restore_pending_selection(&mut self) {
+        let Some(pending) = self.pending_selection.clone() else {
+            return;
+        };
+        // fetching results also brings the matcher's status up to date
+        let at_index = match &pending {
+            PendingSelection::Restore(raw, index) => {
+                u32::try_from(*index).ok().is_some_and(|offset| {
+                    self.channel
+                        .results(1, offset)
+                        .first()
+                        .is_some_and(|entry| &entry.raw == raw)
+                })
+            }
+            PendingSelection::Matching(..)
+            | PendingSelection::Last
+            | PendingSelection::Keep(_) => {
+                self.channel.results(1, 0);
+                false
+            }
+        };
+        if self.channel.running() {
+            return;
+        }
+        self.pending_selection = None;
+        let count = self.channel.result_count();
+        let position = match pending {
+            PendingSelection::Restore(_, index) if at_index => Some(index),
+            PendingSelection::Restore(raw, _) => {
+                self.result_position(|entry| entry.raw == raw)
+            }
+            PendingSelection::Matching(text, mode) => {
+                let position = self.result_position(|entry| match mode {
+                    SelectMode::Exact => entry.raw == text,
+                    SelectMode::Contains => entry.raw.contains(&text),
+                });
+                if position.is_none() {
+                    debug!("No entry to select matches {:?}", text);
+                }
+                position
+            }
+            PendingSelection::Last => (count as usize).checked_sub(1),
+            PendingSelection::Keep(raw) => {
+                let visible = u32::try_from(self.results_area_height())
+                    .unwrap_or(u32::MAX);
+                self.channel
+                    .results(visible, 0)
+                    .iter()
+                    .position(|entry| entry.raw == raw)
+            }
+        };
+        self.results_picker.reset_selection();
+        self.results_picker
+            .select_index(position.unwrap_or(0), count as usize);
+    }
+
+    /// The position of the first result `is_match` accepts, among the first
+    /// `MAX_RESULTS`.
+    fn result_position(
+        &mut self,
+        is_match: impl Fn(&Entry) -> bool,
+    ) -> Option<usize> {
+        let count = self.channel.result_count();
+        self.channel.results(count, 0).iter().position(is_match)
+    }
+
+    /// Names of the channels visited before the current one, oldest first.
+    pub fn channel_provenance(&self) -> Vec<String> {
+        self.channel_stack
+            .iter()
+            .map(|frame| frame.prototype.metadata.name.clone())
+            .collect()
+    }
+
+    /// The current channel's (1-based) position in the channel history and
+    /// the history's length.
+    pub fn channel_history_position(&self) -> (usize, usize) {
+        let position = self.channel_stack.len() + 1;
+        (position, position + self.channel_forward.len())
+    }
+
+    /// Keep the current channel's selections around so they can be restored
+    /// when coming back to it.
+    fn stash_selections(&mut self) {
+        let channel_name = self.current_channel();
+        let selected = self.channel.selected_entries();
+        if selected.is_empty() {
+            self.carried_selections.remove(&channel_name);
+        } else {
+            self.carried_selections.insert(
+                channel_name,
+                (
+                    self.merged_config.channel_source_command.clone(),
+                    selected.clone(),
+                ),
+            );
+        }
+    }
+
+    /// Restore selections previously made in the current channel, provided
+    /// its source still produces the same entries.
+    fn restore_selections(&mut self) {
+        if let Some((source_command, entries)) =
+            self.carried_selections.remove(&self.current_channel())
+        {
+            if source_command != self.merged_config.channel_source_command {
+                debug!("Source command changed, dropping carried selections");
+                return;
+            }
+            for entry in &entries {
+                self.channel.toggle_selection(entry);
+            }
+        }
+    }
+
     pub fn find(&mut self, pattern: &str) {
+        if self.mode == Mode::Channel {
+            // a new query makes the entry to restore irrelevant
+            self.pending_selection = None;
+        }
         match self.mode {
+            // dynamic sources get the pattern as typed
+            Mode::Channel if self.merged_config.channel_source_dynamic => {
+                self.channel.find(pattern);
+            }
             Mode::Channel => {
                 let processed_pattern =
                     Self::preprocess_pattern(self.matching_mode, pattern);
@@ -364,6 +932,21 @@ impl Television {
             .and_then(|entry| entry)
     }
 
+    /// Reload a channel from its file, e.g. after it was edited.
+    pub fn reload_channel_file(&mut self, path: &Path) -> Result<()> {
+        let prototype = load_channel_file(path)?;
+        if let Some(remote_control) = &mut self.remote_control {
+            let mut cable = remote_control.cable_channels.clone();
+            cable.0.insert(prototype.metadata.name.clone(), prototype);
+            *remote_control = RemoteControl::new(
+                cable,
+                self.merged_config.remote_sort_alphabetically,
+            );
+            remote_control.find(self.rc_picker.input.value());
+        }
+        Ok(())
+    }
+
     pub fn get_selected_cable_entry(&mut self) -> Option<CableEntry> {
         if self
             .remote_control
@@ -389,27 +972,60 @@ impl Television {
         }
     }
 
+    /// The entries to act upon, in the order set by `--output-order`.
     #[must_use]
-    pub fn get_selected_entries(&mut self) -> Option<FxHashSet<Entry>> {
+    pub fn get_selected_entries(&mut self) -> Option<FxIndexSet<Entry>> {
+        // while only showing the marked entries, those are what's selected,
+        // and they are listed in the results
+        if self.marked_only && !self.marked_entries.is_empty() {
+            let marked = self.marked_entries.iter().cloned().collect();
+            return Some(self.in_list_order(marked));
+        }
         // if nothing is selected, return the currently hovered entry
         if self.channel.selected_entries().is_empty() {
             return self
                 .get_selected_entry()
-                .map(|e| FxHashSet::from_iter([e]));
+                .map(|e| FxIndexSet::from_iter([e]));
         }
-        Some(self.channel.selected_entries().clone())
+        let selected = self.channel.selected_entries().clone();
+        Some(match self.merged_config.output_order {
+            OutputOrder::Selection => selected,
+            OutputOrder::List => self.in_list_order(selected),
+        })
+    }
+
+    /// Sort `entries` in the order they appear in the results. Entries that
+    /// aren't among the first `MAX_RESULTS` results come last, in the order
+    /// they were selected in.
+    fn in_list_order(
+        &mut self,
+        mut entries: FxIndexSet<Entry>,
+    ) -> FxIndexSet<Entry> {
+        let count = self.channel.result_count();
+        let positions: FxHashMap<String, usize> = self
+            .channel
+            .results(count, 0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| (entry.raw, i))
+            .collect();
+        entries.sort_by_cached_key(|entry| {
+            positions.get(&entry.raw).copied().unwrap_or(usize::MAX)
+        });
+        entries
     }
 
     /// Unified cursor movement for both Channel and Remote-control pickers.
     pub fn move_cursor(&mut self, movement: Movement, step: u32) {
         match self.mode {
             Mode::Channel => {
+                self.navigated_at = Some(Instant::now());
+                self.travel = movement;
+                self.pending_selection = None;
                 self.results_picker.move_cursor(
                     movement,
                     step,
                     self.channel.result_count() as usize,
-                    self.ui_state.layout.results.height.saturating_sub(2)
-                        as usize,
                 );
             }
             Mode::RemoteControl => {
@@ -419,19 +1035,21 @@ impl Television {
                     .expect("remote control should be Some when in RC mode")
                     .result_count()
                     as usize;
-                self.rc_picker.move_cursor(
-                    movement,
-                    step,
-                    total_results,
-                    self.ui_state.layout.remote_control.expect(
-                        "remote UI panel should be contained in the layout when in RC mode"
-                    ).height.saturating_sub(5) // accounting for borders (2) and input box (3)
-                        as usize,
-                );
+                self.rc_picker.move_cursor(movement, step, total_results);
             }
         }
     }
 
+    /// How many entries a page up or down moves the active picker's
+    /// selection by.
+    fn page_size(&self) -> Result<u32> {
+        let height = match self.mode {
+            Mode::Channel => self.results_picker.height(),
+            Mode::RemoteControl => self.rc_picker.height(),
+        };
+        Ok(u32::try_from(height)?)
+    }
+
     fn reset_picker_selection(&mut self) {
         match self.mode {
             Mode::Channel => self.results_picker.reset_selection(),
@@ -470,10 +1088,6 @@ impl Television {
 /// This is to ensure there are no startup artefacts and the UI
 /// stabilizes rapidly after startup.
 const FIRST_TICKS_TO_RENDER: u64 = 10;
-/// Render every N ticks.
-///
-/// Without any user input, this is the default rendering interval.
-const RENDERING_INTERVAL: u64 = 25;
 /// Render every N ticks if the channel is currently running.
 ///
 /// This ensures that the UI stays in sync with the channel
@@ -487,8 +1101,8 @@ impl Television {
     fn should_render(&self, action: &Action) -> bool {
         // always render the first N ticks
         (self.ticks < FIRST_TICKS_TO_RENDER
-            // then render at regular intervals
-            || self.ticks.is_multiple_of(RENDERING_INTERVAL)
+            // then only when something visible changed
+            || self.dirty
             // more frequently if the channel is running
             || (self.channel.running()
                 && self.ticks.is_multiple_of(RENDERING_INTERVAL_FAST))
@@ -496,11 +1110,19 @@ impl Television {
             || matches!(
                 action,
                 Action::AddInputChar(_)
+                    | Action::AddInputString(_)
                     | Action::DeletePrevChar
                     | Action::DeletePrevWord
                     | Action::DeleteNextChar
+                    | Action::DeleteLine
+                    | Action::DeleteNextWord
+                    | Action::DeleteToInputStart
+                    | Action::DeleteToInputEnd
+                    | Action::TransposeChars
                     | Action::GoToPrevChar
                     | Action::GoToNextChar
+                    | Action::GoToPrevWord
+                    | Action::GoToNextWord
                     | Action::GoToInputStart
                     | Action::GoToInputEnd
                     | Action::ToggleSelectionDown
@@ -510,19 +1132,57 @@ impl Television {
                     | Action::SelectPrevEntry
                     | Action::SelectNextPage
                     | Action::SelectPrevPage
+                    | Action::SelectIndex(_)
+                    | Action::SelectEntryAtPosition(_, _)
+                    | Action::MouseClickAt(_, _)
+                    | Action::SelectPrevHistory
+                    | Action::SelectNextHistory
+                    | Action::SwitchToChannel(_)
+                    | Action::ChannelBack
+                    | Action::ChannelForward
+                    | Action::WatchTimer
                     | Action::ScrollPreviewDown
                     | Action::ScrollPreviewUp
                     | Action::ScrollPreviewHalfPageDown
                     | Action::ScrollPreviewHalfPageUp
+                    | Action::ScrollPreviewPageDown
+                    | Action::ScrollPreviewPageUp
+                    | Action::GoToPreviewTop
+                    | Action::GoToPreviewBottom
                     | Action::ToggleHelp
+                    | Action::ToggleHelpOverlay
+                    | Action::ScrollHelpUp
+                    | Action::ScrollHelpDown
                     | Action::TogglePreview
                     | Action::ToggleStatusBar
+                    | Action::ToggleLogs
+                    | Action::ScrollLogUp
+                    | Action::ScrollLogDown
+                    | Action::GoToLogTop
+                    | Action::GoToLogBottom
+                    | Action::LogPanelKey(_)
+                    | Action::FocusPreview
+                    | Action::PreviewPanelKey(_)
+                    | Action::ShowEntryDetail
                     | Action::ToggleRemoteControl
                     | Action::ToggleOrientation
                     | Action::CopyEntryToClipboard
+                    | Action::CopyFormatted
+                    | Action::CopyPreviewToClipboard
                     | Action::CycleSources
+                    | Action::CycleSort
+                    | Action::ToggleMark
+                    | Action::FilterMarked
+                    | Action::EditEntryInline
                     | Action::CyclePreviews
                     | Action::ReloadSource
+                    | Action::Execute(_)
+                    | Action::ExecuteSilent(_)
+                    | Action::ExternalAction(_)
+                    | Action::AnswerConfirmation(_)
+                    | Action::Notify(_)
+                    | Action::FocusGained
+                    | Action::ClearScreen
             ))
             // We want to avoid too much rendering while the channel is reloading
             // to prevent UI flickering.
@@ -536,17 +1196,36 @@ impl Television {
         selected_entry: &Option<Entry>,
     ) -> Result<()> {
         if let Some(selected_entry) = selected_entry {
+            let mut requested = false;
             if let Some((sender, receiver)) = &mut self.preview_handles {
                 // send a preview request if the preview state is out of sync
                 // with the currently selected entry
                 // FIXME: this can't only rely on raw (ex: lines numbers may change for text
                 // but we don't want to regenerate the preview if the file is the same)
                 // NOTE: this is fine for now since we'll get a cache hit if cache is enabled
-                if selected_entry.raw != self.preview_state.preview.entry_raw {
+                // previews are also regenerated when the pane was resized
+                // since commands may fit their output to its width
+                let width = self.preview_content_width();
+                let out_of_sync = selected_entry.raw
+                    != self.preview_state.preview.entry_raw
+                    || width != self.preview_state.preview.width;
+                // while navigating, only preview entries the selection rests
+                // on (selection changes caused by the query aren't delayed)
+                let navigating = self.navigated_at.is_some_and(|t| {
+                    t.elapsed()
+                        < Duration::from_millis(
+                            self.merged_config.preview_debounce_ms,
+                        )
+                });
+                if out_of_sync && !navigating && self.focused {
                     sender.send(PreviewRequest::Preview(Ticket::new(
                         selected_entry.clone(),
+                        width,
                     )))?;
+                    requested = true;
                 }
+                self.preview_state.stale = out_of_sync
+                    && !self.preview_state.preview.entry_raw.is_empty();
                 // try to receive a preview update
                 if let Ok(preview) = receiver.try_recv() {
                     let initial_scroll = Self::calculate_scroll(
@@ -557,12 +1236,116 @@ impl Television {
                     self.action_tx.send(Action::Render)?;
                 }
             }
+            self.copy_pending_preview(selected_entry);
+            if requested {
+                self.request_prefetch(self.preview_content_width())?;
+            }
         } else {
             self.preview_state.reset();
+            self.pending_preview_copy = None;
+        }
+        Ok(())
+    }
+
+    /// Have the previewer prepare the previews of the next entries in the
+    /// direction the selection moves, so they show up right away once it
+    /// gets there.
+    fn request_prefetch(&mut self, width: u16) -> Result<()> {
+        let count = self.merged_config.channel_preview_prefetch;
+        if count == 0 || self.mode != Mode::Channel {
+            return Ok(());
+        }
+        let Some(selected) = self.selected_index() else {
+            return Ok(());
+        };
+        let total = self.channel.result_count();
+        let forward = self.travel == Movement::Next;
+        let tickets = (1..=count)
+            .filter_map(|offset| {
+                if forward {
+                    selected.checked_add(offset)
+                } else {
+                    selected.checked_sub(offset)
+                }
+            })
+            .filter(|index| *index < total)
+            .filter_map(|index| self.channel.get_result(index))
+            .map(|entry| Ticket::new(entry, width))
+            .collect::<Vec<_>>();
+        if let Some((sender, _)) = &self.preview_handles
+            && !tickets.is_empty()
+        {
+            sender.send(PreviewRequest::Prefetch { tickets, forward })?;
         }
         Ok(())
     }
 
+    /// Optimistically remove the entries configured by the action's `remove`
+    /// field from the results.
+    pub fn remove_acted_upon(
+        &mut self,
+        action_spec: &ActionSpec,
+        entries: &FxIndexSet<Entry>,
+    ) {
+        if action_spec.remove.is_empty() {
+            return;
+        }
+        let remove_self = action_spec.remove.iter().any(|p| p == "self");
+        let patterns: Vec<Regex> = action_spec
+            .remove
+            .iter()
+            .filter(|p| *p != "self")
+            .filter_map(|p| {
+                Regex::new(p)
+                    .inspect_err(|e| error!("Invalid remove pattern: {}", e))
+                    .ok()
+            })
+            .collect();
+        let entries = entries.clone();
+        self.channel.remove_entries(Box::new(move |entry: &Entry| {
+            (remove_self && entries.contains(entry))
+                || patterns.iter().any(|re| re.is_match(&entry.raw))
+        }));
+    }
+
+    /// Display the output of an external action in the preview panel until
+    /// the selection changes.
+    ///
+    /// Returns `false` if the current channel has no preview panel.
+    pub fn show_command_output(&mut self, output: &CommandOutput) -> bool {
+        if self.merged_config.channel_preview_command.is_none()
+            || self.merged_config.preview_panel_disabled
+        {
+            return false;
+        }
+        self.merged_config.preview_panel_hidden = false;
+        self.preview_state.enabled = true;
+        // drop any in-flight preview that would replace the output
+        if let Some((_, receiver)) = &mut self.preview_handles {
+            while receiver.try_recv().is_ok() {}
+        }
+        let content = Text::from(output.summary());
+        let total_lines =
+            u16::try_from(content.lines.len()).unwrap_or(u16::MAX);
+        self.preview_state.update(
+            Preview::new(
+                self.currently_selected
+                    .as_ref()
+                    .map(|entry| entry.raw.clone())
+                    .unwrap_or_default(),
+                output.command.clone(),
+                &output.command,
+                content,
+                None,
+                total_lines,
+                None,
+            )
+            .with_width(self.preview_content_width()),
+            0,
+        );
+        true
+    }
+
     fn calculate_scroll(
         preview: &Preview,
         preview_window: Option<&Rect>,
@@ -578,6 +1361,7 @@ impl Television {
     }
 
     pub fn update_results_picker_state(&mut self) {
+        self.restore_pending_selection();
         if self.results_picker.selected().is_none()
             && self.channel.result_count() > 0
         {
@@ -586,16 +1370,127 @@ impl Television {
         }
 
         {
-            let offset = u32::try_from(self.results_picker.offset()).unwrap();
+            let offset = u32::try_from(self.results_picker.offset())
+                .unwrap_or(u32::MAX);
             let height =
                 self.ui_state.layout.results.height.saturating_sub(2).into(); // -2 for borders
 
             self.results_picker.entries =
                 Arc::new(self.channel.results(height, offset));
         }
+        self.fit_grouped_results();
         self.results_picker.total_items = self.channel.result_count();
     }
 
+    /// Group headers take up rows of the results panel, which might push the
+    /// selected entry out of view: scroll down until it fits again.
+    fn fit_grouped_results(&mut self) {
+        let Some(index) = self.merged_config.channel_source_group_by else {
+            return;
+        };
+        let height = self.ui_state.layout.results.height.saturating_sub(2); // -2 for borders
+        let direction = if self.merged_config.input_bar_position
+            == InputPosition::Bottom
+        {
+            ListDirection::BottomToTop
+        } else {
+            ListDirection::TopToBottom
+        };
+        let group_by =
+            Some((index, &self.merged_config.channel_source_delimiter));
+
+        loop {
+            let rows = build_result_rows(
+                &self.results_picker.entries,
+                group_by,
+                direction,
+            );
+            let visible = visible_entries(&rows, height.into())
+                .max(1)
+                .min(height.into());
+            if !self.results_picker.fit_height(visible) {
+                break;
+            }
+            let offset = u32::try_from(self.results_picker.offset())
+                .unwrap_or(u32::MAX);
+            self.results_picker.entries =
+                Arc::new(self.channel.results(height.into(), offset));
+        }
+    }
+
+    /// The size of the preview panel's borders, in rows or columns.
+    fn preview_borders(&self) -> u16 {
+        if self.merged_config.borderless {
+            // the separator on the side of the results
+            1
+        } else if self
+            .merged_config
+            .preview_panel_border_type
+            .to_ratatui_border_type()
+            .is_some()
+        {
+            2
+        } else {
+            0
+        }
+    }
+
+    /// How many lines of preview fit in the preview panel.
+    fn preview_content_height(&self) -> u16 {
+        let Some(window) = self.ui_state.layout.preview_window else {
+            return 0;
+        };
+        let padding = self.merged_config.preview_panel_padding;
+        window.height.saturating_sub(
+            self.preview_borders() + padding.top + padding.bottom,
+        )
+    }
+
+    /// How many columns of preview fit in the preview panel (or in the
+    /// inline preview while the panel is hidden).
+    fn preview_content_width(&self) -> u16 {
+        let Some(window) = self.ui_state.layout.preview_window else {
+            return self
+                .ui_state
+                .layout
+                .inline_preview
+                .map_or(0, |strip| strip.width.saturating_sub(2)); // -2 for padding
+        };
+        let padding = self.merged_config.preview_panel_padding;
+        window.width.saturating_sub(
+            self.preview_borders() + padding.left + padding.right,
+        )
+    }
+
+    /// The number of lines the log panel has to show and how many of them
+    /// fit inside it.
+    fn log_panel_dimensions(&self) -> (usize, usize) {
+        let total = self.log_panel.as_ref().map_or(0, log_line_count);
+        let height = self.ui_state.layout.logs.map_or(0, log_panel_height);
+        (total, height)
+    }
+
+    /// How many rows of results fit in the results panel.
+    fn results_area_height(&self) -> usize {
+        let height = self.ui_state.layout.results.height;
+        if self.merged_config.borderless {
+            // neither borders nor title
+            return usize::from(height);
+        }
+        usize::from(height.saturating_sub(2)) // -2 for borders
+    }
+
+    /// How many channels fit in the remote control's channel list.
+    fn rc_area_height(&self) -> usize {
+        let height = self
+            .ui_state
+            .layout
+            .remote_control
+            .map_or(0, |rc| rc.height);
+        // accounting for borders (2) and input box (3)
+        usize::from(height.saturating_sub(5))
+    }
+
     pub fn update_rc_picker_state(&mut self) {
         if self.rc_picker.selected().is_none()
             && self.remote_control.as_ref().unwrap().result_count() > 0
@@ -605,15 +1500,10 @@ impl Television {
         }
 
         {
-            let offset = u32::try_from(self.rc_picker.offset()).unwrap();
-            let height = self
-                .ui_state
-                .layout
-                .remote_control
-                .unwrap_or_default()
-                .height
-                .saturating_sub(5)
-                .into();
+            let offset =
+                u32::try_from(self.rc_picker.offset()).unwrap_or(u32::MAX);
+            let height =
+                u32::try_from(self.rc_picker.height()).unwrap_or(u32::MAX);
             let new_entries = self
                 .remote_control
                 .as_mut()
@@ -623,7 +1513,7 @@ impl Television {
             self.rc_picker.entries = Arc::new(new_entries);
         }
         self.rc_picker.total_items =
-            self.remote_control.as_ref().unwrap().total_count();
+            self.remote_control.as_ref().unwrap().result_count();
     }
 
     pub fn handle_input_action(&mut self, action: &Action) {
@@ -631,35 +1521,123 @@ impl Television {
             Mode::Channel => &mut self.results_picker.input,
             Mode::RemoteControl => &mut self.rc_picker.input,
         };
-        input.handle(convert_action_to_input_request(action).unwrap());
+        if let Action::AddInputString(text) = action {
+            for c in text.chars() {
+                input.handle(InputRequest::InsertChar(c));
+            }
+        } else {
+            input.handle(convert_action_to_input_request(action).unwrap());
+        }
+        // an entry being edited inline isn't matched against
+        if self.inline_edit.is_some() && self.mode == Mode::Channel {
+            return;
+        }
         match action {
             Action::AddInputChar(_)
+            | Action::AddInputString(_)
             | Action::DeletePrevChar
             | Action::DeletePrevWord
             | Action::DeleteLine
-            | Action::DeleteNextChar => {
+            | Action::DeleteNextChar
+            | Action::DeleteNextWord
+            | Action::DeleteToInputStart
+            | Action::DeleteToInputEnd
+            | Action::TransposeChars => {
                 let new_pattern = input.value().to_string();
                 if new_pattern != self.current_pattern {
                     self.current_pattern.clone_from(&new_pattern);
-                    self.find(&new_pattern);
-                    self.reset_picker_selection();
+                    if self.pattern_debounce.enabled() {
+                        self.pattern_debounce
+                            .schedule(new_pattern, Instant::now());
+                    } else {
+                        self.apply_pattern(&new_pattern);
+                    }
                 }
             }
             _ => {}
         }
     }
 
-    pub fn handle_toggle_selection(&mut self, action: &Action) {
-        if matches!(self.mode, Mode::Channel)
-            && let Some(entry) = &self.currently_selected
-        {
-            self.channel.toggle_selection(entry);
-            if matches!(action, Action::ToggleSelectionDown) {
-                self.move_cursor(Movement::Next, 1);
-            } else {
-                self.move_cursor(Movement::Prev, 1);
+    fn apply_pattern(&mut self, pattern: &str) {
+        let kept = self.entry_to_keep();
+        self.find(pattern);
+        self.reset_picker_selection();
+        self.pending_selection = kept.map(PendingSelection::Keep);
+    }
+
+    /// The entry to keep highlighted through a change of the query.
+    ///
+    /// The cursor only follows an entry the user moved it to: on the first
+    /// result, it stays on the best match.
+    fn entry_to_keep(&mut self) -> Option<String> {
+        if self.mode != Mode::Channel {
+            return None;
+        }
+        // still waiting for the previous query to settle
+        if let Some(PendingSelection::Keep(raw)) = &self.pending_selection {
+            return Some(raw.clone());
+        }
+        if self.results_picker.selected().unwrap_or(0) == 0 {
+            return None;
+        }
+        self.get_selected_entry().map(|entry| entry.raw)
+    }
+
+    /// Apply a debounced pattern if it is due.
+    ///
+    /// Ticks only apply it once the debounce window has elapsed, while input
+    /// edits, renders and resizes leave it pending. Every other action
+    /// flushes it first so that it sees up-to-date results.
+    fn handle_pending_pattern(&mut self, action: &Action) -> bool {
+        let pattern = match action {
+            Action::Tick => self.pattern_debounce.poll(Instant::now()),
+            Action::AddInputChar(_)
+            | Action::AddInputString(_)
+            | Action::DeletePrevChar
+            | Action::DeletePrevWord
+            | Action::DeleteNextChar
+            | Action::DeleteLine
+            | Action::DeleteNextWord
+            | Action::DeleteToInputStart
+            | Action::DeleteToInputEnd
+            | Action::TransposeChars
+            | Action::GoToPrevChar
+            | Action::GoToNextChar
+            | Action::GoToPrevWord
+            | Action::GoToNextWord
+            | Action::GoToInputStart
+            | Action::GoToInputEnd
+            | Action::Render
+            | Action::Resize(_, _) => None,
+            _ => self.pattern_debounce.flush(),
+        };
+        pattern.is_some_and(|pattern| {
+            self.apply_pattern(&pattern);
+            true
+        })
+    }
+
+    pub fn handle_toggle_selection(&mut self, action: &Action) -> Result<()> {
+        match self.mode {
+            Mode::Channel => {
+                if let Some(entry) = &self.currently_selected {
+                    self.channel.toggle_selection(entry);
+                    if matches!(action, Action::ToggleSelectionDown) {
+                        self.move_cursor(Movement::Next, 1);
+                    } else {
+                        self.move_cursor(Movement::Prev, 1);
+                    }
+                }
+            }
+            Mode::RemoteControl => {
+                self.action_tx.send(Action::Error(format!(
+                    "{} is not available in {} mode",
+                    action.description(),
+                    self.mode
+                )))?;
             }
         }
+        Ok(())
     }
 
     pub fn handle_confirm_selection(&mut self) -> Result<()> {
@@ -679,7 +1657,7 @@ impl Television {
                     self.reset_picker_input();
                     self.remote_control.as_mut().unwrap().find(EMPTY_STRING);
                     self.mode = Mode::Channel;
-                    self.change_channel(&new_channel);
+                    self.push_channel(&new_channel);
                 }
             }
         }
@@ -694,19 +1672,230 @@ impl Television {
                 .iter()
                 .map(|e| e.raw.clone())
                 .collect::<Vec<_>>()
-                .join(SPACE);
-
-            tokio::spawn(CLIPBOARD.set(copied_string));
+                .join("\n");
+            self.copy_to_clipboard(
+                copied_string,
+                entries_copied(entries.len()),
+            );
         }
     }
 
-    pub fn cycle_sources(&mut self) {
-        if self.mode == Mode::Channel {
-            self.channel.cycle_sources();
-            self.reset_picker_selection();
+    /// Copy the selected entries formatted with the channel's `copy`
+    /// template, one line per entry, or the entries themselves when it has
+    /// none.
+    pub fn handle_copy_formatted_to_clipboard(&mut self) {
+        if self.mode != Mode::Channel {
+            return;
+        }
+        let template = self
+            .merged_config
+            .channel_source_copy
+            .clone()
+            .unwrap_or(Template::Entry);
+        let delimiter = self.merged_config.channel_source_delimiter.clone();
+        let Some(entries) = self.get_selected_entries() else {
+            return;
+        };
+
+        match entries
+            .iter()
+            .map(|e| e.format(&template, &delimiter))
+            .collect::<Result<Vec<_>>>()
+        {
+            Ok(lines) => {
+                self.copy_to_clipboard(
+                    lines.join("\n"),
+                    entries_copied(entries.len()),
+                );
+            }
+            Err(e) => {
+                let _ = self.action_tx.send(Action::Error(format!(
+                    "Failed to format entries: {e}"
+                )));
+            }
+        }
+    }
+
+    /// Copy the preview of the selected entry to the clipboard without its
+    /// styling. The preview is generated right away if it isn't there yet,
+    /// and copied once it comes back.
+    pub fn handle_copy_preview_to_clipboard(&mut self) -> Result<()> {
+        if self.mode != Mode::Channel {
+            return Ok(());
+        }
+        let Some(entry) = self.get_selected_entry() else {
+            return Ok(());
+        };
+        let Some((sender, _)) = &self.preview_handles else {
+            self.action_tx.send(Action::Error(
+                "This channel has no preview".to_string(),
+            ))?;
+            return Ok(());
+        };
+        if self.preview_state.preview.entry_raw == entry.raw {
+            self.copy_preview();
+        } else {
+            sender.send(PreviewRequest::Preview(Ticket::new(
+                entry.clone(),
+                self.preview_content_width(),
+            )))?;
+            self.pending_preview_copy = Some(entry.raw);
+        }
+        Ok(())
+    }
+
+    /// Copy the current preview, cut short at `preview_copy_max_bytes`.
+    fn copy_preview(&self) {
+        let mut content = plain_text(&self.preview_state.preview.content);
+        let max_bytes = self.merged_config.preview_copy_max_bytes;
+        let notice = if content.len() > max_bytes {
+            content.truncate(prev_char_boundary(&content, max_bytes));
+            format!(
+                "Copied the first {max_bytes} bytes of the preview to the \
+                 clipboard"
+            )
+        } else {
+            "Copied the preview to the clipboard".to_string()
+        };
+        self.copy_to_clipboard(content, notice);
+    }
+
+    /// Copy the preview `handle_copy_preview_to_clipboard` was waiting for
+    /// once it's there, or give up if the selection moved on.
+    fn copy_pending_preview(&mut self, selected_entry: &Entry) {
+        let Some(raw) = &self.pending_preview_copy else {
+            return;
+        };
+        if *raw != selected_entry.raw {
+            self.pending_preview_copy = None;
+        } else if self.preview_state.preview.entry_raw == *raw {
+            self.pending_preview_copy = None;
+            self.copy_preview();
+        }
+    }
+
+    /// Put `content` on the clipboard in the background and report back
+    /// with `notice` once done.
+    fn copy_to_clipboard(&self, content: String, notice: String) {
+        let mode = self.merged_config.clipboard;
+        let action_tx = self.action_tx.clone();
+
+        tokio::spawn(async move {
+            let action = if CLIPBOARD.set(content, mode).await {
+                Action::Notify(notice)
+            } else {
+                Action::Error("Unable to copy to the clipboard".to_string())
+            };
+            let _ = action_tx.send(action);
+        });
+    }
+
+    pub fn cycle_sources(&mut self) {
+        if self.mode == Mode::Channel {
+            self.channel.cycle_sources();
+            self.reset_picker_selection();
+        }
+    }
+
+    /// Sort the results by the next sort field of the channel, going back
+    /// to match order after the last one.
+    pub fn cycle_sort(&mut self) {
+        if self.mode != Mode::Channel {
+            return;
+        }
+        let fields = &self.merged_config.channel_source_sort_fields;
+        if fields.is_empty() {
+            debug!("No sort fields defined for this channel.");
+            return;
+        }
+        self.sort_index = match self.sort_index {
+            None => Some(0),
+            Some(i) if i + 1 < fields.len() => Some(i + 1),
+            Some(_) => None,
+        };
+        let sort = self.sort_index.map(|i| ResultsSort {
+            field: fields[i].field,
+            reverse: fields[i].reverse,
+            delimiter: self.merged_config.channel_source_delimiter.clone(),
+        });
+        self.channel.sort_by(sort);
+        self.reset_picker_selection();
+    }
+
+    /// Mark or unmark the entry under the cursor.
+    pub fn toggle_mark(&mut self) {
+        if self.mode != Mode::Channel {
+            return;
+        }
+        let Some(entry) = self.currently_selected.clone() else {
+            return;
+        };
+        if !self.marked_entries.remove(&entry) {
+            self.marked_entries.insert(entry);
+        }
+        if self.marked_only {
+            if self.marked_entries.is_empty() {
+                self.marked_only = false;
+                self.channel.filter_by(None);
+            } else {
+                self.channel.filter_by(Some(self.marked_entries.clone()));
+            }
+        }
+        self.dirty = true;
+    }
+
+    /// Copy the selected entry into the input to be edited and output as is,
+    /// or cancel the edit and restore the previous query.
+    pub fn toggle_inline_edit(&mut self) {
+        if self.mode != Mode::Channel {
+            return;
+        }
+        if self.inline_edit.is_some() {
+            self.cancel_inline_edit();
+            return;
+        }
+        let Some(entry) = &self.currently_selected else {
+            return;
+        };
+        self.inline_edit = Some(self.results_picker.input.value().to_string());
+        self.results_picker.input = Input::new(entry.raw.clone());
+        self.notice = Some((
+            "Editing entry: enter to output it, esc to cancel".to_string(),
+            Instant::now(),
+        ));
+    }
+
+    /// Restore the query typed before editing an entry inline, if any.
+    pub fn cancel_inline_edit(&mut self) {
+        if let Some(pattern) = self.inline_edit.take() {
+            self.results_picker.input = Input::new(pattern);
         }
     }
 
+    /// The edited entry, if an entry is being edited inline.
+    pub fn inline_edit_output(&self) -> Option<String> {
+        self.inline_edit
+            .as_ref()
+            .map(|_| self.results_picker.input.value().to_string())
+    }
+
+    /// Narrow the results down to the marked entries, or show them all again.
+    pub fn filter_marked(&mut self) -> Result<()> {
+        if self.mode != Mode::Channel {
+            return Ok(());
+        }
+        if !self.marked_only && self.marked_entries.is_empty() {
+            self.action_tx
+                .send(Action::Error("No marked entries".to_string()))?;
+            return Ok(());
+        }
+        self.marked_only = !self.marked_only;
+        self.channel
+            .filter_by(self.marked_only.then(|| self.marked_entries.clone()));
+        self.reset_picker_selection();
+        Ok(())
+    }
+
     pub fn cycle_previews(&mut self) {
         if self.mode == Mode::Channel
             && let Some((sender, _)) = &self.preview_handles
@@ -717,6 +1906,16 @@ impl Television {
         }
     }
 
+    pub fn clear_preview_cache(&mut self) {
+        if self.mode == Mode::Channel
+            && let Some((sender, _)) = &self.preview_handles
+        {
+            sender
+                .send(PreviewRequest::ClearCache)
+                .expect("Failed to send clear cache request to previewer");
+        }
+    }
+
     pub fn handle_reload_source(&mut self) {
         if self.mode == Mode::Channel {
             let current_pattern = self.current_pattern.clone();
@@ -726,18 +1925,78 @@ impl Television {
         }
     }
 
+    /// Scroll the help overlay with the navigation actions, returning whether
+    /// the action was consumed.
+    fn handle_help_overlay_action(&mut self, action: &Action) -> bool {
+        let height = self
+            .ui_state
+            .layout
+            .help_overlay
+            .map_or(0, help_overlay_height);
+        let total = help_overlay_lines(
+            &self.merged_config,
+            self.mode,
+            &self.colorscheme,
+            self.channel_history_position(),
+        )
+        .len();
+        let Some(overlay) = &mut self.help_overlay else {
+            return false;
+        };
+        let half =
+            usize::from(half_page(u16::try_from(height).unwrap_or(u16::MAX)));
+        match action {
+            Action::SelectNextEntry
+            | Action::ScrollPreviewDown
+            | Action::ScrollHelpDown => overlay.scroll_down(1, total, height),
+            Action::SelectPrevEntry
+            | Action::ScrollPreviewUp
+            | Action::ScrollHelpUp => overlay.scroll_up(1),
+            Action::ScrollPreviewHalfPageDown => {
+                overlay.scroll_down(half, total, height);
+            }
+            Action::ScrollPreviewHalfPageUp => overlay.scroll_up(half),
+            Action::SelectNextPage | Action::ScrollPreviewPageDown => {
+                overlay.scroll_down(height, total, height);
+            }
+            Action::SelectPrevPage | Action::ScrollPreviewPageUp => {
+                overlay.scroll_up(height);
+            }
+            Action::GoToPreviewTop => overlay.scroll = 0,
+            Action::GoToPreviewBottom => {
+                overlay.scroll_down(total, total, height);
+            }
+            _ => return false,
+        }
+        true
+    }
+
     pub fn handle_action(&mut self, action: &Action) -> Result<()> {
+        // the help overlay scrolls with the navigation keys while it's open
+        if self.help_overlay.is_some()
+            && self.handle_help_overlay_action(action)
+        {
+            return Ok(());
+        }
+
         // handle actions
         match action {
             Action::AddInputChar(_)
+            | Action::AddInputString(_)
             | Action::DeletePrevChar
             | Action::DeletePrevWord
             | Action::DeleteNextChar
             | Action::DeleteLine
+            | Action::DeleteNextWord
+            | Action::DeleteToInputStart
+            | Action::DeleteToInputEnd
+            | Action::TransposeChars
             | Action::GoToInputEnd
             | Action::GoToInputStart
             | Action::GoToNextChar
-            | Action::GoToPrevChar => {
+            | Action::GoToPrevChar
+            | Action::GoToNextWord
+            | Action::GoToPrevWord => {
                 self.handle_input_action(action);
             }
             Action::SelectNextEntry => {
@@ -747,42 +2006,58 @@ impl Television {
                 self.move_cursor(Movement::Prev, 1);
             }
             Action::SelectNextPage => {
-                if matches!(self.mode, Mode::Channel) {
-                    self.move_cursor(
-                        Movement::Next,
-                        self.ui_state
-                            .layout
-                            .results
-                            .height
-                            .saturating_sub(2)
-                            .into(),
-                    );
-                }
+                self.move_cursor(Movement::Next, self.page_size()?);
             }
             Action::SelectPrevPage => {
+                self.move_cursor(Movement::Prev, self.page_size()?);
+            }
+            Action::SelectIndex(index) => {
                 if matches!(self.mode, Mode::Channel) {
-                    self.move_cursor(
-                        Movement::Prev,
-                        self.ui_state
-                            .layout
-                            .results
-                            .height
-                            .saturating_sub(2)
-                            .into(),
+                    self.navigated_at = Some(Instant::now());
+                    self.results_picker.select_index(
+                        index.saturating_sub(1),
+                        self.channel.result_count() as usize,
                     );
                 }
             }
-            Action::ScrollPreviewDown => self.preview_state.scroll_down(1),
+            Action::ScrollPreviewDown => {
+                let height = self.preview_content_height();
+                self.preview_state.scroll_down(1, height);
+            }
             Action::ScrollPreviewUp => self.preview_state.scroll_up(1),
             Action::ScrollPreviewHalfPageDown => {
-                self.preview_state.scroll_down(20);
+                let height = self.preview_content_height();
+                self.preview_state.scroll_down(half_page(height), height);
             }
             Action::ScrollPreviewHalfPageUp => {
-                self.preview_state.scroll_up(20);
+                let height = self.preview_content_height();
+                self.preview_state.scroll_up(half_page(height));
+            }
+            Action::ScrollPreviewPageDown => {
+                let height = self.preview_content_height();
+                self.preview_state.scroll_down(height.max(1), height);
+            }
+            Action::ScrollPreviewPageUp => {
+                let height = self.preview_content_height();
+                self.preview_state.scroll_up(height.max(1));
+            }
+            Action::GoToPreviewTop => self.preview_state.scroll_to_top(),
+            Action::GoToPreviewBottom => {
+                let height = self.preview_content_height();
+                self.preview_state.scroll_to_bottom(height);
             }
 
             Action::ToggleSelectionDown | Action::ToggleSelectionUp => {
-                self.handle_toggle_selection(action);
+                self.handle_toggle_selection(action)?;
+            }
+            Action::ToggleMark => {
+                self.toggle_mark();
+            }
+            Action::FilterMarked => {
+                self.filter_marked()?;
+            }
+            Action::EditEntryInline => {
+                self.toggle_inline_edit();
             }
             Action::ConfirmSelection => {
                 self.handle_confirm_selection()?;
@@ -790,22 +2065,42 @@ impl Television {
             Action::CopyEntryToClipboard => {
                 self.handle_copy_entry_to_clipboard();
             }
+            Action::CopyFormatted => {
+                self.handle_copy_formatted_to_clipboard();
+            }
+            Action::CopyPreviewToClipboard => {
+                self.handle_copy_preview_to_clipboard()?;
+            }
             Action::CycleSources => {
                 self.cycle_sources();
             }
+            Action::CycleSort => {
+                self.cycle_sort();
+            }
             Action::CyclePreviews => {
                 self.cycle_previews();
             }
+            Action::ClearPreviewCache => {
+                self.clear_preview_cache();
+            }
             Action::ReloadSource | Action::WatchTimer => {
                 self.handle_reload_source();
             }
+            Action::ChannelBack => {
+                self.channel_back()?;
+            }
+            Action::ChannelForward => {
+                self.channel_forward()?;
+            }
             Action::SwitchToChannel(channel_name) => {
                 if let Some(rc) = &self.remote_control {
                     let prototype = rc.zap(channel_name);
-                    self.change_channel(&prototype);
+                    self.push_channel(&prototype);
                 }
             }
             Action::ToggleRemoteControl => {
+                self.help_scroll = 0;
+                self.cancel_inline_edit();
                 if self.remote_control.is_none()
                     || self.merged_config.remote_disabled
                 {
@@ -834,20 +2129,122 @@ impl Television {
                     }
                 }
             }
+            Action::FocusLost => self.focused = false,
+            Action::FocusGained => self.focused = true,
+            Action::ToggleLogs => match &mut self.log_panel {
+                Some(logs) if !logs.focused => logs.focused = true,
+                Some(_) => self.log_panel = None,
+                None => self.log_panel = Some(LogPanelState::default()),
+            },
+            Action::ScrollLogUp
+            | Action::ScrollLogDown
+            | Action::GoToLogTop
+            | Action::GoToLogBottom => {
+                let (total, height) = self.log_panel_dimensions();
+                if let Some(logs) = &mut self.log_panel {
+                    match action {
+                        Action::ScrollLogUp => {
+                            logs.scroll_up(scroll_step(height), total, height);
+                        }
+                        Action::ScrollLogDown => {
+                            logs.scroll_down(
+                                scroll_step(height),
+                                total,
+                                height,
+                            );
+                        }
+                        Action::GoToLogTop => {
+                            logs.scroll_to_top(total, height)
+                        }
+                        _ => logs.scroll_to_bottom(total, height),
+                    }
+                }
+            }
+            Action::LogPanelKey(key) => {
+                let (total, height) = self.log_panel_dimensions();
+                if let Some(logs) = &mut self.log_panel {
+                    logs.handle_key(*key, total, height);
+                }
+            }
+            Action::FocusPreview => {
+                if self.preview_focus.is_some() {
+                    self.preview_focus = None;
+                } else if self.mode == Mode::Channel
+                    && self.preview_state.enabled
+                    && self.ui_state.layout.preview_window.is_some()
+                {
+                    self.preview_focus = Some(PreviewFocus::default());
+                }
+            }
+            Action::PreviewPanelKey(key) => {
+                let height = self.preview_content_height();
+                if let Some(focus) = &mut self.preview_focus
+                    && !focus.handle_key(*key, &mut self.preview_state, height)
+                {
+                    self.preview_focus = None;
+                }
+            }
+            Action::ShowEntryDetail => {
+                if self.entry_detail.is_some() {
+                    self.entry_detail = None;
+                } else if self.mode == Mode::Channel
+                    && let Some(entry) = &self.currently_selected
+                {
+                    self.entry_detail = Some(EntryDetail::new(
+                        entry,
+                        &self.merged_config.channel_source_delimiter,
+                        self.merged_config.channel_preview_command.as_ref(),
+                        self.merged_config.channel_preview_offset.as_ref(),
+                        &self.merged_config.channel_actions,
+                    ));
+                }
+            }
             Action::ToggleHelp => {
                 // Only allow toggling if the help panel is not disabled
                 if !self.merged_config.help_panel_disabled {
                     self.merged_config.help_panel_hidden =
                         !self.merged_config.help_panel_hidden;
+                    self.help_scroll = 0;
+                }
+            }
+            Action::ToggleHelpOverlay => {
+                self.help_overlay = match self.help_overlay {
+                    Some(_) => None,
+                    None => Some(HelpOverlay::default()),
+                };
+            }
+            Action::ScrollHelpUp => {
+                self.help_scroll = self.help_scroll.saturating_sub(1);
+            }
+            Action::ScrollHelpDown => {
+                if let Some(area) = self.ui_state.layout.help_panel {
+                    self.help_scroll = clamp_help_scroll(
+                        self.help_scroll.saturating_add(1),
+                        &self.merged_config,
+                        self.mode,
+                        &self.colorscheme,
+                        area,
+                    );
                 }
             }
             Action::TogglePreview => {
                 // Only allow toggling if in Channel mode and preview is not disabled
                 if self.mode == Mode::Channel
+                    && self.merged_config.channel_preview_command.is_none()
+                {
+                    // there's nothing to show, say so instead of toggling an
+                    // empty panel
+                    self.action_tx.send(Action::Error(
+                        "No preview command for this channel (see --preview-command)"
+                            .to_string(),
+                    ))?;
+                } else if self.mode == Mode::Channel
                     && !self.merged_config.preview_panel_disabled
                 {
                     self.merged_config.preview_panel_hidden =
                         !self.merged_config.preview_panel_hidden;
+                    self.preview_hidden_override =
+                        Some(self.merged_config.preview_panel_hidden);
                 }
             }
             Action::ToggleStatusBar => {
@@ -857,6 +2254,10 @@ impl Television {
                         !self.merged_config.status_bar_hidden;
                 }
             }
+            Action::Notify(message) => {
+                debug!("{}", message);
+                self.notice = Some((message.clone(), Instant::now()));
+            }
             Action::ToggleOrientation => match self.merged_config.layout {
                 Orientation::Portrait => {
                     self.merged_config.layout = Orientation::Landscape;
@@ -871,10 +2272,24 @@ impl Television {
     }
 
     #[allow(clippy::unused_async)]
+    /// Let the `--info-file` know about the selection, the result counts and
+    /// the query (it's only written when they change).
+    fn publish_info(&self) {
+        if let Some(info_writer) = &self.info_writer {
+            info_writer.update(
+                self.currently_selected.as_ref().map(|e| e.raw.as_str()),
+                self.channel.result_count(),
+                self.channel.total_count(),
+                self.results_picker.input.value(),
+            );
+        }
+    }
+
     /// Update the television state based on the action provided.
     ///
     /// This function may return an Action that'll be processed by the parent `App`.
     pub fn update(&mut self, action: &Action) -> Result<Option<Action>> {
+        let pattern_applied = self.handle_pending_pattern(action);
         self.handle_action(action)?;
 
         self.update_results_picker_state();
@@ -887,11 +2302,19 @@ impl Television {
             let selected_entry = self.get_selected_entry();
             self.update_preview_state(&selected_entry)?;
             self.currently_selected = selected_entry;
+            self.publish_info();
         }
         self.ticks += 1;
 
-        Ok(if self.should_render(action) {
-            if self.channel.running() {
+        let render_state = self.render_state();
+        if render_state != self.render_state {
+            self.render_state = render_state;
+            self.dirty = true;
+        }
+
+        Ok(if pattern_applied || self.should_render(action) {
+            self.dirty = false;
+            if self.channel.loading() {
                 self.spinner.tick();
             }
 
@@ -907,11 +2330,43 @@ mod test {
     use crate::{
         action::{Action, Actions},
         cable::Cable,
-        cli::{ChannelCli, GlobalCli},
-        config::layers::ConfigLayers,
+        channels::prototypes::ChannelPrototype,
+        cli::{ChannelCli, GlobalCli, PostProcessedCli},
+        config::{Config, layers::ConfigLayers},
         event::Key,
-        television::{MatchingMode, Mode, Television},
+        television::{MatchingMode, Mode, PendingSelection, Television},
     };
+    use ratatui::buffer::Buffer;
+    use tokio::sync::mpsc::UnboundedReceiver;
+
+    /// A tv on the `prototype` channel with `config` and `cli_args` layered
+    /// over it and `cable` to switch channels from, along with the receiving
+    /// end of the actions it sends.
+    fn television_with(
+        config: Config,
+        prototype: ChannelPrototype,
+        cli_args: PostProcessedCli,
+        cable: Vec<ChannelPrototype>,
+    ) -> (Television, UnboundedReceiver<Action>) {
+        let (action_tx, action_rx) = tokio::sync::mpsc::unbounded_channel();
+        let tv = Television::new(
+            action_tx,
+            ConfigLayers::new(config, prototype, cli_args),
+            Cable::from_prototypes(cable),
+        );
+        (tv, action_rx)
+    }
+
+    /// A tv on the `prototype` channel with the default config.
+    fn television(prototype: ChannelPrototype) -> Television {
+        television_with(
+            Config::default(),
+            prototype,
+            PostProcessedCli::default(),
+            Vec::new(),
+        )
+        .0
+    }
 
     #[test]
     fn test_prompt_preprocessing() {
@@ -1002,4 +2457,1364 @@ mod test {
             Some(&Actions::single(Action::SelectNextEntry)),
         );
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_channel_ui_overrides_and_manual_preview_toggle() {
+        use crate::screen::layout::InputPosition;
+
+        let files = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "files"
+
+            [source]
+            command = "echo 1"
+
+            [preview]
+            command = "echo {}"
+            "#,
+        )
+        .unwrap();
+        let history = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "history"
+
+            [source]
+            command = "echo 1"
+
+            [preview]
+            command = "echo {}"
+
+            [ui.input_bar]
+            position = "bottom"
+
+            [ui.preview_panel]
+            hidden = true
+            "#,
+        )
+        .unwrap();
+
+        let (mut tv, _) = television_with(
+            Config::default(),
+            files.clone(),
+            PostProcessedCli::default(),
+            vec![files.clone(), history.clone()],
+        );
+
+        // the channel's ui table overlays the global config
+        tv.change_channel(&history);
+        assert!(tv.merged_config.preview_panel_hidden);
+        assert_eq!(tv.merged_config.input_bar_position, InputPosition::Bottom);
+
+        // and is restored when switching back
+        tv.change_channel(&files);
+        assert!(!tv.merged_config.preview_panel_hidden);
+        assert_eq!(tv.merged_config.input_bar_position, InputPosition::Top);
+
+        // a manual toggle wins for the rest of the session
+        tv.handle_action(&Action::TogglePreview).unwrap();
+        assert!(tv.merged_config.preview_panel_hidden);
+        tv.change_channel(&history);
+        tv.change_channel(&files);
+        assert!(tv.merged_config.preview_panel_hidden);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_selections_survive_channel_switch() {
+        use crate::channels::entry::Entry;
+
+        let files = ChannelPrototype::new("files", "echo 1");
+        let env = ChannelPrototype::new("env", "echo 2");
+        let (mut tv, mut action_rx) = television_with(
+            Config::default(),
+            files.clone(),
+            PostProcessedCli::default(),
+            vec![files.clone(), env.clone()],
+        );
+
+        let entry = Entry::new("1".to_string());
+        tv.channel.toggle_selection(&entry);
+
+        tv.change_channel(&env);
+        assert!(tv.channel.selected_entries().is_empty());
+
+        tv.change_channel(&files);
+        assert!(tv.channel.selected_entries().contains(&entry));
+
+        // selections are not supported in remote control mode
+        tv.mode = Mode::RemoteControl;
+        tv.handle_action(&Action::ToggleSelectionDown).unwrap();
+        assert!(matches!(action_rx.try_recv(), Ok(Action::Error(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_channel_history_back_and_forward() {
+        use crate::channels::entry::Entry;
+
+        let files = ChannelPrototype::new("files", "echo 1");
+        let env = ChannelPrototype::new("env", "echo 2");
+        let text = ChannelPrototype::new("text", "echo 3");
+        let (mut tv, mut action_rx) = television_with(
+            Config::default(),
+            files.clone(),
+            PostProcessedCli::default(),
+            vec![files.clone(), env.clone(), text.clone()],
+        );
+
+        let entry = Entry::new("1".to_string());
+        tv.channel.toggle_selection(&entry);
+        tv.set_pattern("1");
+
+        // nested switches stack up
+        tv.push_channel(&env);
+        tv.set_pattern("2");
+        tv.push_channel(&text);
+        assert_eq!(tv.current_channel(), "text");
+        assert_eq!(tv.channel_provenance(), vec!["files", "env"]);
+        assert_eq!(tv.channel_history_position(), (3, 3));
+
+        tv.handle_action(&Action::ChannelBack).unwrap();
+        assert_eq!(tv.current_channel(), "env");
+        assert_eq!(tv.current_pattern, "2");
+        assert_eq!(tv.results_picker.input.value(), "2");
+        assert_eq!(tv.channel_provenance(), vec!["files"]);
+
+        tv.handle_action(&Action::ChannelBack).unwrap();
+        assert_eq!(tv.current_channel(), "files");
+        assert_eq!(tv.current_pattern, "1");
+        assert!(tv.channel.selected_entries().contains(&entry));
+        assert_eq!(tv.channel_history_position(), (1, 3));
+
+        // nothing left to go back to
+        tv.handle_action(&Action::ChannelBack).unwrap();
+        assert_eq!(tv.current_channel(), "files");
+        assert!(matches!(action_rx.try_recv(), Ok(Action::Error(_))));
+
+        tv.handle_action(&Action::ChannelForward).unwrap();
+        assert_eq!(tv.current_channel(), "env");
+        assert_eq!(tv.current_pattern, "2");
+        assert_eq!(tv.channel_history_position(), (2, 3));
+
+        // switching channels drops the channels ahead
+        tv.push_channel(&files);
+        assert_eq!(tv.channel_history_position(), (3, 3));
+        tv.handle_action(&Action::ChannelForward).unwrap();
+        assert!(matches!(action_rx.try_recv(), Ok(Action::Error(_))));
+        assert_eq!(tv.channel_provenance(), vec!["files", "env"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_channel_history_restores_the_selected_entry() {
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+        use ratatui::layout::Rect;
+        use std::time::Duration;
+
+        let mut tv =
+            television(ChannelPrototype::new("files", "printf 'a\nb\nc\n'"));
+        tv.update_ui_state(UiState::new(Layout {
+            results: Rect::new(0, 0, 20, 10),
+            ..Layout::default()
+        }));
+
+        // the entry moved since the channel was left
+        tv.pending_selection =
+            Some(PendingSelection::Restore("c".to_string(), 0));
+        for _ in 0..100 {
+            tv.update_results_picker_state();
+            if tv.pending_selection.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(tv.results_picker.selected(), Some(2));
+
+        // the entry is gone
+        tv.pending_selection =
+            Some(PendingSelection::Restore("z".to_string(), 1));
+        tv.update_results_picker_state();
+        assert_eq!(tv.results_picker.selected(), Some(0));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_narrowing_the_query_keeps_the_highlighted_entry() {
+        use crate::channels::channel::Source;
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+        use ratatui::layout::Rect;
+        use std::{sync::Arc, time::Duration};
+
+        async fn settle(tv: &mut Television) {
+            for _ in 0..100 {
+                tv.update_results_picker_state();
+                if tv.pending_selection.is_none() && !tv.channel.running() {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            assert!(tv.pending_selection.is_none());
+        }
+
+        fn highlighted(tv: &mut Television) -> String {
+            tv.get_selected_entry().unwrap().raw
+        }
+
+        let mut tv = television(ChannelPrototype::new("fruits", ""));
+        tv.update_ui_state(UiState::new(Layout {
+            results: Rect::new(0, 0, 20, 10),
+            ..Layout::default()
+        }));
+        tv.channel.set_source(Source::Static(Arc::new(
+            ["apple", "banana", "cherry", "blueberry", "date", "fig"]
+                .map(String::from)
+                .to_vec(),
+        )));
+        tv.channel.load();
+        settle(&mut tv).await;
+
+        let banana = tv.result_position(|entry| entry.raw == "banana");
+        tv.results_picker.select_index(banana.unwrap(), 6);
+        assert_eq!(highlighted(&mut tv), "banana");
+
+        // still a match, still highlighted
+        tv.apply_pattern("b");
+        settle(&mut tv).await;
+        assert_eq!(highlighted(&mut tv), "banana");
+        tv.apply_pattern("ba");
+        settle(&mut tv).await;
+        assert_eq!(highlighted(&mut tv), "banana");
+
+        // gone, back to the best match
+        tv.apply_pattern("bl");
+        settle(&mut tv).await;
+        assert_eq!(tv.results_picker.selected(), Some(0));
+        assert_eq!(highlighted(&mut tv), "blueberry");
+
+        // the best match isn't followed once it's no longer the best
+        tv.apply_pattern("");
+        settle(&mut tv).await;
+        assert_eq!(tv.results_picker.selected(), Some(0));
+    }
+
+    /// The entry the cursor lands on once a static channel is done loading
+    /// with `channel_cli`.
+    async fn initial_selection(channel_cli: ChannelCli) -> Option<usize> {
+        use crate::channels::channel::Source;
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+        use ratatui::layout::Rect;
+        use std::{sync::Arc, time::Duration};
+
+        let (mut tv, _) = television_with(
+            Config::default(),
+            ChannelPrototype::new("fruits", ""),
+            PostProcessedCli {
+                channel: channel_cli,
+                ..PostProcessedCli::default()
+            },
+            Vec::new(),
+        );
+        tv.update_ui_state(UiState::new(Layout {
+            results: Rect::new(0, 0, 20, 4),
+            ..Layout::default()
+        }));
+        tv.channel.set_source(Source::Static(Arc::new(
+            ["apple", "banana", "cherry", "blueberry", "date", "fig"]
+                .map(String::from)
+                .to_vec(),
+        )));
+        tv.channel.load();
+        for _ in 0..100 {
+            tv.update_results_picker_state();
+            if tv.pending_selection.is_none() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(tv.pending_selection.is_none());
+        tv.results_picker.selected()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_initial_selection() {
+        use crate::cli::args::SelectMode;
+
+        let select = |text: &str, select_mode| ChannelCli {
+            select: Some(text.to_string()),
+            select_mode,
+            ..ChannelCli::default()
+        };
+        assert_eq!(
+            initial_selection(select("cherry", SelectMode::Exact)).await,
+            Some(2)
+        );
+        assert_eq!(
+            initial_selection(select("berry", SelectMode::Contains)).await,
+            Some(3)
+        );
+        // nothing matches: the cursor stays at the top
+        assert_eq!(
+            initial_selection(select("berry", SelectMode::Exact)).await,
+            Some(0)
+        );
+        assert_eq!(
+            initial_selection(ChannelCli {
+                select_last: true,
+                ..ChannelCli::default()
+            })
+            .await,
+            Some(5)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_channel_stack_is_bounded() {
+        use super::MAX_CHANNEL_STACK;
+
+        let files = ChannelPrototype::new("files", "echo 1");
+        let env = ChannelPrototype::new("env", "echo 2");
+        let (mut tv, _) = television_with(
+            Config::default(),
+            files.clone(),
+            PostProcessedCli::default(),
+            vec![files.clone(), env.clone()],
+        );
+
+        for i in 0..MAX_CHANNEL_STACK + 2 {
+            tv.push_channel(if i % 2 == 0 { &env } else { &files });
+        }
+        assert_eq!(tv.channel_provenance().len(), MAX_CHANNEL_STACK);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_sticky_cli_overrides_survive_channel_switch() {
+        let files = ChannelPrototype::new("files", "echo 1");
+        let env = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "env"
+
+            [source]
+            command = "env"
+            entry_delimiter = "="
+
+            [preview]
+            command = "echo {}"
+            "#,
+        )
+        .unwrap();
+        let cli_preview = Template::parse("cat {}").unwrap();
+
+        for sticky in [false, true] {
+            let cli_args = PostProcessedCli {
+                channel: ChannelCli {
+                    preview_command: Some(cli_preview.clone()),
+                    source_entry_delimiter: Some(':'),
+                    ..Default::default()
+                },
+                global: GlobalCli {
+                    override_sticky: sticky,
+                    ..Default::default()
+                },
+            };
+            let (mut tv, _) = television_with(
+                Config::default(),
+                files.clone(),
+                cli_args,
+                vec![files.clone(), env.clone()],
+            );
+            // the CLI beats the starting channel
+            assert_eq!(
+                tv.merged_config
+                    .channel_preview_command
+                    .as_ref()
+                    .unwrap()
+                    .get_nth(0)
+                    .raw(),
+                "cat {}"
+            );
+            assert_eq!(
+                tv.merged_config.channel_source_record_separator.as_deref(),
+                Some(":")
+            );
+
+            tv.change_channel(&env);
+            let (preview, delimiter) = if sticky {
+                ("cat {}", Some(":"))
+            } else {
+                ("echo {}", Some("="))
+            };
+            assert_eq!(
+                tv.merged_config
+                    .channel_preview_command
+                    .as_ref()
+                    .unwrap()
+                    .get_nth(0)
+                    .raw(),
+                preview
+            );
+            assert_eq!(
+                tv.merged_config.channel_source_record_separator.as_deref(),
+                delimiter
+            );
+        }
+    }
+
+    #[test]
+    fn test_entry_preview_only_for_record_separators() {
+        let preview_of = |source: &str| {
+            let prototype = toml::from_str::<ChannelPrototype>(&format!(
+                "[metadata]\nname = \"records\"\n[source]\n{source}"
+            ))
+            .unwrap();
+            ConfigLayers::new(
+                Config::default(),
+                prototype,
+                PostProcessedCli::default(),
+            )
+            .merge()
+            .channel_preview_command
+        };
+
+        let preview = preview_of(
+            "command = \"git log -z\"\nrecord_separator = \"\\\\0\"",
+        )
+        .unwrap();
+        assert!(preview.is_entry_preview_nth(0));
+        // the legacy key splits single-line entries, e.g. `find -print0`
+        assert_eq!(
+            preview_of(
+                "command = \"find -print0\"\nentry_delimiter = \"\\\\0\""
+            ),
+            None
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_toggle_preview_without_preview_command() {
+        let (mut tv, mut action_rx) = television_with(
+            Config::default(),
+            ChannelPrototype::stdin(),
+            PostProcessedCli::default(),
+            Vec::new(),
+        );
+        assert!(tv.merged_config.channel_preview_command.is_none());
+        let hidden = tv.merged_config.preview_panel_hidden;
+
+        tv.handle_action(&Action::TogglePreview).unwrap();
+
+        assert_eq!(tv.merged_config.preview_panel_hidden, hidden);
+        assert!(matches!(action_rx.try_recv(), Ok(Action::Error(_))));
+    }
+
+    #[test]
+    fn test_pattern_debounce_applies_final_pattern_once() {
+        use super::PatternDebounce;
+        use std::time::{Duration, Instant};
+
+        let mut debounce = PatternDebounce::new(40);
+        let start = Instant::now();
+        let at = |ms| start + Duration::from_millis(ms);
+
+        debounce.schedule("a".to_string(), at(0));
+        assert_eq!(debounce.poll(at(20)), None);
+        debounce.schedule("ab".to_string(), at(20));
+        debounce.schedule("abc".to_string(), at(30));
+        // the window restarts with every keystroke
+        assert_eq!(debounce.poll(at(60)), None);
+        assert_eq!(debounce.poll(at(70)), Some("abc".to_string()));
+        assert_eq!(debounce.poll(at(200)), None);
+        assert_eq!(debounce.flush(), None);
+
+        // non-input actions don't wait for the window to elapse
+        debounce.schedule("abcd".to_string(), at(200));
+        assert_eq!(debounce.flush(), Some("abcd".to_string()));
+        assert_eq!(debounce.poll(at(400)), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_preview_debounced_while_navigating() {
+        use crate::channels::entry::Entry;
+        use crate::previewer::{Preview, Request};
+
+        let mut tv = television(ChannelPrototype::stdin());
+        tv.merged_config.preview_debounce_ms = 60_000;
+        let (request_tx, mut request_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (preview_tx, preview_rx) = tokio::sync::mpsc::unbounded_channel();
+        tv.preview_handles = Some((request_tx, preview_rx));
+
+        // selection changes that don't come from navigation preview at once
+        let first = Entry::new("first".to_string());
+        tv.update_preview_state(&Some(first.clone())).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Preview(_))));
+        preview_tx
+            .send(Preview::new(
+                "first".to_string(),
+                String::new(),
+                "first",
+                ratatui::text::Text::from("content"),
+                None,
+                1,
+                None,
+            ))
+            .unwrap();
+        tv.update_preview_state(&Some(first)).unwrap();
+        assert!(!tv.preview_state.stale);
+
+        // while navigating, the previous preview is kept but marked stale
+        tv.handle_action(&Action::SelectNextEntry).unwrap();
+        let second = Entry::new("second".to_string());
+        tv.update_preview_state(&Some(second)).unwrap();
+        assert!(request_rx.try_recv().is_err());
+        assert!(tv.preview_state.stale);
+        assert_eq!(tv.preview_state.preview.entry_raw, "first");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_copy_preview_waits_for_the_preview() {
+        use crate::channels::{channel::Source, entry::Entry};
+        use crate::previewer::Request;
+        use std::{sync::Arc, time::Duration};
+
+        let mut tv = television(ChannelPrototype::new("letters", ""));
+        let (request_tx, mut request_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (_preview_tx, preview_rx) = tokio::sync::mpsc::unbounded_channel();
+        tv.preview_handles = Some((request_tx, preview_rx));
+        tv.channel.set_source(Source::Static(Arc::new(vec![
+            "a".to_string(),
+            "b".to_string(),
+        ])));
+        tv.channel.load();
+        for _ in 0..100 {
+            tv.update_results_picker_state();
+            if tv.get_selected_entry().is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // the preview isn't there yet: it is requested at once
+        tv.handle_action(&Action::CopyPreviewToClipboard).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Preview(_))));
+        assert_eq!(tv.pending_preview_copy.as_deref(), Some("a"));
+
+        // the copy is dropped once the selection moves on
+        tv.update_preview_state(&Some(Entry::new("b".to_string())))
+            .unwrap();
+        assert_eq!(tv.pending_preview_copy, None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_preview_regenerated_after_resize() {
+        use crate::channels::entry::Entry;
+        use crate::previewer::{Preview, Request};
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+
+        let (mut tv, _action_rx) = television_with(
+            Config::default(),
+            ChannelPrototype::stdin(),
+            PostProcessedCli::default(),
+            Vec::new(),
+        );
+        let (request_tx, mut request_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (preview_tx, preview_rx) = tokio::sync::mpsc::unbounded_channel();
+        tv.preview_handles = Some((request_tx, preview_rx));
+        let resize = |tv: &mut Television, width| {
+            tv.update_ui_state(UiState::new(Layout {
+                preview_window: Some(Rect::new(0, 0, width, 20)),
+                ..Layout::default()
+            }));
+        };
+        resize(&mut tv, 82);
+        let width = tv.preview_content_width();
+        let entry = Some(Entry::new("1".to_string()));
+
+        tv.update_preview_state(&entry).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Preview(_))));
+        preview_tx
+            .send(
+                Preview::new(
+                    "1".to_string(),
+                    String::new(),
+                    "1",
+                    ratatui::text::Text::from("content"),
+                    None,
+                    1,
+                    None,
+                )
+                .with_width(width),
+            )
+            .unwrap();
+        tv.update_preview_state(&entry).unwrap();
+        while request_rx.try_recv().is_ok() {}
+        // up to date: nothing left to request
+        tv.update_preview_state(&entry).unwrap();
+        assert!(request_rx.try_recv().is_err());
+
+        // the preview was generated for the previous width
+        resize(&mut tv, 42);
+        tv.update_preview_state(&entry).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Preview(_))));
+        assert!(tv.preview_state.stale);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pasted_text_is_a_single_pattern_change() {
+        let mut tv = television(ChannelPrototype::stdin());
+        tv.pattern_debounce = PatternDebounce::new(60_000);
+
+        tv.handle_action(&Action::AddInputChar('>')).unwrap();
+        tv.handle_action(&Action::GoToInputStart).unwrap();
+        tv.handle_action(&Action::AddInputString("fö bar ".to_string()))
+            .unwrap();
+
+        assert_eq!(tv.results_picker.input.value(), "fö bar >");
+        assert_eq!(tv.results_picker.input.cursor(), 7);
+        assert_eq!(tv.current_pattern, "fö bar >");
+        assert_eq!(tv.pattern_debounce.flush(), Some("fö bar >".to_string()));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_pattern_debounce_survives_renders() {
+        use std::time::Duration;
+
+        let mut tv = television(ChannelPrototype::stdin());
+        tv.pattern_debounce = PatternDebounce::new(200);
+
+        tv.update(&Action::AddInputChar('a')).unwrap();
+        // renders and early ticks come every frame and don't apply it
+        tv.update(&Action::Render).unwrap();
+        tv.update(&Action::Tick).unwrap();
+        tv.update(&Action::Render).unwrap();
+        assert!(tv.pattern_debounce.pending.is_some());
+
+        std::thread::sleep(Duration::from_millis(250));
+        tv.update(&Action::Render).unwrap();
+        assert!(tv.pattern_debounce.pending.is_some());
+        tv.update(&Action::Tick).unwrap();
+        assert!(tv.pattern_debounce.pending.is_none());
+
+        // navigating needs the results of the current query
+        tv.update(&Action::AddInputChar('b')).unwrap();
+        tv.update(&Action::SelectNextEntry).unwrap();
+        assert!(tv.pattern_debounce.pending.is_none());
+
+        // and so does any other action on the selected entry
+        tv.update(&Action::AddInputChar('c')).unwrap();
+        tv.update(&Action::GoToPrevChar).unwrap();
+        assert!(tv.pattern_debounce.pending.is_some());
+        tv.update(&Action::CopyPreviewToClipboard).unwrap();
+        assert!(tv.pattern_debounce.pending.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_toggle_entry_detail() {
+        use crate::channels::entry::Entry;
+
+        let mut tv = television(ChannelPrototype::stdin());
+
+        // nothing to show without a selected entry
+        tv.handle_action(&Action::ShowEntryDetail).unwrap();
+        assert!(tv.entry_detail.is_none());
+
+        tv.currently_selected = Some(Entry::new("a b".to_string()));
+        tv.handle_action(&Action::ShowEntryDetail).unwrap();
+        let detail = tv.entry_detail.as_ref().unwrap();
+        assert_eq!(detail.raw, "a b");
+        assert_eq!(detail.fields, vec!["a", "b"]);
+
+        tv.handle_action(&Action::ShowEntryDetail).unwrap();
+        assert!(tv.entry_detail.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_help_overlay_captures_navigation() {
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+        use ratatui::layout::Rect;
+
+        let mut tv = television(ChannelPrototype::stdin());
+
+        tv.handle_action(&Action::ToggleHelpOverlay).unwrap();
+        assert!(tv.help_overlay.is_some());
+        tv.update_ui_state(UiState::new(Layout {
+            help_overlay: Some(Rect::new(0, 0, 80, 5)),
+            ..Layout::default()
+        }));
+
+        // navigation scrolls the overlay instead of the results
+        tv.handle_action(&Action::SelectNextEntry).unwrap();
+        assert_eq!(tv.help_overlay.unwrap().scroll, 1);
+        assert_eq!(tv.results_picker.selected(), None);
+        tv.handle_action(&Action::SelectPrevEntry).unwrap();
+        assert_eq!(tv.help_overlay.unwrap().scroll, 0);
+
+        tv.handle_action(&Action::ToggleHelpOverlay).unwrap();
+        assert!(tv.help_overlay.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_inline_edit_restores_query() {
+        use crate::channels::entry::Entry;
+
+        let mut tv = television(ChannelPrototype::stdin());
+
+        tv.handle_action(&Action::AddInputString("fo".to_string()))
+            .unwrap();
+        tv.currently_selected = Some(Entry::new("foo/bar".to_string()));
+        tv.handle_action(&Action::EditEntryInline).unwrap();
+        assert_eq!(tv.results_picker.input.value(), "foo/bar");
+
+        // editing the entry doesn't change the query
+        tv.handle_action(&Action::AddInputChar('z')).unwrap();
+        assert_eq!(tv.current_pattern, "fo");
+        assert_eq!(tv.inline_edit_output().as_deref(), Some("foo/barz"));
+
+        // cancelling restores the query
+        tv.handle_action(&Action::EditEntryInline).unwrap();
+        assert_eq!(tv.results_picker.input.value(), "fo");
+        assert!(tv.inline_edit.is_none());
+        assert!(tv.inline_edit_output().is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_selected_entries_order() {
+        use crate::channels::{channel::Source, entry::Entry};
+        use crate::cli::args::OutputOrder;
+        use std::{sync::Arc, time::Duration};
+
+        let mut tv = television(ChannelPrototype::new("letters", ""));
+        tv.channel.set_source(Source::Static(Arc::new(
+            ["a", "b", "c", "d"].map(String::from).to_vec(),
+        )));
+        tv.channel.load();
+        for _ in 0..100 {
+            tv.channel.results(4, 0);
+            if !tv.channel.running() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        for raw in ["c", "a", "d", "c", "b", "c"] {
+            tv.channel.toggle_selection(&Entry::new(raw.to_string()));
+        }
+        let selected = |tv: &mut Television| {
+            tv.get_selected_entries()
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.raw)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(selected(&mut tv), vec!["a", "d", "b", "c"]);
+        tv.merged_config.output_order = OutputOrder::List;
+        assert_eq!(selected(&mut tv), vec!["a", "b", "c", "d"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_marks_and_marked_filter() {
+        use crate::channels::entry::Entry;
+
+        let (mut tv, mut action_rx) = television_with(
+            Config::default(),
+            ChannelPrototype::new("files", "printf 'a\nb\nc\n'"),
+            PostProcessedCli::default(),
+            Vec::new(),
+        );
+
+        // there's nothing to filter on yet
+        tv.handle_action(&Action::FilterMarked).unwrap();
+        assert!(matches!(action_rx.try_recv(), Ok(Action::Error(_))));
+        assert!(!tv.marked_only);
+
+        for raw in ["a", "c"] {
+            tv.currently_selected = Some(Entry::new(raw.to_string()));
+            tv.handle_action(&Action::ToggleMark).unwrap();
+        }
+        // marks survive query changes
+        tv.apply_pattern("b");
+        assert_eq!(tv.marked_entries.len(), 2);
+
+        tv.handle_action(&Action::FilterMarked).unwrap();
+        assert!(tv.marked_only);
+        let selected = tv.get_selected_entries().unwrap();
+        assert_eq!(selected.len(), 2);
+        assert!(selected.contains(&Entry::new("a".to_string())));
+
+        // unmarking everything turns the filter off
+        for raw in ["a", "c"] {
+            tv.currently_selected = Some(Entry::new(raw.to_string()));
+            tv.handle_action(&Action::ToggleMark).unwrap();
+        }
+        assert!(tv.marked_entries.is_empty());
+        assert!(!tv.marked_only);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_clear_preview_cache() {
+        use crate::previewer::Request;
+
+        let mut config = Config::default();
+        config.ui.preview_cache_size = 3;
+        let (mut tv, _) = television_with(
+            config,
+            ChannelPrototype::stdin(),
+            PostProcessedCli::default(),
+            Vec::new(),
+        );
+        assert_eq!(tv.merged_config.preview_cache_size, 3);
+        let (request_tx, mut request_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (_, preview_rx) = tokio::sync::mpsc::unbounded_channel();
+        tv.preview_handles = Some((request_tx, preview_rx));
+
+        tv.handle_action(&Action::ClearPreviewCache).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::ClearCache)));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_previews_on_hold_while_unfocused() {
+        use crate::channels::entry::Entry;
+        use crate::previewer::Request;
+
+        let mut tv = television(ChannelPrototype::stdin());
+        let (request_tx, mut request_rx) =
+            tokio::sync::mpsc::unbounded_channel();
+        let (_, preview_rx) = tokio::sync::mpsc::unbounded_channel();
+        tv.preview_handles = Some((request_tx, preview_rx));
+        let entry = Some(Entry::new("1".to_string()));
+
+        tv.handle_action(&Action::FocusLost).unwrap();
+        tv.update_preview_state(&entry).unwrap();
+        assert!(request_rx.try_recv().is_err());
+
+        tv.handle_action(&Action::FocusGained).unwrap();
+        tv.update_preview_state(&entry).unwrap();
+        assert!(matches!(request_rx.try_recv(), Ok(Request::Preview(_))));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_notice_is_shown_then_expires() {
+        use std::time::{Duration, Instant};
+
+        let mut tv = television(ChannelPrototype::stdin());
+
+        tv.handle_action(&Action::Notify("Copied 2 entries".to_string()))
+            .unwrap();
+        assert_eq!(
+            tv.dump_context().tv_state.notice.as_deref(),
+            Some("Copied 2 entries")
+        );
+
+        tv.notice = Some((
+            "Copied 2 entries".to_string(),
+            Instant::now() - super::NOTICE_DURATION - Duration::from_secs(1),
+        ));
+        assert!(tv.dump_context().tv_state.notice.is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_only_renders_when_something_changed() {
+        use std::time::{Duration, Instant};
+
+        let mut tv = television(ChannelPrototype::new("files", "echo 1"));
+
+        for _ in 0..super::FIRST_TICKS_TO_RENDER {
+            tv.update(&Action::Tick).unwrap();
+        }
+        // nothing is going on: idle ticks don't render
+        for _ in 0..100 {
+            assert_eq!(tv.update(&Action::Tick).unwrap(), None);
+        }
+
+        assert_eq!(
+            tv.update(&Action::SelectNextEntry).unwrap(),
+            Some(Action::Render)
+        );
+        assert_eq!(tv.update(&Action::Tick).unwrap(), None);
+
+        // an expiring notice needs a redraw to disappear
+        tv.update(&Action::Notify("Copied 1 entry".to_string()))
+            .unwrap();
+        assert_eq!(tv.update(&Action::Tick).unwrap(), None);
+        tv.notice = Some((
+            "Copied 1 entry".to_string(),
+            Instant::now() - super::NOTICE_DURATION - Duration::from_secs(1),
+        ));
+        assert_eq!(tv.update(&Action::Tick).unwrap(), Some(Action::Render));
+        assert_eq!(tv.update(&Action::Tick).unwrap(), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_remote_control_pages_by_its_own_height() {
+        use crate::render::UiState;
+        use crate::screen::layout::Layout;
+        use ratatui::layout::Rect;
+        use std::time::Duration;
+
+        let channels = ["a", "b", "c", "d", "e", "f"]
+            .map(|name| ChannelPrototype::new(name, "true"));
+        let (mut tv, _) = television_with(
+            Config::default(),
+            ChannelPrototype::stdin(),
+            PostProcessedCli::default(),
+            channels.to_vec(),
+        );
+        tv.handle_action(&Action::ToggleRemoteControl).unwrap();
+        // 2 channels fit in the remote control, 18 results in the panel
+        tv.update_ui_state(UiState::new(Layout {
+            results: Rect::new(0, 0, 40, 20),
+            remote_control: Some(Rect::new(40, 0, 40, 7)),
+            ..Layout::default()
+        }));
+        for _ in 0..100 {
+            tv.update_rc_picker_state();
+            if tv.rc_picker.total_items == 6 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(tv.rc_picker.height(), 2);
+        assert_eq!(tv.results_picker.height(), 18);
+
+        tv.handle_action(&Action::SelectNextPage).unwrap();
+        assert_eq!(tv.rc_picker.selected(), Some(2));
+        assert_eq!(tv.rc_picker.offset(), 1);
+        tv.handle_action(&Action::SelectNextPage).unwrap();
+        assert_eq!(tv.rc_picker.selected(), Some(4));
+        assert_eq!(tv.rc_picker.offset(), 3);
+        tv.handle_action(&Action::SelectPrevPage).unwrap();
+        assert_eq!(tv.rc_picker.selected(), Some(2));
+        assert_eq!(tv.rc_picker.offset(), 2);
+    }
+
+    /// A tv on a "files" channel listing static entries, with a preview of
+    /// the first one, set up without running any source or preview command.
+    async fn drawable_television(mut config: Config) -> Television {
+        use crate::channels::channel::Source;
+        use crate::previewer::Preview;
+        use std::{sync::Arc, time::Duration};
+
+        let files = toml::from_str::<ChannelPrototype>(
+            r#"
+            [metadata]
+            name = "files"
+
+            [source]
+            command = "ls"
+
+            [preview]
+            command = "cat {}"
+            "#,
+        )
+        .unwrap();
+        let env = ChannelPrototype::new("env", "printenv");
+        // the glyphs would depend on the locale the tests run in otherwise
+        config.ui.ascii.get_or_insert(false);
+        // the status bar shows the version, which changes with every release
+        config.ui.status_bar.hidden = true;
+        let (mut tv, _) = television_with(
+            config,
+            files.clone(),
+            PostProcessedCli::default(),
+            vec![files, env],
+        );
+        tv.channel.set_source(Source::Static(Arc::new(vec![
+            "alpha.txt".to_string(),
+            "beta.txt".to_string(),
+            "gamma.txt".to_string(),
+        ])));
+        tv.channel.load();
+        // a spinner would be drawn while the source is running
+        for _ in 0..100 {
+            tv.channel.results(10, 0);
+            if !tv.channel.running() && tv.channel.result_count() == 3 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(tv.channel.result_count(), 3);
+        tv.preview_state.update(
+            Preview::new(
+                "alpha.txt".to_string(),
+                "cat alpha.txt".to_string(),
+                "cat alpha",
+                ratatui::text::Text::from(
+                    "first line of alpha\nsecond line of alpha",
+                ),
+                None,
+                2,
+                None,
+            ),
+            0,
+        );
+        tv
+    }
+
+    /// Draw `tv` into a `width` x `height` test terminal.
+    ///
+    /// The first frame lays the panels out, which tells tv how many entries
+    /// fit, and the second one shows them.
+    fn render_buffer(tv: &mut Television, width: u16, height: u16) -> Buffer {
+        use crate::draw::draw;
+        use crate::render::UiState;
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, height)).unwrap();
+        for _ in 0..2 {
+            terminal
+                .draw(|f| {
+                    let drawn = draw(tv.dump_context(), f, f.area()).unwrap();
+                    tv.update_ui_state(UiState::new(drawn.layout));
+                })
+                .unwrap();
+            tv.update_results_picker_state();
+            tv.currently_selected = tv.get_selected_entry();
+            if tv.mode == Mode::RemoteControl {
+                tv.update_rc_picker_state();
+            }
+        }
+        terminal.backend().buffer().clone()
+    }
+
+    /// Draw `tv` into a `width` x `height` test terminal, leaving the styles
+    /// out so that it compares to `Buffer::with_lines`.
+    fn render(tv: &mut Television, width: u16, height: u16) -> Buffer {
+        use ratatui::style::Style;
+
+        let mut buffer = render_buffer(tv, width, height);
+        buffer.set_style(buffer.area, Style::reset());
+        buffer
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_results_and_preview() {
+        let mut tv = drawable_television(Config::default()).await;
+
+        // the input bar on top of the results, the preview next to them
+        assert_eq!(
+            render(&mut tv, 60, 10),
+            Buffer::with_lines([
+                "╭────────── files ───────────╮╭──────── cat alpha ─────────╮",
+                "│>                     1 / 3 ││ first line of alpha        ▲",
+                "╰────────────────────────────╯│ second line of alpha       █",
+                "╭───────── Results ──────────╮│                            █",
+                "│> alpha.txt                 ││                            █",
+                "│  beta.txt                  ││                            █",
+                "│  gamma.txt                 ││                            █",
+                "│                            ││                            █",
+                "│                            ││                            ▼",
+                "╰────────────────────────────╯╰────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_input_at_bottom() {
+        use crate::screen::layout::InputPosition;
+
+        let mut config = Config::default();
+        config.ui.input_bar.position = InputPosition::Bottom;
+        let mut tv = drawable_television(config).await;
+
+        // entries are listed upwards from the input bar
+        assert_eq!(
+            render(&mut tv, 60, 10),
+            Buffer::with_lines([
+                "╭───────── Results ──────────╮╭──────── cat alpha ─────────╮",
+                "│                            ││ first line of alpha        ▲",
+                "│                            ││ second line of alpha       █",
+                "│  gamma.txt                 ││                            █",
+                "│  beta.txt                  ││                            █",
+                "│> alpha.txt                 ││                            █",
+                "╰────────────────────────────╯│                            █",
+                "╭────────────────────────────╮│                            █",
+                "│>                     1 / 3 ││                            ▼",
+                "╰────────── files ───────────╯╰────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_borderless() {
+        let mut config = Config::default();
+        config.ui.borderless = true;
+        let mut tv = drawable_television(config).await;
+
+        // single lines set the input bar and the preview apart
+        assert_eq!(
+            render(&mut tv, 40, 10),
+            Buffer::with_lines([
+                ">             1 / 3 │     cat alpha     ",
+                "────────────────────│ first line of alp ",
+                "> alpha.txt         │ second line of al ",
+                "  beta.txt          │                   ",
+                "  gamma.txt         │                   ",
+                "                    │                   ",
+                "                    │                   ",
+                "                    │                   ",
+                "                    │                   ",
+                "                    │                   ",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_wide_characters() {
+        use crate::channels::channel::Source;
+        use std::{sync::Arc, time::Duration};
+        use unicode_width::UnicodeWidthStr;
+
+        let mut config = Config::default();
+        config.ui.preview_panel.hidden = true;
+        let mut tv = drawable_television(config).await;
+        tv.channel.set_source(Source::Static(Arc::new(
+            ["「メモ」.md", "日本語のメモ.txt", "notes.txt"]
+                .map(String::from)
+                .to_vec(),
+        )));
+        tv.channel.load();
+        tv.apply_pattern("メモ");
+        for _ in 0..100 {
+            tv.channel.results(10, 0);
+            if !tv.channel.running() && tv.channel.result_count() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(tv.channel.result_count(), 2);
+
+        let buffer = render_buffer(&mut tv, 40, 8);
+        // the cells of a row, leaving out those hidden by the wide glyphs
+        // spilling over them
+        let cells = |y: u16| {
+            let mut cells = Vec::new();
+            let mut x = 0;
+            while x < buffer.area.width {
+                let width = buffer[(x, y)].symbol().width().max(1);
+                cells.push(&buffer[(x, y)]);
+                x += u16::try_from(width).unwrap();
+            }
+            cells
+        };
+        let selected = tv.get_selected_entry().unwrap().raw;
+        let colors = &tv.colorscheme.results;
+        for entry in ["「メモ」.md", "日本語のメモ.txt"] {
+            let y = (0..buffer.area.height)
+                .find(|&y| {
+                    cells(y)
+                        .iter()
+                        .map(|cell| cell.symbol())
+                        .collect::<String>()
+                        .contains(entry)
+                })
+                .unwrap();
+            let row = cells(y);
+            // exactly the glyphs of "メモ" are highlighted
+            let highlighted: String = row
+                .iter()
+                .filter(|cell| cell.fg == colors.match_foreground_color)
+                .map(|cell| cell.symbol())
+                .collect();
+            assert_eq!(highlighted, "メモ", "{entry}");
+            // and the background of the selected entry runs up to the border
+            if entry == selected {
+                assert!(
+                    row[1..row.len() - 1]
+                        .iter()
+                        .all(|cell| cell.bg == colors.result_selected_bg)
+                );
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_borderless_without_input_decorations() {
+        use crate::screen::layout::InputPosition;
+        use crate::utils::input::Input;
+
+        let mut config = Config::default();
+        config.ui.borderless = true;
+        config.ui.hide_input_decorations = true;
+        config.ui.input_bar.position = InputPosition::Bottom;
+        config.ui.preview_panel.hidden = true;
+        let mut tv = drawable_television(config).await;
+        tv.results_picker.input = Input::new("query".to_string());
+
+        // only the query is left in the input bar, under a separator
+        assert_eq!(
+            render(&mut tv, 40, 10),
+            Buffer::with_lines([
+                "                                        ",
+                "                                        ",
+                "                                        ",
+                "                                        ",
+                "                                        ",
+                "  gamma.txt                             ",
+                "  beta.txt                              ",
+                "> alpha.txt                             ",
+                "────────────────────────────────────────",
+                "query                                   ",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_custom_titles() {
+        let mut config = Config::default();
+        config.ui.input_bar.header = Some("{mode}: {channel}".to_string());
+        config.ui.results_panel.title = Some(String::new());
+        let mut tv = drawable_television(config).await;
+
+        assert_eq!(
+            render(&mut tv, 60, 10),
+            Buffer::with_lines([
+                "╭────── Channel: files ──────╮╭──────── cat alpha ─────────╮",
+                "│>                     1 / 3 ││ first line of alpha        ▲",
+                "╰────────────────────────────╯│ second line of alpha       █",
+                "╭────────────────────────────╮│                            █",
+                "│> alpha.txt                 ││                            █",
+                "│  beta.txt                  ││                            █",
+                "│  gamma.txt                 ││                            █",
+                "│                            ││                            █",
+                "│                            ││                            ▼",
+                "╰────────────────────────────╯╰────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_remote_control() {
+        use std::time::Duration;
+
+        let mut config = Config::default();
+        config.ui.remote_control.show_channel_descriptions = false;
+        let mut tv = drawable_television(config).await;
+        tv.handle_action(&Action::ToggleRemoteControl).unwrap();
+        assert_eq!(tv.mode, Mode::RemoteControl);
+        for _ in 0..100 {
+            tv.update_rc_picker_state();
+            if tv.rc_picker.total_items == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        // the channels pop up over the middle of the screen, too short for
+        // more than one of them and the remote's logo
+        assert_eq!(
+            render(&mut tv, 100, 12),
+            Buffer::with_lines([
+                "╭──────────────────── files ───── Remote Control ╮╭────────────────── cat alpha ───────────────────╮",
+                "│>                                         1 / 3 ││ first line of alpha                            ▲",
+                "╰────────────────────────────────────────────────╯│ second line of alpha                           █",
+                "╭──────────────────╭───────────────────────── Channels ─────────────────────────╮                  █",
+                "│> alpha.txt       │> 🍿 env                                                    │                  █",
+                "│  beta.txt        ╰ 2/2 ─────────────────────────────────────────────────────  ╯                  █",
+                "│  gamma.txt       ╭────────────────────────── Search ──────────────────────────╮                  █",
+                "│                  │>                                                           │                  █",
+                "│                  ╰────────────────────────────────────────────────────────────╯                  █",
+                "│                                                ││                                                █",
+                "│                                                ││                                                ▼",
+                "╰────────────────────────────────────────────────╯╰────────────────────────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_help_panel() {
+        let mut tv = drawable_television(Config::default()).await;
+        tv.handle_action(&Action::ToggleHelp).unwrap();
+
+        // the help panel floats over the bottom right corner, listing no
+        // keybindings as the config doesn't define any
+        assert_eq!(
+            render(&mut tv, 60, 12),
+            Buffer::with_lines([
+                "╭────────── files ───────────╮╭──────── cat alpha ─────────╮",
+                "│>                     1 / 3 ││ first line of alpha        ▲",
+                "╰────────────────────────────╯│ second line of alpha       █",
+                "╭───────── Results ──────────╮│  ╭──────── Help ─────────╮ █",
+                "│> alpha.txt                 ││  │ Channel Mode          │ █",
+                "│  beta.txt                  ││  │                       │ █",
+                "│  gamma.txt                 ││  │                       │ █",
+                "│                            ││  │                       │ █",
+                "│                            ││  │                       │ █",
+                "│                            ││  │                       │ █",
+                "│                            ││  ╰───────────────────────╯ ▼",
+                "╰────────────────────────────╯╰────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_logs_panel() {
+        use crate::logging::TuiLogLayer;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let subscriber = tracing_subscriber::registry().with(TuiLogLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("log panel draw test");
+        });
+        let mut tv = drawable_television(Config::default()).await;
+        tv.handle_action(&Action::ToggleLogs).unwrap();
+        // leave out what the other tests logged
+        tv.log_panel.as_mut().unwrap().filter =
+            "log panel draw test".to_string();
+
+        // the log panel floats over the bottom of the screen
+        assert_eq!(
+            render(&mut tv, 60, 10),
+            Buffer::with_lines([
+                "╭────────── files ───────────╮╭──────── cat alpha ─────────╮",
+                "│>                     1 / 3 ││ first line of alpha        ▲",
+                "╰────────────────────────────╯│ second line of alpha       █",
+                "╭───────── Results ──────────╮│                            █",
+                "│> alpha.txt                 ││                            █",
+                "╭────────────────────────── Logs ──────── ≤ TRACE · follow ╮",
+                "│INFO  log panel draw test                                 │",
+                "│                                                          │",
+                "│                                                          │",
+                "╰ /log panel draw test ────────────────────────────────────╯",
+            ])
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_draw_pathological_sizes() {
+        use crate::screen::layout::{InputPosition, Orientation};
+
+        let sizes = [(1, 1), (3, 200), (200, 2), (200, 1), (1, 200), (0, 0)];
+        let mut borderless = Config::default();
+        borderless.ui.borderless = true;
+        let mut portrait = Config::default();
+        portrait.ui.orientation = Orientation::Portrait;
+        portrait.ui.input_bar.position = InputPosition::Bottom;
+
+        for config in [Config::default(), borderless, portrait] {
+            let mut tv = drawable_television(config).await;
+            for action in [
+                None,
+                Some(Action::ToggleStatusBar),
+                Some(Action::ToggleHelp),
+                Some(Action::ToggleLogs),
+                Some(Action::ToggleHelpOverlay),
+                Some(Action::ToggleRemoteControl),
+            ] {
+                if let Some(action) = action {
+                    tv.handle_action(&action).unwrap();
+                }
+                // none of these should panic
+                for (width, height) in sizes {
+                    render(&mut tv, width, height);
+                }
+            }
+        }
+    }
 }
@@ -7,7 +7,10 @@ use std::{
 use anyhow::Result;
 use crossterm::{
     cursor,
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
+        EnableBracketedPaste, EnableFocusChange, EnableMouseCapture,
+    },
     execute,
     terminal::{
         ClearType, EnterAlternateScreen, LeaveAlternateScreen, ScrollUp,
@@ -49,8 +52,16 @@ pub struct Tui<W>
 where
     W: Write,
 {
-    pub terminal: ratatui::Terminal<CrosstermBackend<W>>,
+    /// Only taken out by `Tui::into_terminal`.
+    terminal: Option<ratatui::Terminal<CrosstermBackend<W>>>,
     pub viewport: Viewport,
+    /// Whether the terminal was handed over by the caller, see
+    /// `Tui::with_terminal`.
+    borrowed: bool,
+    /// Whether raw mode was off before a borrowed terminal was handed over.
+    restore_raw_mode: bool,
+    /// Whether the borrowed terminal is currently set up for tv.
+    entered: bool,
 }
 
 pub const TESTING_ENV_VAR: &str = "TV_TEST";
@@ -124,7 +135,44 @@ where
 
         options.viewport = viewport.clone();
         let terminal = Terminal::with_options(backend, options)?;
-        Ok(Self { terminal, viewport })
+        Ok(Self {
+            terminal: Some(terminal),
+            viewport,
+            borrowed: false,
+            restore_raw_mode: false,
+            entered: false,
+        })
+    }
+
+    /// Draw on a terminal owned by the caller, e.g. an application embedding
+    /// tv, over its whole area.
+    ///
+    /// The alternate screen is left alone, raw mode is only turned off again
+    /// on exit if it was off to begin with, and the terminal can be taken
+    /// back with `Tui::into_terminal`.
+    pub fn with_terminal(
+        terminal: Terminal<CrosstermBackend<W>>,
+    ) -> Result<Self> {
+        let restore_raw_mode = !is_raw_mode_enabled()?;
+        if restore_raw_mode {
+            enable_raw_mode()?;
+        }
+        Ok(Self {
+            terminal: Some(terminal),
+            viewport: Viewport::Fullscreen,
+            borrowed: true,
+            restore_raw_mode,
+            entered: false,
+        })
+    }
+
+    /// Exit and hand the terminal back.
+    pub fn into_terminal(mut self) -> Result<Terminal<CrosstermBackend<W>>> {
+        self.exit()?;
+        Ok(self
+            .terminal
+            .take()
+            .expect("the terminal was already taken"))
     }
 
     /// Handles scrolling logic when there's insufficient space for the requested height.
@@ -234,32 +282,47 @@ where
         if self.viewport != Viewport::Fullscreen {
             self.viewport = Viewport::Fixed(layout);
         }
-        self.terminal.resize(layout)?;
+        self.resize(layout)?;
 
         Ok(())
     }
 
     pub fn enter(&mut self) -> Result<()> {
-        let backend = self.terminal.backend_mut();
-
-        execute!(backend, EnableMouseCapture)?;
-
-        if self.viewport == Viewport::Fullscreen {
+        let borrowed = self.borrowed;
+        let fullscreen = self.viewport == Viewport::Fullscreen;
+        let backend = self.backend_mut();
+
+        execute!(
+            backend,
+            EnableMouseCapture,
+            EnableFocusChange,
+            EnableBracketedPaste
+        )?;
+
+        // a borrowed terminal stays on whichever screen the caller is using
+        if fullscreen && !borrowed {
             execute!(backend, EnterAlternateScreen)?;
-            self.terminal.clear()?;
         }
+        if fullscreen {
+            self.clear()?;
+        }
+        self.entered = true;
         Ok(())
     }
 
     pub fn exit(&mut self) -> Result<()> {
+        if self.borrowed {
+            return self.exit_borrowed();
+        }
         if is_raw_mode_enabled()? {
             debug!("Exiting terminal");
-            let backend = self.terminal.backend_mut();
+            let viewport = self.viewport.clone();
+            let backend = self.backend_mut();
 
             disable_raw_mode()?;
 
             // Move cursor to the top of the application area.
-            if let Viewport::Fixed(rect) = self.viewport {
+            if let Viewport::Fixed(rect) = viewport {
                 execute!(backend, cursor::MoveTo(0, rect.y))?;
             }
 
@@ -269,9 +332,14 @@ where
             )?;
 
             execute!(backend, cursor::Show)?;
-            execute!(backend, DisableMouseCapture)?;
+            execute!(
+                backend,
+                DisableMouseCapture,
+                DisableFocusChange,
+                DisableBracketedPaste
+            )?;
 
-            if self.viewport == Viewport::Fullscreen {
+            if viewport == Viewport::Fullscreen {
                 execute!(backend, LeaveAlternateScreen)?;
             }
         }
@@ -279,6 +347,27 @@ where
         Ok(())
     }
 
+    /// Give a borrowed terminal back in the state it was handed over in,
+    /// with tv's frame cleared.
+    fn exit_borrowed(&mut self) -> Result<()> {
+        if !self.entered {
+            return Ok(());
+        }
+        debug!("Exiting borrowed terminal");
+        self.entered = false;
+        if self.restore_raw_mode {
+            disable_raw_mode()?;
+        }
+        execute!(
+            self.backend_mut(),
+            DisableMouseCapture,
+            DisableFocusChange,
+            DisableBracketedPaste
+        )?;
+        self.clear()?;
+        Ok(())
+    }
+
     pub fn suspend(&mut self) -> Result<()> {
         self.exit()?;
         #[cfg(not(windows))]
@@ -287,11 +376,33 @@ where
     }
 
     pub fn resume(&mut self) -> Result<()> {
+        if !is_raw_mode_enabled()? {
+            enable_raw_mode()?;
+        }
         self.enter()?;
         Ok(())
     }
 }
 
+/// Put the terminal back into a usable state without going through a `Tui`.
+///
+/// This is meant for places where the `Tui` isn't reachable, such as the
+/// panic hook.
+pub fn restore_terminal() -> Result<()> {
+    if is_raw_mode_enabled()? {
+        disable_raw_mode()?;
+    }
+    execute!(
+        stderr(),
+        DisableMouseCapture,
+        DisableFocusChange,
+        DisableBracketedPaste,
+        LeaveAlternateScreen,
+        cursor::Show
+    )?;
+    Ok(())
+}
+
 impl<W> Deref for Tui<W>
 where
     W: Write,
@@ -299,7 +410,9 @@ where
     type Target = ratatui::Terminal<CrosstermBackend<W>>;
 
     fn deref(&self) -> &Self::Target {
-        &self.terminal
+        self.terminal
+            .as_ref()
+            .expect("the terminal was already taken")
     }
 }
 
@@ -308,7 +421,9 @@ where
     W: Write,
 {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.terminal
+        self.terminal
+            .as_mut()
+            .expect("the terminal was already taken")
     }
 }
 
@@ -317,6 +432,10 @@ where
     W: Write,
 {
     fn drop(&mut self) {
+        // handed back through `Tui::into_terminal`, which already exited
+        if self.terminal.is_none() {
+            return;
+        }
         match self.exit() {
             Ok(()) => debug!("Successfully exited terminal"),
             Err(e) => debug!("Failed to exit terminal: {:?}", e),
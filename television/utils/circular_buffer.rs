@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+/// A fixed-capacity buffer that drops its oldest items once full.
+///
+/// # Example
+/// ```
+/// use television::utils::circular_buffer::CircularBuffer;
+///
+/// let mut buffer = CircularBuffer::new(2);
+/// buffer.push(1);
+/// buffer.push(2);
+/// buffer.push(3);
+/// assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3]);
+/// assert_eq!(buffer.iter().rev().copied().collect::<Vec<_>>(), vec![3, 2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircularBuffer<T> {
+    items: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> CircularBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            items: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Push an item, evicting the oldest one if the buffer is full.
+    pub fn push(&mut self, item: T) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.items.len() == self.capacity {
+            self.items.pop_front();
+        }
+        self.items.push_back(item);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Iterate over the items from oldest to newest (use `.rev()` for the
+    /// other way around).
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_evicts_oldest() {
+        let mut buffer = CircularBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(i);
+        }
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut buffer = CircularBuffer::new(0);
+        buffer.push(1);
+        assert!(buffer.is_empty());
+    }
+}
@@ -24,8 +24,9 @@ SOFTWARE.
 
 use std::ffi::OsString;
 
-use crate::utils::rocell::RoCell;
+use crate::{config::ui::ClipboardMode, utils::rocell::RoCell};
 use parking_lot::Mutex;
+use tracing::warn;
 
 pub static CLIPBOARD: RoCell<Clipboard> = RoCell::new();
 
@@ -79,23 +80,30 @@ impl Clipboard {
         self.content.lock().clone()
     }
 
+    /// Copy `s` to the clipboard, returning whether it could be copied.
+    ///
+    /// In `Auto` mode, the system clipboard tools are tried first and the
+    /// OSC 52 escape sequence is only used as a fallback.
+    pub async fn set(
+        &self,
+        s: impl AsRef<std::ffi::OsStr>,
+        mode: ClipboardMode,
+    ) -> bool {
+        s.as_ref().clone_into(&mut self.content.lock());
+        if mode == ClipboardMode::Auto && Self::set_system(s.as_ref()).await {
+            return true;
+        }
+        osc52::set(s.as_ref())
+            .inspect_err(|e| warn!("Unable to copy using OSC 52: {}", e))
+            .is_ok()
+    }
+
     #[cfg(unix)]
-    pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
-        use std::{
-            io::{BufWriter, stderr},
-            process::Stdio,
-        };
+    async fn set_system(s: &std::ffi::OsStr) -> bool {
+        use std::process::Stdio;
 
-        use crossterm::execute;
         use tokio::{io::AsyncWriteExt, process::Command};
 
-        s.as_ref().clone_into(&mut self.content.lock());
-        execute!(
-            BufWriter::new(stderr()),
-            osc52::SetClipboard::new(s.as_ref())
-        )
-        .ok();
-
         let all = [
             ("pbcopy", &[][..]),
             ("termux-clipboard-set", &[]),
@@ -115,40 +123,39 @@ impl Clipboard {
 
             let Ok(mut child) = cmd else { continue };
 
-            let mut stdin = child.stdin.take().unwrap();
-            if stdin
-                .write_all(s.as_ref().as_encoded_bytes())
-                .await
-                .is_err()
-            {
+            let Some(mut stdin) = child.stdin.take() else {
+                continue;
+            };
+            if stdin.write_all(s.as_encoded_bytes()).await.is_err() {
                 continue;
             }
             drop(stdin);
 
             if child.wait().await.is_ok_and(|s| s.success()) {
-                break;
+                return true;
             }
         }
+        false
     }
 
     #[cfg(windows)]
-    pub async fn set(&self, s: impl AsRef<std::ffi::OsStr>) {
+    async fn set_system(s: &std::ffi::OsStr) -> bool {
         use clipboard_win::{formats, set_clipboard};
 
-        let s = s.as_ref().to_owned();
-        *self.content.lock() = s.clone();
-
+        let s = s.to_owned();
         tokio::task::spawn_blocking(move || {
             set_clipboard(formats::Unicode, s.to_string_lossy())
         })
         .await
-        .ok();
+        .is_ok_and(|r| r.is_ok())
     }
 }
 
-#[cfg(unix)]
 mod osc52 {
-    use std::ffi::OsStr;
+    use std::{
+        ffi::OsStr,
+        io::{BufWriter, stderr},
+    };
 
     use base64::{Engine, engine::general_purpose};
 
@@ -173,5 +180,22 @@ mod osc52 {
         ) -> std::fmt::Result {
             write!(f, "\x1b]52;c;{}\x1b\\", self.content)
         }
+
+        #[cfg(windows)]
+        fn execute_winapi(&self) -> std::io::Result<()> {
+            Err(std::io::Error::other(
+                "OSC 52 requires ANSI escape sequences support",
+            ))
+        }
+    }
+
+    /// Ask the terminal to copy `content` through the OSC 52 escape sequence.
+    ///
+    /// This goes through stderr, which is where the UI is drawn.
+    pub fn set(content: &OsStr) -> std::io::Result<()> {
+        crossterm::execute!(
+            BufWriter::new(stderr()),
+            SetClipboard::new(content)
+        )
     }
 }
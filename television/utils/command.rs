@@ -1,18 +1,23 @@
 use crate::{
     channels::{
         entry::Entry,
-        prototypes::{ActionSpec, ExecutionMode, Template},
+        prototypes::{ActionSpec, Delimiter, ExecutionMode, Template},
+    },
+    utils::{
+        hashmaps::FxIndexSet,
+        shell::Shell,
+        strings::{CMD_RE, SPACE},
     },
-    utils::{shell::Shell, strings::SPACE},
 };
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use lazy_regex::{Lazy, Regex, regex};
-use rustc_hash::FxHashSet;
 #[cfg(unix)]
 use std::os::unix::process::CommandExt;
 use std::{
     collections::HashMap,
-    process::{Command, ExitStatus, Stdio},
+    io::Write,
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::OnceLock,
 };
 use tracing::debug;
 
@@ -21,10 +26,120 @@ static COMPLEX_BRACES_REGEX: &Lazy<Regex> = regex!(r"\{[^}]+\}");
 #[cfg(not(unix))]
 use tracing::warn;
 
+/// The shell commands are run through, set from the `shell` configuration
+/// option at startup.
+static COMMAND_SHELL: OnceLock<CommandShell> = OnceLock::new();
+
+/// A shell to run commands through: its program followed by the arguments
+/// preceding the command, e.g. `pwsh -NoProfile -Command`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandShell {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Which shell this is, which decides how arguments are quoted.
+    pub kind: Shell,
+}
+
+impl From<Shell> for CommandShell {
+    fn from(shell: Shell) -> Self {
+        let args: &[&str] = match shell {
+            Shell::Psh => &["-NoLogo", "-NoProfile", "-Command"],
+            Shell::Cmd => &["/C"],
+            _ => &["-c"],
+        };
+        Self {
+            program: shell.executable().to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+            kind: shell,
+        }
+    }
+}
+
+impl CommandShell {
+    /// The shell of the `shell` configuration option, e.g.
+    /// `["pwsh", "-NoProfile", "-Command"]`.
+    pub fn from_config(shell: &[String]) -> Result<Self> {
+        let Some((program, args)) = shell.split_first() else {
+            anyhow::bail!("The `shell` option needs at least a program");
+        };
+        Ok(Self {
+            program: program.clone(),
+            args: args.to_vec(),
+            kind: Shell::try_from(program.as_str()).unwrap_or_default(),
+        })
+    }
+
+    /// The shell to use when none is configured: `$SHELL` on Unix,
+    /// PowerShell on Windows (`pwsh` if it is installed), as `cmd` can't
+    /// run most of the commands written for POSIX shells.
+    pub fn detect() -> Self {
+        #[cfg(windows)]
+        if std::env::var("SHELL").is_err() {
+            let mut shell = Self::from(Shell::Psh);
+            if which::which("pwsh").is_ok() {
+                shell.program = "pwsh".to_string();
+            }
+            return shell;
+        }
+        Self::from(Shell::from_env().unwrap_or_default())
+    }
+
+    /// Create the command running `command` through this shell.
+    ///
+    /// # Arguments
+    /// * `command` - The command string to execute
+    /// * `interactive` - Whether to run in interactive mode (Unix only)
+    /// * `envs` - Environment variables to set for the command
+    pub fn command<S>(
+        &self,
+        command: &str,
+        interactive: bool,
+        envs: &HashMap<String, String, S>,
+    ) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+
+        #[cfg(unix)]
+        if interactive {
+            cmd.arg("-i");
+        }
+
+        #[cfg(not(unix))]
+        if interactive {
+            warn!("Interactive mode is not supported on Windows.");
+        }
+
+        cmd.envs(envs);
+        // `cmd` parses its command line by itself, which the quoting of
+        // regular arguments would get in the way of
+        #[cfg(windows)]
+        if self.kind == Shell::Cmd {
+            std::os::windows::process::CommandExt::raw_arg(&mut cmd, command);
+            return cmd;
+        }
+        cmd.arg(command);
+        cmd
+    }
+}
+
+/// Run all commands through `shell` from now on.
+///
+/// This is meant to be called once at startup, later calls are ignored.
+pub fn set_command_shell(shell: CommandShell) {
+    if COMMAND_SHELL.set(shell).is_err() {
+        debug!("The command shell was already set");
+    }
+}
+
+/// The shell commands are run through.
+pub fn command_shell() -> &'static CommandShell {
+    COMMAND_SHELL.get_or_init(CommandShell::detect)
+}
+
 /// Create a shell command configured for the current platform
 ///
-/// Creates a `Command` instance configured with the appropriate shell for the current platform
-/// and sets up the command to execute the provided command string.
+/// Creates a `Command` instance running the provided command string through
+/// the configured shell (see [`command_shell`]).
 ///
 /// # Arguments
 /// * `command` - The command string to execute
@@ -38,27 +153,7 @@ pub fn shell_command<S>(
     interactive: bool,
     envs: &HashMap<String, String, S>,
 ) -> Command {
-    let shell = Shell::from_env().unwrap_or_default();
-    let mut cmd = Command::new(shell.executable());
-
-    cmd.args(match shell {
-        Shell::Psh => vec!["-NoLogo", "-NoProfile", "-Command"],
-        Shell::Cmd => vec!["/C"],
-        _ => vec!["-c"],
-    });
-
-    #[cfg(unix)]
-    if interactive {
-        cmd.arg("-i");
-    }
-
-    #[cfg(not(unix))]
-    if interactive {
-        warn!("Interactive mode is not supported on Windows.");
-    }
-
-    cmd.envs(envs).arg(command);
-    cmd
+    command_shell().command(command, interactive, envs)
 }
 
 /// Format a command string from entries using template processing
@@ -80,8 +175,8 @@ pub fn shell_command<S>(
 ///     channels::{entry::Entry, prototypes::Template},
 ///     utils::command::format_command
 /// };
-/// # use rustc_hash::FxHashSet;
-/// let mut entries = FxHashSet::default();
+/// # use television::utils::hashmaps::FxIndexSet;
+/// let mut entries = FxIndexSet::default();
 /// entries.insert(Entry::new("file1.txt".to_string()));
 /// entries.insert(Entry::new("file 2.txt".to_string()));
 /// let template = Template::parse("nvim {split:\\n:..|map:{append:'|prepend:'}|join: }").unwrap();
@@ -92,7 +187,17 @@ pub fn shell_command<S>(
 /// assert!(result.contains("'file 2.txt'"));
 /// ```
 pub fn format_command(
-    entries: &FxHashSet<Entry>,
+    entries: &FxIndexSet<Entry>,
+    template: &Template,
+    separator: &str,
+) -> Result<String> {
+    format_command_for(command_shell().kind, entries, template, separator)
+}
+
+/// [`format_command`] quoting entries for `shell`.
+fn format_command_for(
+    shell: Shell,
+    entries: &FxIndexSet<Entry>,
     template: &Template,
     separator: &str,
 ) -> Result<String> {
@@ -115,7 +220,7 @@ pub fn format_command(
         // Multiple entries: quote each and join with spaces
         let quoted_entries: Vec<String> = entries
             .iter()
-            .map(|entry| format!("'{}'", entry.raw.replace('\'', r"\'")))
+            .map(|entry| shell.quote(&entry.raw))
             .collect();
         let entries_joined = quoted_entries.join(SPACE);
         let formatted_command = template_str.replace("{}", &entries_joined);
@@ -140,6 +245,206 @@ pub fn format_command(
     }
 }
 
+/// Format a single entry with the given template.
+///
+/// Positional placeholders like `{0}` and `{1}` are replaced with the fields
+/// obtained by splitting the entry on `delimiter`, the rest of the template
+/// is then evaluated the same way preview commands are.
+///
+/// # Example
+/// ```
+/// use television::{
+///     channels::prototypes::{Delimiter, Template},
+///     utils::command::format_entry,
+/// };
+///
+/// let template = Template::parse("{0}:{1}").unwrap();
+/// let formatted = format_entry(
+///     &template,
+///     "src/main.rs 42 fn main()",
+///     &Delimiter::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(formatted, "src/main.rs:42");
+/// ```
+pub fn format_entry(
+    template: &Template,
+    entry: &str,
+    delimiter: &Delimiter,
+) -> Result<String> {
+    format_entry_with(template, entry, || delimiter.split(entry))
+}
+
+/// Format a single entry with the given template, like [`format_entry`],
+/// `fields` only being called for the fields of the entry when the template
+/// has positional placeholders.
+pub fn format_entry_with<'a>(
+    template: &Template,
+    entry: &'a str,
+    fields: impl FnOnce() -> Vec<&'a str>,
+) -> Result<String> {
+    if !CMD_RE.is_match(template.raw()) {
+        return template.format(entry);
+    }
+
+    let fields = fields();
+    let substituted =
+        CMD_RE.replace_all(template.raw(), |caps: &regex::Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| fields.get(index))
+                .copied()
+                .unwrap_or_default()
+                .to_string()
+        });
+    // nothing left for the template engine to evaluate
+    if !substituted.contains('{') {
+        return Ok(substituted.into_owned());
+    }
+    Template::parse(&substituted)
+        .map_err(|e| anyhow!(e))?
+        .format(entry)
+}
+
+/// Split a formatted command into its program and arguments without
+/// invoking a shell.
+///
+/// Single and double quotes group words together and a backslash escapes the
+/// next character, including inside single quotes so that the output of
+/// [`format_command`] round-trips.
+///
+/// # Example
+/// ```
+/// use television::utils::command::split_command_line;
+///
+/// assert_eq!(
+///     split_command_line("nvim 'file 1.txt' \"file2\""),
+///     vec!["nvim", "file 1.txt", "file2"]
+/// );
+/// ```
+pub fn split_command_line(command: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = command.chars();
+    while let Some(c) = chars.next() {
+        match (c, quote) {
+            ('\\', _) => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+                in_word = true;
+            }
+            ('\'' | '"', None) => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (c, _) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    args
+}
+
+/// The captured result of running an external action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// The formatted command that was run.
+    pub command: String,
+    /// The exit code of the command (`None` if killed by a signal).
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    pub fn success(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+
+    /// A short description of a failed command, including its stderr.
+    pub fn error_message(&self) -> String {
+        format!(
+            "`{}` failed ({}): {}",
+            self.command,
+            self.exit_code_label(),
+            self.stderr.trim()
+        )
+    }
+
+    /// A human readable summary of the command, its exit status and output.
+    pub fn summary(&self) -> String {
+        let mut summary =
+            format!("$ {}\n[{}]\n", self.command, self.exit_code_label());
+        for output in [&self.stdout, &self.stderr] {
+            if !output.is_empty() {
+                summary.push_str(output);
+                if !output.ends_with('\n') {
+                    summary.push('\n');
+                }
+            }
+        }
+        summary
+    }
+
+    fn exit_code_label(&self) -> String {
+        self.exit_code.map_or_else(
+            || "terminated by signal".to_string(),
+            |code| format!("exit code {code}"),
+        )
+    }
+}
+
+/// Run an external action in a child process and capture its output instead
+/// of handing it the terminal.
+pub fn capture_action(
+    action_spec: &ActionSpec,
+    entries: &FxIndexSet<Entry>,
+) -> Result<CommandOutput> {
+    let template = action_spec.command.get_nth(0);
+    let stdin = action_stdin(action_spec, entries);
+    let formatted_command = if stdin.is_some() {
+        template.raw().to_string()
+    } else {
+        format_command(entries, template, &action_spec.separator)?
+    };
+    debug!("Capturing output of: {:?}", formatted_command);
+
+    let mut cmd = shell_command(
+        &formatted_command,
+        action_spec.command.interactive,
+        &action_spec.command.env,
+    );
+    cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
+    let output = match stdin {
+        Some(input) => {
+            spawn_with_stdin(&mut cmd, input)?.wait_with_output()?
+        }
+        None => cmd.stdin(Stdio::null()).output()?,
+    };
+
+    Ok(CommandOutput {
+        command: formatted_command,
+        exit_code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
 /// Execute an external action with the appropriate execution mode and output handling
 ///
 /// Takes an `ActionSpec` and a set of entries, creates a command using the action's template,
@@ -154,26 +459,47 @@ pub fn format_command(
 ///
 /// # Behavior
 /// - `ExecutionMode::Execute` - make the current process become what the command does
+/// - `ExecutionMode::Become` - same as `Execute` but without the shell wrapper
 /// - `ExecutionMode::Fork` - spawns the command as a child process
 pub fn execute_action(
     action_spec: &ActionSpec,
-    entries: &FxHashSet<Entry>,
+    entries: &FxIndexSet<Entry>,
 ) -> Result<ExitStatus> {
     debug!("Executing external action with {} entries", entries.len());
 
     let template: &Template = action_spec.command.get_nth(0);
-    let formatted_command =
-        format_command(entries, template, &action_spec.separator)?;
+    let stdin = action_stdin(action_spec, entries);
+    let formatted_command = if stdin.is_some() {
+        template.raw().to_string()
+    } else {
+        format_command(entries, template, &action_spec.separator)?
+    };
+
+    let mut cmd = if action_spec.mode == ExecutionMode::Become {
+        let args = split_command_line(&formatted_command);
+        let Some((program, args)) = args.split_first() else {
+            anyhow::bail!("Empty command: {:?}", formatted_command);
+        };
+        let mut cmd = Command::new(program);
+        cmd.args(args).envs(&action_spec.command.env);
+        cmd
+    } else {
+        shell_command(
+            &formatted_command,
+            action_spec.command.interactive,
+            &action_spec.command.env,
+        )
+    };
 
-    let mut cmd = shell_command(
-        &formatted_command,
-        action_spec.command.interactive,
-        &action_spec.command.env,
-    );
+    // the process has to stick around to write the command its input
+    if let Some(input) = stdin {
+        cmd.stdout(Stdio::inherit()).stderr(Stdio::inherit());
+        return Ok(spawn_with_stdin(&mut cmd, input)?.wait()?);
+    }
 
     #[cfg(unix)]
     match action_spec.mode {
-        ExecutionMode::Execute => {
+        ExecutionMode::Execute | ExecutionMode::Become => {
             let err = cmd.exec();
             eprintln!("Failed to execute command: {}", err);
             Err(err.into())
@@ -191,9 +517,10 @@ pub fn execute_action(
     // On windows we can't replace the current process, so we always fork
     #[cfg(not(unix))]
     {
-        if action_spec.mode == ExecutionMode::Execute {
+        if action_spec.mode != ExecutionMode::Fork {
             debug!(
-                "ExecutionMode::Execute is not supported on Windows. Falling back to Fork."
+                "{:?} is not supported on Windows. Falling back to Fork.",
+                action_spec.mode
             );
         }
         cmd.stdin(Stdio::inherit())
@@ -205,14 +532,97 @@ pub fn execute_action(
     }
 }
 
+/// What the action's command reads on its stdin, if it reads the entries
+/// there rather than having them substituted into it: one entry per line, in
+/// the order they were selected in.
+fn action_stdin(
+    action_spec: &ActionSpec,
+    entries: &FxIndexSet<Entry>,
+) -> Option<String> {
+    let input = action_spec.command.stdin_nth(0)?;
+    let default_delimiter = Delimiter::default();
+    let delimiter = action_spec
+        .command
+        .delimiter_nth(0)
+        .unwrap_or(&default_delimiter);
+    let lines = entries
+        .iter()
+        .map(|entry| input.for_entry(&entry.raw, delimiter))
+        .collect::<Vec<_>>();
+    Some(lines.join("\n") + "\n")
+}
+
+/// Spawn `cmd` and write `input` to its stdin from another thread, which
+/// closes it once done.
+fn spawn_with_stdin(cmd: &mut Command, input: String) -> Result<Child> {
+    let mut child = cmd.stdin(Stdio::piped()).spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        std::thread::spawn(move || {
+            // commands are free to exit before reading it all
+            let _ = stdin.write_all(input.as_bytes());
+        });
+    }
+    Ok(child)
+}
+
+/// Split a `path:line[:...]` entry into its path and optional line number.
+///
+/// Entries that don't carry a line number are returned as is.
+pub fn split_line_number(entry: &str) -> (&str, Option<usize>) {
+    let mut parts = entry.splitn(3, ':');
+    if let (Some(path), Some(line)) = (parts.next(), parts.next())
+        && let Ok(line) = line.parse::<usize>()
+    {
+        return (path, Some(line));
+    }
+    (entry, None)
+}
+
+/// Build an editor invocation opening all the given entries at once.
+///
+/// Entries of the form `path:line` are opened at that line using the
+/// `+{line}` convention understood by most terminal editors. Paths are
+/// quoted for the shell commands are run through (see [`command_shell`]).
+///
+/// # Example
+/// ```
+/// use television::utils::command::{command_shell, editor_command};
+///
+/// let command = editor_command("nvim", &["src/main.rs:12", "Cargo.toml"]);
+/// let shell = command_shell().kind;
+/// assert_eq!(
+///     command,
+///     format!(
+///         "nvim +12 {} {}",
+///         shell.quote("src/main.rs"),
+///         shell.quote("Cargo.toml")
+///     )
+/// );
+/// ```
+pub fn editor_command<S: AsRef<str>>(editor: &str, entries: &[S]) -> String {
+    let mut command = editor.to_string();
+    for entry in entries {
+        let (path, line) = split_line_number(entry.as_ref());
+        if let Some(line) = line {
+            command.push_str(&format!(" +{line}"));
+        }
+        command.push(' ');
+        command.push_str(&command_shell().kind.quote(path));
+    }
+    command
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::channels::entry::Entry;
+    use crate::channels::{
+        entry::Entry,
+        prototypes::{OutputMode, StdinInput},
+    };
 
     #[test]
     fn test_simple_braces_syntactic_sugar() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
 
         // Simple braces should use syntactic sugar with quotes
@@ -223,7 +633,7 @@ mod tests {
 
     #[test]
     fn test_simple_braces_multiple_entries() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -237,18 +647,85 @@ mod tests {
 
     #[test]
     fn test_simple_braces_with_quotes_in_filename() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file's name.txt".to_string()));
 
-        // Simple braces should escape single quotes in filenames
+        // Simple braces should quote filenames the way the shell expects
         let template = Template::parse("nvim {}").unwrap();
-        let result = format_command(&entries, &template, "\n").unwrap();
-        assert_eq!(result, "nvim 'file\\'s name.txt'");
+        let result =
+            format_command_for(Shell::Bash, &entries, &template, "\n")
+                .unwrap();
+        assert_eq!(result, r"nvim 'file'\''s name.txt'");
+        let result =
+            format_command_for(Shell::Psh, &entries, &template, "\n").unwrap();
+        assert_eq!(result, "nvim 'file''s name.txt'");
+        let result =
+            format_command_for(Shell::Cmd, &entries, &template, "\n").unwrap();
+        assert_eq!(result, r#"nvim "file's name.txt""#);
+    }
+
+    #[test]
+    fn test_command_shell_from_config() {
+        let config = ["pwsh", "-NoProfile", "-Command"].map(String::from);
+        let shell = CommandShell::from_config(&config).unwrap();
+        assert_eq!(shell.program, "pwsh");
+        assert_eq!(shell.args, vec!["-NoProfile", "-Command"]);
+        assert_eq!(shell.kind, Shell::Psh);
+        assert!(CommandShell::from_config(&[]).is_err());
+    }
+
+    #[test]
+    fn test_command_shell_arguments() {
+        let shell = CommandShell::from_config(
+            &["pwsh", "-NoProfile", "-Command"].map(String::from),
+        )
+        .unwrap();
+        let command =
+            shell.command("Get-Item 'a b'", false, &HashMap::<_, _>::new());
+        assert_eq!(command.get_program(), "pwsh");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["-NoProfile", "-Command", "Get-Item 'a b'"]
+        );
+
+        let command = CommandShell::from(Shell::Bash).command(
+            "echo hi",
+            false,
+            &HashMap::<_, _>::new(),
+        );
+        assert_eq!(command.get_program(), "bash");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["-c", "echo hi"]
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_cmd_gets_the_command_line_as_is() {
+        let command = CommandShell::from(Shell::Cmd).command(
+            r#"type "a b.txt" | findstr x"#,
+            false,
+            &HashMap::<_, _>::new(),
+        );
+        assert_eq!(command.get_program(), "cmd");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["/C", r#"type "a b.txt" | findstr x"#]
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_powershell_is_the_default_on_windows() {
+        if std::env::var("SHELL").is_err() {
+            assert_eq!(CommandShell::detect().kind, Shell::Psh);
+        }
     }
 
     #[test]
     fn test_complex_braces_use_template_system() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -265,7 +742,7 @@ mod tests {
 
     #[test]
     fn test_complex_braces_use_template_system_with_quotes_in_filename() {
-        let mut entries = FxHashSet::default();
+        let mut entries = FxIndexSet::default();
         entries.insert(Entry::new("file1's.txt".to_string()));
         entries.insert(Entry::new("file2.txt".to_string()));
 
@@ -279,4 +756,218 @@ mod tests {
         // Result should be escaped with single quotes in filenames
         assert_eq!(result, "nvim 'file1\\'s.txt' 'file2.txt'");
     }
+
+    fn fixed(delimiter: &str) -> Delimiter {
+        Delimiter::Fixed(delimiter.to_string())
+    }
+
+    #[test]
+    fn test_format_entry_with_delimiter() {
+        let template = Template::parse("{0}:{1}").unwrap();
+        assert_eq!(
+            format_entry(&template, "src/main.rs:12:fn main()", &fixed(":"))
+                .unwrap(),
+            "src/main.rs:12"
+        );
+        // fields missing from the entry are left empty
+        assert_eq!(
+            format_entry(&template, "src/main.rs", &fixed(":")).unwrap(),
+            "src/main.rs:"
+        );
+        // the delimiter can be longer than a single character
+        let template = Template::parse("{1} -> {0}").unwrap();
+        assert_eq!(
+            format_entry(&template, "key => value", &fixed(" => ")).unwrap(),
+            "value -> key"
+        );
+    }
+
+    #[test]
+    fn test_format_entry_with_regex_delimiter() {
+        let delimiter = Delimiter::parse(r"re:\s+").unwrap();
+        let line = "root       1234  0.0  0.1  /usr/bin/tv --inline";
+        let template = Template::parse("kill {1} # {4}").unwrap();
+        // runs of whitespace make a single delimiter
+        assert_eq!(
+            format_entry(&template, line, &delimiter).unwrap(),
+            "kill 1234 # /usr/bin/tv"
+        );
+        // leading whitespace doesn't make an empty first field
+        let template = Template::parse("{0}").unwrap();
+        assert_eq!(
+            format_entry(&template, "   42 ?  Ss", &delimiter).unwrap(),
+            "42"
+        );
+        // whereas a single space splits on every space
+        let template = Template::parse("kill {1}").unwrap();
+        assert_eq!(
+            format_entry(&template, line, &Delimiter::default()).unwrap(),
+            "kill "
+        );
+    }
+
+    #[test]
+    fn test_format_entry_without_positional_placeholders() {
+        let template = Template::parse("bat -n {}").unwrap();
+        assert_eq!(
+            format_entry(&template, "a:b", &fixed(":")).unwrap(),
+            template.format("a:b").unwrap()
+        );
+        let template = Template::parse("{split:\\::0}").unwrap();
+        assert_eq!(format_entry(&template, "a:b", &fixed(" ")).unwrap(), "a");
+    }
+
+    #[test]
+    fn test_split_line_number() {
+        assert_eq!(
+            split_line_number("src/main.rs:12"),
+            ("src/main.rs", Some(12))
+        );
+        assert_eq!(
+            split_line_number("src/main.rs:12:fn main() {"),
+            ("src/main.rs", Some(12))
+        );
+        assert_eq!(split_line_number("src/main.rs"), ("src/main.rs", None));
+        assert_eq!(split_line_number(r"C:\foo.rs"), (r"C:\foo.rs", None));
+    }
+
+    #[test]
+    fn test_editor_command_multiple_entries() {
+        let command = editor_command("code -w", &["a.rs:3", "it's.txt"]);
+        let shell = command_shell().kind;
+        assert_eq!(
+            command,
+            format!(
+                "code -w +3 {} {}",
+                shell.quote("a.rs"),
+                shell.quote("it's.txt")
+            )
+        );
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line("git  checkout 'my branch'"),
+            vec!["git", "checkout", "my branch"]
+        );
+        assert_eq!(split_command_line("echo ''"), vec!["echo", ""]);
+        assert_eq!(
+            split_command_line(r#"a "b c" d\ e"#),
+            vec!["a", "b c", "d e"]
+        );
+    }
+
+    #[test]
+    fn test_split_command_line_round_trips_formatted_commands() {
+        let mut entries = FxIndexSet::default();
+        entries.insert(Entry::new("file's name.txt".to_string()));
+        let template = Template::parse("nvim {}").unwrap();
+        let command = format_command(&entries, &template, "\n").unwrap();
+        assert_eq!(
+            split_command_line(&command),
+            vec!["nvim", "file's name.txt"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_become_reports_exec_failure() {
+        let mut entries = FxIndexSet::default();
+        entries.insert(Entry::new("x".to_string()));
+        let action_spec = ActionSpec {
+            description: None,
+            command: crate::channels::prototypes::CommandSpec {
+                inner: vec![
+                    Template::parse("tv-this-binary-does-not-exist {}")
+                        .unwrap(),
+                ],
+                interactive: false,
+                env: rustc_hash::FxHashMap::default(),
+                labels: Vec::new(),
+                conditions: Vec::new(),
+                delimiters: Vec::new(),
+                stdin: Vec::new(),
+            },
+            mode: ExecutionMode::Become,
+            separator: SPACE.to_string(),
+            confirm: false,
+            output: OutputMode::default(),
+            reload: false,
+            remove: Vec::new(),
+        };
+        // a failed exec returns instead of replacing the test process
+        assert!(execute_action(&action_spec, &entries).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_action_reports_failures() {
+        let mut entries = FxIndexSet::default();
+        entries.insert(Entry::new("x".to_string()));
+        let action_spec = ActionSpec {
+            description: None,
+            command: crate::channels::prototypes::CommandSpec {
+                inner: vec![
+                    Template::parse("echo out {} && echo oops >&2 && exit 3")
+                        .unwrap(),
+                ],
+                interactive: false,
+                env: rustc_hash::FxHashMap::default(),
+                labels: Vec::new(),
+                conditions: Vec::new(),
+                delimiters: Vec::new(),
+                stdin: Vec::new(),
+            },
+            mode: ExecutionMode::Fork,
+            separator: SPACE.to_string(),
+            confirm: false,
+            output: OutputMode::Silent,
+            reload: false,
+            remove: Vec::new(),
+        };
+
+        let output = capture_action(&action_spec, &entries).unwrap();
+
+        assert!(!output.success());
+        assert_eq!(output.exit_code, Some(3));
+        assert_eq!(output.stdout, "out x\n");
+        let message = output.error_message();
+        assert!(message.contains("echo out 'x'"));
+        assert!(message.contains("exit code 3"));
+        assert!(message.contains("oops"));
+        assert!(output.summary().starts_with("$ echo out 'x'"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_action_pipes_entries_on_stdin() {
+        let mut entries = FxIndexSet::default();
+        entries.insert(Entry::new("b:it's".to_string()));
+        entries.insert(Entry::new("a:$(no)".to_string()));
+        let mut command = crate::channels::prototypes::CommandSpec::from(
+            Template::parse("cat").unwrap(),
+        );
+        command.stdin = vec![Some(StdinInput::Entry)];
+        let mut action_spec = ActionSpec {
+            description: None,
+            command,
+            mode: ExecutionMode::Fork,
+            separator: SPACE.to_string(),
+            confirm: false,
+            output: OutputMode::Silent,
+            reload: false,
+            remove: Vec::new(),
+        };
+
+        let output = capture_action(&action_spec, &entries).unwrap();
+        assert_eq!(output.command, "cat");
+        // in the order the entries were selected in
+        assert_eq!(output.stdout, "b:it's\na:$(no)\n");
+
+        action_spec.command.stdin = vec![Some(StdinInput::Field(1))];
+        action_spec.command.delimiters = vec![Some(fixed(":"))];
+        let output = capture_action(&action_spec, &entries).unwrap();
+        assert_eq!(output.stdout, "it's\n$(no)\n");
+    }
 }
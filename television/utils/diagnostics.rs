@@ -0,0 +1,216 @@
+use crate::{
+    cable::get_cable_files,
+    channels::prototypes::ChannelPrototype,
+    config::{CONFIG_FILE_NAME, Config, get_config_dir, get_data_dir},
+    screen::colors::ColorMode,
+    utils::command::command_shell,
+};
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+/// A configuration or channel file along with whether it could be parsed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileStatus {
+    pub path: PathBuf,
+    /// The parsing error, on a single line.
+    pub error: Option<String>,
+}
+
+impl FileStatus {
+    fn check<T: serde::de::DeserializeOwned>(path: &Path) -> Self {
+        let error = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| {
+                toml::from_str::<T>(&content).map_err(|e| e.to_string())
+            })
+            .err()
+            .map(|e| {
+                e.lines()
+                    .map(str::trim)
+                    .filter(|l| !l.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            });
+        Self {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+impl Display for FileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            Some(error) => {
+                write!(f, "{} (error: {error})", self.path.display())
+            }
+            None => write!(f, "{} (ok)", self.path.display()),
+        }
+    }
+}
+
+/// Everything worth knowing about the environment when reporting a bug, as
+/// printed by `tv info`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Diagnostics {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub config_dir: PathBuf,
+    pub data_dir: PathBuf,
+    /// `None` when running on the default configuration.
+    pub config_file: Option<FileStatus>,
+    pub cable_dir: PathBuf,
+    pub channel_files: Vec<FileStatus>,
+    pub theme: String,
+    /// The shell commands are run through, followed by its arguments.
+    pub shell: String,
+    pub readable_stdin: bool,
+    /// Columns and rows, if stdout is a terminal.
+    pub terminal_size: Option<(u16, u16)>,
+    /// How many colors the terminal seems to display.
+    pub color_mode: ColorMode,
+}
+
+impl Diagnostics {
+    pub fn collect(
+        config_file: Option<&Path>,
+        cable_dir: &Path,
+        config: &Config,
+        readable_stdin: bool,
+    ) -> Self {
+        let config_dir = get_config_dir();
+        let config_file = config_file
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                Some(config_dir.join(CONFIG_FILE_NAME))
+                    .filter(|path| path.is_file())
+            })
+            .map(|path| FileStatus::check::<Config>(&path));
+        let mut channel_files: Vec<FileStatus> = if cable_dir.is_dir() {
+            get_cable_files(cable_dir)
+                .iter()
+                .map(|path| FileStatus::check::<ChannelPrototype>(path))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        channel_files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            config_dir,
+            data_dir: get_data_dir(),
+            config_file,
+            cable_dir: cable_dir.to_path_buf(),
+            channel_files,
+            theme: config.ui.theme.clone(),
+            shell: shell_command_line(),
+            readable_stdin,
+            terminal_size: crossterm::terminal::size().ok(),
+            color_mode: ColorMode::detect(),
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// The shell commands are run through, e.g. `pwsh -NoProfile -Command`.
+fn shell_command_line() -> String {
+    let shell = command_shell();
+    std::iter::once(&shell.program)
+        .chain(&shell.args)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One `key: value` pair per line, repeating the key for lists.
+impl Display for Diagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "version: {}", self.version)?;
+        writeln!(f, "os: {}", self.os)?;
+        writeln!(f, "arch: {}", self.arch)?;
+        writeln!(f, "config_dir: {}", self.config_dir.display())?;
+        writeln!(f, "data_dir: {}", self.data_dir.display())?;
+        match &self.config_file {
+            Some(status) => writeln!(f, "config_file: {status}")?,
+            None => writeln!(f, "config_file: (default)")?,
+        }
+        writeln!(f, "cable_dir: {}", self.cable_dir.display())?;
+        for status in &self.channel_files {
+            writeln!(f, "channel_file: {status}")?;
+        }
+        writeln!(f, "theme: {}", self.theme)?;
+        writeln!(f, "shell: {}", self.shell)?;
+        writeln!(f, "readable_stdin: {}", self.readable_stdin)?;
+        match self.terminal_size {
+            Some((columns, rows)) => {
+                writeln!(f, "terminal_size: {columns}x{rows}")?;
+            }
+            None => writeln!(f, "terminal_size: (unknown)")?,
+        }
+        write!(f, "color_mode: {}", self.color_mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics_report_channel_file_status() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("good.toml"),
+            "[metadata]\nname = \"good\"\n[source]\ncommand = \"ls\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("bad.toml"), "[metadata\n").unwrap();
+        let config_dir = tempfile::tempdir().unwrap();
+        let config_file = config_dir.path().join("config.toml");
+        std::fs::write(&config_file, "").unwrap();
+
+        let diagnostics = Diagnostics::collect(
+            Some(&config_file),
+            dir.path(),
+            &Config::default(),
+            false,
+        );
+
+        assert_eq!(diagnostics.config_file.as_ref().unwrap().error, None);
+        assert_eq!(diagnostics.channel_files.len(), 2);
+        let bad = &diagnostics.channel_files[0];
+        assert!(bad.path.ends_with("bad.toml"));
+        assert!(bad.error.as_ref().is_some_and(|e| !e.contains('\n')));
+        let good = &diagnostics.channel_files[1];
+        assert!(good.path.ends_with("good.toml"));
+        assert_eq!(good.error, None);
+
+        let text = diagnostics.to_string();
+        assert!(text.contains("\nchannel_file: "));
+        assert!(text.contains("good.toml (ok)"));
+        assert!(text.contains("bad.toml (error: "));
+        assert!(text.contains("readable_stdin: false"));
+        let shell = command_shell();
+        assert!(text.contains(&format!(
+            "\nshell: {} {}\n",
+            shell.program,
+            shell.args.join(" ")
+        )));
+        assert!(text.contains("\ncolor_mode: "));
+        assert!(text.lines().all(|line| line.contains(": ")));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&diagnostics.to_json().unwrap()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["channel_files"].as_array().unwrap().len(), 2);
+    }
+}
@@ -1,6 +1,10 @@
 use std::hash::Hash;
 
-use rustc_hash::FxHashMap;
+use indexmap::IndexSet;
+use rustc_hash::{FxBuildHasher, FxHashMap};
+
+/// A set that remembers the order its elements were inserted in.
+pub type FxIndexSet<T> = IndexSet<T, FxBuildHasher>;
 
 pub fn invert_hashmap<K, V>(hashmap: &FxHashMap<K, V>) -> FxHashMap<V, K>
 where
@@ -42,6 +42,8 @@ pub enum InputRequest {
     DeleteNextWord,
     DeleteLine,
     DeleteTillEnd,
+    DeleteTillStart,
+    TransposeChars,
 }
 
 #[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy, Hash)]
@@ -94,9 +96,9 @@ impl Input {
     pub fn handle(&mut self, req: InputRequest) -> InputResponse {
         use InputRequest::{
             DeleteLine, DeleteNextChar, DeleteNextWord, DeletePrevChar,
-            DeletePrevWord, DeleteTillEnd, GoToEnd, GoToNextChar,
-            GoToNextWord, GoToPrevChar, GoToPrevWord, GoToStart, InsertChar,
-            SetCursor,
+            DeletePrevWord, DeleteTillEnd, DeleteTillStart, GoToEnd,
+            GoToNextChar, GoToNextWord, GoToPrevChar, GoToPrevWord, GoToStart,
+            InsertChar, SetCursor, TransposeChars,
         };
         match req {
             SetCursor(pos) => {
@@ -328,11 +330,49 @@ impl Input {
             }
 
             DeleteTillEnd => {
-                self.value = self.value.chars().take(self.cursor).collect();
-                Some(StateChanged {
-                    value: true,
-                    cursor: false,
-                })
+                if self.cursor == self.value.chars().count() {
+                    None
+                } else {
+                    self.value =
+                        self.value.chars().take(self.cursor).collect();
+                    Some(StateChanged {
+                        value: true,
+                        cursor: false,
+                    })
+                }
+            }
+
+            DeleteTillStart => {
+                if self.cursor == 0 {
+                    None
+                } else {
+                    self.value =
+                        self.value.chars().skip(self.cursor).collect();
+                    self.cursor = 0;
+                    Some(StateChanged {
+                        value: true,
+                        cursor: true,
+                    })
+                }
+            }
+
+            TransposeChars => {
+                // like readline: swap the characters around the cursor, or
+                // the last two when at the end, and move past them
+                let count = self.value.chars().count();
+                if self.cursor == 0 || count < 2 {
+                    None
+                } else {
+                    let right = self.cursor.min(count - 1);
+                    let mut chars: Vec<char> = self.value.chars().collect();
+                    chars.swap(right - 1, right);
+                    self.value = chars.into_iter().collect();
+                    self.cursor = right + 1;
+                    Some(StateChanged {
+                        value: true,
+                        cursor: true,
+                    })
+                }
             }
         }
     }
@@ -588,4 +628,70 @@ mod tests {
         assert_eq!(input.visual_cursor(), 23);
         assert_eq!(input.visual_scroll(6), 18);
     }
+
+    #[test]
+    fn word_motions_on_unicode_text() {
+        let mut input: Input = "héllo wörld".into();
+
+        input.handle(InputRequest::GoToPrevWord);
+        assert_eq!(input.cursor(), 6);
+        input.handle(InputRequest::GoToPrevWord);
+        assert_eq!(input.cursor(), 0);
+        assert_eq!(input.handle(InputRequest::GoToPrevWord), None);
+
+        input.handle(InputRequest::GoToNextWord);
+        assert_eq!(input.cursor(), 6);
+        input.handle(InputRequest::GoToNextWord);
+        assert_eq!(input.cursor(), 11);
+    }
+
+    #[test]
+    fn delete_next_word_on_unicode_text() {
+        let mut input = Input::from("héllo wörld").with_cursor(0);
+
+        let resp = input.handle(InputRequest::DeleteNextWord);
+        assert_eq!(
+            resp,
+            Some(StateChanged {
+                value: true,
+                cursor: false,
+            })
+        );
+        assert_eq!(input.value(), "wörld");
+        assert_eq!(input.cursor(), 0);
+    }
+
+    #[test]
+    fn delete_till_start_and_end_on_unicode_text() {
+        let mut input = Input::from("héllo wörld").with_cursor(6);
+        input.handle(InputRequest::DeleteTillStart);
+        assert_eq!(input.value(), "wörld");
+        assert_eq!(input.cursor(), 0);
+        assert_eq!(input.handle(InputRequest::DeleteTillStart), None);
+
+        let mut input = Input::from("héllo wörld").with_cursor(5);
+        input.handle(InputRequest::DeleteTillEnd);
+        assert_eq!(input.value(), "héllo");
+        assert_eq!(input.cursor(), 5);
+        assert_eq!(input.handle(InputRequest::DeleteTillEnd), None);
+    }
+
+    #[test]
+    fn transpose_unicode_chars() {
+        let mut input = Input::from("¡☆x").with_cursor(1);
+        input.handle(InputRequest::TransposeChars);
+        assert_eq!(input.value(), "☆¡x");
+        assert_eq!(input.cursor(), 2);
+
+        // at the end of the input, the last two characters are swapped
+        let mut input: Input = "ab☆".into();
+        input.handle(InputRequest::TransposeChars);
+        assert_eq!(input.value(), "a☆b");
+        assert_eq!(input.cursor(), 3);
+
+        let mut input = Input::from("ab").with_cursor(0);
+        assert_eq!(input.handle(InputRequest::TransposeChars), None);
+        let mut input: Input = "☆".into();
+        assert_eq!(input.handle(InputRequest::TransposeChars), None);
+    }
 }
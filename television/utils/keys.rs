@@ -0,0 +1,70 @@
+use crate::event::{Key, convert_raw_event_to_key};
+use anyhow::Result;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    terminal::{disable_raw_mode, enable_raw_mode},
+};
+use std::io::{Write, stdout};
+
+/// Describes a key press: crossterm's view of the event along with the
+/// string to use in `config.toml` to bind it, if it can be bound at all.
+pub fn describe_key_event(event: &KeyEvent) -> String {
+    match convert_raw_event_to_key(*event) {
+        Key::Null => format!("{event:?}  ->  (can't be bound)"),
+        key => format!("{event:?}  ->  {key}"),
+    }
+}
+
+/// Echoes every key press until `ctrl-c` is pressed, to help writing
+/// keybindings.
+pub fn echo_keys() -> Result<()> {
+    let mut out = stdout();
+    write!(
+        out,
+        "Press any key to see how to bind it, ctrl-c to exit.\r\n\r\n"
+    )?;
+    out.flush()?;
+
+    enable_raw_mode()?;
+    let result = (|| -> Result<()> {
+        loop {
+            let Event::Key(key_event) = event::read()? else {
+                continue;
+            };
+            if key_event.kind == KeyEventKind::Release {
+                continue;
+            }
+            write!(out, "{}\r\n", describe_key_event(&key_event))?;
+            out.flush()?;
+            if key_event.code == KeyCode::Char('c')
+                && key_event.modifiers == KeyModifiers::CONTROL
+            {
+                return Ok(());
+            }
+        }
+    })();
+    disable_raw_mode()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_key_event() {
+        let description = describe_key_event(&KeyEvent::new(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL,
+        ));
+        assert!(description.starts_with("KeyEvent { code: Char('a')"));
+        assert!(description.ends_with("  ->  ctrl-a"));
+        assert!(
+            describe_key_event(&KeyEvent::new(
+                KeyCode::CapsLock,
+                KeyModifiers::NONE
+            ))
+            .ends_with("(can't be bound)")
+        );
+    }
+}
@@ -1,10 +1,13 @@
 pub mod cache;
+pub mod circular_buffer;
 pub mod clipboard;
 pub mod command;
+pub mod diagnostics;
 pub mod files;
 pub mod hashmaps;
 pub mod indices;
 pub mod input;
+pub mod keys;
 pub mod metadata;
 pub mod paths;
 pub mod rocell;
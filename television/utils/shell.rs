@@ -79,7 +79,7 @@ impl TryFrom<&str> for Shell {
             Ok(Shell::Zsh)
         } else if value.contains("fish") {
             Ok(Shell::Fish)
-        } else if value.contains("powershell") {
+        } else if value.contains("powershell") || value.contains("pwsh") {
             Ok(Shell::Psh)
         } else if value.contains("cmd") {
             Ok(Shell::Cmd)
@@ -138,6 +138,27 @@ impl Shell {
             Shell::Nu => "nu",
         }
     }
+
+    /// Quote `arg` so that the shell passes it to a command as a single
+    /// argument, as is.
+    pub fn quote(&self, arg: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => {
+                format!("'{}'", arg.replace('\'', r"'\''"))
+            }
+            Shell::Fish => {
+                format!("'{}'", arg.replace('\\', r"\\").replace('\'', r"\'"))
+            }
+            Shell::Psh => format!("'{}'", arg.replace('\'', "''")),
+            Shell::Cmd => format!("\"{}\"", arg.replace('"', "\"\"")),
+            Shell::Nu => {
+                format!(
+                    "\"{}\"",
+                    arg.replace('\\', r"\\").replace('"', "\\\"")
+                )
+            }
+        }
+    }
 }
 
 impl From<CliShell> for Shell {
@@ -171,6 +192,13 @@ const COMPLETION_BASH: &str = include_str!("shell/completion.bash");
 const COMPLETION_FISH: &str = include_str!("shell/completion.fish");
 const COMPLETION_NU: &str = include_str!("shell/completion.nu");
 const COMPLETION_POWERSHELL: &str = include_str!("shell/completion.ps1");
+const COMPLETION_CMD: &str = include_str!("shell/completion.lua");
+
+/// How clap registers its PowerShell completer, which is saved instead so
+/// that our own completer can fall back to it.
+const CLAP_POWERSHELL_COMPLETER: &str =
+    "Register-ArgumentCompleter -Native -CommandName 'tv' -ScriptBlock {";
+const SAVED_POWERSHELL_COMPLETER: &str = "$global:TvClapCompleter = {";
 
 // create the appropriate key binding for each supported shell
 pub fn ctrl_keybinding(shell: Shell, character: char) -> Result<String> {
@@ -185,14 +213,59 @@ pub fn ctrl_keybinding(shell: Shell, character: char) -> Result<String> {
                 Ok(format!(r"ctrl-{lower_char}"))
             }
         }
-        Shell::Nu => Ok(format!(r"Ctrl-{character}")),
+        // nu binds the control modifier separately from the keycode
+        Shell::Nu => {
+            if character == ' ' {
+                Ok("space".to_string())
+            } else {
+                Ok(format!("char_{}", character.to_ascii_lowercase()))
+            }
+        }
         Shell::Psh => Ok(format!(r"Ctrl+{}", character.to_ascii_lowercase())),
+        // clink uses readline's key syntax
+        Shell::Cmd => {
+            if character == ' ' {
+                Ok(r"\C-@".to_string())
+            } else {
+                Ok(format!(r"\C-{}", character.to_ascii_lowercase()))
+            }
+        }
+    }
+}
+
+/// The channel searched by the shell history widget.
+pub fn history_channel(
+    shell: Shell,
+    config: &ShellIntegrationConfig,
+) -> Result<String> {
+    if let Some(channel) = &config.history_channel {
+        return Ok(channel.clone());
+    }
+    match shell {
+        Shell::Bash => Ok("bash-history".to_string()),
+        Shell::Zsh => Ok("zsh-history".to_string()),
+        Shell::Fish => Ok("fish-history".to_string()),
+        Shell::Nu => Ok("nu-history".to_string()),
+        Shell::Psh => Ok("pwsh-history".to_string()),
         Shell::Cmd => {
             anyhow::bail!("This shell is not yet supported: {:?}", shell)
         }
     }
 }
 
+/// The command the shell history widget runs to pick a history entry.
+pub fn history_command(
+    shell: Shell,
+    config: &ShellIntegrationConfig,
+) -> Result<String> {
+    // zsh only writes its history file on exit (unless told otherwise) so
+    // the current session's history is piped in instead
+    if shell == Shell::Zsh && config.history_channel.is_none() {
+        return Ok("history -n -1 0 | tv".to_string());
+    }
+    Ok(format!("tv {}", history_channel(shell, config)?))
+}
+
 pub fn completion_script(shell: Shell) -> Result<&'static str> {
     match shell {
         Shell::Bash => Ok(COMPLETION_BASH),
@@ -200,9 +273,7 @@ pub fn completion_script(shell: Shell) -> Result<&'static str> {
         Shell::Fish => Ok(COMPLETION_FISH),
         Shell::Nu => Ok(COMPLETION_NU),
         Shell::Psh => Ok(COMPLETION_POWERSHELL),
-        Shell::Cmd => {
-            anyhow::bail!("This shell is not yet supported: {:?}", shell)
-        }
+        Shell::Cmd => Ok(COMPLETION_CMD),
     }
 }
 
@@ -212,7 +283,7 @@ pub fn render_autocomplete_script_template(
     config: &ShellIntegrationConfig,
 ) -> Result<String> {
     // Custom autocomplete
-    let script = template
+    let mut script = template
         .replace(
             "{tv_smart_autocomplete_keybinding}",
             &ctrl_keybinding(
@@ -227,9 +298,25 @@ pub fn render_autocomplete_script_template(
                 config.get_command_history_keybinding_character(),
             )?,
         );
+    // not every shell has a history widget (nor a history channel)
+    if script.contains("{tv_shell_history_") {
+        script = script
+            .replace(
+                "{tv_shell_history_command}",
+                &history_command(shell, config)?,
+            )
+            .replace(
+                "{tv_shell_history_channel}",
+                &history_channel(shell, config)?,
+            );
+    }
 
-    let clap_autocomplete =
+    let mut clap_autocomplete =
         render_clap_autocomplete(shell).unwrap_or_default();
+    if shell == Shell::Psh {
+        clap_autocomplete = clap_autocomplete
+            .replace(CLAP_POWERSHELL_COMPLETER, SAVED_POWERSHELL_COMPLETER);
+    }
 
     Ok(clap_autocomplete + &script)
 }
@@ -259,6 +346,17 @@ fn render_clap_autocomplete(shell: Shell) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::event::Key;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(Shell::Bash.quote("it's"), r"'it'\''s'");
+        assert_eq!(Shell::Zsh.quote("$HOME"), "'$HOME'");
+        assert_eq!(Shell::Fish.quote(r"it's a\b"), r"'it\'s a\\b'");
+        assert_eq!(Shell::Psh.quote("it's"), "'it''s'");
+        assert_eq!(Shell::Cmd.quote(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(Shell::Nu.quote(r#"say "hi""#), r#""say \"hi\"""#);
+    }
 
     #[test]
     fn test_bash_ctrl_keybinding() {
@@ -312,7 +410,63 @@ mod tests {
         let shell = Shell::Nu;
         let result = ctrl_keybinding(shell, character);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "Ctrl-s");
+        assert_eq!(result.unwrap(), "char_s");
+        assert_eq!(ctrl_keybinding(shell, ' ').unwrap(), "space");
+    }
+
+    #[test]
+    fn test_history_command() {
+        let mut config = ShellIntegrationConfig::default();
+        assert_eq!(
+            history_command(Shell::Bash, &config).unwrap(),
+            "tv bash-history"
+        );
+        assert_eq!(
+            history_command(Shell::Zsh, &config).unwrap(),
+            "history -n -1 0 | tv"
+        );
+        assert!(history_command(Shell::Cmd, &config).is_err());
+
+        config.history_channel = Some("my-history".to_string());
+        assert_eq!(
+            history_command(Shell::Zsh, &config).unwrap(),
+            "tv my-history"
+        );
+        assert_eq!(
+            history_channel(Shell::Psh, &config).unwrap(),
+            "my-history"
+        );
+    }
+
+    #[test]
+    fn test_scripts_use_configured_keybindings_and_channels() {
+        let mut config = ShellIntegrationConfig::default();
+        config
+            .keybindings
+            .insert("smart_autocomplete".to_string(), Key::Ctrl('f'));
+        config
+            .keybindings
+            .insert("command_history".to_string(), Key::Ctrl('h'));
+        config.history_channel = Some("my-history".to_string());
+
+        for (shell, autocomplete, history) in [
+            (Shell::Bash, r#""\C-F": tv_smart_autocomplete"#, r#""\C-H""#),
+            (Shell::Zsh, "'^F' tv-smart-autocomplete", "'^H'"),
+            (Shell::Fish, "ctrl-f tv_smart_autocomplete", "ctrl-h"),
+            (Shell::Nu, "keycode: char_f", "keycode: char_h"),
+            (Shell::Psh, "-Chord 'Ctrl+f'", "-Chord 'Ctrl+h'"),
+        ] {
+            let script = render_autocomplete_script_template(
+                shell,
+                completion_script(shell).unwrap(),
+                &config,
+            )
+            .unwrap();
+            assert!(script.contains(autocomplete), "{shell}: {autocomplete}");
+            assert!(script.contains(history), "{shell}: {history}");
+            assert!(script.contains("my-history"), "{shell}");
+            assert!(!script.contains("{tv_"), "{shell}");
+        }
     }
 
     #[test]
@@ -351,8 +505,57 @@ mod tests {
         );
         assert!(result.is_ok());
         let result = result.unwrap();
-        // PowerShell completion should contain the completion function
-        assert!(result.contains("Register-ArgumentCompleter"));
+        // PowerShell completion should contain the completion function,
+        // saved for our own completer to fall back to
+        assert!(result.contains(SAVED_POWERSHELL_COMPLETER));
+        assert!(!result.contains(CLAP_POWERSHELL_COMPLETER));
+    }
+
+    #[test]
+    fn test_powershell_script_completes_channels_and_subcommands() {
+        let shell = Shell::Psh;
+        let script = render_autocomplete_script_template(
+            shell,
+            completion_script(shell).unwrap(),
+            &ShellIntegrationConfig::default(),
+        )
+        .unwrap();
+        // a single completer is registered, falling back to clap's
+        assert_eq!(script.matches("Register-ArgumentCompleter").count(), 1);
+        assert!(script.contains("tv list-channels"));
+        for subcommand in Cli::command().get_subcommands() {
+            assert!(
+                script.contains(&format!("'{}'", subcommand.get_name())),
+                "{}",
+                subcommand.get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn test_cmd_ctrl_keybinding() {
+        let shell = Shell::Cmd;
+        assert_eq!(ctrl_keybinding(shell, 'S').unwrap(), "\\C-s");
+        assert_eq!(ctrl_keybinding(shell, ' ').unwrap(), "\\C-@");
+    }
+
+    #[test]
+    fn test_cmd_completion_script() {
+        let shell = Shell::Cmd;
+        let script = render_autocomplete_script_template(
+            shell,
+            completion_script(shell).unwrap(),
+            &ShellIntegrationConfig::default(),
+        )
+        .unwrap();
+        assert!(script.contains(r#"clink.argmatcher("tv")"#));
+        assert!(script.contains("tv list-channels"));
+        for subcommand in Cli::command().get_subcommands() {
+            assert!(
+                script.contains(&format!("\"{}\"", subcommand.get_name()))
+            );
+        }
+        assert!(script.contains(r#"rl.setbinding([["\C-t"]]"#));
     }
 
     #[test]
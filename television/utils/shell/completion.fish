@@ -127,7 +127,7 @@ function tv_shell_history
     # move to the next line so that the prompt is not overwritten
     printf "\n"
 
-    set -l output (tv fish-history --input "$current_prompt" --inline --no-status-bar)
+    set -l output ({tv_shell_history_command} --input "$current_prompt" --inline --no-status-bar)
 
     if test -n "$output"
         commandline -r "$output"
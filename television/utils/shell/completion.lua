@@ -0,0 +1,73 @@
+-- Television cmd integration
+-- Requires clink (https://chrisant996.github.io/clink/)
+
+local function tv_channels()
+    local channels = {}
+    local pipe = io.popen("tv list-channels 2>nul")
+    if pipe then
+        for line in pipe:lines() do
+            table.insert(channels, line)
+        end
+        pipe:close()
+    end
+    table.sort(channels)
+    return channels
+end
+
+local shells = clink.argmatcher():addarg({ "bash", "zsh", "fish", "power-shell", "cmd", "nu" })
+
+clink.argmatcher("tv")
+    :addarg({
+        tv_channels,
+        "list-channels",
+        "init" .. shells,
+        "update-channels",
+    })
+    :addflags({
+        "--autocomplete-prompt",
+        "--autocomplete-output",
+        "--input",
+        "--query",
+        "--inline",
+        "--no-status-bar",
+        "--help",
+        "--version",
+    })
+
+-- quotes can't be escaped reliably on the cmd command line
+local function quote(text)
+    return '"' .. text:gsub('"', "") .. '"'
+end
+
+function tv_smart_autocomplete(rl_buffer)
+    local cursor = rl_buffer:getcursor()
+    local lhs = rl_buffer:getbuffer():sub(1, cursor - 1)
+    local last_word = lhs:match("(%S*)$")
+    local prompt = lhs:sub(1, #lhs - #last_word)
+
+    -- move to the next line so that the prompt is not overwritten
+    print("")
+
+    local pipe = io.popen(
+        "tv --no-status-bar --inline --autocomplete-output full"
+            .. " --autocomplete-prompt " .. quote(prompt)
+            .. " --input " .. quote(last_word)
+    )
+    local output = pipe and pipe:read("*a") or ""
+    if pipe then
+        pipe:close()
+    end
+    output = output:gsub("[\r\n]+$", "")
+
+    if output ~= "" then
+        -- the output is the whole prompt with the selection appended
+        rl_buffer:beginundogroup()
+        rl_buffer:remove(1, cursor)
+        rl_buffer:setcursor(1)
+        rl_buffer:insert(output)
+        rl_buffer:endundogroup()
+    end
+    rl_buffer:refreshline()
+end
+
+rl.setbinding([["{tv_smart_autocomplete_keybinding}"]], [["luafunc:tv_smart_autocomplete"]])
@@ -1,6 +1,25 @@
 # Television PowerShell Integration
 # Requires PSReadLine module (usually included by default in PowerShell 5.1+)
 
+# Complete channel names for the first argument, everything else is left to
+# the completer generated by clap (saved as $global:TvClapCompleter above)
+Register-ArgumentCompleter -Native -CommandName 'tv' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+
+    $previous = @($commandAst.CommandElements | Where-Object { $_.Extent.EndOffset -lt $cursorPosition })
+    if ($previous.Count -eq 1 -and -not $wordToComplete.StartsWith('-')) {
+        tv list-channels 2>$null |
+            Where-Object { $_ -like "$wordToComplete*" } |
+            Sort-Object |
+            ForEach-Object {
+                [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)
+            }
+    }
+    if ($global:TvClapCompleter) {
+        & $global:TvClapCompleter $wordToComplete $commandAst $cursorPosition
+    }
+}
+
 function Get-AvailableSpaceBelowPrompt {
     $windowHeight = [Console]::WindowHeight
     $windowTop = [Console]::WindowTop
@@ -147,7 +166,7 @@ function Invoke-TvShellHistory {
         # Use .NET Process class for explicit stream control
         $psi = New-Object System.Diagnostics.ProcessStartInfo
         $psi.FileName = "tv"
-        $psi.Arguments = "pwsh-history --inline --no-status-bar --input `"$currentPromptEscaped`""
+        $psi.Arguments = "{tv_shell_history_channel} --inline --no-status-bar --input `"$currentPromptEscaped`""
         $psi.UseShellExecute = $false
         $psi.RedirectStandardOutput = $true
         $psi.RedirectStandardError = $false  # Let TUI render to console
@@ -1,6 +1,8 @@
 use std::borrow::Cow;
 
 use lazy_regex::{Lazy, Regex, regex};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
 
 use crate::screen::result_item::ResultItem;
 
@@ -340,6 +342,29 @@ fn is_emoji(ch: char) -> bool {
         '\u{1F900}'..='\u{1F9FF}',
         // flags
         '\u{1F1E6}'..='\u{1F1FF}',
+        // misc. symbols and dingbats
+        '\u{2600}'..='\u{27BF}',
+    ]
+    .iter()
+    .any(|range| range.contains(&ch))
+}
+
+/// Whether `ch` takes no cell of its own but combines with the glyph before
+/// it: combining accents, variation selectors and the zero width joiner of
+/// emoji sequences.
+fn is_combining_character(ch: char) -> bool {
+    [
+        // combining diacritical marks (and their extensions)
+        '\u{0300}'..='\u{036F}',
+        '\u{1AB0}'..='\u{1AFF}',
+        '\u{1DC0}'..='\u{1DFF}',
+        '\u{20D0}'..='\u{20FF}',
+        '\u{FE20}'..='\u{FE2F}',
+        // zero width joiner
+        '\u{200D}'..='\u{200D}',
+        // variation selectors
+        '\u{FE00}'..='\u{FE0F}',
+        '\u{E0100}'..='\u{E01EF}',
     ]
     .iter()
     .any(|range| range.contains(&ch))
@@ -486,6 +511,12 @@ pub fn replace_non_printable_bulk<'a>(
                     c if ('\u{2800}'..='\u{28FF}').contains(&c) => {
                         output.push(c);
                     }
+                    // Other double width glyphs (CJK punctuation, fullwidth
+                    // forms, emojis...) and the characters combining with
+                    // the previous glyph
+                    c if c.width() == Some(2) || is_combining_character(c) => {
+                        output.push(c);
+                    }
                     // Unicode characters above 0x0700 seem unstable with ratatui
                     c if c > '\u{0700}' => {
                         output.push(NULL_SYMBOL);
@@ -635,11 +666,14 @@ pub fn preprocess_line(line: &str) -> (Cow<'_, str>, Vec<i16>) {
 pub fn make_result_item_printable(
     result_item: &(impl ResultItem + ?Sized),
 ) -> (std::borrow::Cow<'_, str>, Vec<(u32, u32)>) {
-    let (printable, transformation_offsets) =
-        preprocess_line(result_item.display());
+    // only the first line of multi-line records is shown
+    let line = result_item.display().lines().next().unwrap_or_default();
+    let (printable, transformation_offsets) = preprocess_line(line);
     let mut match_indices = Vec::new();
 
     if let Some(ranges) = result_item.match_ranges() {
+        // the matcher counts graphemes while the offsets are per character
+        let ranges = grapheme_ranges_to_char_ranges(line, ranges);
         // PERF: Pre-allocate with known capacity
         match_indices.reserve(ranges.len());
 
@@ -665,6 +699,52 @@ pub fn make_result_item_printable(
     (printable, match_indices)
 }
 
+/// Convert match ranges counted in grapheme clusters into ranges counted in
+/// characters of `s`.
+///
+/// The matcher sees each grapheme cluster (a letter and its combining accents,
+/// an emoji ZWJ sequence...) as a single character, so its ranges drift away
+/// from the characters of a string holding such clusters.
+///
+/// # Examples
+/// ```
+/// use television::utils::strings::grapheme_ranges_to_char_ranges;
+///
+/// assert_eq!(grapheme_ranges_to_char_ranges("abc", &[(1, 2)]), vec![(1, 2)]);
+/// // "e\u{301}" is a single grapheme made of two characters
+/// assert_eq!(
+///     grapheme_ranges_to_char_ranges("cafe\u{301} au lait", &[(3, 6)]),
+///     vec![(3, 7)]
+/// );
+/// ```
+///
+/// # Panics
+/// This will panic if the number of characters in `s` doesn't fit into a
+/// `u32`.
+pub fn grapheme_ranges_to_char_ranges(
+    s: &str,
+    ranges: &[(u32, u32)],
+) -> Vec<(u32, u32)> {
+    if s.is_ascii() {
+        return ranges.to_vec();
+    }
+    // the character index each grapheme starts at, then the character count
+    let mut starts = Vec::with_capacity(s.len() + 1);
+    let mut char_count = 0;
+    for grapheme in s.graphemes(true) {
+        starts.push(char_count);
+        char_count += u32::try_from(grapheme.chars().count()).unwrap();
+    }
+    starts.push(char_count);
+    let char_index = |grapheme_index: u32| {
+        starts[(grapheme_index as usize).min(starts.len() - 1)]
+    };
+    ranges
+        .iter()
+        .map(|&(start, end)| (char_index(start), char_index(end)))
+        .collect()
+}
+
 /// Shrink a string to a maximum length, adding an ellipsis in the middle.
 ///
 /// If the string is shorter than the maximum length, it is returned as is.
@@ -743,11 +823,63 @@ pub fn to_title_case(s: &str) -> String {
         .join(" ")
 }
 
+/// The Levenshtein distance between two strings, in characters.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// The candidate closest to `name`, if any is close enough to be a likely
+/// typo of it.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::channels::entry::Entry;
 
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("hiden", "hidden"), 1);
+        assert_eq!(edit_distance("été", "ete"), 2);
+    }
+
+    #[test]
+    fn test_closest_match() {
+        let candidates = ["show_categories", "hidden", "disabled"];
+        assert_eq!(closest_match("hiden", candidates), Some("hidden"));
+        assert_eq!(
+            closest_match("show_category", candidates),
+            Some("show_categories")
+        );
+        assert_eq!(closest_match("size", candidates), None);
+    }
+
     fn test_next_char_boundary(input: &str, start: usize, expected: usize) {
         let actual = next_char_boundary(input, start);
         assert_eq!(actual, expected);
@@ -958,6 +1090,25 @@ mod tests {
         assert_eq!(output, "⠓⠑⠇⠇⠕⠀⠺⠕⠗⠇⠙");
         assert_eq!(offsets, vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
     }
+    #[test]
+    fn test_wide_characters() {
+        let input = "「メモ」ＡＢ㐀🪐";
+        let config = ReplaceNonPrintableConfig::default();
+        let (output, offsets) = replace_non_printable_bulk(input, &config);
+        assert_eq!(output, input);
+        assert_eq!(offsets, vec![0; 8]);
+    }
+
+    #[test]
+    fn test_combining_characters() {
+        // combining accent, emoji presentation selector, ZWJ sequence
+        let input = "cafe\u{301} \u{2764}\u{FE0F} \u{1F469}\u{200D}\u{1F4BB}";
+        let config = ReplaceNonPrintableConfig::default();
+        let (output, offsets) = replace_non_printable_bulk(input, &config);
+        assert_eq!(output, input);
+        assert_eq!(offsets, vec![0; 12]);
+    }
+
     #[test]
     fn test_replace_non_printable_no_range_changes() {
         let input = "Hello,\x00World!";
@@ -1088,4 +1239,75 @@ mod tests {
         assert_eq!(printable, "ジェ abc");
         assert_eq!(match_indices, vec![(0, 1), (2, 3)]);
     }
+
+    #[test]
+    fn test_make_match_string_printable_wide_characters() {
+        use unicode_width::UnicodeWidthStr;
+
+        let entry = Entry::new("「メモ」\tnotes.md".to_string())
+            .with_match_indices(&[1, 2, 5]);
+        let (printable, match_indices) = make_result_item_printable(&entry);
+        assert_eq!(printable, "「メモ」    notes.md");
+        assert_eq!(printable.width(), 20);
+        assert_eq!(match_indices, vec![(1, 3), (8, 9)]);
+        // the ranges cover the matched glyphs and nothing else
+        let matched: Vec<String> = match_indices
+            .iter()
+            .map(|&(start, end)| {
+                printable
+                    .chars()
+                    .skip(start as usize)
+                    .take((end - start) as usize)
+                    .collect()
+            })
+            .collect();
+        assert_eq!(matched, vec!["メモ", "n"]);
+    }
+
+    #[test]
+    fn test_make_match_string_printable_graphemes() {
+        // "e\u{301}" is one grapheme made of two characters
+        let entry = Entry::new("cafe\u{301}\tmenu".to_string())
+            .with_match_indices(&[3, 5]);
+        let (printable, match_indices) = make_result_item_printable(&entry);
+        assert_eq!(printable, "cafe\u{301}    menu");
+        assert_eq!(match_indices, vec![(3, 5), (9, 10)]);
+
+        // so is an emoji ZWJ sequence, made of three characters
+        let entry = Entry::new("\u{1F469}\u{200D}\u{1F4BB} dev".to_string())
+            .with_match_indices(&[0, 2]);
+        let (printable, match_indices) = make_result_item_printable(&entry);
+        assert_eq!(printable, "\u{1F469}\u{200D}\u{1F4BB} dev");
+        assert_eq!(match_indices, vec![(0, 3), (4, 5)]);
+    }
+
+    #[test]
+    fn test_grapheme_ranges_to_char_ranges() {
+        // one character per grapheme, whatever their width
+        assert_eq!(
+            grapheme_ranges_to_char_ranges("日本語.txt", &[(0, 2), (4, 7)]),
+            vec![(0, 2), (4, 7)]
+        );
+        // combining accents belong to the letter before them
+        assert_eq!(
+            grapheme_ranges_to_char_ranges(
+                "e\u{301}te\u{301} ete",
+                &[(0, 1), (2, 3), (4, 7)]
+            ),
+            vec![(0, 2), (3, 5), (6, 9)]
+        );
+        // emoji ZWJ sequences are a single grapheme
+        assert_eq!(
+            grapheme_ranges_to_char_ranges(
+                "\u{1F469}\u{200D}\u{1F4BB} dev",
+                &[(0, 1), (2, 5)]
+            ),
+            vec![(0, 3), (4, 7)]
+        );
+        // ranges reaching past the end stop at the last character
+        assert_eq!(
+            grapheme_ranges_to_char_ranges("e\u{301}", &[(0, 3)]),
+            vec![(0, 2)]
+        );
+    }
 }
@@ -1,12 +1,12 @@
 //! This module tests the inner `App` struct of the `television` crate.
 
-use std::{collections::HashSet, path::PathBuf, time::Duration};
+use std::{collections::HashSet, path::PathBuf, sync::Arc, time::Duration};
 
 use television::{
     action::Action,
-    app::App,
+    app::{App, AppOutput, ExitStatus},
     cable::Cable,
-    channels::prototypes::ChannelPrototype,
+    channels::{channel::Source, prototypes::ChannelPrototype},
     cli::{ChannelCli, PostProcessedCli},
     config::{default_config_from_file, layers::ConfigLayers},
 };
@@ -32,9 +32,39 @@ fn setup_app(
     select_1: bool,
     exact: bool,
 ) -> (
-    JoinHandle<television::app::AppOutput>,
+    JoinHandle<AppOutput>,
     tokio::sync::mpsc::UnboundedSender<Action>,
 ) {
+    setup_app_with_cli(
+        channel_prototype,
+        PostProcessedCli {
+            channel: ChannelCli {
+                select_1,
+                exact,
+                ..ChannelCli::default()
+            },
+            ..PostProcessedCli::default()
+        },
+    )
+}
+
+/// Same as `setup_app`, with the given CLI options.
+fn setup_app_with_cli(
+    channel_prototype: Option<ChannelPrototype>,
+    cli: PostProcessedCli,
+) -> (
+    JoinHandle<AppOutput>,
+    tokio::sync::mpsc::UnboundedSender<Action>,
+) {
+    spawn_app(build_app(channel_prototype, cli))
+}
+
+/// Builds an app with the given channel and CLI options, run from the
+/// tests' target directory.
+fn build_app(
+    channel_prototype: Option<ChannelPrototype>,
+    cli: PostProcessedCli,
+) -> App {
     let target_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
         .join("tests")
         .join("target_dir");
@@ -46,27 +76,24 @@ fn setup_app(
     // this speeds up the tests
     config.application.tick_rate = 100;
 
-    let layered_config = ConfigLayers::new(
-        config,
-        chan,
-        PostProcessedCli {
-            channel: ChannelCli {
-                select_1,
-                exact,
-                ..ChannelCli::default()
-            },
-            ..PostProcessedCli::default()
-        },
-    );
-    let mut app = App::new(
+    let layered_config = ConfigLayers::new(config, chan, cli);
+    App::new(
         layered_config,
         Cable::from_prototypes(vec![
             ChannelPrototype::new("files", "find . -type f"),
             ChannelPrototype::new("dirs", "find . -type d"),
             ChannelPrototype::new("env", "printenv"),
         ]),
-    );
+    )
+}
 
+/// Starts `app` in a separate task, see `setup_app`.
+fn spawn_app(
+    mut app: App,
+) -> (
+    JoinHandle<AppOutput>,
+    tokio::sync::mpsc::UnboundedSender<Action>,
+) {
     // retrieve the app's action channel handle to send a quit action
     let tx = app.action_tx.clone();
 
@@ -91,6 +118,46 @@ async fn test_app_does_quit() {
     assert!(f.is_finished());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_quit_exits_with_abort_status() {
+    for action in [Action::Quit, Action::Abort] {
+        let (f, tx) = setup_app(None, false, false);
+        tx.send(action).unwrap();
+
+        let output = timeout(DEFAULT_TIMEOUT, f)
+            .await
+            .expect("app did not finish within the default timeout")
+            .unwrap();
+
+        assert!(output.selected_entries.is_none());
+        assert_eq!(output.exit_status, ExitStatus::Aborted);
+        assert_eq!(output.exit_status.code(), 130);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_exits_with_no_match_status_when_nothing_to_take() {
+    let (f, _) = setup_app_with_cli(
+        Some(ChannelPrototype::new("empty", "true")),
+        PostProcessedCli {
+            channel: ChannelCli {
+                take_1: true,
+                ..ChannelCli::default()
+            },
+            ..PostProcessedCli::default()
+        },
+    );
+
+    let output = timeout(DEFAULT_TIMEOUT, f)
+        .await
+        .expect("app did not finish within the default timeout")
+        .unwrap();
+
+    assert!(output.selected_entries.is_none());
+    assert_eq!(output.exit_status, ExitStatus::NoMatch);
+    assert_eq!(output.exit_status.code(), 1);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
 async fn test_app_starts_normally() {
     let (f, _) = setup_app(None, false, false);
@@ -119,9 +186,17 @@ async fn test_app_basic_search() {
         .expect("app did not finish within the default timeout")
         .unwrap();
 
+    assert_eq!(output.exit_status, ExitStatus::Selected);
+    assert_eq!(output.exit_status.code(), 0);
     assert!(output.selected_entries.is_some());
     assert_eq!(
-        &output.selected_entries.unwrap().drain().next().unwrap().raw,
+        &output
+            .selected_entries
+            .unwrap()
+            .drain(..)
+            .next()
+            .unwrap()
+            .raw,
         "./file1.txt"
     );
 }
@@ -185,7 +260,10 @@ async fn test_app_exact_search_multiselect() {
     assert!(selected_entries.is_some());
     // should contain a single entry with the prompt
     assert!(!selected_entries.as_ref().unwrap().is_empty());
-    assert_eq!(selected_entries.unwrap().drain().next().unwrap().raw, "fie");
+    assert_eq!(
+        selected_entries.unwrap().drain(..).next().unwrap().raw,
+        "fie"
+    );
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 3)]
@@ -246,7 +324,13 @@ async fn test_app_exits_when_select_1_and_only_one_result() {
 
     assert!(output.selected_entries.is_some());
     assert_eq!(
-        &output.selected_entries.unwrap().drain().next().unwrap().raw,
+        &output
+            .selected_entries
+            .unwrap()
+            .drain(..)
+            .next()
+            .unwrap()
+            .raw,
         "file1.txt"
     );
 }
@@ -267,3 +351,122 @@ async fn test_app_does_not_exit_when_select_1_and_more_than_one_result() {
 
     assert!(output.is_err());
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_reloads_source_after_forked_action() {
+    let dir = tempfile::tempdir().unwrap();
+    let list = dir.path().join("list.txt");
+    std::fs::write(&list, "first\n").unwrap();
+    let prototype: ChannelPrototype = toml::from_str(&format!(
+        r#"
+        [metadata]
+        name = "list"
+
+        [source]
+        command = "cat '{list}'"
+
+        [actions.replace]
+        command = "echo second > '{list}'"
+        mode = "fork"
+        output = "silent"
+        reload = true
+        "#,
+        list = list.display()
+    ))
+    .unwrap();
+    let (f, tx) = setup_app(Some(prototype), false, false);
+
+    tx.send(Action::ToggleSelectionDown).unwrap();
+    tx.send(Action::ExternalAction("actions:replace".to_string()))
+        .unwrap();
+    for _ in 0..=10 {
+        tx.send(Action::Tick).unwrap();
+        sleep(Duration::from_millis(30)).await;
+    }
+    tx.send(Action::ConfirmSelection).unwrap();
+
+    let output = timeout(DEFAULT_TIMEOUT, f)
+        .await
+        .expect("app did not finish within the default timeout")
+        .unwrap();
+    // the selection made before the action is gone, and the entry under
+    // the cursor comes from the reloaded source
+    let selected: Vec<String> = output
+        .selected_entries
+        .unwrap()
+        .into_iter()
+        .map(|entry| entry.raw)
+        .collect();
+    assert_eq!(selected, ["second"]);
+}
+
+/// Select the single entry of a grep-like channel, displayed without its
+/// path and line number, and return what would be printed to stdout.
+///
+/// The entry comes from the channel's source command, or from `source`
+/// when given.
+async fn select_grep_like_entry(
+    output_line_numbers: &str,
+    source: Option<Source>,
+) -> String {
+    let prototype: ChannelPrototype = toml::from_str(&format!(
+        r#"
+        [metadata]
+        name = "grep_like"
+        icon_mode = "path"
+
+        [source]
+        command = "echo 'file1.txt:12:some match'"
+        display = "{{split:\\::2}}"
+        output = "{{split:\\::0}}"
+        {output_line_numbers}
+
+        [preview]
+        command = "cat '{{split:\\::0}}'"
+        offset = "{{split:\\::1}}"
+        "#
+    ))
+    .unwrap();
+    let mut app = build_app(Some(prototype), PostProcessedCli::default());
+    if let Some(source) = source {
+        app.television.channel.set_source(source);
+    }
+    let (f, tx) = spawn_app(app);
+
+    for _ in 0..=10 {
+        tx.send(Action::Tick).unwrap();
+    }
+    sleep(Duration::from_millis(100)).await;
+    tx.send(Action::ConfirmSelection).unwrap();
+
+    let output = timeout(DEFAULT_TIMEOUT, f)
+        .await
+        .expect("app did not finish within the default timeout")
+        .unwrap();
+    let entry = output.selected_entries.unwrap().drain(..).next().unwrap();
+    assert_eq!(entry.display(), "some match");
+    entry.output().unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_outputs_line_numbers() {
+    assert_eq!(select_grep_like_entry("", None).await, "file1.txt:12");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_outputs_line_numbers_of_static_entries() {
+    let source =
+        Source::Static(Arc::new(vec!["file2.txt:7:some match".to_string()]));
+    assert_eq!(
+        select_grep_like_entry("", Some(source)).await,
+        "file2.txt:7"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 3)]
+async fn test_app_outputs_no_line_numbers_when_disabled() {
+    assert_eq!(
+        select_grep_like_entry("output_line_numbers = false", None).await,
+        "file1.txt"
+    );
+}
@@ -25,7 +25,7 @@ fn test_input_prefills_search_box() {
 
     // Send Ctrl+C to exit the application gracefully
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that custom keybindings override default keyboard shortcuts.
@@ -50,7 +50,7 @@ fn test_keybindings_override_default() {
 
     // Test that our custom "a" key now quits the application
     tester.send("'a'");
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that multiple keybinding overrides can be specified simultaneously.
@@ -76,7 +76,7 @@ fn test_multiple_keybindings_override() {
 
     // Use "a" to quit the application
     tester.send("'a'");
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the --exact flag enables exact substring matching instead of fuzzy matching.
@@ -103,7 +103,7 @@ fn test_exact_matching_enabled() {
 
     // Send Ctrl+C to exit the application
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 #[test]
@@ -131,5 +131,5 @@ fn test_exact_matching_enabled_fails() {
 
     // Send Ctrl+C to exit the application
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
@@ -186,3 +186,47 @@ fn test_expect_with_selection() {
 
     PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
 }
+
+/// Tests the exit status of tv: 130 when aborted with one of the
+/// `--abort-keys`, 1 when there was nothing to select and 0 on a selection.
+#[test]
+fn test_exit_status_with_abort_keys() {
+    // aborted, even though the key is bound to something else
+    let mut tester = PtyTester::new();
+    let cmd = tv_local_config_and_cable_with_args(&[
+        "files",
+        "--keybindings",
+        "ctrl-g=\"toggle_preview\"",
+        "--abort-keys",
+        "ctrl-g",
+    ]);
+    let mut child = tester.spawn_command_tui(cmd);
+    tester.send(&ctrl('g'));
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
+
+    // nothing to select
+    let mut tester = PtyTester::new();
+    let cmd = tv_local_config_and_cable_with_args(&[
+        "--source-command",
+        "true",
+        "--take-1",
+        "--abort-keys",
+        "ctrl-g",
+    ]);
+    let mut child = tester.spawn_command(cmd);
+    PtyTester::assert_exit_status(&mut child, EXIT_NO_MATCH, DEFAULT_DELAY);
+
+    // a selection
+    let mut tester = PtyTester::new();
+    let cmd = tv_local_config_and_cable_with_args(&[
+        "files",
+        "--input",
+        "Cargo.toml",
+        "--abort-keys",
+        "ctrl-g",
+    ]);
+    let mut child = tester.spawn_command_tui(cmd);
+    tester.send(ENTER);
+    tester.assert_raw_output_contains("Cargo.toml");
+    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+}
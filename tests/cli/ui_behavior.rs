@@ -20,15 +20,15 @@ fn test_toggle_preview_keybinding() {
     // Verify preview is initially visible (shows "Hide Preview:" option)
     tester.assert_tui_frame_contains("Hide Preview:");
 
-    // Send Ctrl+O to toggle preview off
-    tester.send(&ctrl('o'));
+    // Send Alt+O to toggle preview off
+    tester.send(&alt('o'));
 
     // Verify preview is now hidden (shows "Show Preview:" option)
     tester.assert_tui_frame_contains("Show Preview:");
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the toggle remote control keybinding functionality works correctly.
@@ -51,7 +51,7 @@ fn test_toggle_remote_control_keybinding() {
 
     // Send Ctrl+C again to exit the application
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the toggle status bar keybinding functionality works correctly.
@@ -75,7 +75,7 @@ fn test_toggle_status_bar_keybinding() {
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the toggle help keybinding functionality works correctly.
@@ -95,7 +95,7 @@ fn test_toggle_help_keybinding() {
 
     // Send Ctrl+C to exit (help panel should close and app should exit)
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the preview scrolling keybindings functionality works correctly.
@@ -127,7 +127,7 @@ fn test_scroll_preview_keybindings() {
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the reload source keybinding functionality works correctly.
@@ -164,7 +164,7 @@ fn test_reload_source_keybinding() {
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that the cycle sources keybinding functionality works correctly.
@@ -185,7 +185,7 @@ fn test_cycle_sources_keybinding() {
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that preview toggle is disabled when in remote control mode.
@@ -215,7 +215,7 @@ fn test_toggle_preview_disabled_in_remote_control_mode() {
     tester.assert_not_tui_frame_contains("Show Preview:");
 
     // Try to toggle preview - this should NOT work in remote control mode
-    tester.send(&ctrl('o'));
+    tester.send(&alt('o'));
 
     // Verify we're still in remote control mode and preview is still visible
     // (the toggle should have been ignored)
@@ -233,10 +233,30 @@ fn test_toggle_preview_disabled_in_remote_control_mode() {
     tester.assert_not_tui_frame_contains("Back to Channel:");
 
     // Verify preview toggle works again in channel mode
-    tester.send(&ctrl('o'));
+    tester.send(&alt('o'));
     tester.assert_tui_frame_contains("Show Preview:");
 
     // Send Ctrl+C to exit
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
+}
+
+/// Tests that SIGTERM makes tv quit cleanly and restore the terminal.
+#[cfg(unix)]
+#[test]
+fn test_sigterm_quits_and_restores_terminal() {
+    let mut tester = PtyTester::new();
+
+    let cmd = tv_local_config_and_cable_with_args(&["files"]);
+    let mut child = tester.spawn_command_tui(cmd);
+    tester.assert_tui_frame_contains("files");
+
+    let pid = child.process_id().expect("tv should have a pid");
+    // SAFETY: sending a signal to our own child process
+    unsafe {
+        libc::kill(libc::pid_t::try_from(pid).unwrap(), libc::SIGTERM);
+    }
+
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
+    tester.assert_terminal_restored();
 }
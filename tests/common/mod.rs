@@ -38,6 +38,11 @@ pub const DEFAULT_PTY_SIZE: PtySize = PtySize {
 
 pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
 
+/// Exit status of tv when quit without selecting anything.
+pub const EXIT_ABORTED: u32 = 130;
+/// Exit status of tv when there was nothing to select.
+pub const EXIT_NO_MATCH: u32 = 1;
+
 /// A helper to test terminal user interfaces (TUIs) using a pseudo-terminal (pty).
 ///
 /// This struct provides methods to spawn commands in a pty, read their output, and send input to
@@ -61,8 +66,8 @@ pub const DEFAULT_DELAY: Duration = Duration::from_millis(100);
 ///     // Send a Ctrl+C to exit the application
 ///     tester.send(&ctrl('c'));
 ///
-///     // Assert that the child process exits successfully
-///     PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+///     // Assert that the child process exits as aborted
+///     PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 /// }
 /// ```
 pub struct PtyTester {
@@ -196,20 +201,46 @@ impl PtyTester {
 
     /// Waits for the child process to exit, asserting that it exits with a success status.
     /// This uses exponential backoff to wait for the process to exit to avoid flakiness.
+    ///
+    /// tv only exits successfully when something was selected, see
+    /// [`PtyTester::assert_exit_status`] for the other exits.
     pub fn assert_exit_ok(
         child: &mut Box<dyn portable_pty::Child + Send + Sync>,
         timeout: Duration,
     ) {
+        let status = Self::wait_for_exit(child, timeout);
+        assert!(
+            status.success(),
+            "Process exited with non-zero status: {:?}",
+            status
+        );
+    }
+
+    /// Waits for the child process to exit, asserting that it exits with the
+    /// given code (e.g. [`EXIT_ABORTED`] when quit without a selection).
+    pub fn assert_exit_status(
+        child: &mut Box<dyn portable_pty::Child + Send + Sync>,
+        code: u32,
+        timeout: Duration,
+    ) {
+        let status = Self::wait_for_exit(child, timeout);
+        assert_eq!(
+            status.exit_code(),
+            code,
+            "Process exited with an unexpected status: {:?}",
+            status
+        );
+    }
+
+    /// Waits for the child process to exit with exponential backoff,
+    /// panicking if it doesn't.
+    fn wait_for_exit(
+        child: &mut Box<dyn portable_pty::Child + Send + Sync>,
+        timeout: Duration,
+    ) -> portable_pty::ExitStatus {
         for i in 0..4 {
             match child.try_wait() {
-                Ok(Some(status)) => {
-                    assert!(
-                        status.success(),
-                        "Process exited with non-zero status: {:?}",
-                        status
-                    );
-                    return;
-                }
+                Ok(Some(status)) => return status,
                 Ok(None) => {
                     // Process is still running, continue waiting
                     sleep(timeout * 2u32.pow(i));
@@ -255,7 +286,7 @@ impl PtyTester {
     ///     │  cable/unix/files.toml                                   │
     ///     │  cable/unix/fish-history.toml                            │
     ///     │  cable/unix/git-branch.toml                              │
-    ///     ╰─────────── help: <Ctrl-g>  preview: <Ctrl-o> ────────────╯
+    ///     ╰─────────── help: <Ctrl-g>  preview: <Alt-o> ─────────────╯
     /// */
     /// ```
     pub fn get_tui_frame(&mut self) -> String {
@@ -330,6 +361,31 @@ impl PtyTester {
         );
     }
 
+    /// Asserts that the terminal was put back into its original state once
+    /// the child process exited (raw mode off, alternate screen left).
+    #[cfg(unix)]
+    pub fn assert_terminal_restored(&mut self) {
+        // process whatever the child wrote on its way out
+        let _ = self.read_tui_output();
+        assert!(
+            !self.parser.screen().alternate_screen(),
+            "Terminal is still in the alternate screen"
+        );
+
+        let fd = self
+            .pair
+            .master
+            .as_raw_fd()
+            .expect("pty master should have a file descriptor");
+        // SAFETY: `termios` is a plain C struct filled in by `tcgetattr`
+        let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+        assert_eq!(unsafe { libc::tcgetattr(fd, &raw mut termios) }, 0);
+        assert!(
+            termios.c_lflag & libc::ICANON != 0,
+            "Terminal is still in raw mode"
+        );
+    }
+
     pub fn assert_raw_output_contains(&mut self, expected: &str) {
         let output = self.read_raw_output();
         assert!(
@@ -345,6 +401,10 @@ pub fn ctrl(c: char) -> String {
     ((c as u8 & 0x1F) as char).to_string()
 }
 
+pub fn alt(c: char) -> String {
+    format!("{ESC}{c}")
+}
+
 pub fn f(c: u8) -> String {
     let seq = match c {
         1 => "\x1bOP",    // F1
@@ -381,8 +441,13 @@ pub const LOCAL_CONFIG_AND_CABLE: &[&str] = &[
 ];
 
 /// A command builder initialized with the tv binary path.
+///
+/// The locale is set to UTF-8 so that tv draws the same glyphs wherever the
+/// tests run (see the `ui.ascii` setting).
 pub fn tv() -> CommandBuilder {
-    CommandBuilder::new(*TV_BIN_PATH)
+    let mut cmd = CommandBuilder::new(*TV_BIN_PATH);
+    cmd.env("LC_ALL", "C.UTF-8");
+    cmd
 }
 
 /// A command builder initialized with the tv binary path and the provided arguments.
@@ -55,7 +55,7 @@ fn test_cli_ui_overrides_config_file() {
     ]);
 
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests CLI overrides of channel-specific configuration
@@ -106,7 +106,7 @@ fn test_cli_overrides_channel_source_and_preview() {
     tester.assert_not_tui_frame_contains("channel-item-1");
 
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests CLI working directory parameter
@@ -204,7 +204,7 @@ fn test_cli_header_footer_overrides() {
     ]);
 
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests that empty CLI arguments don't override non-empty config values
@@ -234,7 +234,7 @@ fn test_empty_cli_args_dont_override() {
     tester.assert_tui_frame_contains("config-prompt>");
 
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 /// Tests CLI override of input bar position
@@ -269,7 +269,7 @@ fn test_cli_input_position_override() {
     );
 
     tester.send(&ctrl('c'));
-    PtyTester::assert_exit_ok(&mut child, DEFAULT_DELAY);
+    PtyTester::assert_exit_status(&mut child, EXIT_ABORTED, DEFAULT_DELAY);
 }
 
 #[test]